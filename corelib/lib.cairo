@@ -25,7 +25,7 @@ extern func felt_mul(a: felt, b: felt) -> felt nopanic;
 extern type NonZero<T>;
 // TODO(spapini): Add generic impls for NonZero for Copy, Drop.
 enum JumpNzResult<T> { Zero: (), NonZero: NonZero::<T>, }
-extern func unwrap_nz<T>(a: NonZero::<T>) -> T nopanic;
+extern func unwrap_non_zero<T>(a: NonZero::<T>) -> T nopanic;
 
 extern func felt_div(a: felt, b: NonZero::<felt>) -> felt nopanic;
 
@@ -98,7 +98,7 @@ fn create_entry_code(
         if &main_func.signature.ret_types[i] != ty {
             anyhow::bail!("We only support main functions with no parameters.");
         }
-        if ty == &"RangeCheck".into() {
+        if ty == &"RangeCheck".into() || ty == &"SegmentArena".into() {
             casm_extend! {ctx,
                 %{ memory[ap + 0] = segments.add() %}
                 ap += 1;
@@ -153,10 +153,13 @@ fn create_metadata(
 fn find_main(
     sierra_program: &Arc<sierra::program::Program>,
 ) -> Option<&sierra::program::GenFunction<StatementIdx>> {
-    sierra_program
-        .funcs
-        .iter()
-        .find(|f| if let Some(name) = &f.id.debug_name { name.ends_with("::main") } else { false })
+    sierra_program.funcs.iter().find(|f| {
+        if let Some(name) = &f.id.debug_name {
+            name.ends_with("::main")
+        } else {
+            false
+        }
+    })
 }
 
 /// Returns the total sizes of inputs and outputs per a Sierra function entry point.
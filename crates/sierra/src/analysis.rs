@@ -0,0 +1,145 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use thiserror::Error;
+
+use crate::extensions::lib_func::{ConcreteLibFunc, SierraApChange};
+use crate::extensions::{GenericLibFunc, GenericType};
+use crate::ids::FunctionId;
+use crate::program::{BranchTarget, Program, Statement, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "analysis_test.rs"]
+mod test;
+
+/// Errors encountered while building the control flow graph of a function.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum CfgError {
+    #[error("error from the program registry")]
+    ProgramRegistryError(#[from] Box<ProgramRegistryError>),
+    #[error("statement is out of bounds")]
+    StatementOutOfBounds(StatementIdx),
+    #[error("invocation has a different number of branches than its libfunc")]
+    BranchCountMismatch(StatementIdx),
+    #[error("invocation falls through on a branch other than its libfunc's fallthrough branch")]
+    InvalidFallthrough(StatementIdx),
+}
+
+/// The control flow graph of a function, with a node per statement reachable from the function's
+/// entry point.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Cfg {
+    /// The entry point of the function.
+    pub entry_point: StatementIdx,
+    /// The successors of each statement, ordered by the branches leading to them.
+    successors: HashMap<StatementIdx, Vec<StatementIdx>>,
+    /// The predecessors of each statement.
+    predecessors: HashMap<StatementIdx, Vec<StatementIdx>>,
+}
+impl Cfg {
+    /// Returns the statements of the graph, in ascending order.
+    pub fn statements(&self) -> Vec<StatementIdx> {
+        let mut statements: Vec<_> = self.successors.keys().copied().collect();
+        statements.sort_by_key(|idx| idx.0);
+        statements
+    }
+    /// Returns the statements that may run right after the given statement.
+    pub fn successors(&self, idx: &StatementIdx) -> &[StatementIdx] {
+        self.successors.get(idx).map(|v| &v[..]).unwrap_or_default()
+    }
+    /// Returns the statements that may run right before the given statement.
+    pub fn predecessors(&self, idx: &StatementIdx) -> &[StatementIdx] {
+        self.predecessors.get(idx).map(|v| &v[..]).unwrap_or_default()
+    }
+}
+
+/// Builds the control flow graph of a function in the program.
+pub fn build_cfg<TType: GenericType, TLibFunc: GenericLibFunc>(
+    program: &Program,
+    function_id: &FunctionId,
+    registry: &ProgramRegistry<TType, TLibFunc>,
+) -> Result<Cfg, CfgError> {
+    let entry_point = registry.get_function(function_id)?.entry_point;
+    let mut successors = HashMap::<StatementIdx, Vec<StatementIdx>>::new();
+    let mut predecessors = HashMap::<StatementIdx, Vec<StatementIdx>>::new();
+    let mut queue = VecDeque::from([entry_point]);
+    while let Some(idx) = queue.pop_front() {
+        if successors.contains_key(&idx) {
+            continue;
+        }
+        let statement = program.get_statement(&idx).ok_or(CfgError::StatementOutOfBounds(idx))?;
+        let statement_successors = match statement {
+            Statement::Return(_) => vec![],
+            Statement::Invocation(invocation) => {
+                let libfunc = registry.get_libfunc(&invocation.libfunc_id)?;
+                if invocation.branches.len() != libfunc.branch_signatures().len() {
+                    return Err(CfgError::BranchCountMismatch(idx));
+                }
+                for (branch_index, branch) in invocation.branches.iter().enumerate() {
+                    if branch.target == BranchTarget::Fallthrough
+                        && libfunc.fallthrough() != Some(branch_index)
+                    {
+                        return Err(CfgError::InvalidFallthrough(idx));
+                    }
+                }
+                invocation.branches.iter().map(|branch| idx.next(&branch.target)).collect()
+            }
+        };
+        for successor in &statement_successors {
+            predecessors.entry(*successor).or_default().push(idx);
+            queue.push_back(*successor);
+        }
+        successors.insert(idx, statement_successors);
+    }
+    Ok(Cfg { entry_point, successors, predecessors })
+}
+
+/// Computes the change to `ap` caused by a call to a function, from the `ap` changes of the
+/// branches of the libfuncs it invokes. The change is known only if every path from the entry point
+/// reaches a return with the same known change. Branches of `branch_align` are considered as not
+/// changing `ap`, as aligning the converging branches is up to the compiler.
+pub fn function_ap_change<TType: GenericType, TLibFunc: GenericLibFunc>(
+    program: &Program,
+    function_id: &FunctionId,
+    registry: &ProgramRegistry<TType, TLibFunc>,
+) -> Result<SierraApChange, CfgError> {
+    let entry_point = registry.get_function(function_id)?.entry_point;
+    let mut statement_ap_changes = HashMap::<StatementIdx, usize>::new();
+    let mut return_ap_change = None;
+    let mut queue = VecDeque::from([(entry_point, 0)]);
+    while let Some((idx, ap_change)) = queue.pop_front() {
+        match statement_ap_changes.entry(idx) {
+            Entry::Occupied(entry) if *entry.get() == ap_change => continue,
+            // The statement is reached with different `ap` changes, e.g. in a loop.
+            Entry::Occupied(_) => return Ok(SierraApChange::Unknown),
+            Entry::Vacant(entry) => entry.insert(ap_change),
+        };
+        match program.get_statement(&idx).ok_or(CfgError::StatementOutOfBounds(idx))? {
+            Statement::Return(_) => {
+                if *return_ap_change.get_or_insert(ap_change) != ap_change {
+                    return Ok(SierraApChange::Unknown);
+                }
+            }
+            Statement::Invocation(invocation) => {
+                let libfunc = registry.get_libfunc(&invocation.libfunc_id)?;
+                let branch_signatures = libfunc.branch_signatures();
+                if invocation.branches.len() != branch_signatures.len() {
+                    return Err(CfgError::BranchCountMismatch(idx));
+                }
+                for (branch, branch_signature) in invocation.branches.iter().zip(branch_signatures)
+                {
+                    let branch_ap_change = match branch_signature.ap_change {
+                        SierraApChange::Known(branch_ap_change) => branch_ap_change,
+                        SierraApChange::BranchAlign => 0,
+                        SierraApChange::Unknown
+                        | SierraApChange::NotImplemented
+                        | SierraApChange::FinalizeLocals => return Ok(SierraApChange::Unknown),
+                    };
+                    queue.push_back((idx.next(&branch.target), ap_change + branch_ap_change));
+                }
+            }
+        }
+    }
+    Ok(return_ap_change.map_or(SierraApChange::Unknown, SierraApChange::Known))
+}
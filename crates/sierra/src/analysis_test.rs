@@ -0,0 +1,151 @@
+use indoc::indoc;
+use test_log::test;
+
+use super::{build_cfg, function_ap_change, CfgError};
+use crate::extensions::core::{CoreLibFunc, CoreType};
+use crate::extensions::lib_func::SierraApChange;
+use crate::program::StatementIdx;
+use crate::program_registry::ProgramRegistry;
+use crate::ProgramParser;
+
+#[test]
+fn jump_nz_has_two_successors() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type NonZeroFelt = NonZero<felt>;
+            libfunc felt_jump_nz = felt_jump_nz;
+            libfunc felt_const_0 = felt_const<0>;
+            libfunc unwrap_nz = unwrap_non_zero<felt>;
+            felt_jump_nz(a) { fallthrough() 3(b) };
+            felt_const_0() -> (c);
+            return(c);
+            unwrap_nz(b) -> (c);
+            return(c);
+            main@0(a: felt) -> (felt);
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    let cfg = build_cfg(&program, &"main".into(), &registry).unwrap();
+    assert_eq!(cfg.statements(), (0..5).map(StatementIdx).collect::<Vec<_>>());
+    assert_eq!(cfg.successors(&StatementIdx(0)), &[StatementIdx(1), StatementIdx(3)]);
+    assert_eq!(cfg.successors(&StatementIdx(2)), &[]);
+    assert_eq!(cfg.predecessors(&StatementIdx(3)), &[StatementIdx(0)]);
+    assert_eq!(cfg.predecessors(&StatementIdx(0)), &[]);
+}
+
+#[test]
+fn missing_branch() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type NonZeroFelt = NonZero<felt>;
+            libfunc felt_jump_nz = felt_jump_nz;
+            felt_jump_nz(a) { fallthrough() };
+            return();
+            main@0(a: felt) -> ();
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    assert_eq!(
+        build_cfg(&program, &"main".into(), &registry),
+        Err(CfgError::BranchCountMismatch(StatementIdx(0)))
+    );
+}
+
+#[test]
+fn jump_is_a_terminator() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            libfunc jump = jump;
+            jump() { 2() };
+            return(a);
+            return(a);
+            main@0(a: felt) -> (felt);
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    let cfg = build_cfg(&program, &"main".into(), &registry).unwrap();
+    assert_eq!(cfg.statements(), vec![StatementIdx(0), StatementIdx(2)]);
+    assert_eq!(cfg.successors(&StatementIdx(0)), &[StatementIdx(2)]);
+}
+
+#[test]
+fn jump_cannot_fall_through() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            libfunc jump = jump;
+            jump() { fallthrough() };
+            return();
+            main@0() -> ();
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    assert_eq!(
+        build_cfg(&program, &"main".into(), &registry),
+        Err(CfgError::InvalidFallthrough(StatementIdx(0)))
+    );
+}
+
+#[test]
+fn known_function_ap_change() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type NonZeroFelt = NonZero<felt>;
+            libfunc felt_jump_nz = felt_jump_nz;
+            libfunc branch_align = branch_align;
+            libfunc drop_nz = drop<NonZeroFelt>;
+            libfunc store_temp_felt = store_temp<felt>;
+            libfunc jump = jump;
+            felt_jump_nz(a) { fallthrough() 4(b) };
+            branch_align() -> ();
+            store_temp_felt(c) -> (c);
+            jump() { 7() };
+            branch_align() -> ();
+            drop_nz(b) -> ();
+            store_temp_felt(c) -> (c);
+            return(c);
+            main@0(a: felt, c: felt) -> (felt);
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    assert_eq!(
+        function_ap_change(&program, &"main".into(), &registry),
+        Ok(SierraApChange::Known(1))
+    );
+}
+
+#[test]
+fn unknown_function_ap_change() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type NonZeroFelt = NonZero<felt>;
+            libfunc felt_jump_nz = felt_jump_nz;
+            libfunc drop_nz = drop<NonZeroFelt>;
+            libfunc store_temp_felt = store_temp<felt>;
+            libfunc revoke_ap_tracking = revoke_ap_tracking;
+            felt_jump_nz(a) { fallthrough() 3(b) };
+            store_temp_felt(c) -> (c);
+            return(c);
+            drop_nz(b) -> ();
+            return(c);
+            revoke_ap_tracking() -> ();
+            return(c);
+            other@0(a: felt, c: felt) -> (felt);
+            revoked@5(c: felt) -> (felt);
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    // The branches return with different `ap` changes.
+    assert_eq!(
+        function_ap_change(&program, &"other".into(), &registry),
+        Ok(SierraApChange::Unknown)
+    );
+    assert_eq!(
+        function_ap_change(&program, &"revoked".into(), &registry),
+        Ok(SierraApChange::Unknown)
+    );
+}
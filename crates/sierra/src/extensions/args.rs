@@ -0,0 +1,78 @@
+//! Helpers for extracting the generic arguments of types and libfuncs during specialization.
+//!
+//! All the helpers fail with [SpecializationError::WrongNumberOfGenericArgs] if the number of
+//! arguments differs from the expected one, and with [SpecializationError::UnsupportedGenericArg]
+//! if an argument is of the wrong kind.
+
+use num_bigint::BigInt;
+
+use super::error::{ArgCount, GenericArgKind};
+use super::SpecializationError;
+use crate::ids::{ConcreteTypeId, FunctionId};
+use crate::program::GenericArg;
+
+/// Extracts a single type from the generic arguments.
+pub fn args_as_single_type(args: &[GenericArg]) -> Result<ConcreteTypeId, SpecializationError> {
+    match args {
+        [GenericArg::Type(ty)] => Ok(ty.clone()),
+        [arg] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type)),
+        _ => Err(SpecializationError::wrong_arg_count(1, args.len())),
+    }
+}
+
+/// Extracts two types from the generic arguments.
+pub fn args_as_two_types(
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, ConcreteTypeId), SpecializationError> {
+    match args {
+        [GenericArg::Type(ty0), GenericArg::Type(ty1)] => Ok((ty0.clone(), ty1.clone())),
+        [GenericArg::Type(_), arg] | [arg, _] => {
+            Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type))
+        }
+        _ => Err(SpecializationError::wrong_arg_count(2, args.len())),
+    }
+}
+
+/// Extracts a single value from the generic arguments.
+pub fn args_as_single_value(args: &[GenericArg]) -> Result<BigInt, SpecializationError> {
+    match args {
+        [GenericArg::Value(value)] => Ok(value.clone()),
+        [arg] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value)),
+        _ => Err(SpecializationError::wrong_arg_count(1, args.len())),
+    }
+}
+
+/// Extracts a type followed by a value from the generic arguments.
+pub fn args_as_type_and_value(
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, BigInt), SpecializationError> {
+    match args {
+        [GenericArg::Type(ty), GenericArg::Value(value)] => Ok((ty.clone(), value.clone())),
+        [GenericArg::Type(_), arg] => {
+            Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value))
+        }
+        [arg, _] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type)),
+        _ => Err(SpecializationError::wrong_arg_count(2, args.len())),
+    }
+}
+
+/// Extracts a single user function from the generic arguments.
+pub fn args_as_single_user_func(args: &[GenericArg]) -> Result<FunctionId, SpecializationError> {
+    match args {
+        [GenericArg::UserFunc(function_id)] => Ok(function_id.clone()),
+        [arg] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::UserFunc)),
+        _ => Err(SpecializationError::wrong_arg_count(1, args.len())),
+    }
+}
+
+/// Returns the error for generic arguments that should have been either empty or a single value,
+/// as taken by libfuncs with an optional constant operand.
+pub fn optional_value_args_error(args: &[GenericArg]) -> SpecializationError {
+    match args {
+        [arg] => SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value),
+        _ => SpecializationError::WrongNumberOfGenericArgs {
+            expected: ArgCount::AtMost(1),
+            actual: args.len(),
+        },
+    }
+}
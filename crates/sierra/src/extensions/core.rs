@@ -1,9 +1,28 @@
 use super::ap_tracking::RevokeApTrackingLibFunc;
 use super::array::{ArrayLibFunc, ArrayType};
-use super::dict_felt_to::{DictFeltToLibFunc, DictFeltToType};
+use super::bitwise::{BitwiseLibFunc, BitwiseType};
+use super::boolean::{BoolLibFunc, BoolType};
+use super::branch_align::BranchAlignLibFunc;
+use super::bytes31::{Bytes31LibFunc, Bytes31Type};
+use super::casts::CastLibFunc;
+use super::circuit::{CircuitLibFunc, CircuitType};
+use super::consts::ConstAsImmediateLibFunc;
+use super::coupon::{CouponLibFunc, CouponType};
+use super::debug::DebugPrintLibFunc;
+use super::dict_felt_to::{
+    DictFeltToEntryType, DictFeltToLibFunc, DictFeltToType, SquashedDictFeltToType,
+};
 use super::drop::DropLibFunc;
 use super::duplicate::DupLibFunc;
+use super::ec::{EcLibFunc, EcOpType, EcPointType, EcStateType};
+use super::ecdsa::{EcdsaVerifyLibFunc, SignatureType};
 use super::enm::{EnumLibFunc, EnumType};
+use super::event::EmitEventLibFunc;
+use super::interoperability::{
+    ClassHashTryFromFeltLibFunc, ClassHashType, ContractAddressTryFromFeltLibFunc,
+    ContractAddressType,
+};
+use super::jump_not_zero::GenericJumpNotZeroLibFunc;
 use super::modules::boxing::{BoxLibFunc, BoxType};
 use super::modules::felt::{FeltLibFunc, FeltType};
 use super::modules::function_call::FunctionCallLibFunc;
@@ -12,42 +31,124 @@ use super::modules::integer::{Uint128LibFunc, Uint128Type};
 use super::modules::mem::MemLibFunc;
 use super::modules::non_zero::{NonZeroType, UnwrapNonZeroLibFunc};
 use super::modules::unconditional_jump::UnconditionalJumpLibFunc;
+use super::nullable::{NullableLibFunc, NullableType};
+use super::option::OptionLibFunc;
+use super::panic::{PanicLibFunc, PanicableType};
+use super::pedersen::{PedersenLibFunc, PedersenType};
+use super::poseidon::{PoseidonLibFunc, PoseidonType};
+use super::pow::PowLibFunc;
 use super::range_check::RangeCheckType;
+use super::result::ResultLibFunc;
+use super::secp256::{Secp256LibFunc, Secp256PointType};
+use super::segment_arena::SegmentArenaType;
+use super::snapshot::{SnapshotTakeLibFunc, SnapshotType};
+use super::span::{SpanLibFunc, SpanType};
+use super::storage::{StorageAddressLibFunc, StorageAddressType, StorageBaseAddressType};
 use super::strct::{StructLibFunc, StructType};
+use super::syscalls::{SyscallLibFunc, SystemType};
+use super::uint::{
+    Uint16LibFunc, Uint16Type, Uint32LibFunc, Uint32Type, Uint64LibFunc, Uint64Type, Uint8LibFunc,
+    Uint8Type,
+};
+use super::uint256::{Uint256LibFunc, Uint256Type};
 use super::uninitialized::UninitializedType;
+use super::unit::{UnitLibFunc, UnitType};
 use crate::{define_libfunc_hierarchy, define_type_hierarchy};
 
 define_type_hierarchy! {
     pub enum CoreType {
         Array(ArrayType),
+        Span(SpanType),
+        Bitwise(BitwiseType),
+        Circuit(CircuitType),
+        Bool(BoolType),
+        Pedersen(PedersenType),
+        EcOp(EcOpType),
+        EcPoint(EcPointType),
+        EcState(EcStateType),
+        Signature(SignatureType),
+        System(SystemType),
+        Secp256Point(Secp256PointType),
+        StorageBaseAddress(StorageBaseAddressType),
+        StorageAddress(StorageAddressType),
+        ContractAddress(ContractAddressType),
+        ClassHash(ClassHashType),
+        Poseidon(PoseidonType),
         Box(BoxType),
+        Coupon(CouponType),
+        Nullable(NullableType),
+        Panicable(PanicableType),
+        Snapshot(SnapshotType),
         Felt(FeltType),
         GasBuiltin(GasBuiltinType),
+        Uint8(Uint8Type),
+        Uint16(Uint16Type),
+        Uint32(Uint32Type),
+        Uint64(Uint64Type),
         Uint128(Uint128Type),
+        Uint256(Uint256Type),
+        Bytes31(Bytes31Type),
         NonZero(NonZeroType),
         RangeCheck(RangeCheckType),
+        SegmentArena(SegmentArenaType),
         Uninitialized(UninitializedType),
+        Unit(UnitType),
         Enum(EnumType),
         Struct(StructType),
         DictFeltTo(DictFeltToType),
+        SquashedDictFeltTo(SquashedDictFeltToType),
+        DictFeltToEntry(DictFeltToEntryType),
     }, CoreTypeConcrete
 }
 
 define_libfunc_hierarchy! {
     pub enum CoreLibFunc {
         ApTracking(RevokeApTrackingLibFunc),
+        BranchAlign(BranchAlignLibFunc),
         Array(ArrayLibFunc),
+        Span(SpanLibFunc),
+        Bitwise(BitwiseLibFunc),
+        Circuit(CircuitLibFunc),
+        Bool(BoolLibFunc),
+        Pedersen(PedersenLibFunc),
+        Ec(EcLibFunc),
+        EcdsaVerify(EcdsaVerifyLibFunc),
+        Syscall(SyscallLibFunc),
+        Secp256(Secp256LibFunc),
+        StorageAddress(StorageAddressLibFunc),
+        ContractAddressTryFromFelt(ContractAddressTryFromFeltLibFunc),
+        ClassHashTryFromFelt(ClassHashTryFromFeltLibFunc),
+        Poseidon(PoseidonLibFunc),
         Box(BoxLibFunc),
+        Nullable(NullableLibFunc),
+        Panic(PanicLibFunc),
+        DebugPrint(DebugPrintLibFunc),
         Drop(DropLibFunc),
         Dup(DupLibFunc),
+        SnapshotTake(SnapshotTakeLibFunc),
+        EmitEvent(EmitEventLibFunc),
         Felt(FeltLibFunc),
+        ConstAsImmediate(ConstAsImmediateLibFunc),
+        Pow(PowLibFunc),
         FunctionCall(FunctionCallLibFunc),
+        Coupon(CouponLibFunc),
         Gas(GasLibFunc),
+        Uint8(Uint8LibFunc),
+        Uint16(Uint16LibFunc),
+        Uint32(Uint32LibFunc),
+        Uint64(Uint64LibFunc),
         Uint128(Uint128LibFunc),
+        Uint256(Uint256LibFunc),
+        Bytes31(Bytes31LibFunc),
+        Cast(CastLibFunc),
         Mem(MemLibFunc),
         UnwrapNonZero(UnwrapNonZeroLibFunc),
+        JumpNotZero(GenericJumpNotZeroLibFunc),
         UnconditionalJump(UnconditionalJumpLibFunc),
+        Unit(UnitLibFunc),
         Enum(EnumLibFunc),
+        Option(OptionLibFunc),
+        Result(ResultLibFunc),
         Struct(StructLibFunc),
         DictFeltTo(DictFeltToLibFunc),
     }, CoreConcreteLibFunc
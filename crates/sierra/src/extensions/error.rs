@@ -1,18 +1,27 @@
+use std::fmt;
+
 use num_bigint::BigInt;
 use thiserror::Error;
 
 use crate::ids::{ConcreteTypeId, FunctionId, GenericLibFuncId, GenericTypeId};
-use crate::program::GenericArg;
+use crate::program::{ConcreteLibFuncLongId, ConcreteTypeLongId, GenericArg};
 
 /// Error occurring while specializing extensions.
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum SpecializationError {
     #[error("Could not find the requested extension")]
     UnsupportedId,
-    #[error("Expected a different number of generic arguments")]
-    WrongNumberOfGenericArgs,
-    #[error("Provided generic arg is unsupported")]
-    UnsupportedGenericArg,
+    #[error("Expected {expected} generic arguments, got {actual}")]
+    WrongNumberOfGenericArgs { expected: ArgCount, actual: usize },
+    #[error(
+        "Generic arg `{arg}` is unsupported{}",
+        .expected.map(|kind| format!(", expected {kind}")).unwrap_or_default()
+    )]
+    UnsupportedGenericArg {
+        arg: GenericArg,
+        /// The kind of arg expected instead, if the arg is of the wrong kind.
+        expected: Option<GenericArgKind>,
+    },
     #[error("index is out of a relevant range")]
     IndexOutOfRange {
         index: BigInt,
@@ -25,15 +34,82 @@ pub enum SpecializationError {
     TypeWasNotDeclared(GenericTypeId, Vec<GenericArg>),
     #[error("Missing type info for the requested type")]
     MissingTypeInfo(ConcreteTypeId),
+    #[error("Type cannot be duplicated")]
+    TypeNotDuplicatable(ConcreteTypeId),
+    #[error("Type cannot be dropped")]
+    TypeNotDroppable(ConcreteTypeId),
+    #[error("Type cannot be stored")]
+    TypeNotStorable(ConcreteTypeId),
+    #[error("Short string does not fit in a felt")]
+    ShortStringOverflow(String),
+    #[error("Constant is out of the felt field")]
+    FeltConstOutOfRange(BigInt),
+}
+
+impl SpecializationError {
+    /// Returns the error for getting `actual` generic args instead of exactly `expected`.
+    pub fn wrong_arg_count(expected: usize, actual: usize) -> Self {
+        Self::WrongNumberOfGenericArgs { expected: ArgCount::Exactly(expected), actual }
+    }
+    /// Returns the error for a generic arg of a different kind than `expected`.
+    pub fn wrong_arg_kind(arg: &GenericArg, expected: GenericArgKind) -> Self {
+        Self::UnsupportedGenericArg { arg: arg.clone(), expected: Some(expected) }
+    }
+    /// Returns the error for a generic arg of the right kind, whose value is unsupported.
+    pub fn unsupported_arg(arg: GenericArg) -> Self {
+        Self::UnsupportedGenericArg { arg, expected: None }
+    }
+}
+
+/// The number of generic args expected by a specialization.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArgCount {
+    Exactly(usize),
+    AtMost(usize),
+    AtLeast(usize),
+}
+impl fmt::Display for ArgCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgCount::Exactly(count) => write!(f, "{count}"),
+            ArgCount::AtMost(count) => write!(f, "at most {count}"),
+            ArgCount::AtLeast(count) => write!(f, "at least {count}"),
+        }
+    }
+}
+
+/// The kind of a generic arg.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GenericArgKind {
+    UserType,
+    Type,
+    Value,
+    ShortString,
+    UserFunc,
+    LibFunc,
+}
+impl fmt::Display for GenericArgKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GenericArgKind::UserType => "a user type",
+            GenericArgKind::Type => "a type",
+            GenericArgKind::Value => "a value",
+            GenericArgKind::ShortString => "a short string",
+            GenericArgKind::UserFunc => "a user function",
+            GenericArgKind::LibFunc => "a libfunc",
+        })
+    }
 }
 
 /// Extension related errors.
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum ExtensionError {
-    #[error("Could not specialize type")]
-    TypeSpecialization { type_id: GenericTypeId, error: SpecializationError },
-    #[error("Could not specialize libfunc")]
-    LibFuncSpecialization { libfunc_id: GenericLibFuncId, error: SpecializationError },
+    #[error("Could not specialize type `{long_id}`: {error}")]
+    TypeSpecialization { long_id: ConcreteTypeLongId, error: SpecializationError },
+    #[error("Could not specialize libfunc `{long_id}`: {error}")]
+    LibFuncSpecialization { long_id: ConcreteLibFuncLongId, error: SpecializationError },
     #[error("The requested functionality is not implemented yet")]
     NotImplemented,
+    #[error("Libfunc `{0}` is already registered")]
+    LibFuncIdAlreadyRegistered(GenericLibFuncId),
 }
@@ -1,11 +1,14 @@
+use super::args::args_as_single_type;
 use super::error::{ExtensionError, SpecializationError};
 use super::type_specialization_context::TypeSpecializationContext;
 use crate::ids::{ConcreteTypeId, FunctionId, GenericLibFuncId, GenericTypeId};
-use crate::program::{Function, FunctionSignature, GenericArg};
+use crate::program::{ConcreteLibFuncLongId, Function, FunctionSignature, GenericArg};
 
 /// Trait for the specialization of libfunc signatures.
 pub trait SignatureSpecializationContext: TypeSpecializationContext {
     /// Returns concrete type id given a generic type and the generic arguments.
+    /// Implementations backed by a fixed set of declarations return `None` for undeclared types,
+    /// while implementations generating programs may declare the type on demand instead.
     fn try_get_concrete_type(
         &self,
         id: GenericTypeId,
@@ -118,13 +121,19 @@ impl<TGenericLibFunc: GenericLibFunc> GenericLibFuncEx for TGenericLibFunc {
         generic_args: &[GenericArg],
     ) -> Result<LibFuncSignature, ExtensionError> {
         Self::by_id(libfunc_id)
-            .ok_or_else(move || ExtensionError::LibFuncSpecialization {
-                libfunc_id: libfunc_id.clone(),
+            .ok_or_else(|| ExtensionError::LibFuncSpecialization {
+                long_id: ConcreteLibFuncLongId {
+                    generic_id: libfunc_id.clone(),
+                    generic_args: generic_args.to_vec(),
+                },
                 error: SpecializationError::UnsupportedId,
             })?
             .specialize_signature(context, generic_args)
-            .map_err(move |error| ExtensionError::LibFuncSpecialization {
-                libfunc_id: libfunc_id.clone(),
+            .map_err(|error| ExtensionError::LibFuncSpecialization {
+                long_id: ConcreteLibFuncLongId {
+                    generic_id: libfunc_id.clone(),
+                    generic_args: generic_args.to_vec(),
+                },
                 error,
             })
     }
@@ -135,13 +144,19 @@ impl<TGenericLibFunc: GenericLibFunc> GenericLibFuncEx for TGenericLibFunc {
         generic_args: &[GenericArg],
     ) -> Result<TGenericLibFunc::Concrete, ExtensionError> {
         Self::by_id(libfunc_id)
-            .ok_or_else(move || ExtensionError::LibFuncSpecialization {
-                libfunc_id: libfunc_id.clone(),
+            .ok_or_else(|| ExtensionError::LibFuncSpecialization {
+                long_id: ConcreteLibFuncLongId {
+                    generic_id: libfunc_id.clone(),
+                    generic_args: generic_args.to_vec(),
+                },
                 error: SpecializationError::UnsupportedId,
             })?
             .specialize(context, generic_args)
-            .map_err(move |error| ExtensionError::LibFuncSpecialization {
-                libfunc_id: libfunc_id.clone(),
+            .map_err(|error| ExtensionError::LibFuncSpecialization {
+                long_id: ConcreteLibFuncLongId {
+                    generic_id: libfunc_id.clone(),
+                    generic_args: generic_args.to_vec(),
+                },
                 error,
             })
     }
@@ -191,6 +206,8 @@ impl<TNamedLibFunc: NamedLibFunc> GenericLibFunc for TNamedLibFunc {
 }
 
 /// Trait for implementing a specialization generator not holding anything more than a signature.
+/// The concrete libfunc is a [SignatureOnlyConcreteLibFunc]. Libfuncs with ids too long to be
+/// inlined implement [GenericLibFunc] directly with the same concrete type instead.
 pub trait SignatureOnlyGenericLibFunc: Default {
     const ID: GenericLibFuncId;
 
@@ -223,6 +240,47 @@ impl<T: SignatureOnlyGenericLibFunc> NamedLibFunc for T {
     }
 }
 
+/// Trait for implementing a specialization generator with a single type as its generic argument,
+/// whose concrete libfunc holds that type along with the signature.
+pub trait SignatureAndTypeGenericLibFunc: Default {
+    const ID: GenericLibFuncId;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        ty: ConcreteTypeId,
+    ) -> Result<LibFuncSignature, SpecializationError>;
+}
+
+/// Wrapper implementing [NamedLibFunc] for [SignatureAndTypeGenericLibFunc]s, as a second blanket
+/// implementation would conflict with the one of [SignatureOnlyGenericLibFunc].
+#[derive(Default)]
+pub struct WrapSignatureAndTypeGenericLibFunc<T: SignatureAndTypeGenericLibFunc>(T);
+impl<T: SignatureAndTypeGenericLibFunc> NamedLibFunc for WrapSignatureAndTypeGenericLibFunc<T> {
+    type Concrete = SignatureAndTypeConcreteLibFunc;
+    const ID: GenericLibFuncId = T::ID;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        self.0.specialize_signature(context, args_as_single_type(args)?)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(SignatureAndTypeConcreteLibFunc {
+            ty: ty.clone(),
+            signature: self.0.specialize_signature(context.upcast(), ty)?,
+        })
+    }
+}
+
 /// Trait for implementing a specialization generator with no generic arguments.
 pub trait NoGenericArgsGenericLibFunc: Default {
     const ID: GenericLibFuncId;
@@ -243,12 +301,13 @@ impl<T: NoGenericArgsGenericLibFunc> SignatureOnlyGenericLibFunc for T {
         if args.is_empty() {
             self.specialize_signature(context)
         } else {
-            Err(SpecializationError::WrongNumberOfGenericArgs)
+            Err(SpecializationError::wrong_arg_count(0, args.len()))
         }
     }
 }
 
 /// Information regarding a parameter of the libfunc.
+#[derive(Debug)]
 pub struct ParamSignature {
     /// The type of the parameter.
     pub ty: ConcreteTypeId,
@@ -329,6 +388,9 @@ pub enum SierraApChange {
     Known(usize),
     // The libfunc allocates locals, the `ap` change depends on the environment.
     FinalizeLocals,
+    /// The libfunc is `branch_align`, whose `ap` change is determined by the other branches
+    /// converging with its branch.
+    BranchAlign,
     /// Indicates that the value of ApChange was not assigned properly yet. Behaves as `Unknown`.
     /// This will be removed, once all places using it are fixed.
     // TODO(lior): Remove this value once it is no longer used.
@@ -336,13 +398,40 @@ pub enum SierraApChange {
 }
 /// Trait for a specialized library function.
 pub trait ConcreteLibFunc {
+    /// The full signature of the library function, including the information of every output
+    /// variable in every branch.
+    fn signature(&self) -> &LibFuncSignature;
+
     /// The parameter types and other information for the parameters for calling a library
     /// function.
-    fn param_signatures(&self) -> &[ParamSignature];
+    fn param_signatures(&self) -> &[ParamSignature] {
+        &self.signature().param_signatures
+    }
     /// The output types and other information returning from a library function per branch.
-    fn branch_signatures(&self) -> &[BranchSignature];
+    fn branch_signatures(&self) -> &[BranchSignature] {
+        &self.signature().branch_signatures
+    }
     /// The index of the fallthrough branch of the library function if any.
-    fn fallthrough(&self) -> Option<usize>;
+    fn fallthrough(&self) -> Option<usize> {
+        self.signature().fallthrough
+    }
+
+    /// Returns whether the library function only aligns its branch with other converging
+    /// branches, so its `ap` and gas changes are determined by those branches.
+    fn is_branch_align(&self) -> bool {
+        matches!(
+            self.branch_signatures(),
+            [BranchSignature { ap_change: SierraApChange::BranchAlign, .. }]
+        )
+    }
+
+    /// Returns whether the `ap` tracking is lost after the library function in any of its
+    /// branches, so that temporary variables from before the invocation may no longer be used.
+    fn revokes_ap_tracking(&self) -> bool {
+        self.branch_signatures()
+            .iter()
+            .any(|branch| matches!(branch.ap_change, SierraApChange::Unknown))
+    }
 
     /// Returns the output types returning from a library function per branch.
     fn output_types(&self) -> Vec<Vec<ConcreteTypeId>> {
@@ -356,6 +445,7 @@ pub trait ConcreteLibFunc {
 }
 
 /// Represents the signature of a library function.
+#[derive(Debug)]
 pub struct LibFuncSignature {
     /// The parameter types and other information for the parameters for calling a library
     /// function.
@@ -412,17 +502,22 @@ impl SignatureBasedConcreteLibFunc for SignatureOnlyConcreteLibFunc {
     }
 }
 
+/// Struct providing a ConcreteLibFunc with a signature and the type it was specialized with.
+pub struct SignatureAndTypeConcreteLibFunc {
+    pub ty: ConcreteTypeId,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for SignatureAndTypeConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
 impl<TSignatureBasedConcreteLibFunc: SignatureBasedConcreteLibFunc> ConcreteLibFunc
     for TSignatureBasedConcreteLibFunc
 {
-    fn param_signatures(&self) -> &[ParamSignature] {
-        &self.signature().param_signatures
-    }
-    fn branch_signatures(&self) -> &[BranchSignature] {
-        &self.signature().branch_signatures
-    }
-    fn fallthrough(&self) -> Option<usize> {
-        self.signature().fallthrough
+    fn signature(&self) -> &LibFuncSignature {
+        SignatureBasedConcreteLibFunc::signature(self)
     }
 }
 
@@ -440,25 +535,17 @@ impl<TSignatureBasedConcreteLibFunc: SignatureBasedConcreteLibFunc> ConcreteLibF
 /// ```
 #[macro_export]
 macro_rules! define_concrete_libfunc_hierarchy {
-    (pub enum $name:ident { $($variant_name:ident ($variant:ty),)* }) => {
+    (pub enum $name:ident {
+        $($(#[$attr:meta])* $variant_name:ident ($variant:ty),)*
+    }) => {
         #[allow(clippy::enum_variant_names)]
         pub enum $name {
-            $($variant_name ($variant),)*
+            $($(#[$attr])* $variant_name ($variant),)*
         }
         impl $crate::extensions::ConcreteLibFunc for $name {
-            $crate::extensions::lib_func::concrete_method_impl! {
-                fn param_signatures(&self) -> &[$crate::extensions::lib_func::ParamSignature] {
-                    $($variant_name => $variant,)*
-                }
-            }
-            $crate::extensions::lib_func::concrete_method_impl!{
-                fn branch_signatures(&self) -> &[$crate::extensions::lib_func::BranchSignature] {
-                    $($variant_name => $variant,)*
-                }
-            }
-            $crate::extensions::lib_func::concrete_method_impl!{
-                fn fallthrough(&self) -> Option<usize> {
-                    $($variant_name => $variant,)*
+            $crate::concrete_method_impl! {
+                fn signature(&self) -> &$crate::extensions::lib_func::LibFuncSignature {
+                    $($(#[$attr])* $variant_name => $variant,)*
                 }
             }
         }
@@ -467,21 +554,25 @@ macro_rules! define_concrete_libfunc_hierarchy {
 
 /// Implements a method for an enum of library calls by recursively calling the enum option existing
 /// implementation.
+/// Exported only for use by `define_concrete_libfunc_hierarchy!` in other crates.
+#[doc(hidden)]
+#[macro_export]
 macro_rules! concrete_method_impl {
     (fn $method_name:ident(&self $(,$var_name:ident : $var:ty)*) -> $ret_type:ty {
-        $($variant_name:ident => $variant:ty,)*
+        $($(#[$attr:meta])* $variant_name:ident => $variant:ty,)*
     }) => {
         fn $method_name(&self $(,$var_name:ident : $var:ty)*) -> $ret_type {
             match self {
-                $(Self::$variant_name(value) => value.$method_name()),*
+                $($(#[$attr])* Self::$variant_name(value) => {
+                    $crate::extensions::ConcreteLibFunc::$method_name(value)
+                }),*
             }
         }
     }
 }
-pub(crate) use concrete_method_impl;
 
 /// Forms a libfunc type from an enum of libfuncs.
-/// The new enum implements GenericLibFunc.
+/// The new enum implements GenericLibFunc, and provides the name of its variants.
 /// All the variant types must also implement GenericLibFunc.
 /// Usage example:
 /// ```ignore
@@ -492,21 +583,49 @@ pub(crate) use concrete_method_impl;
 ///     }, MyLibFuncConcrete
 /// }
 /// ```
+/// Hierarchies may be nested, so crates providing additional libfuncs can extend the core set
+/// without forking it:
+/// ```ignore
+/// define_libfunc_hierarchy! {
+///     pub enum ExtendedLibFunc {
+///       Core(CoreLibFunc),
+///       Extra(ExtraLibFunc),
+///     }, ExtendedConcreteLibFunc
+/// }
+/// ```
+/// Variants are queried by id in order, so an id supported by an earlier variant shadows later
+/// ones.
+/// Variants may carry attributes, e.g. `#[cfg(feature = "...")]` for feature-gated libfunc groups,
+/// which are applied to all the code generated for the variant.
 #[macro_export]
 macro_rules! define_libfunc_hierarchy {
-    (pub enum $name:ident { $($variant_name:ident ($variant:ty),)* },
-    $concrete_name:ident) => {
+    (pub enum $name:ident {
+        $($(#[$attr:meta])* $variant_name:ident ($variant:ty),)*
+    }, $concrete_name:ident) => {
         #[allow(clippy::enum_variant_names)]
         pub enum $name {
-            $($variant_name ($variant)),*
+            $($(#[$attr])* $variant_name ($variant)),*
+        }
+
+        impl $name {
+            /// Returns the name of the variant, e.g. for inspecting programs before
+            /// specialization.
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    $($(#[$attr])* Self::$variant_name(_) => stringify!($variant_name)),*
+                }
+            }
         }
 
         impl $crate::extensions::GenericLibFunc for $name {
             type Concrete = $concrete_name;
             fn by_id(id: &$crate::ids::GenericLibFuncId) -> Option<Self> {
                 $(
-                    if let Some(res) = <$variant>::by_id(id){
-                        return Some(Self::$variant_name(res));
+                    $(#[$attr])*
+                    {
+                        if let Some(res) = <$variant>::by_id(id){
+                            return Some(Self::$variant_name(res));
+                        }
                     }
                 )*
                 None
@@ -521,6 +640,7 @@ macro_rules! define_libfunc_hierarchy {
                 >{
                 match self {
                     $(
+                        $(#[$attr])*
                         Self::$variant_name(value) => {
                             <$variant as $crate::extensions::GenericLibFunc>::specialize_signature(
                                 value, context, args,
@@ -536,6 +656,7 @@ macro_rules! define_libfunc_hierarchy {
             ) -> Result<Self::Concrete, $crate::extensions::SpecializationError>{
                 match self {
                     $(
+                        $(#[$attr])*
                         Self::$variant_name(value) => {
                             Ok(Self::Concrete::$variant_name(
                                 <$variant as $crate::extensions::GenericLibFunc>::specialize(
@@ -551,7 +672,10 @@ macro_rules! define_libfunc_hierarchy {
 
         $crate::define_concrete_libfunc_hierarchy! {
             pub enum $concrete_name {
-                $($variant_name (<$variant as $crate::extensions::GenericLibFunc> ::Concrete),)*
+                $(
+                    $(#[$attr])*
+                    $variant_name (<$variant as $crate::extensions::GenericLibFunc> ::Concrete),
+                )*
             }
         }
     }
@@ -5,8 +5,9 @@ use crate::extensions::{NoGenericArgsGenericLibFunc, SpecializationError};
 use crate::ids::GenericLibFuncId;
 
 /// Revoke the ap tracking.
-/// This LibFunc is changes to ap_tracking state to unknown,
-/// allowing a path with known ap tracking to converge with a path with unknown ap tracking.
+/// This LibFunc changes the ap_tracking state to unknown, allowing a path with known ap tracking to
+/// converge with a path with unknown ap tracking. Its unknown ap change marks the revocation in the
+/// signature, as reported by `ConcreteLibFunc::revokes_ap_tracking`.
 #[derive(Default)]
 pub struct RevokeApTrackingLibFunc {}
 impl NoGenericArgsGenericLibFunc for RevokeApTrackingLibFunc {
@@ -1,14 +1,25 @@
-use super::as_single_type;
+use num_bigint::BigInt;
+
+use super::felt::FeltType;
+use super::range_check::RangeCheckType;
+use super::strct::{StructConcreteType, StructType};
 use crate::define_libfunc_hierarchy;
+use crate::extensions::args::{
+    args_as_single_type, args_as_single_user_func, args_as_single_value, args_as_type_and_value,
+};
 use crate::extensions::lib_func::{
-    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
-    SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+    SpecializationContext,
 };
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
-use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::extensions::{
+    ConcreteType, NamedLibFunc, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
 use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
-use crate::program::GenericArg;
+use crate::program::{Function, GenericArg};
 
 /// Type representing an array.
 #[derive(Default)]
@@ -22,7 +33,7 @@ impl NamedType for ArrayType {
         context: &dyn TypeSpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         let info = context.get_type_info(ty.clone())?;
         if info.storable {
             Ok(ArrayConcreteType {
@@ -36,7 +47,7 @@ impl NamedType for ArrayType {
                 ty,
             })
         } else {
-            Err(SpecializationError::UnsupportedGenericArg)
+            Err(SpecializationError::unsupported_arg(args[0].clone()))
         }
     }
 }
@@ -55,8 +66,16 @@ define_libfunc_hierarchy! {
     pub enum ArrayLibFunc {
         New(ArrayNewLibFunc),
         Append(ArrayAppendLibFunc),
-        // TODO(orizi): Add length after libfunc result unpacking is supported.
-        // TODO(orizi): Add access after enums are supported.
+        Reverse(ArrayReverseLibFunc),
+        Zip(ArrayZipLibFunc),
+        Fold(ArrayFoldLibFunc),
+        Eq(ArrayEqLibFunc),
+        FindConst(ArrayFindConstLibFunc),
+        Rotate(ArrayRotateLibFunc),
+        Len(ArrayLenLibFunc),
+        At(ArrayAtLibFunc),
+        PopFront(ArrayPopFrontLibFunc),
+        Slice(ArraySliceLibFunc),
     }, ArrayConcreteLibFunc
 }
 
@@ -71,7 +90,7 @@ impl SignatureOnlyGenericLibFunc for ArrayNewLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![],
             vec![OutputVarInfo {
@@ -94,7 +113,7 @@ impl SignatureOnlyGenericLibFunc for ArrayAppendLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty.clone())?;
         Ok(LibFuncSignature::new_non_branch(
             vec![arr_ty.clone(), ty],
@@ -107,3 +126,570 @@ impl SignatureOnlyGenericLibFunc for ArrayAppendLibFunc {
         ))
     }
 }
+
+/// LibFunc for getting the length of an array.
+#[derive(Default)]
+pub struct ArrayLenLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArrayLenLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_len");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![arr_ty.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: arr_ty,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: context.get_concrete_type(FeltType::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for getting a copy of the element of an array at a given index.
+/// Branches to the first branch with the element if the index is in range, and to the second
+/// branch otherwise.
+#[derive(Default)]
+pub struct ArrayAtLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArrayAtLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_at");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        // The element stays in the array, so returning it duplicates it.
+        if !context.get_type_info(ty.clone())?.duplicatable {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty.clone())?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        let arr_output = || OutputVarInfo {
+            ty: arr_ty.clone(),
+            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(arr_ty.clone()),
+                ParamSignature::new(context.get_concrete_type(FeltType::id(), &[])?),
+            ],
+            branch_signatures: vec![
+                // In range:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        arr_output(),
+                        OutputVarInfo {
+                            ty,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Out of range:
+                BranchSignature {
+                    vars: vec![range_check_output(), arr_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for popping the first element of an array.
+/// Branches to the first branch with the rest of the array and the element if the array is not
+/// empty, and to the second branch with the empty array otherwise.
+#[derive(Default)]
+pub struct ArrayPopFrontLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArrayPopFrontLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_pop_front");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty.clone())?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(arr_ty.clone())],
+            branch_signatures: vec![
+                // Non-empty:
+                BranchSignature {
+                    vars: vec![
+                        OutputVarInfo {
+                            ty: arr_ty.clone(),
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                        OutputVarInfo {
+                            ty,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Empty:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: arr_ty,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for getting a copy of the `length` elements of an array starting at index `start`.
+/// Branches to the first branch with the slice if it is in range, and to the second branch
+/// otherwise.
+#[derive(Default)]
+pub struct ArraySliceLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArraySliceLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_slice");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        // The elements stay in the array, so returning them duplicates them.
+        if !context.get_type_info(ty.clone())?.duplicatable {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        let arr_output = || OutputVarInfo {
+            ty: arr_ty.clone(),
+            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(arr_ty.clone()),
+                // Start:
+                ParamSignature::new(felt_ty.clone()),
+                // Length:
+                ParamSignature::new(felt_ty),
+            ],
+            branch_signatures: vec![
+                // In range:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        arr_output(),
+                        OutputVarInfo {
+                            ty: arr_ty.clone(),
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Out of range:
+                BranchSignature {
+                    vars: vec![range_check_output(), arr_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for reversing the order of the elements of an array.
+#[derive(Default)]
+pub struct ArrayReverseLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArrayReverseLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_reverse");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![arr_ty.clone()],
+            vec![OutputVarInfo {
+                ty: arr_ty,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Unknown,
+        ))
+    }
+}
+
+/// LibFunc for zipping two arrays of the same length into an array of pairs, where the pair type
+/// is a struct with exactly two members. Branches to the first branch with the zipped array if
+/// the lengths are equal, and to the second branch with the original arrays otherwise.
+#[derive(Default)]
+pub struct ArrayZipLibFunc {}
+impl NamedLibFunc for ArrayZipLibFunc {
+    type Concrete = ArrayZipConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_zip");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(ArrayZipConcreteLibFunc::new(context, args)?.signature)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        ArrayZipConcreteLibFunc::new(context.upcast(), args)
+    }
+}
+
+pub struct ArrayZipConcreteLibFunc {
+    /// The element type of the first array.
+    pub first_ty: ConcreteTypeId,
+    /// The element type of the second array.
+    pub second_ty: ConcreteTypeId,
+    /// The struct type of the elements of the zipped array.
+    pub pair_ty: ConcreteTypeId,
+    /// The type of the zipped array.
+    pub result_ty: ConcreteTypeId,
+    pub signature: LibFuncSignature,
+}
+impl ArrayZipConcreteLibFunc {
+    fn new(
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self, SpecializationError> {
+        let pair_ty = args_as_single_type(args)?;
+        let long_id = context.get_type_info(pair_ty.clone())?.long_id;
+        if long_id.generic_id != StructType::ID {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        let members = StructConcreteType::new(
+            context.as_type_specialization_context(),
+            &long_id.generic_args,
+        )?
+        .members;
+        let (first_ty, second_ty) = match &members[..] {
+            [first_ty, second_ty] => (first_ty.clone(), second_ty.clone()),
+            _ => return Err(SpecializationError::unsupported_arg(args[0].clone())),
+        };
+        let first_arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), first_ty.clone())?;
+        let second_arr_ty =
+            context.get_wrapped_concrete_type(ArrayType::id(), second_ty.clone())?;
+        let result_ty = context.get_wrapped_concrete_type(ArrayType::id(), pair_ty.clone())?;
+        let signature = LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(first_arr_ty.clone()),
+                ParamSignature::new(second_arr_ty.clone()),
+            ],
+            branch_signatures: vec![
+                // Equal length:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: result_ty.clone(),
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    }],
+                    ap_change: SierraApChange::Unknown,
+                },
+                // Mismatched length:
+                BranchSignature {
+                    vars: vec![
+                        OutputVarInfo {
+                            ty: first_arr_ty,
+                            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                        },
+                        OutputVarInfo {
+                            ty: second_arr_ty,
+                            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+                        },
+                    ],
+                    ap_change: SierraApChange::Unknown,
+                },
+            ],
+            fallthrough: Some(0),
+        };
+        Ok(Self { first_ty, second_ty, pair_ty, result_ty, signature })
+    }
+}
+impl SignatureBasedConcreteLibFunc for ArrayZipConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for folding an array into an accumulator, by calling a user function taking the
+/// accumulator and an element, and returning the new accumulator, on each element in order.
+#[derive(Default)]
+pub struct ArrayFoldLibFunc {}
+impl NamedLibFunc for ArrayFoldLibFunc {
+    type Concrete = ArrayFoldConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_fold");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (accumulator_ty, element_ty) = get_fold_types(context, args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![
+                context.get_wrapped_concrete_type(ArrayType::id(), element_ty)?,
+                accumulator_ty.clone(),
+            ],
+            vec![OutputVarInfo {
+                ty: accumulator_ty,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Unknown,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (accumulator_ty, element_ty) = get_fold_types(context.upcast(), args)?;
+        Ok(ArrayFoldConcreteLibFunc {
+            function: context.get_function(&args_as_single_user_func(args)?)?,
+            element_ty,
+            accumulator_ty,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Returns the accumulator and element types of `array_fold`, as deduced from the signature of
+/// the folding function.
+fn get_fold_types(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, ConcreteTypeId), SpecializationError> {
+    let signature = context.get_function_signature(&args_as_single_user_func(args)?)?;
+    match (&signature.param_types[..], &signature.ret_types[..]) {
+        ([accumulator_ty, element_ty], [ret_ty]) if accumulator_ty == ret_ty => {
+            Ok((accumulator_ty.clone(), element_ty.clone()))
+        }
+        _ => Err(SpecializationError::unsupported_arg(args[0].clone())),
+    }
+}
+
+pub struct ArrayFoldConcreteLibFunc {
+    /// The folding function.
+    pub function: Function,
+    pub element_ty: ConcreteTypeId,
+    pub accumulator_ty: ConcreteTypeId,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for ArrayFoldConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for checking whether two arrays are equal element-wise. Branches to the first branch if
+/// equal, and to the second otherwise, returning both arrays on either branch.
+#[derive(Default)]
+pub struct ArrayEqLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArrayEqLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_eq");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        let branch_signature = || BranchSignature {
+            vars: vec![
+                OutputVarInfo {
+                    ty: arr_ty.clone(),
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: arr_ty.clone(),
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+                },
+            ],
+            ap_change: SierraApChange::Unknown,
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(arr_ty.clone()),
+                ParamSignature::new(arr_ty.clone()),
+            ],
+            // Equal, and not equal.
+            branch_signatures: vec![branch_signature(), branch_signature()],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for finding the index of the first element of a felt array equal to a constant.
+/// Branches to the first branch with the index if found, and to the second branch otherwise.
+#[derive(Default)]
+pub struct ArrayFindConstLibFunc {}
+impl NamedLibFunc for ArrayFindConstLibFunc {
+    type Concrete = ArrayFindConstConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_find_const");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        args_as_single_value(args)?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), felt_type.clone())?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        let arr_output = || OutputVarInfo {
+            ty: arr_ty.clone(),
+            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(arr_ty.clone()),
+            ],
+            branch_signatures: vec![
+                // Found:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        arr_output(),
+                        OutputVarInfo {
+                            ty: felt_type,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::Unknown,
+                },
+                // Not found:
+                BranchSignature {
+                    vars: vec![range_check_output(), arr_output()],
+                    ap_change: SierraApChange::Unknown,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(ArrayFindConstConcreteLibFunc {
+            target: args_as_single_value(args)?,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct ArrayFindConstConcreteLibFunc {
+    /// The searched value.
+    pub target: BigInt,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for ArrayFindConstConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for rotating an array to the left by a constant offset, modulo the array's length.
+#[derive(Default)]
+pub struct ArrayRotateLibFunc {}
+impl NamedLibFunc for ArrayRotateLibFunc {
+    type Concrete = ArrayRotateConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_rotate");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (ty, _) = as_rotate_args(args)?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![arr_ty.clone()],
+            vec![OutputVarInfo {
+                ty: arr_ty,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Unknown,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, offset) = as_rotate_args(args)?;
+        Ok(ArrayRotateConcreteLibFunc {
+            offset,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Returns the element type and the offset given as the generic args of `array_rotate`.
+fn as_rotate_args(args: &[GenericArg]) -> Result<(ConcreteTypeId, usize), SpecializationError> {
+    let (ty, offset) = args_as_type_and_value(args)?;
+    let offset = usize::try_from(offset)
+        .map_err(|_| SpecializationError::unsupported_arg(args[1].clone()))?;
+    Ok((ty, offset))
+}
+
+pub struct ArrayRotateConcreteLibFunc {
+    /// The number of positions the array is rotated to the left by.
+    pub offset: usize,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for ArrayRotateConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
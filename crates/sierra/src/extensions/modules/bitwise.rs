@@ -0,0 +1,114 @@
+use super::felt::FeltType;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, NamedType, TypeInfo};
+use crate::extensions::{
+    GenericLibFunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SignatureBasedConcreteLibFunc, SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type for Bitwise builtin.
+#[derive(Default)]
+pub struct BitwiseType {}
+impl NoGenericArgsGenericType for BitwiseType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Bitwise");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
+
+/// Bitwise operators.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BitwiseOperator {
+    And,
+    Or,
+    Xor,
+}
+
+/// LibFunc for bitwise operations on felts, using the Bitwise builtin.
+pub struct BitwiseLibFunc {
+    pub operator: BitwiseOperator,
+}
+impl BitwiseLibFunc {
+    fn new(operator: BitwiseOperator) -> Self {
+        Self { operator }
+    }
+}
+impl GenericLibFunc for BitwiseLibFunc {
+    type Concrete = BitwiseConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        const AND: GenericLibFuncId = GenericLibFuncId::new_inline("bitwise_and");
+        const OR: GenericLibFuncId = GenericLibFuncId::new_inline("bitwise_or");
+        const XOR: GenericLibFuncId = GenericLibFuncId::new_inline("bitwise_xor");
+        match id {
+            id if id == &AND => Some(Self::new(BitwiseOperator::And)),
+            id if id == &OR => Some(Self::new(BitwiseOperator::Or)),
+            id if id == &XOR => Some(Self::new(BitwiseOperator::Xor)),
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        let bitwise_type = context.get_concrete_type(BitwiseType::id(), &[])?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![bitwise_type.clone(), felt_type.clone(), felt_type.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: bitwise_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: felt_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(BitwiseConcreteLibFunc {
+            operator: self.operator,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct BitwiseConcreteLibFunc {
+    pub operator: BitwiseOperator,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for BitwiseConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
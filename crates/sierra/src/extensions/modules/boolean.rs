@@ -0,0 +1,188 @@
+use super::felt::FeltType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    GenericLibFunc, NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType,
+    OutputVarReferenceInfo, SignatureBasedConcreteLibFunc, SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type for booleans, represented by a felt which is either 0 or 1.
+#[derive(Default)]
+pub struct BoolType {}
+impl NoGenericArgsGenericType for BoolType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("bool");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 1,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum BoolLibFunc {
+        Operation(BoolOperationLibFunc),
+        Not(BoolNotLibFunc),
+        ToFelt(BoolToFeltLibFunc),
+        FromFelt(BoolFromFeltLibFunc),
+    }, BoolConcreteLibFunc
+}
+
+/// Binary operators for booleans.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BoolOperator {
+    And,
+    Or,
+    Xor,
+}
+
+/// LibFunc for binary operations on booleans.
+pub struct BoolOperationLibFunc {
+    pub operator: BoolOperator,
+}
+impl BoolOperationLibFunc {
+    fn new(operator: BoolOperator) -> Self {
+        Self { operator }
+    }
+}
+impl GenericLibFunc for BoolOperationLibFunc {
+    type Concrete = BoolOperationConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        const AND: GenericLibFuncId = GenericLibFuncId::new_inline("bool_and");
+        const OR: GenericLibFuncId = GenericLibFuncId::new_inline("bool_or");
+        const XOR: GenericLibFuncId = GenericLibFuncId::new_inline("bool_xor");
+        match id {
+            id if id == &AND => Some(Self::new(BoolOperator::And)),
+            id if id == &OR => Some(Self::new(BoolOperator::Or)),
+            id if id == &XOR => Some(Self::new(BoolOperator::Xor)),
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        let bool_type = context.get_concrete_type(BoolType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![bool_type.clone(), bool_type.clone()],
+            vec![OutputVarInfo {
+                ty: bool_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(BoolOperationConcreteLibFunc {
+            operator: self.operator,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct BoolOperationConcreteLibFunc {
+    pub operator: BoolOperator,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for BoolOperationConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for negating a boolean.
+#[derive(Default)]
+pub struct BoolNotLibFunc {}
+impl NoGenericArgsGenericLibFunc for BoolNotLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bool_not");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let bool_type = context.get_concrete_type(BoolType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![bool_type.clone()],
+            vec![OutputVarInfo {
+                ty: bool_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for converting a boolean into a felt.
+#[derive(Default)]
+pub struct BoolToFeltLibFunc {}
+impl NoGenericArgsGenericLibFunc for BoolToFeltLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bool_to_felt");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature::new_non_branch(
+            vec![context.get_concrete_type(BoolType::id(), &[])?],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(FeltType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for converting a felt into a boolean.
+/// Branches to the first branch if the felt is 0 or 1, and to the second branch otherwise.
+#[derive(Default)]
+pub struct BoolFromFeltLibFunc {}
+impl NoGenericArgsGenericLibFunc for BoolFromFeltLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bool_from_felt");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(
+                context.get_concrete_type(FeltType::id(), &[])?,
+            )],
+            branch_signatures: vec![
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: context.get_concrete_type(BoolType::id(), &[])?,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                BranchSignature { vars: vec![], ap_change: SierraApChange::NotImplemented },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
@@ -1,5 +1,5 @@
-use super::as_single_type;
 use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
     SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
@@ -22,7 +22,7 @@ impl NamedType for BoxType {
         context: &dyn TypeSpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         Ok(BoxConcreteType { info: context.get_type_info(ty.clone())?, ty })
     }
 }
@@ -55,7 +55,7 @@ impl SignatureOnlyGenericLibFunc for IntoBoxLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![ty.clone()],
             vec![OutputVarInfo {
@@ -78,7 +78,7 @@ impl SignatureOnlyGenericLibFunc for UnboxLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![context.get_wrapped_concrete_type(BoxType::id(), ty.clone())?],
             vec![OutputVarInfo {
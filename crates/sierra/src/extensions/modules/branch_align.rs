@@ -0,0 +1,21 @@
+use crate::extensions::lib_func::{
+    LibFuncSignature, SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::{NoGenericArgsGenericLibFunc, SpecializationError};
+use crate::ids::GenericLibFuncId;
+
+/// Aligns the ap and gas changes of a branch with the other branches converging with it.
+/// Invoked at the start of branches that merge, it has no inputs and no outputs, and its effect is
+/// determined by the branches it is aligned with rather than by the libfunc itself.
+#[derive(Default)]
+pub struct BranchAlignLibFunc {}
+impl NoGenericArgsGenericLibFunc for BranchAlignLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("branch_align");
+
+    fn specialize_signature(
+        &self,
+        _context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature::new_non_branch(vec![], vec![], SierraApChange::BranchAlign))
+    }
+}
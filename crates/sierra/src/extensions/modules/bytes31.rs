@@ -0,0 +1,193 @@
+//! The bytes31 type, holding up to 31 bytes packed into a single felt, as a building block for
+//! representing string-like data.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type uint8 = uint8;
+//! type bytes31 = bytes31;
+//! libfunc bytes31_try_from_felt = bytes31_try_from_felt;
+//! libfunc bytes31_concat = bytes31_concat;
+//! ...
+//! bytes31_try_from_felt(rc, x) { fallthrough(rc, a) out_of_range(rc) };
+//! bytes31_concat(rc, a, b, b_len) { fallthrough(rc, ab) too_long(rc) };
+//! ```
+
+use super::felt::FeltType;
+use super::range_check::RangeCheckType;
+use super::uint::Uint8Type;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+
+/// The maximal number of bytes a bytes31 can hold.
+pub const BYTES31_MAX_LEN: u32 = 31;
+
+/// Type for bytes31, a felt known to be smaller than 2^248.
+#[derive(Default)]
+pub struct Bytes31Type {}
+impl NoGenericArgsGenericType for Bytes31Type {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("bytes31");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 1,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum Bytes31LibFunc {
+        TryFromFelt(Bytes31TryFromFeltLibFunc),
+        ToFelt(Bytes31ToFeltLibFunc),
+        Concat(Bytes31ConcatLibFunc),
+        Split(Bytes31SplitLibFunc),
+    }, Bytes31ConcreteLibFunc
+}
+
+/// Returns the signature of a libfunc taking a range check and the given params, branching to the
+/// first branch with the given outputs on success, and to the second branch with only the range
+/// check otherwise.
+pub(super) fn range_checked_branch_signature(
+    context: &dyn SignatureSpecializationContext,
+    params: Vec<ConcreteTypeId>,
+    success_outputs: Vec<OutputVarInfo>,
+) -> Result<LibFuncSignature, SpecializationError> {
+    let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+    let range_check_output = || OutputVarInfo {
+        ty: range_check_type.clone(),
+        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst { param_idx: 0 }),
+    };
+    Ok(LibFuncSignature {
+        param_signatures: [range_check_type.clone()]
+            .into_iter()
+            .chain(params)
+            .map(ParamSignature::new)
+            .collect(),
+        branch_signatures: vec![
+            // Success:
+            BranchSignature {
+                vars: [range_check_output()].into_iter().chain(success_outputs).collect(),
+                ap_change: SierraApChange::NotImplemented,
+            },
+            // Failure:
+            BranchSignature {
+                vars: vec![range_check_output()],
+                ap_change: SierraApChange::NotImplemented,
+            },
+        ],
+        fallthrough: Some(0),
+    })
+}
+
+/// LibFunc for converting a felt into a bytes31.
+/// Branches to the first branch with the value if it fits in 31 bytes, and to the second branch
+/// otherwise.
+#[derive(Default)]
+pub struct Bytes31TryFromFeltLibFunc {}
+impl NoGenericArgsGenericLibFunc for Bytes31TryFromFeltLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bytes31_try_from_felt");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        range_checked_branch_signature(
+            context,
+            vec![context.get_concrete_type(FeltType::id(), &[])?],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(Bytes31Type::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+            }],
+        )
+    }
+}
+
+/// LibFunc for converting a bytes31 into a felt.
+#[derive(Default)]
+pub struct Bytes31ToFeltLibFunc {}
+impl NoGenericArgsGenericLibFunc for Bytes31ToFeltLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bytes31_to_felt");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature::new_non_branch(
+            vec![context.get_concrete_type(Bytes31Type::id(), &[])?],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(FeltType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for appending the lowest `b_len` bytes of `b` after the bytes of `a`.
+/// Branches to the first branch with the concatenation if `b` fits in `b_len` bytes and the result
+/// fits in 31 bytes, and to the second branch otherwise.
+#[derive(Default)]
+pub struct Bytes31ConcatLibFunc {}
+impl NoGenericArgsGenericLibFunc for Bytes31ConcatLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bytes31_concat");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let bytes31_type = context.get_concrete_type(Bytes31Type::id(), &[])?;
+        range_checked_branch_signature(
+            context,
+            vec![
+                bytes31_type.clone(),
+                bytes31_type.clone(),
+                context.get_concrete_type(Uint8Type::id(), &[])?,
+            ],
+            vec![OutputVarInfo {
+                ty: bytes31_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// LibFunc for splitting a bytes31 into its high bytes and its lowest `n` bytes.
+/// Branches to the first branch with the high and low parts if `n` is at most 31, and to the
+/// second branch otherwise.
+#[derive(Default)]
+pub struct Bytes31SplitLibFunc {}
+impl NoGenericArgsGenericLibFunc for Bytes31SplitLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("bytes31_split");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let bytes31_type = context.get_concrete_type(Bytes31Type::id(), &[])?;
+        let bytes31_output = || OutputVarInfo {
+            ty: bytes31_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        range_checked_branch_signature(
+            context,
+            vec![bytes31_type.clone(), context.get_concrete_type(Uint8Type::id(), &[])?],
+            vec![bytes31_output(), bytes31_output()],
+        )
+    }
+}
@@ -0,0 +1,186 @@
+use super::felt::FeltType;
+use super::integer::bounded_int_bit_width;
+use super::range_check::RangeCheckType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_two_types;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::{
+    NamedLibFunc, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId};
+use crate::program::GenericArg;
+
+define_libfunc_hierarchy! {
+    pub enum CastLibFunc {
+        Upcast(UpcastLibFunc),
+        Downcast(DowncastLibFunc),
+    }, CastConcreteLibFunc
+}
+
+/// A type that can be cast from or into.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CastType {
+    /// A bounded unsigned integer of the given bit width.
+    Uint(u32),
+    /// A felt, which can hold any bounded unsigned integer.
+    Felt,
+}
+impl CastType {
+    /// Returns whether every value of `other` is also a value of `self`, while the opposite does
+    /// not hold.
+    fn is_wider_than(&self, other: &CastType) -> bool {
+        match (self, other) {
+            (CastType::Felt, CastType::Uint(_)) => true,
+            (CastType::Uint(bits), CastType::Uint(other_bits)) => bits > other_bits,
+            (CastType::Uint(_) | CastType::Felt, CastType::Felt) => false,
+        }
+    }
+}
+
+/// Returns the cast type of the given concrete type, or an error if it can not be cast.
+fn get_cast_type(
+    context: &dyn SignatureSpecializationContext,
+    ty: ConcreteTypeId,
+) -> Result<CastType, SpecializationError> {
+    let generic_id = context.get_type_info(ty.clone())?.long_id.generic_id;
+    if generic_id == FeltType::ID {
+        Ok(CastType::Felt)
+    } else {
+        bounded_int_bit_width(&generic_id)
+            .map(CastType::Uint)
+            .ok_or_else(|| SpecializationError::unsupported_arg(GenericArg::Type(ty)))
+    }
+}
+
+/// A concrete type along with its cast type.
+type CastTypeInfo = (ConcreteTypeId, CastType);
+
+/// Returns the source and target types of a cast, along with their cast types.
+fn get_cast_types(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(CastTypeInfo, CastTypeInfo), SpecializationError> {
+    let (from, to) = args_as_two_types(args)?;
+    Ok(((from.clone(), get_cast_type(context, from)?), (to.clone(), get_cast_type(context, to)?)))
+}
+
+/// Concrete cast libfunc, keeping the cast types of the source and the target.
+pub struct CastConcreteLibFuncInfo {
+    pub from: CastType,
+    pub to: CastType,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for CastConcreteLibFuncInfo {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for casting a value into a strictly wider type, which always succeeds.
+#[derive(Default)]
+pub struct UpcastLibFunc {}
+impl NamedLibFunc for UpcastLibFunc {
+    type Concrete = CastConcreteLibFuncInfo;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("upcast");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ((from_ty, from), (to_ty, to)) = get_cast_types(context, args)?;
+        if !to.is_wider_than(&from) {
+            return Err(SpecializationError::unsupported_arg(args[1].clone()));
+        }
+        Ok(LibFuncSignature::new_non_branch(
+            vec![from_ty],
+            vec![OutputVarInfo {
+                ty: to_ty,
+                ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ((_, from), (_, to)) = get_cast_types(context.upcast(), args)?;
+        Ok(CastConcreteLibFuncInfo {
+            from,
+            to,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+/// LibFunc for casting a value into a strictly narrower type. Branches to the first branch with
+/// the cast value if it fits in the target type, and to the second branch otherwise.
+#[derive(Default)]
+pub struct DowncastLibFunc {}
+impl NamedLibFunc for DowncastLibFunc {
+    type Concrete = CastConcreteLibFuncInfo;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("downcast");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ((from_ty, from), (to_ty, to)) = get_cast_types(context, args)?;
+        if !from.is_wider_than(&to) {
+            return Err(SpecializationError::unsupported_arg(args[1].clone()));
+        }
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(from_ty),
+            ],
+            branch_signatures: vec![
+                // In range:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        OutputVarInfo {
+                            ty: to_ty,
+                            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Out of range:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ((_, from), (_, to)) = get_cast_types(context.upcast(), args)?;
+        Ok(CastConcreteLibFuncInfo {
+            from,
+            to,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
@@ -0,0 +1,666 @@
+//! Circuits of modular arithmetic gates, evaluated using the AddMod and MulMod builtins.
+//!
+//! A circuit is described by types: `CircuitInput<i>` is the circuit's `i`th input, the gate types
+//! apply a modular operation to their operands, and `Circuit<...>` lists the circuit's outputs.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type NonZeroFelt = NonZero<felt>;
+//! type AddMod = AddMod;
+//! type MulMod = MulMod;
+//! type In0 = CircuitInput<0>;
+//! type In1 = CircuitInput<1>;
+//! type Sum = AddModGate<In0, In1>;
+//! type Product = MulModGate<Sum, In1>;
+//! type C = Circuit<Product>;
+//! type Accumulator = CircuitInputAccumulator<C>;
+//! type Data = CircuitData<C>;
+//! type Outputs = CircuitOutputs<C>;
+//! libfunc init_circuit_data = init_circuit_data<C>;
+//! libfunc add_circuit_input = add_circuit_input<C>;
+//! libfunc eval_circuit = eval_circuit<C>;
+//! libfunc get_product = get_circuit_output<C, 0>;
+//! ...
+//! init_circuit_data() -> (acc);
+//! add_circuit_input(acc, a) { fallthrough(acc) done(data) };
+//! eval_circuit(add_mod, mul_mod, data, modulus) { fallthrough(add_mod, mul_mod, outputs) fail(add_mod, mul_mod) };
+//! get_product(outputs) -> (product);
+//! ```
+
+use num_traits::{Signed, ToPrimitive};
+use utils::try_extract_matches;
+
+use super::felt::FeltType;
+use super::non_zero::NonZeroType;
+use crate::extensions::args::{args_as_single_type, args_as_type_and_value};
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+    SpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::{GenericType, InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    ArgCount, ConcreteType, GenericArgKind, NamedLibFunc, NamedType, NoGenericArgsGenericType,
+    OutputVarReferenceInfo, SignatureBasedConcreteLibFunc, SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+use crate::program::{ConcreteTypeLongId, GenericArg};
+use crate::{define_libfunc_hierarchy, define_type_hierarchy};
+
+define_type_hierarchy! {
+    pub enum CircuitType {
+        AddMod(AddModType),
+        MulMod(MulModType),
+        Input(CircuitInputType),
+        Gate(CircuitGateType),
+        Circuit(CircuitDescriptionType),
+        InputAccumulator(CircuitInputAccumulatorType),
+        Data(CircuitDataType),
+        Outputs(CircuitOutputsType),
+    }, CircuitTypeConcrete
+}
+
+/// Returns the info of a builtin type.
+fn builtin_info(long_id: ConcreteTypeLongId) -> InfoOnlyConcreteType {
+    InfoOnlyConcreteType {
+        info: TypeInfo { long_id, storable: true, droppable: false, duplicatable: false, size: 1 },
+    }
+}
+
+/// Type for the AddMod builtin, applying modular additions.
+#[derive(Default)]
+pub struct AddModType {}
+impl NoGenericArgsGenericType for AddModType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("AddMod");
+
+    fn specialize(&self) -> Self::Concrete {
+        builtin_info(Self::concrete_type_long_id(&[]))
+    }
+}
+
+/// Type for the MulMod builtin, applying modular multiplications.
+#[derive(Default)]
+pub struct MulModType {}
+impl NoGenericArgsGenericType for MulModType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("MulMod");
+
+    fn specialize(&self) -> Self::Concrete {
+        builtin_info(Self::concrete_type_long_id(&[]))
+    }
+}
+
+/// Returns the info of a type only describing a circuit, which has no values.
+fn descriptor_info(long_id: ConcreteTypeLongId) -> TypeInfo {
+    TypeInfo { long_id, storable: false, droppable: true, duplicatable: true, size: 0 }
+}
+
+/// Type for an input of a circuit, given by its index.
+#[derive(Default)]
+pub struct CircuitInputType {}
+impl NamedType for CircuitInputType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("CircuitInput");
+
+    fn specialize(
+        &self,
+        _context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        extract_input_index(args)?;
+        Ok(InfoOnlyConcreteType { info: descriptor_info(Self::concrete_type_long_id(args)) })
+    }
+}
+
+/// Returns the index of a circuit input, given its generic args.
+fn extract_input_index(args: &[GenericArg]) -> Result<usize, SpecializationError> {
+    match args {
+        [GenericArg::Value(index)] if !index.is_negative() => {
+            index.to_usize().ok_or_else(|| SpecializationError::unsupported_arg(args[0].clone()))
+        }
+        [GenericArg::Value(_)] => Err(SpecializationError::unsupported_arg(args[0].clone())),
+        [arg] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value)),
+        _ => Err(SpecializationError::wrong_arg_count(1, args.len())),
+    }
+}
+
+/// The modular operations a circuit gate can apply.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GateOperator {
+    Add,
+    Sub,
+    Mul,
+    Inverse,
+}
+impl GateOperator {
+    /// Returns the number of operands the operator takes.
+    fn arity(&self) -> usize {
+        match self {
+            GateOperator::Add | GateOperator::Sub | GateOperator::Mul => 2,
+            GateOperator::Inverse => 1,
+        }
+    }
+}
+
+/// Type for a gate of a circuit, applying a modular operation to its operands, which are inputs
+/// or other gates.
+pub struct CircuitGateType {
+    pub operator: GateOperator,
+}
+impl CircuitGateType {
+    const ADD: GenericTypeId = GenericTypeId::new_inline("AddModGate");
+    const SUB: GenericTypeId = GenericTypeId::new_inline("SubModGate");
+    const MUL: GenericTypeId = GenericTypeId::new_inline("MulModGate");
+    const INVERSE: GenericTypeId = GenericTypeId::new_inline("InverseGate");
+
+    /// Returns the operator of the gate with the given generic id.
+    fn operator_by_id(id: &GenericTypeId) -> Option<GateOperator> {
+        match id {
+            id if id == &Self::ADD => Some(GateOperator::Add),
+            id if id == &Self::SUB => Some(GateOperator::Sub),
+            id if id == &Self::MUL => Some(GateOperator::Mul),
+            id if id == &Self::INVERSE => Some(GateOperator::Inverse),
+            _ => None,
+        }
+    }
+}
+impl GenericType for CircuitGateType {
+    type Concrete = InfoOnlyConcreteType;
+
+    fn by_id(id: &GenericTypeId) -> Option<Self> {
+        Self::operator_by_id(id).map(|operator| Self { operator })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let generic_id = match self.operator {
+            GateOperator::Add => Self::ADD,
+            GateOperator::Sub => Self::SUB,
+            GateOperator::Mul => Self::MUL,
+            GateOperator::Inverse => Self::INVERSE,
+        };
+        let long_id = ConcreteTypeLongId { generic_id, generic_args: args.to_vec() };
+        gate_operation(context, self.operator, args)?;
+        Ok(InfoOnlyConcreteType { info: descriptor_info(long_id) })
+    }
+}
+
+/// A node in the expression tree computing an output of a circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitGate {
+    Input(usize),
+    Operation { operator: GateOperator, operands: Vec<CircuitGate> },
+}
+impl CircuitGate {
+    /// Returns the number of operations in the gate's expression tree.
+    fn n_operations(&self) -> usize {
+        match self {
+            CircuitGate::Input(_) => 0,
+            CircuitGate::Operation { operands, .. } => {
+                1 + operands.iter().map(CircuitGate::n_operations).sum::<usize>()
+            }
+        }
+    }
+
+    /// Adds the indices of the inputs used by the gate to `indices`.
+    fn collect_inputs(&self, indices: &mut Vec<usize>) {
+        match self {
+            CircuitGate::Input(index) => indices.push(*index),
+            CircuitGate::Operation { operands, .. } => {
+                operands.iter().for_each(|operand| operand.collect_inputs(indices))
+            }
+        }
+    }
+}
+
+/// Returns the gate described by a circuit element type.
+fn gate_from_type(
+    context: &dyn TypeSpecializationContext,
+    ty: ConcreteTypeId,
+) -> Result<CircuitGate, SpecializationError> {
+    let long_id = context.get_type_info(ty.clone())?.long_id;
+    if long_id.generic_id == CircuitInputType::ID {
+        return Ok(CircuitGate::Input(extract_input_index(&long_id.generic_args)?));
+    }
+    let operator = CircuitGateType::operator_by_id(&long_id.generic_id)
+        .ok_or_else(|| SpecializationError::unsupported_arg(GenericArg::Type(ty)))?;
+    gate_operation(context, operator, &long_id.generic_args)
+}
+
+/// Returns the gate applying the operator to the operands given as generic args.
+fn gate_operation(
+    context: &dyn TypeSpecializationContext,
+    operator: GateOperator,
+    args: &[GenericArg],
+) -> Result<CircuitGate, SpecializationError> {
+    if args.len() != operator.arity() {
+        return Err(SpecializationError::wrong_arg_count(operator.arity(), args.len()));
+    }
+    let operands = args
+        .iter()
+        .map(|arg| {
+            let ty = try_extract_matches!(arg, GenericArg::Type)
+                .ok_or_else(|| SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type))?;
+            gate_from_type(context, ty.clone())
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(CircuitGate::Operation { operator, operands })
+}
+
+/// The structure of a circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitInfo {
+    /// The number of inputs of the circuit.
+    pub n_inputs: usize,
+    /// The gates computing the circuit's outputs.
+    pub outputs: Vec<CircuitGate>,
+}
+impl CircuitInfo {
+    /// Returns the circuit described by the generic args of a `Circuit` type.
+    fn new(
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self, SpecializationError> {
+        if args.is_empty() {
+            return Err(SpecializationError::WrongNumberOfGenericArgs {
+                expected: ArgCount::AtLeast(1),
+                actual: 0,
+            });
+        }
+        let outputs: Vec<_> = args
+            .iter()
+            .map(|arg| {
+                let ty = try_extract_matches!(arg, GenericArg::Type).ok_or_else(|| {
+                    SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type)
+                })?;
+                gate_from_type(context, ty.clone())
+            })
+            .collect::<Result<_, _>>()?;
+        let mut indices = vec![];
+        outputs.iter().for_each(|output| output.collect_inputs(&mut indices));
+        indices.sort_unstable();
+        indices.dedup();
+        // The inputs are provided in order, so every index up to the last must be used.
+        if let Some((i, _)) = indices.iter().enumerate().find(|(i, index)| i != *index) {
+            // The first missing input index.
+            return Err(SpecializationError::unsupported_arg(GenericArg::Value(i.into())));
+        }
+        Ok(Self { n_inputs: indices.len(), outputs })
+    }
+
+    /// Returns the number of modular operations required to evaluate the circuit.
+    pub fn n_operations(&self) -> usize {
+        self.outputs.iter().map(CircuitGate::n_operations).sum()
+    }
+}
+
+/// Returns the circuit described by a `Circuit` type.
+fn get_circuit_info(
+    context: &dyn TypeSpecializationContext,
+    ty: ConcreteTypeId,
+) -> Result<CircuitInfo, SpecializationError> {
+    let long_id = context.get_type_info(ty.clone())?.long_id;
+    if long_id.generic_id != CircuitDescriptionType::ID {
+        return Err(SpecializationError::unsupported_arg(GenericArg::Type(ty)));
+    }
+    CircuitInfo::new(context, &long_id.generic_args)
+}
+
+/// Type describing a circuit, given its output gates.
+#[derive(Default)]
+pub struct CircuitDescriptionType {}
+impl NamedType for CircuitDescriptionType {
+    type Concrete = CircuitConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Circuit");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(CircuitConcreteType {
+            info: descriptor_info(Self::concrete_type_long_id(args)),
+            circuit: CircuitInfo::new(context, args)?,
+        })
+    }
+}
+
+pub struct CircuitConcreteType {
+    pub info: TypeInfo,
+    pub circuit: CircuitInfo,
+}
+impl ConcreteType for CircuitConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+/// Specializes a type holding values of a circuit, given the circuit as its single generic arg.
+fn specialize_circuit_value_type(
+    context: &dyn TypeSpecializationContext,
+    long_id: ConcreteTypeLongId,
+    duplicatable: bool,
+    size: usize,
+) -> Result<InfoOnlyConcreteType, SpecializationError> {
+    get_circuit_info(context, args_as_single_type(&long_id.generic_args)?)?;
+    Ok(InfoOnlyConcreteType {
+        info: TypeInfo { long_id, storable: true, droppable: true, duplicatable, size },
+    })
+}
+
+/// Type for the inputs of a circuit, while they are being provided.
+#[derive(Default)]
+pub struct CircuitInputAccumulatorType {}
+impl NamedType for CircuitInputAccumulatorType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("CircuitInputAccumulator");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        // The start and the end of the inputs provided so far.
+        specialize_circuit_value_type(context, Self::concrete_type_long_id(args), false, 2)
+    }
+}
+
+/// Type for all the inputs of a circuit, ready for evaluation.
+#[derive(Default)]
+pub struct CircuitDataType {}
+impl NamedType for CircuitDataType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("CircuitData");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        specialize_circuit_value_type(context, Self::concrete_type_long_id(args), false, 1)
+    }
+}
+
+/// Type for the values of an evaluated circuit's gates.
+#[derive(Default)]
+pub struct CircuitOutputsType {}
+impl NamedType for CircuitOutputsType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("CircuitOutputs");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        // The values of the gates, and the modulus they were computed with.
+        specialize_circuit_value_type(context, Self::concrete_type_long_id(args), true, 2)
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum CircuitLibFunc {
+        InitCircuitData(InitCircuitDataLibFunc),
+        AddInput(AddCircuitInputLibFunc),
+        Eval(EvalCircuitLibFunc),
+        GetOutput(GetCircuitOutputLibFunc),
+    }, CircuitConcreteLibFunc
+}
+
+/// LibFunc for starting to provide the inputs of a circuit.
+#[derive(Default)]
+pub struct InitCircuitDataLibFunc {}
+impl SignatureOnlyGenericLibFunc for InitCircuitDataLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("init_circuit_data");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let circuit_ty = args_as_single_type(args)?;
+        get_circuit_info(context.as_type_specialization_context(), circuit_ty.clone())?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: context
+                    .get_wrapped_concrete_type(CircuitInputAccumulatorType::id(), circuit_ty)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for providing the next input of a circuit.
+/// Branches to the first branch with the accumulator if more inputs are required, and to the
+/// second branch with the circuit's data once the last input was provided.
+#[derive(Default)]
+pub struct AddCircuitInputLibFunc {}
+impl NamedLibFunc for AddCircuitInputLibFunc {
+    type Concrete = AddCircuitInputConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("add_circuit_input");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let circuit_ty = args_as_single_type(args)?;
+        get_circuit_info(context.as_type_specialization_context(), circuit_ty.clone())?;
+        let accumulator_ty = context
+            .get_wrapped_concrete_type(CircuitInputAccumulatorType::id(), circuit_ty.clone())?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(accumulator_ty.clone()),
+                ParamSignature::new(context.get_concrete_type(FeltType::id(), &[])?),
+            ],
+            branch_signatures: vec![
+                // More inputs are required:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: accumulator_ty,
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // All inputs were provided:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: context.get_wrapped_concrete_type(CircuitDataType::id(), circuit_ty)?,
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let circuit = get_circuit_info(
+            context.upcast().as_type_specialization_context(),
+            args_as_single_type(args)?,
+        )?;
+        Ok(AddCircuitInputConcreteLibFunc {
+            n_inputs: circuit.n_inputs,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct AddCircuitInputConcreteLibFunc {
+    /// The number of inputs of the circuit.
+    pub n_inputs: usize,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for AddCircuitInputConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for evaluating a circuit modulo a given modulus, using the AddMod and MulMod builtins.
+/// Branches to the first branch with the outputs on success, and to the second branch if an
+/// inverse gate was applied to a value with no inverse modulo the modulus.
+#[derive(Default)]
+pub struct EvalCircuitLibFunc {}
+impl NamedLibFunc for EvalCircuitLibFunc {
+    type Concrete = EvalCircuitConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("eval_circuit");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let circuit_ty = args_as_single_type(args)?;
+        get_circuit_info(context.as_type_specialization_context(), circuit_ty.clone())?;
+        let add_mod_ty = context.get_concrete_type(AddModType::id(), &[])?;
+        let mul_mod_ty = context.get_concrete_type(MulModType::id(), &[])?;
+        let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
+        let builtin_outputs = || {
+            vec![
+                OutputVarInfo {
+                    ty: add_mod_ty.clone(),
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+                OutputVarInfo {
+                    ty: mul_mod_ty.clone(),
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ]
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(add_mod_ty.clone()),
+                ParamSignature::new(mul_mod_ty.clone()),
+                ParamSignature::new(
+                    context.get_wrapped_concrete_type(CircuitDataType::id(), circuit_ty.clone())?,
+                ),
+                // The modulus:
+                ParamSignature::new(context.get_wrapped_concrete_type(NonZeroType::id(), felt_ty)?),
+            ],
+            branch_signatures: vec![
+                // Success:
+                BranchSignature {
+                    vars: builtin_outputs()
+                        .into_iter()
+                        .chain([OutputVarInfo {
+                            ty: context
+                                .get_wrapped_concrete_type(CircuitOutputsType::id(), circuit_ty)?,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        }])
+                        .collect(),
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Failure:
+                BranchSignature {
+                    vars: builtin_outputs(),
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(EvalCircuitConcreteLibFunc {
+            circuit: get_circuit_info(
+                context.upcast().as_type_specialization_context(),
+                args_as_single_type(args)?,
+            )?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct EvalCircuitConcreteLibFunc {
+    pub circuit: CircuitInfo,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for EvalCircuitConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for getting an output of an evaluated circuit, given the output's index.
+#[derive(Default)]
+pub struct GetCircuitOutputLibFunc {}
+impl GetCircuitOutputLibFunc {
+    /// Returns the circuit's type and the output's index, given the libfunc's generic args.
+    fn extract_args(
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<(ConcreteTypeId, usize), SpecializationError> {
+        let (circuit_ty, index) = args_as_type_and_value(args)?;
+        let circuit =
+            get_circuit_info(context.as_type_specialization_context(), circuit_ty.clone())?;
+        let output_index = index
+            .to_usize()
+            .filter(|output_index| *output_index < circuit.outputs.len())
+            .ok_or_else(|| SpecializationError::IndexOutOfRange {
+                index: index.clone(),
+                range_size: circuit.outputs.len(),
+            })?;
+        Ok((circuit_ty.clone(), output_index))
+    }
+}
+impl NamedLibFunc for GetCircuitOutputLibFunc {
+    type Concrete = GetCircuitOutputConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("get_circuit_output");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (circuit_ty, _) = Self::extract_args(context, args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![context.get_wrapped_concrete_type(CircuitOutputsType::id(), circuit_ty)?],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(FeltType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, index) = Self::extract_args(context.upcast(), args)?;
+        Ok(GetCircuitOutputConcreteLibFunc {
+            index,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct GetCircuitOutputConcreteLibFunc {
+    /// The index of the output in the circuit's outputs.
+    pub index: usize,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for GetCircuitOutputConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
@@ -0,0 +1,192 @@
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type FeltPair = Struct<ut@FeltPair, felt, felt>;
+//! type Tuple<> = Struct<ut@Tuple>;
+//! type Option = Enum<ut@Option, FeltPair, Tuple<>>;
+//! libfunc some_pair = const_as_immediate<Option, 0, 1, 'abc'>;
+//! libfunc none = const_as_immediate<Option, 1>;
+//! ...
+//! some_pair() -> (value);
+//! ```
+
+use num_bigint::BigInt;
+use utils::try_extract_matches;
+
+use super::enm::EnumType;
+use super::felt::{short_string_to_felt, FeltType};
+use super::strct::StructType;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::{
+    ArgCount, GenericArgKind, NamedLibFunc, NamedType, OutputVarReferenceInfo,
+    SignatureBasedConcreteLibFunc, SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId};
+use crate::program::GenericArg;
+
+/// A constant value, shaped according to the type it was declared for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConstValue {
+    Felt(BigInt),
+    Struct(Vec<ConstValue>),
+    Enum {
+        /// The index of the relevant variant.
+        index: usize,
+        value: Box<ConstValue>,
+    },
+}
+
+/// Returns the member types of a struct or the variant types of an enum, given the generic args
+/// of its declaration.
+fn inner_types(generic_args: &[GenericArg]) -> Result<Vec<ConcreteTypeId>, SpecializationError> {
+    generic_args
+        .iter()
+        .skip(1)
+        .map(|arg| {
+            try_extract_matches!(arg, GenericArg::Type)
+                .cloned()
+                .ok_or_else(|| SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type))
+        })
+        .collect()
+}
+
+/// Consumes the generic args describing a constant of the given type, and returns the constant.
+///
+/// A felt is described by a single value or short string, a struct by the descriptions of its
+/// members in order, and an enum by the index of its variant followed by the variant's
+/// description.
+fn extract_const_value<'a>(
+    context: &dyn SignatureSpecializationContext,
+    ty: ConcreteTypeId,
+    args: &mut impl Iterator<Item = &'a GenericArg>,
+) -> Result<ConstValue, SpecializationError> {
+    let long_id = context.get_type_info(ty.clone())?.long_id;
+    if long_id.generic_id == FeltType::ID {
+        match args.next() {
+            Some(GenericArg::Value(value)) => Ok(ConstValue::Felt(value.clone())),
+            Some(GenericArg::ShortString(s)) => Ok(ConstValue::Felt(short_string_to_felt(s)?)),
+            Some(arg) => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value)),
+            None => Err(missing_const_arg()),
+        }
+    } else if long_id.generic_id == StructType::ID {
+        inner_types(&long_id.generic_args)?
+            .into_iter()
+            .map(|member| extract_const_value(context, member, args))
+            .collect::<Result<_, _>>()
+            .map(ConstValue::Struct)
+    } else if long_id.generic_id == EnumType::ID {
+        let variants = inner_types(&long_id.generic_args)?;
+        let index = match args.next() {
+            Some(GenericArg::Value(index)) => index,
+            Some(arg) => {
+                return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value));
+            }
+            None => return Err(missing_const_arg()),
+        };
+        let variant_index = usize::try_from(index)
+            .ok()
+            .filter(|variant_index| *variant_index < variants.len())
+            .ok_or_else(|| SpecializationError::IndexOutOfRange {
+                index: index.clone(),
+                range_size: variants.len(),
+            })?;
+        Ok(ConstValue::Enum {
+            index: variant_index,
+            value: Box::new(extract_const_value(context, variants[variant_index].clone(), args)?),
+        })
+    } else {
+        Err(SpecializationError::unsupported_arg(GenericArg::Type(ty)))
+    }
+}
+
+/// The error of running out of generic args while describing a constant.
+///
+/// The actual count is filled in by [extract_const], which knows how many args were given.
+fn missing_const_arg() -> SpecializationError {
+    SpecializationError::WrongNumberOfGenericArgs { expected: ArgCount::AtLeast(1), actual: 0 }
+}
+
+/// Returns the type of the constant and the constant itself, given the libfunc's generic args.
+fn extract_const(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, ConstValue), SpecializationError> {
+    let (ty, value_args) = match args {
+        [GenericArg::Type(ty), value_args @ ..] => (ty, value_args),
+        [] => {
+            return Err(SpecializationError::WrongNumberOfGenericArgs {
+                expected: ArgCount::AtLeast(2),
+                actual: 0,
+            });
+        }
+        [arg, ..] => return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type)),
+    };
+    let mut value_args = value_args.iter();
+    let value =
+        extract_const_value(context, ty.clone(), &mut value_args).map_err(|err| match err {
+            SpecializationError::WrongNumberOfGenericArgs { .. } => {
+                SpecializationError::WrongNumberOfGenericArgs {
+                    expected: ArgCount::AtLeast(args.len() + 1),
+                    actual: args.len(),
+                }
+            }
+            err => err,
+        })?;
+    let unused = value_args.count();
+    if unused != 0 {
+        return Err(SpecializationError::WrongNumberOfGenericArgs {
+            expected: ArgCount::Exactly(args.len() - unused),
+            actual: args.len(),
+        });
+    }
+    Ok((ty.clone(), value))
+}
+
+/// LibFunc for creating a constant of a felt, or of a struct or an enum built of felts.
+#[derive(Default)]
+pub struct ConstAsImmediateLibFunc {}
+impl NamedLibFunc for ConstAsImmediateLibFunc {
+    type Concrete = ConstAsImmediateConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("const_as_immediate");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (ty, _) = extract_const(context, args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, value) = extract_const(context.upcast(), args)?;
+        Ok(ConstAsImmediateConcreteLibFunc {
+            value,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct ConstAsImmediateConcreteLibFunc {
+    pub value: ConstValue,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for ConstAsImmediateConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
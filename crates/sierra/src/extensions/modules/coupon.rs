@@ -0,0 +1,163 @@
+//! Coupons for pre-paid calls of user functions.
+//!
+//! Buying a coupon pays for the content of a function call in advance, and refunding an unused
+//! coupon returns its cost.
+//!
+//! Sierra example:
+//! ```ignore
+//! type CouponFoo = Coupon<user@foo>;
+//! libfunc buy_foo = coupon_buy<user@foo>;
+//! libfunc refund_foo = coupon_refund<user@foo>;
+//! ...
+//! buy_foo() -> (coupon);
+//! refund_foo(coupon) -> ();
+//! ```
+
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_user_func;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::TypeInfo;
+use crate::extensions::{
+    ConcreteType, NamedLibFunc, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, FunctionId, GenericLibFuncId, GenericTypeId};
+use crate::program::{Function, GenericArg};
+
+/// Type representing a pre-paid call of a user function.
+/// A coupon has no runtime representation, as its cost is accounted for when it is bought.
+#[derive(Default)]
+pub struct CouponType {}
+impl NamedType for CouponType {
+    type Concrete = CouponConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Coupon");
+
+    fn specialize(
+        &self,
+        _context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let function_id = args_as_single_user_func(args)?;
+        Ok(CouponConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                storable: true,
+                droppable: true,
+                duplicatable: false,
+                size: 0,
+            },
+            function_id,
+        })
+    }
+}
+
+pub struct CouponConcreteType {
+    pub info: TypeInfo,
+    /// The function the coupon pays for.
+    pub function_id: FunctionId,
+}
+impl ConcreteType for CouponConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+/// Returns the coupon type of the given function.
+pub fn get_coupon_type(
+    context: &dyn SignatureSpecializationContext,
+    function_id: FunctionId,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    context.get_concrete_type(CouponType::id(), &[GenericArg::UserFunc(function_id)])
+}
+
+define_libfunc_hierarchy! {
+    pub enum CouponLibFunc {
+        Buy(CouponBuyLibFunc),
+        Refund(CouponRefundLibFunc),
+    }, CouponConcreteLibFunc
+}
+
+/// LibFunc for buying a coupon for a call of a user function, paying for the call's content.
+#[derive(Default)]
+pub struct CouponBuyLibFunc {}
+impl NamedLibFunc for CouponBuyLibFunc {
+    type Concrete = CouponConcreteLibFuncInfo;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("coupon_buy");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let function_id = args_as_single_user_func(args)?;
+        // Validates that the function exists.
+        context.get_function_signature(&function_id)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: get_coupon_type(context, function_id.clone())?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(CouponConcreteLibFuncInfo {
+            function: context.get_function(&args_as_single_user_func(args)?)?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+/// LibFunc for refunding an unused coupon, returning the cost of the call's content.
+#[derive(Default)]
+pub struct CouponRefundLibFunc {}
+impl NamedLibFunc for CouponRefundLibFunc {
+    type Concrete = CouponConcreteLibFuncInfo;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("coupon_refund");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let function_id = args_as_single_user_func(args)?;
+        context.get_function_signature(&function_id)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![get_coupon_type(context, function_id)?],
+            vec![],
+            SierraApChange::Known(0),
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(CouponConcreteLibFuncInfo {
+            function: context.get_function(&args_as_single_user_func(args)?)?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct CouponConcreteLibFuncInfo {
+    /// The function the coupon pays for.
+    pub function: Function,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for CouponConcreteLibFuncInfo {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
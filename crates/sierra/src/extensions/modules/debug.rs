@@ -0,0 +1,29 @@
+use crate::extensions::args::args_as_single_type;
+use crate::extensions::lib_func::{
+    LibFuncSignature, SierraApChange, SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+};
+use crate::extensions::SpecializationError;
+use crate::ids::GenericLibFuncId;
+use crate::program::GenericArg;
+
+/// LibFunc for printing a value for debugging purposes. Consumes the value, so only droppable
+/// types are supported.
+#[derive(Default)]
+pub struct DebugPrintLibFunc {}
+impl SignatureOnlyGenericLibFunc for DebugPrintLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("debug_print");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        generic_args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(generic_args)?;
+        let info = context.get_type_info(ty.clone())?;
+        if info.droppable {
+            Ok(LibFuncSignature::new_non_branch(vec![ty], vec![], SierraApChange::Known(0)))
+        } else {
+            Err(SpecializationError::TypeNotDroppable(ty))
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use super::as_single_type;
 use super::felt::FeltType;
+use super::range_check::RangeCheckType;
+use super::segment_arena::SegmentArenaType;
 use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
     SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
@@ -11,6 +13,23 @@ use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, Special
 use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
 use crate::program::GenericArg;
 
+/// Returns the value type of the dict_felt_to types, given as their single generic arg, along with
+/// its info.
+fn specialize_value_type(
+    context: &dyn TypeSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, TypeInfo), SpecializationError> {
+    let ty = args_as_single_type(args)?;
+    let info = context.get_type_info(ty.clone())?;
+    // TODO(Gil): the implementation support values of size 1. Remove when other sizes are
+    // supported.
+    if info.storable && info.size == 1 {
+        Ok((ty, info))
+    } else {
+        Err(SpecializationError::unsupported_arg(args[0].clone()))
+    }
+}
+
 /// Type representing a dictionary from a felt to any type of size one.
 #[derive(Default)]
 pub struct DictFeltToType {}
@@ -23,24 +42,17 @@ impl NamedType for DictFeltToType {
         context: &dyn TypeSpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
-        let info = context.get_type_info(ty.clone())?;
-        // TODO(Gil): the implementation support values of size 1. Remove when other sizes are
-        // supported.
-        if info.storable && info.size == 1 {
-            Ok(DictFeltToConcreteType {
-                info: TypeInfo {
-                    long_id: Self::concrete_type_long_id(args),
-                    duplicatable: false,
-                    droppable: info.droppable,
-                    storable: true,
-                    size: 2,
-                },
-                ty,
-            })
-        } else {
-            Err(SpecializationError::UnsupportedGenericArg)
-        }
+        let (ty, info) = specialize_value_type(context, args)?;
+        Ok(DictFeltToConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                duplicatable: false,
+                droppable: info.droppable,
+                storable: true,
+                size: 2,
+            },
+            ty,
+        })
     }
 }
 
@@ -55,16 +67,98 @@ impl ConcreteType for DictFeltToConcreteType {
     }
 }
 
+/// Type representing a squashed dict_felt_to - the array of its accesses, as (key, prev_value,
+/// new_value) entries, sorted by key and then by the order of the accesses.
+#[derive(Default)]
+pub struct SquashedDictFeltToType {}
+impl NamedType for SquashedDictFeltToType {
+    type Concrete = SquashedDictFeltToConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("SquashedDictFeltTo");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (ty, info) = specialize_value_type(context, args)?;
+        Ok(SquashedDictFeltToConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                duplicatable: false,
+                droppable: info.droppable,
+                storable: true,
+                size: 2,
+            },
+            ty,
+        })
+    }
+}
+
+pub struct SquashedDictFeltToConcreteType {
+    pub info: TypeInfo,
+    pub ty: ConcreteTypeId,
+}
+
+impl ConcreteType for SquashedDictFeltToConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+/// Type representing a pending access to a single key of a dict_felt_to, holding the dict until
+/// the access is finalized with the key's new value.
+#[derive(Default)]
+pub struct DictFeltToEntryType {}
+impl NamedType for DictFeltToEntryType {
+    type Concrete = DictFeltToEntryConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("DictFeltToEntry");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (ty, _) = specialize_value_type(context, args)?;
+        Ok(DictFeltToEntryConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                duplicatable: false,
+                // Must be finalized, so that the access is recorded in the dict.
+                droppable: false,
+                storable: true,
+                // The dict, followed by the accessed key.
+                size: 3,
+            },
+            ty,
+        })
+    }
+}
+
+pub struct DictFeltToEntryConcreteType {
+    pub info: TypeInfo,
+    pub ty: ConcreteTypeId,
+}
+
+impl ConcreteType for DictFeltToEntryConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
 define_libfunc_hierarchy! {
     pub enum DictFeltToLibFunc {
         New(DictFeltToNewLibFunc),
         Read(DictFeltToReadLibFunc),
         Write(DictFeltToWriteLibFunc),
-        // TODO(Gil): Add DictFeltToSquash,
+        EntryGet(DictFeltToEntryGetLibFunc),
+        EntryFinalize(DictFeltToEntryFinalizeLibFunc),
+        Squash(DictFeltToSquashLibFunc),
     }, DictFeltToConcreteLibFunc
 }
 
-/// LibFunc for creating a new dict_felt_to.
+/// LibFunc for creating a new dict_felt_to, given its default value, of the dict's value type.
+/// The dict's segment is allocated by a hint, and is only recorded in the segment arena once the
+/// dict is squashed, so the arena is passed through unchanged.
 #[derive(Default)]
 pub struct DictFeltToNewLibFunc {}
 impl SignatureOnlyGenericLibFunc for DictFeltToNewLibFunc {
@@ -75,14 +169,20 @@ impl SignatureOnlyGenericLibFunc for DictFeltToNewLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
-        let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
+        let ty = args_as_single_type(args)?;
+        let segment_arena_ty = context.get_concrete_type(SegmentArenaType::id(), &[])?;
         Ok(LibFuncSignature::new_non_branch(
-            vec![felt_ty],
-            vec![OutputVarInfo {
-                ty: context.get_wrapped_concrete_type(DictFeltToType::id(), ty)?,
-                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
-            }],
+            vec![segment_arena_ty.clone(), ty.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: segment_arena_ty,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: context.get_wrapped_concrete_type(DictFeltToType::id(), ty)?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
             SierraApChange::Known(1),
         ))
     }
@@ -99,7 +199,7 @@ impl SignatureOnlyGenericLibFunc for DictFeltToWriteLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
         let dict_ty = context.get_wrapped_concrete_type(DictFeltToType::id(), ty.clone())?;
         Ok(LibFuncSignature::new_non_branch(
@@ -124,11 +224,12 @@ impl SignatureOnlyGenericLibFunc for DictFeltToReadLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let generic_ty = as_single_type(args)?;
+        let generic_ty = args_as_single_type(args)?;
+        let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
         let dict_ty =
             context.get_wrapped_concrete_type(DictFeltToType::id(), generic_ty.clone())?;
         Ok(LibFuncSignature::new_non_branch(
-            vec![dict_ty.clone(), generic_ty.clone()],
+            vec![dict_ty.clone(), felt_ty],
             vec![
                 OutputVarInfo {
                     ty: dict_ty,
@@ -143,3 +244,107 @@ impl SignatureOnlyGenericLibFunc for DictFeltToReadLibFunc {
         ))
     }
 }
+
+/// LibFunc for starting an access to a key of a dict_felt_to, returning the key's current value
+/// along with the pending entry.
+/// Together with `dict_entry_finalize`, allows updating a key's value with a single dict access,
+/// instead of a read followed by a write.
+#[derive(Default)]
+pub struct DictFeltToEntryGetLibFunc {}
+impl SignatureOnlyGenericLibFunc for DictFeltToEntryGetLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("dict_entry_get");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let generic_ty = args_as_single_type(args)?;
+        let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
+        let dict_ty =
+            context.get_wrapped_concrete_type(DictFeltToType::id(), generic_ty.clone())?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![dict_ty, felt_ty],
+            vec![
+                OutputVarInfo {
+                    ty: context
+                        .get_wrapped_concrete_type(DictFeltToEntryType::id(), generic_ty.clone())?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+                OutputVarInfo {
+                    ty: generic_ty,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for finalizing a pending dict_felt_to entry with the key's new value, returning the
+/// dict.
+#[derive(Default)]
+pub struct DictFeltToEntryFinalizeLibFunc {}
+impl SignatureOnlyGenericLibFunc for DictFeltToEntryFinalizeLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("dict_entry_finalize");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let generic_ty = args_as_single_type(args)?;
+        let entry_ty =
+            context.get_wrapped_concrete_type(DictFeltToEntryType::id(), generic_ty.clone())?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![entry_ty, generic_ty.clone()],
+            vec![OutputVarInfo {
+                ty: context.get_wrapped_concrete_type(DictFeltToType::id(), generic_ty)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for squashing a dict_felt_to into its sorted array of accesses, finalizing the dict's
+/// segment in the segment arena.
+#[derive(Default)]
+pub struct DictFeltToSquashLibFunc {}
+impl SignatureOnlyGenericLibFunc for DictFeltToSquashLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("dict_felt_to_squash");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let generic_ty = args_as_single_type(args)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let dict_ty =
+            context.get_wrapped_concrete_type(DictFeltToType::id(), generic_ty.clone())?;
+        let squashed_dict_ty =
+            context.get_wrapped_concrete_type(SquashedDictFeltToType::id(), generic_ty)?;
+        let segment_arena_ty = context.get_concrete_type(SegmentArenaType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![range_check_type.clone(), segment_arena_ty.clone(), dict_ty],
+            vec![
+                OutputVarInfo {
+                    ty: range_check_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+                OutputVarInfo {
+                    ty: segment_arena_ty,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 1,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: squashed_dict_ty,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::Unknown,
+        ))
+    }
+}
@@ -1,4 +1,4 @@
-use super::as_single_type;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     LibFuncSignature, SierraApChange, SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
 };
@@ -17,12 +17,12 @@ impl SignatureOnlyGenericLibFunc for DropLibFunc {
         context: &dyn SignatureSpecializationContext,
         generic_args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(generic_args)?;
+        let ty = args_as_single_type(generic_args)?;
         let info = context.get_type_info(ty.clone())?;
         if info.droppable {
             Ok(LibFuncSignature::new_non_branch(vec![ty], vec![], SierraApChange::Known(0)))
         } else {
-            Err(SpecializationError::UnsupportedGenericArg)
+            Err(SpecializationError::TypeNotDroppable(ty))
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::as_single_type;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     LibFuncSignature, OutputVarInfo, SierraApChange, SignatureOnlyGenericLibFunc,
     SignatureSpecializationContext,
@@ -18,10 +18,10 @@ impl SignatureOnlyGenericLibFunc for DupLibFunc {
         context: &dyn SignatureSpecializationContext,
         generic_args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(generic_args)?;
+        let ty = args_as_single_type(generic_args)?;
         let info = context.get_type_info(ty.clone())?;
         if !info.duplicatable {
-            return Err(SpecializationError::UnsupportedGenericArg);
+            return Err(SpecializationError::TypeNotDuplicatable(ty));
         }
 
         Ok(LibFuncSignature::new_non_branch(
@@ -0,0 +1,204 @@
+// Module providing the elliptic curve related extensions.
+use super::felt::FeltType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+
+/// Type for the EC operation builtin.
+#[derive(Default)]
+pub struct EcOpType {}
+impl NoGenericArgsGenericType for EcOpType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("EcOp");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
+
+/// Type for a point on the STARK curve, represented by its two coordinates.
+#[derive(Default)]
+pub struct EcPointType {}
+impl NoGenericArgsGenericType for EcPointType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("EcPoint");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 2,
+            },
+        }
+    }
+}
+
+/// Type for an accumulator of EC operations, which may temporarily be the point at infinity.
+#[derive(Default)]
+pub struct EcStateType {}
+impl NoGenericArgsGenericType for EcStateType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("EcState");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 3,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum EcLibFunc {
+        TryNew(EcPointTryNewLibFunc),
+        StateInit(EcStateInitLibFunc),
+        StateAddMul(EcStateAddMulLibFunc),
+        StateFinalize(EcStateFinalizeLibFunc),
+    }, EcConcreteLibFunc
+}
+
+/// Returns the signature of a branch returning only an EC point.
+fn ec_point_branch(ec_point_type: ConcreteTypeId) -> BranchSignature {
+    BranchSignature {
+        vars: vec![OutputVarInfo {
+            ty: ec_point_type,
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        }],
+        ap_change: SierraApChange::NotImplemented,
+    }
+}
+
+/// LibFunc for creating an EC point from its coordinates, branching on whether the point is on
+/// the curve.
+#[derive(Default)]
+pub struct EcPointTryNewLibFunc {}
+impl NoGenericArgsGenericLibFunc for EcPointTryNewLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("ec_point_try_new");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let ec_point_type = context.get_concrete_type(EcPointType::id(), &[])?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(felt_type.clone()),
+                ParamSignature::new(felt_type),
+            ],
+            branch_signatures: vec![
+                // On curve:
+                ec_point_branch(ec_point_type),
+                // Not on curve:
+                BranchSignature { vars: vec![], ap_change: SierraApChange::NotImplemented },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for initializing an EC state.
+#[derive(Default)]
+pub struct EcStateInitLibFunc {}
+impl NoGenericArgsGenericLibFunc for EcStateInitLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("ec_state_init");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ec_state_type = context.get_concrete_type(EcStateType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: ec_state_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for adding a scalar multiple of an EC point to an EC state, using the EC operation
+/// builtin.
+#[derive(Default)]
+pub struct EcStateAddMulLibFunc {}
+impl NoGenericArgsGenericLibFunc for EcStateAddMulLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("ec_state_add_mul");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ec_op_type = context.get_concrete_type(EcOpType::id(), &[])?;
+        let ec_state_type = context.get_concrete_type(EcStateType::id(), &[])?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let ec_point_type = context.get_concrete_type(EcPointType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![ec_op_type.clone(), ec_state_type.clone(), felt_type, ec_point_type],
+            vec![
+                OutputVarInfo {
+                    ty: ec_op_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: ec_state_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for extracting the accumulated EC point from an EC state, branching on whether the
+/// result is the point at infinity.
+#[derive(Default)]
+pub struct EcStateFinalizeLibFunc {}
+impl NoGenericArgsGenericLibFunc for EcStateFinalizeLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("ec_state_finalize");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ec_state_type = context.get_concrete_type(EcStateType::id(), &[])?;
+        let ec_point_type = context.get_concrete_type(EcPointType::id(), &[])?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(ec_state_type)],
+            branch_signatures: vec![
+                // Finite point:
+                ec_point_branch(ec_point_type),
+                // Point at infinity:
+                BranchSignature { vars: vec![], ap_change: SierraApChange::NotImplemented },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
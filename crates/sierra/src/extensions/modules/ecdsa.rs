@@ -0,0 +1,75 @@
+use super::felt::FeltType;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, NoGenericArgsGenericLibFunc,
+    OutputVarInfo, ParamSignature, SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericType, OutputVarReferenceInfo, SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+
+/// Type for the signature verification builtin.
+#[derive(Default)]
+pub struct SignatureType {}
+impl NoGenericArgsGenericType for SignatureType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Signature");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
+
+/// LibFunc for verifying an ECDSA signature `(r, s)` of a message hash by a public key, branching
+/// on whether the signature is valid.
+#[derive(Default)]
+pub struct EcdsaVerifyLibFunc {}
+impl NoGenericArgsGenericLibFunc for EcdsaVerifyLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("ecdsa_verify");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let signature_type = context.get_concrete_type(SignatureType::id(), &[])?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let branch_signature = || BranchSignature {
+            vars: vec![OutputVarInfo {
+                ty: signature_type.clone(),
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                    param_idx: 0,
+                }),
+            }],
+            ap_change: SierraApChange::NotImplemented,
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(signature_type.clone()),
+                // Message hash:
+                ParamSignature::new(felt_type.clone()),
+                // Public key:
+                ParamSignature::new(felt_type.clone()),
+                // r:
+                ParamSignature::new(felt_type.clone()),
+                // s:
+                ParamSignature::new(felt_type),
+            ],
+            branch_signatures: vec![
+                // Valid:
+                branch_signature(),
+                // Invalid:
+                branch_signature(),
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
@@ -21,8 +21,9 @@ use num_bigint::ToBigInt;
 use num_traits::Signed;
 use utils::try_extract_matches;
 
-use super::as_single_type;
+use super::strct::StructType;
 use crate::define_libfunc_hierarchy;
+use crate::extensions::args::{args_as_single_type, args_as_type_and_value};
 use crate::extensions::lib_func::{
     BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
     SignatureOnlyGenericLibFunc, SignatureSpecializationContext, SpecializationContext,
@@ -30,8 +31,8 @@ use crate::extensions::lib_func::{
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
 use crate::extensions::{
-    ConcreteType, NamedLibFunc, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
-    SpecializationError,
+    ArgCount, ConcreteType, GenericArgKind, NamedLibFunc, NamedType, OutputVarReferenceInfo,
+    SignatureBasedConcreteLibFunc, SpecializationError,
 };
 use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
 use crate::program::{ConcreteTypeLongId, GenericArg};
@@ -62,21 +63,29 @@ impl EnumConcreteType {
         args: &[GenericArg],
     ) -> Result<Self, SpecializationError> {
         let mut args_iter = args.iter();
-        args_iter
-            .next()
-            .and_then(|arg| try_extract_matches!(arg, GenericArg::UserType))
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
+        match args_iter.next() {
+            Some(GenericArg::UserType(_)) => {}
+            Some(arg) => {
+                return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::UserType));
+            }
+            None => {
+                return Err(SpecializationError::WrongNumberOfGenericArgs {
+                    expected: ArgCount::AtLeast(1),
+                    actual: 0,
+                });
+            }
+        }
         let mut duplicatable = true;
         let mut droppable = true;
         let mut variants: Vec<ConcreteTypeId> = Vec::new();
         let mut variant_max_size = 0;
         for arg in args_iter {
             let ty = try_extract_matches!(arg, GenericArg::Type)
-                .ok_or(SpecializationError::UnsupportedGenericArg)?
+                .ok_or_else(|| SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type))?
                 .clone();
             let info = context.get_type_info(ty.clone())?;
             if !info.storable {
-                return Err(SpecializationError::UnsupportedGenericArg);
+                return Err(SpecializationError::unsupported_arg(arg.clone()));
             }
             if !info.duplicatable {
                 duplicatable = false;
@@ -87,6 +96,8 @@ impl EnumConcreteType {
             variants.push(ty);
             variant_max_size = cmp::max(variant_max_size, info.size);
         }
+        // An enum without variants has no values, so it holds no variant index either.
+        let size = if variants.is_empty() { 0 } else { 1 + variant_max_size };
         Ok(EnumConcreteType {
             info: TypeInfo {
                 long_id: ConcreteTypeLongId {
@@ -96,7 +107,7 @@ impl EnumConcreteType {
                 duplicatable,
                 droppable,
                 storable: true,
-                size: 1 + variant_max_size,
+                size,
             },
             variants,
         })
@@ -138,13 +149,7 @@ impl EnumInitLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<EnumInitConcreteLibFunc, SpecializationError> {
-        let (enum_type, index) = match args {
-            [GenericArg::Type(enum_type), GenericArg::Value(index)] => {
-                (enum_type.clone(), index.clone())
-            }
-            [_, _] => return Err(SpecializationError::UnsupportedGenericArg),
-            _ => return Err(SpecializationError::WrongNumberOfGenericArgs),
-        };
+        let (enum_type, index) = args_as_type_and_value(args)?;
         let generic_args = context.get_type_info(enum_type.clone())?.long_id.generic_args;
         let variant_types =
             EnumConcreteType::new(context.as_type_specialization_context(), &generic_args)?
@@ -201,7 +206,7 @@ impl SignatureOnlyGenericLibFunc for EnumMatchLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let enum_type = as_single_type(args)?;
+        let enum_type = args_as_single_type(args)?;
         let generic_args = context.get_type_info(enum_type.clone())?.long_id.generic_args;
         let variant_types =
             EnumConcreteType::new(context.as_type_specialization_context(), &generic_args)?
@@ -224,3 +229,46 @@ impl SignatureOnlyGenericLibFunc for EnumMatchLibFunc {
         })
     }
 }
+
+/// The index of the `Some` variant of the canonical option enum.
+pub const OPTION_SOME_VARIANT_INDEX: usize = 0;
+/// The index of the `None` variant of the canonical option enum.
+pub const OPTION_NONE_VARIANT_INDEX: usize = 1;
+/// The index of the `Ok` variant of the canonical result enum.
+pub const RESULT_OK_VARIANT_INDEX: usize = 0;
+/// The index of the `Err` variant of the canonical result enum.
+pub const RESULT_ERR_VARIANT_INDEX: usize = 1;
+
+/// Returns the unit type, `Struct<ut@Tuple>`.
+pub fn get_unit_type(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    context.get_concrete_type(StructType::id(), &[GenericArg::UserType("Tuple".into())])
+}
+
+/// Returns the canonical option type wrapping `ty`, `Enum<ut@Option, ty, Struct<ut@Tuple>>`.
+pub fn get_option_type(
+    context: &dyn SignatureSpecializationContext,
+    ty: ConcreteTypeId,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    context.get_concrete_type(
+        EnumType::id(),
+        &[
+            GenericArg::UserType("Option".into()),
+            GenericArg::Type(ty),
+            GenericArg::Type(get_unit_type(context)?),
+        ],
+    )
+}
+
+/// Returns the canonical result type of `ok_ty` and `err_ty`, `Enum<ut@Result, ok_ty, err_ty>`.
+pub fn get_result_type(
+    context: &dyn SignatureSpecializationContext,
+    ok_ty: ConcreteTypeId,
+    err_ty: ConcreteTypeId,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    context.get_concrete_type(
+        EnumType::id(),
+        &[GenericArg::UserType("Result".into()), GenericArg::Type(ok_ty), GenericArg::Type(err_ty)],
+    )
+}
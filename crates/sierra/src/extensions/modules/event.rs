@@ -0,0 +1,36 @@
+use super::array::ArrayType;
+use super::felt::FeltType;
+use super::syscalls::SystemType;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, NoGenericArgsGenericLibFunc, OutputVarInfo,
+    SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::{NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::ids::GenericLibFuncId;
+
+/// LibFunc for emitting an event through the system builtin, made of an array of felt keys and an
+/// array of felt data.
+#[derive(Default)]
+pub struct EmitEventLibFunc {}
+impl NoGenericArgsGenericLibFunc for EmitEventLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("emit_event");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let system_type = context.get_concrete_type(SystemType::id(), &[])?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let felt_array_type = context.get_wrapped_concrete_type(ArrayType::id(), felt_type)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![system_type.clone(), felt_array_type.clone(), felt_array_type],
+            vec![OutputVarInfo {
+                ty: system_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                    param_idx: 0,
+                }),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
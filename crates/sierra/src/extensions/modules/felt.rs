@@ -1,21 +1,41 @@
-use num_bigint::BigInt;
-use num_traits::Zero;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Signed, Zero};
 
+use super::array::ArrayType;
+use super::integer::bounded_int_bit_width;
 use super::jump_not_zero::{JumpNotZeroLibFunc, JumpNotZeroTraits};
 use super::non_zero::NonZeroType;
+use super::range_check::RangeCheckType;
+use crate::extensions::args::{args_as_single_value, optional_value_args_error};
 use crate::extensions::lib_func::{
-    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
-    SignatureSpecializationContext, SpecializationContext,
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
 use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
 use crate::extensions::{
-    GenericLibFunc, NamedLibFunc, NamedType, NoGenericArgsGenericType, OutputVarReferenceInfo,
-    SignatureBasedConcreteLibFunc, SpecializationError,
+    GenericArgKind, GenericLibFunc, NamedLibFunc, NamedType, NoGenericArgsGenericLibFunc,
+    NoGenericArgsGenericType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
 };
-use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
 use crate::program::GenericArg;
 use crate::{define_concrete_libfunc_hierarchy, define_libfunc_hierarchy};
 
+/// Returns the prime of the felt field, 2^251 + 17*2^192 + 1.
+pub fn felt_prime() -> BigInt {
+    (BigInt::one() << 251) + BigInt::from(17) * (BigInt::one() << 192) + BigInt::one()
+}
+
+/// Validates that a constant given as a generic arg is within the felt field, allowing negative
+/// values standing for their residue modulo the prime.
+fn validate_felt_const(c: &BigInt) -> Result<(), SpecializationError> {
+    if c.abs() >= felt_prime() {
+        Err(SpecializationError::FeltConstOutOfRange(c.clone()))
+    } else {
+        Ok(())
+    }
+}
+
 /// Type for felt.
 /// The native type of the Cairo architecture.
 #[derive(Default)]
@@ -40,8 +60,14 @@ impl NoGenericArgsGenericType for FeltType {
 define_libfunc_hierarchy! {
     pub enum FeltLibFunc {
         Operation(FeltOperationLibFunc),
+        Inverse(FeltInverseLibFunc),
         Const(FeltConstLibFunc),
         JumpNotZero(FeltJumpNotZeroLibFunc),
+        IsZero(FeltIsZeroLibFunc),
+        AssertLtConst(FeltAssertLtConstLibFunc),
+        Popcount(FeltPopcountLibFunc),
+        Pack(FeltPackLibFunc),
+        Clamp(FeltClampLibFunc),
     }, FeltConcrete
 }
 
@@ -53,6 +79,16 @@ impl JumpNotZeroTraits for FeltTraits {
 }
 pub type FeltJumpNotZeroLibFunc = JumpNotZeroLibFunc<FeltTraits>;
 
+/// Branches on whether a felt is zero, falling through on zero and providing the value wrapped as
+/// `NonZero<felt>` otherwise.
+#[derive(Default)]
+pub struct FeltIsZeroTraits {}
+impl JumpNotZeroTraits for FeltIsZeroTraits {
+    const JUMP_NOT_ZERO: GenericLibFuncId = GenericLibFuncId::new_inline("felt_is_zero");
+    const GENERIC_TYPE_ID: GenericTypeId = <FeltType as NamedType>::ID;
+}
+pub type FeltIsZeroLibFunc = JumpNotZeroLibFunc<FeltIsZeroTraits>;
+
 /// Felt arithmetic operators.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FeltOperator {
@@ -111,8 +147,9 @@ impl GenericLibFunc for FeltOperationLibFunc {
                 SierraApChange::Known(0),
             )),
             [GenericArg::Value(c)] => {
+                validate_felt_const(c)?;
                 if matches!(self.operator, FeltOperator::Div) && c.is_zero() {
-                    Err(SpecializationError::UnsupportedGenericArg)
+                    Err(SpecializationError::unsupported_arg(args[0].clone()))
                 } else {
                     Ok(LibFuncSignature::new_non_branch(
                         vec![ty.clone()],
@@ -124,7 +161,7 @@ impl GenericLibFunc for FeltOperationLibFunc {
                     ))
                 }
             }
-            _ => Err(SpecializationError::UnsupportedGenericArg),
+            _ => Err(optional_value_args_error(args)),
         }
     }
 
@@ -140,7 +177,7 @@ impl GenericLibFunc for FeltOperationLibFunc {
             })),
             [GenericArg::Value(c)] => {
                 if matches!(self.operator, FeltOperator::Div) && c.is_zero() {
-                    Err(SpecializationError::UnsupportedGenericArg)
+                    Err(SpecializationError::unsupported_arg(args[0].clone()))
                 } else {
                     Ok(FeltOperationConcreteLibFunc::Const(FeltOperationWithConstConcreteLibFunc {
                         operator: self.operator,
@@ -149,7 +186,7 @@ impl GenericLibFunc for FeltOperationLibFunc {
                     }))
                 }
             }
-            _ => Err(SpecializationError::UnsupportedGenericArg),
+            _ => Err(optional_value_args_error(args)),
         }
     }
 }
@@ -183,6 +220,29 @@ impl SignatureBasedConcreteLibFunc for FeltOperationWithConstConcreteLibFunc {
     }
 }
 
+/// LibFunc for computing the multiplicative inverse of a non-zero felt in the prime field.
+#[derive(Default)]
+pub struct FeltInverseLibFunc {}
+impl NoGenericArgsGenericLibFunc for FeltInverseLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("felt_inv");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let non_zero_type = context.get_wrapped_concrete_type(NonZeroType::id(), felt_type)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![non_zero_type.clone()],
+            vec![OutputVarInfo {
+                ty: non_zero_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
 /// LibFunc for creating a constant felt.
 #[derive(Default)]
 pub struct FeltConstLibFunc {}
@@ -193,8 +253,9 @@ impl NamedLibFunc for FeltConstLibFunc {
     fn specialize_signature(
         &self,
         context: &dyn SignatureSpecializationContext,
-        _args: &[GenericArg],
+        args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
+        felt_const_arg(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![],
             vec![OutputVarInfo {
@@ -210,21 +271,45 @@ impl NamedLibFunc for FeltConstLibFunc {
         context: &dyn SpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self::Concrete, SpecializationError> {
-        match args {
-            [GenericArg::Value(c)] => Ok(FeltConstConcreteLibFunc {
-                c: c.clone(),
-                signature: <Self as NamedLibFunc>::specialize_signature(
-                    self,
-                    context.upcast(),
-                    args,
-                )?,
-            }),
-            _ => Err(SpecializationError::UnsupportedGenericArg),
-        }
+        Ok(FeltConstConcreteLibFunc {
+            c: felt_const_arg(args)?,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Extracts and validates the constant of a `felt_const` libfunc, given either as a value or as
+/// a short string.
+fn felt_const_arg(args: &[GenericArg]) -> Result<BigInt, SpecializationError> {
+    let c = match args {
+        [GenericArg::Value(c)] => c.clone(),
+        [GenericArg::ShortString(s)] => short_string_to_felt(s)?,
+        [arg] => return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value)),
+        _ => return Err(SpecializationError::wrong_arg_count(1, args.len())),
+    };
+    validate_felt_const(&c)?;
+    Ok(c)
+}
+
+/// The maximal number of characters in a short string.
+/// Any 32 printable characters already exceed the field prime.
+const SHORT_STRING_MAX_LEN: usize = 31;
+
+/// Packs a short string into a felt, with each ASCII character taking a byte and the first
+/// character taking the most significant byte.
+pub fn short_string_to_felt(s: &str) -> Result<BigInt, SpecializationError> {
+    if !s.is_ascii() {
+        return Err(SpecializationError::unsupported_arg(GenericArg::ShortString(s.to_string())));
+    }
+    if s.len() > SHORT_STRING_MAX_LEN {
+        return Err(SpecializationError::ShortStringOverflow(s.to_string()));
     }
+    Ok(BigInt::from_bytes_be(Sign::Plus, s.as_bytes()))
 }
 
 pub struct FeltConstConcreteLibFunc {
+    /// The constant, in the range `(-prime, prime)`. A negative constant stands for its residue
+    /// modulo the prime.
     pub c: BigInt,
     pub signature: LibFuncSignature,
 }
@@ -233,3 +318,273 @@ impl SignatureBasedConcreteLibFunc for FeltConstConcreteLibFunc {
         &self.signature
     }
 }
+
+/// LibFunc for checking that a felt is smaller than a constant bound, using the range check
+/// builtin. Branches to the first branch if in range, and to the second otherwise.
+#[derive(Default)]
+pub struct FeltAssertLtConstLibFunc {}
+impl NamedLibFunc for FeltAssertLtConstLibFunc {
+    type Concrete = FeltAssertLtConstConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("felt_assert_lt_const");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if args_as_single_value(args)? <= BigInt::zero() {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(context.get_concrete_type(FeltType::id(), &[])?),
+            ],
+            branch_signatures: vec![
+                // In range:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Out of range:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let signature = <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?;
+        Ok(FeltAssertLtConstConcreteLibFunc { bound: args_as_single_value(args)?, signature })
+    }
+}
+
+pub struct FeltAssertLtConstConcreteLibFunc {
+    /// The exclusive upper bound of the range.
+    pub bound: BigInt,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for FeltAssertLtConstConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// The maximal bit width of a felt value, as felts have 252 bits.
+const MAX_BIT_WIDTH: u32 = 252;
+
+/// LibFunc for counting the set bits among the low `width` bits of a felt.
+#[derive(Default)]
+pub struct FeltPopcountLibFunc {}
+impl NamedLibFunc for FeltPopcountLibFunc {
+    type Concrete = FeltPopcountConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("felt_popcount");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        extract_bit_width(args)?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![felt_type.clone()],
+            vec![OutputVarInfo {
+                ty: felt_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(FeltPopcountConcreteLibFunc {
+            width: extract_bit_width(args)?,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Extracts a bit width generic arg, which must be between 1 and `MAX_BIT_WIDTH`.
+fn extract_bit_width(args: &[GenericArg]) -> Result<u32, SpecializationError> {
+    match u32::try_from(args_as_single_value(args)?) {
+        Ok(width) if (1..=MAX_BIT_WIDTH).contains(&width) => Ok(width),
+        _ => Err(SpecializationError::unsupported_arg(args[0].clone())),
+    }
+}
+
+pub struct FeltPopcountConcreteLibFunc {
+    /// The number of low bits of the input that are counted.
+    pub width: u32,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for FeltPopcountConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for packing an array of felts, each smaller than `2^bits`, into a single felt, with the
+/// first element at the lowest bits. Branches to the first branch with the packed felt, and to the
+/// second branch if an element does not fit in `bits` bits or the packed value exceeds the prime.
+#[derive(Default)]
+pub struct FeltPackLibFunc {}
+impl NamedLibFunc for FeltPackLibFunc {
+    type Concrete = FeltPackConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("pack_felts");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        extract_bit_width(args)?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let array_type = context.get_wrapped_concrete_type(ArrayType::id(), felt_type.clone())?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(array_type),
+            ],
+            branch_signatures: vec![
+                // Packed:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        OutputVarInfo {
+                            ty: felt_type,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Overflow:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(FeltPackConcreteLibFunc {
+            bits: extract_bit_width(args)?,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct FeltPackConcreteLibFunc {
+    /// The number of bits each packed element occupies.
+    pub bits: u32,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for FeltPackConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for converting a felt into a bounded integer type, saturating to the maximal value of
+/// the type instead of failing when the felt does not fit.
+#[derive(Default)]
+pub struct FeltClampLibFunc {}
+impl NamedLibFunc for FeltClampLibFunc {
+    type Concrete = FeltClampConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("felt_clamp");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (target_ty, _) = as_clamp_target(context, args)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![range_check_type.clone(), context.get_concrete_type(FeltType::id(), &[])?],
+            vec![
+                OutputVarInfo {
+                    ty: range_check_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: target_ty,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, bit_width) = as_clamp_target(context.upcast(), args)?;
+        Ok(FeltClampConcreteLibFunc {
+            bit_width,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Returns the bounded integer type given as the generic arg of `felt_clamp`, and its bit width.
+fn as_clamp_target(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, u32), SpecializationError> {
+    let target_ty = match args {
+        [GenericArg::Type(ty)] => ty,
+        [arg] => return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type)),
+        _ => return Err(SpecializationError::wrong_arg_count(1, args.len())),
+    };
+    let generic_id = context.get_type_info(target_ty.clone())?.long_id.generic_id;
+    let bit_width = bounded_int_bit_width(&generic_id)
+        .ok_or_else(|| SpecializationError::unsupported_arg(args[0].clone()))?;
+    Ok((target_ty.clone(), bit_width))
+}
+
+pub struct FeltClampConcreteLibFunc {
+    /// The bit width of the target type, bounding the clamped value.
+    pub bit_width: u32,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for FeltClampConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
@@ -1,3 +1,4 @@
+use crate::extensions::args::args_as_single_user_func;
 use crate::extensions::lib_func::{
     LibFuncSignature, OutputVarInfo, SierraApChange, SignatureBasedConcreteLibFunc,
     SignatureSpecializationContext, SpecializationContext,
@@ -18,30 +19,26 @@ impl NamedLibFunc for FunctionCallLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        match args {
-            [GenericArg::UserFunc(function_id)] => {
-                let signature = context.get_function_signature(function_id)?;
-                let ap_change = match context.get_function_ap_change(function_id)? {
-                    // The call uses two stack slots.
-                    SierraApChange::Known(ap_change) => SierraApChange::Known(ap_change + 2),
-                    ap_change => ap_change,
-                };
-                Ok(LibFuncSignature::new_non_branch(
-                    signature.param_types.clone(),
-                    signature
-                        .ret_types
-                        .iter()
-                        .enumerate()
-                        .map(|(i, ty)| OutputVarInfo {
-                            ty: ty.clone(),
-                            ref_info: OutputVarReferenceInfo::NewTempVar { idx: i },
-                        })
-                        .collect(),
-                    ap_change,
-                ))
-            }
-            _ => Err(SpecializationError::UnsupportedGenericArg),
-        }
+        let function_id = args_as_single_user_func(args)?;
+        let signature = context.get_function_signature(&function_id)?;
+        let ap_change = match context.get_function_ap_change(&function_id)? {
+            // The call uses two stack slots.
+            SierraApChange::Known(ap_change) => SierraApChange::Known(ap_change + 2),
+            ap_change => ap_change,
+        };
+        Ok(LibFuncSignature::new_non_branch(
+            signature.param_types.clone(),
+            signature
+                .ret_types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| OutputVarInfo {
+                    ty: ty.clone(),
+                    ref_info: OutputVarReferenceInfo::NewTempVar { idx: i },
+                })
+                .collect(),
+            ap_change,
+        ))
     }
 
     fn specialize(
@@ -49,13 +46,10 @@ impl NamedLibFunc for FunctionCallLibFunc {
         context: &dyn SpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self::Concrete, SpecializationError> {
-        match args {
-            [GenericArg::UserFunc(function_id)] => Ok(Self::Concrete {
-                function: context.get_function(function_id)?,
-                signature: self.specialize_signature(context.upcast(), args)?,
-            }),
-            _ => Err(SpecializationError::UnsupportedGenericArg),
-        }
+        Ok(Self::Concrete {
+            function: context.get_function(&args_as_single_user_func(args)?)?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
     }
 }
 
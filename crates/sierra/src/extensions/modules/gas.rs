@@ -1,4 +1,5 @@
 // Module providing the gas related extensions.
+use super::integer::Uint128Type;
 use super::range_check::RangeCheckType;
 use crate::define_libfunc_hierarchy;
 use crate::extensions::lib_func::{
@@ -37,6 +38,8 @@ define_libfunc_hierarchy! {
         BurnGas(BurnGasLibFunc),
         GetGas(GetGasLibFunc),
         RefundGas(RefundGasLibFunc),
+        GasMark(GasMarkLibFunc),
+        GasElapsed(GasElapsedLibFunc),
     }, GasConcreteLibFunc
 }
 
@@ -133,3 +136,62 @@ impl NoGenericArgsGenericLibFunc for BurnGasLibFunc {
         Ok(LibFuncSignature::new_non_branch(vec![], vec![], SierraApChange::Known(0)))
     }
 }
+
+/// LibFunc for marking the current value of the gas counter, to later measure the gas consumed
+/// since the mark using `gas_elapsed`.
+#[derive(Default)]
+pub struct GasMarkLibFunc {}
+impl NoGenericArgsGenericLibFunc for GasMarkLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("gas_mark");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let gas_builtin_type = context.get_concrete_type(GasBuiltinType::id(), &[])?;
+        let uint128_type = context.get_concrete_type(Uint128Type::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![gas_builtin_type.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: gas_builtin_type,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: uint128_type,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+            ],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for getting the gas consumed since a mark created by `gas_mark`.
+#[derive(Default)]
+pub struct GasElapsedLibFunc {}
+impl NoGenericArgsGenericLibFunc for GasElapsedLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("gas_elapsed");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let gas_builtin_type = context.get_concrete_type(GasBuiltinType::id(), &[])?;
+        let uint128_type = context.get_concrete_type(Uint128Type::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![gas_builtin_type.clone(), uint128_type.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: gas_builtin_type,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: uint128_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::Known(0),
+        ))
+    }
+}
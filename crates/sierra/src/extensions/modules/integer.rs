@@ -1,17 +1,22 @@
+use num_bigint::BigInt;
 use num_traits::Zero;
 
+use super::array::ArrayType;
 use super::felt::FeltType;
 use super::jump_not_zero::{JumpNotZeroLibFunc, JumpNotZeroTraits};
 use super::non_zero::NonZeroType;
 use super::range_check::RangeCheckType;
+use super::uint::{Uint16Traits, Uint32Traits, Uint64Traits, Uint64Type, Uint8Traits, UintTraits};
+use crate::extensions::args::optional_value_args_error;
 use crate::extensions::lib_func::{
     BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
     SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
 use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
 use crate::extensions::{
-    GenericLibFunc, NamedLibFunc, NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType,
-    OutputVarReferenceInfo, SignatureBasedConcreteLibFunc, SpecializationError,
+    GenericArgKind, GenericLibFunc, NamedLibFunc, NamedType, NoGenericArgsGenericLibFunc,
+    NoGenericArgsGenericType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
 };
 use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
 use crate::program::GenericArg;
@@ -37,6 +42,19 @@ impl NoGenericArgsGenericType for Uint128Type {
     }
 }
 
+/// Returns the bit width of the bounded integer type with the given generic id, or `None` if the
+/// type is not a bounded integer type.
+pub fn bounded_int_bit_width(generic_id: &GenericTypeId) -> Option<u32> {
+    match generic_id {
+        id if id == &Uint8Traits::GENERIC_TYPE_ID => Some(Uint8Traits::BITS),
+        id if id == &Uint16Traits::GENERIC_TYPE_ID => Some(Uint16Traits::BITS),
+        id if id == &Uint32Traits::GENERIC_TYPE_ID => Some(Uint32Traits::BITS),
+        id if id == &Uint64Traits::GENERIC_TYPE_ID => Some(Uint64Traits::BITS),
+        id if id == &<Uint128Type as NamedType>::ID => Some(128),
+        _ => None,
+    }
+}
+
 define_libfunc_hierarchy! {
     pub enum Uint128LibFunc {
         Operation(Uint128OperationLibFunc),
@@ -46,6 +64,11 @@ define_libfunc_hierarchy! {
         FromFelt(Uint128FromFeltLibFunc),
         ToFelt(Uint128ToFeltLibFunc),
         JumpNotZero(Uint128JumpNotZeroLibFunc),
+        ArraySum(Uint128ArraySumLibFunc),
+        SafeDivMod(Uint128SafeDivModLibFunc),
+        WideMul(Uint128WideMulLibFunc),
+        Sqrt(Uint128SqrtLibFunc),
+        Shift(Uint128ShiftLibFunc),
     }, Uint128Concrete
 }
 
@@ -70,6 +93,13 @@ pub enum IntOperator {
     Mod,
 }
 
+/// Returns the constant given as a generic arg of a uint128 libfunc, checking it fits in a
+/// uint128.
+fn as_uint128_const(c: &BigInt) -> Result<u128, SpecializationError> {
+    u128::try_from(c)
+        .map_err(|_| SpecializationError::unsupported_arg(GenericArg::Value(c.clone())))
+}
+
 /// Libfunc for uint128 operations.
 pub struct Uint128OperationLibFunc {
     pub operator: IntOperator,
@@ -111,6 +141,9 @@ impl GenericLibFunc for Uint128OperationLibFunc {
     ) -> Result<LibFuncSignature, SpecializationError> {
         let ty = context.get_concrete_type(Uint128Type::id(), &[])?;
         let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        if let [GenericArg::Value(c)] = args {
+            as_uint128_const(c)?;
+        }
         match (args, self.operator) {
             ([], IntOperator::Div | IntOperator::Mod) => Ok(LibFuncSignature::new_non_branch(
                 vec![
@@ -261,7 +294,7 @@ impl GenericLibFunc for Uint128OperationLibFunc {
                     fallthrough: Some(0),
                 })
             }
-            _ => Err(SpecializationError::UnsupportedGenericArg),
+            _ => Err(optional_value_args_error(args)),
         }
     }
 
@@ -279,18 +312,18 @@ impl GenericLibFunc for Uint128OperationLibFunc {
             }
             [GenericArg::Value(c)] => {
                 if matches!(self.operator, IntOperator::Div | IntOperator::Mod) && c.is_zero() {
-                    Err(SpecializationError::UnsupportedGenericArg)
+                    Err(SpecializationError::unsupported_arg(args[0].clone()))
                 } else {
                     Ok(Uint128OperationConcreteLibFunc::Const(
                         Uint128OperationWithConstConcreteLibFunc {
                             operator: self.operator,
-                            c: u128::try_from(c).unwrap(),
+                            c: as_uint128_const(c)?,
                             signature: self.specialize_signature(context.upcast(), args)?,
                         },
                     ))
                 }
             }
-            _ => Err(SpecializationError::UnsupportedGenericArg),
+            _ => Err(optional_value_args_error(args)),
         }
     }
 }
@@ -353,14 +386,15 @@ impl NamedLibFunc for Uint128ConstLibFunc {
     ) -> Result<Self::Concrete, SpecializationError> {
         match args {
             [GenericArg::Value(c)] => Ok(Uint128ConstConcreteLibFunc {
-                c: u128::try_from(c).unwrap(),
+                c: as_uint128_const(c)?,
                 signature: <Self as NamedLibFunc>::specialize_signature(
                     self,
                     context.upcast(),
                     args,
                 )?,
             }),
-            _ => Err(SpecializationError::UnsupportedGenericArg),
+            [arg] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value)),
+            _ => Err(SpecializationError::wrong_arg_count(1, args.len())),
         }
     }
 }
@@ -520,3 +554,309 @@ impl NoGenericArgsGenericLibFunc for Uint128ToFeltLibFunc {
         ))
     }
 }
+
+/// LibFunc for summing an array of uint128s.
+/// Branches to the first branch with the sum, and to the second branch on overflow.
+#[derive(Default)]
+pub struct Uint128ArraySumLibFunc {}
+impl NoGenericArgsGenericLibFunc for Uint128ArraySumLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("uint128_array_sum");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let uint128_type = context.get_concrete_type(Uint128Type::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        // The number of range checks used depends on the length of the array, so the range check
+        // output is not at a constant offset from its input.
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(
+                    context.get_wrapped_concrete_type(ArrayType::id(), uint128_type.clone())?,
+                ),
+            ],
+            branch_signatures: vec![
+                // No overflow:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        OutputVarInfo {
+                            ty: uint128_type,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::Unknown,
+                },
+                // Overflow:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::Unknown,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for dividing a uint128 by a non-zero uint128, returning both the quotient and the
+/// remainder.
+#[derive(Default)]
+pub struct Uint128SafeDivModLibFunc {}
+impl NoGenericArgsGenericLibFunc for Uint128SafeDivModLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("u128_safe_divmod");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = context.get_concrete_type(Uint128Type::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![
+                range_check_type.clone(),
+                ty.clone(),
+                context.get_wrapped_concrete_type(NonZeroType::id(), ty.clone())?,
+            ],
+            vec![
+                OutputVarInfo {
+                    ty: range_check_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                // Quotient:
+                OutputVarInfo {
+                    ty: ty.clone(),
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+                // Remainder:
+                OutputVarInfo {
+                    ty,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for multiplying two uint128s into their full 256-bit product, returned as its high and
+/// low uint128 halves.
+#[derive(Default)]
+pub struct Uint128WideMulLibFunc {}
+impl NoGenericArgsGenericLibFunc for Uint128WideMulLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("u128_wide_mul");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = context.get_concrete_type(Uint128Type::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let uint128_output = || OutputVarInfo {
+            ty: ty.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        Ok(LibFuncSignature::new_non_branch(
+            vec![range_check_type.clone(), ty.clone(), ty.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: range_check_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                // High:
+                uint128_output(),
+                // Low:
+                uint128_output(),
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for computing the integer square root of a uint128, which always fits in a uint64.
+///
+/// When compiled to CASM, the root is computed by a hint, and only verified by range checks
+/// asserting `root^2 <= value < (root + 1)^2`.
+#[derive(Default)]
+pub struct Uint128SqrtLibFunc {}
+impl NoGenericArgsGenericLibFunc for Uint128SqrtLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("u128_sqrt");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![range_check_type.clone(), context.get_concrete_type(Uint128Type::id(), &[])?],
+            vec![
+                OutputVarInfo {
+                    ty: range_check_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: context.get_concrete_type(Uint64Type::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// The number of bits in a uint128, bounding the amounts a uint128 can be shifted by.
+const UINT128_BITS: u32 = 128;
+
+/// Shift operators for uint128.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShiftOperator {
+    Shl,
+    Shr,
+}
+
+/// Libfunc for shifting a uint128, either by a uint128 amount or by a constant amount given as a
+/// generic arg.
+/// Shifting left branches to the first branch with the result if no set bits were shifted out, and
+/// to the second branch otherwise. Shifting right never fails, and shifting by 128 or more bits
+/// results in 0.
+pub struct Uint128ShiftLibFunc {
+    pub operator: ShiftOperator,
+}
+impl Uint128ShiftLibFunc {
+    fn new(operator: ShiftOperator) -> Self {
+        Self { operator }
+    }
+}
+impl GenericLibFunc for Uint128ShiftLibFunc {
+    type Concrete = Uint128ShiftConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        const SHL: GenericLibFuncId = GenericLibFuncId::new_inline("u128_shl");
+        const SHR: GenericLibFuncId = GenericLibFuncId::new_inline("u128_shr");
+        match id {
+            id if id == &SHL => Some(Self::new(ShiftOperator::Shl)),
+            id if id == &SHR => Some(Self::new(ShiftOperator::Shr)),
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = context.get_concrete_type(Uint128Type::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let params = match args {
+            [] => vec![range_check_type.clone(), ty.clone(), ty.clone()],
+            [GenericArg::Value(_)] => {
+                extract_shift_amount(args)?;
+                vec![range_check_type.clone(), ty.clone()]
+            }
+            _ => return Err(optional_value_args_error(args)),
+        };
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        let result_output = OutputVarInfo {
+            ty,
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        Ok(match self.operator {
+            ShiftOperator::Shl => LibFuncSignature {
+                param_signatures: params.into_iter().map(ParamSignature::new).collect(),
+                branch_signatures: vec![
+                    BranchSignature {
+                        vars: vec![range_check_output(), result_output],
+                        ap_change: SierraApChange::NotImplemented,
+                    },
+                    BranchSignature {
+                        vars: vec![range_check_output()],
+                        ap_change: SierraApChange::NotImplemented,
+                    },
+                ],
+                fallthrough: Some(0),
+            },
+            ShiftOperator::Shr => LibFuncSignature::new_non_branch(
+                params,
+                vec![range_check_output(), result_output],
+                SierraApChange::NotImplemented,
+            ),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let signature = self.specialize_signature(context.upcast(), args)?;
+        Ok(match args {
+            [] => Uint128ShiftConcreteLibFunc::Binary(Uint128ShiftBinaryConcreteLibFunc {
+                operator: self.operator,
+                signature,
+            }),
+            _ => Uint128ShiftConcreteLibFunc::Const(Uint128ShiftWithConstConcreteLibFunc {
+                operator: self.operator,
+                amount: extract_shift_amount(args)?,
+                signature,
+            }),
+        })
+    }
+}
+
+/// Extracts a constant shift amount from the generic args, rejecting amounts of 128 bits or more.
+fn extract_shift_amount(args: &[GenericArg]) -> Result<u32, SpecializationError> {
+    match args {
+        [GenericArg::Value(amount)] => u32::try_from(amount)
+            .ok()
+            .filter(|amount| *amount < UINT128_BITS)
+            .ok_or_else(|| SpecializationError::unsupported_arg(args[0].clone())),
+        _ => Err(optional_value_args_error(args)),
+    }
+}
+
+/// uint128 shifts by a uint128 amount.
+pub struct Uint128ShiftBinaryConcreteLibFunc {
+    pub operator: ShiftOperator,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for Uint128ShiftBinaryConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// uint128 shifts by a constant amount.
+pub struct Uint128ShiftWithConstConcreteLibFunc {
+    pub operator: ShiftOperator,
+    /// The shift amount, smaller than 128.
+    pub amount: u32,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for Uint128ShiftWithConstConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+define_concrete_libfunc_hierarchy! {
+    pub enum Uint128ShiftConcreteLibFunc {
+        Binary(Uint128ShiftBinaryConcreteLibFunc),
+        Const(Uint128ShiftWithConstConcreteLibFunc),
+    }
+}
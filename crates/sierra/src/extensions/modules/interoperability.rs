@@ -0,0 +1,107 @@
+//! Types identifying contracts and contract classes, as felts smaller than 2^251.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type ContractAddress = ContractAddress;
+//! libfunc contract_address_try_from_felt252 = contract_address_try_from_felt252;
+//! ...
+//! contract_address_try_from_felt252(rc, x) { fallthrough(rc, address) out_of_range(rc) };
+//! ```
+
+use std::marker::PhantomData;
+
+use super::bytes31::range_checked_branch_signature;
+use super::felt::FeltType;
+use crate::extensions::lib_func::{
+    LibFuncSignature, OutputVarInfo, SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+
+/// The number of bits of the values of the bounded felt types.
+pub const BOUNDED_FELT_BITS: u32 = 251;
+
+/// Trait for implementing a felt type bounded by 2^251 and its conversion from felt.
+pub trait BoundedFeltTraits: Default {
+    /// The id of the generic type.
+    const GENERIC_TYPE_ID: GenericTypeId;
+    /// The id of the library function converting a felt into the type, branching if it is out of
+    /// range.
+    const TRY_FROM_FELT: GenericLibFuncId;
+}
+
+#[derive(Default)]
+pub struct ContractAddressTraits {}
+impl BoundedFeltTraits for ContractAddressTraits {
+    const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("ContractAddress");
+    const TRY_FROM_FELT: GenericLibFuncId =
+        GenericLibFuncId::new_inline("contract_address_try_from_felt252");
+}
+pub type ContractAddressType = BoundedFeltType<ContractAddressTraits>;
+pub type ContractAddressTryFromFeltLibFunc = BoundedFeltTryFromFeltLibFunc<ContractAddressTraits>;
+
+#[derive(Default)]
+pub struct ClassHashTraits {}
+impl BoundedFeltTraits for ClassHashTraits {
+    const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("ClassHash");
+    const TRY_FROM_FELT: GenericLibFuncId =
+        GenericLibFuncId::new_inline("class_hash_try_from_felt252");
+}
+pub type ClassHashType = BoundedFeltType<ClassHashTraits>;
+pub type ClassHashTryFromFeltLibFunc = BoundedFeltTryFromFeltLibFunc<ClassHashTraits>;
+
+/// Type for a felt known to be smaller than 2^251.
+#[derive(Default)]
+pub struct BoundedFeltType<TBoundedFeltTraits: BoundedFeltTraits> {
+    _phantom: PhantomData<TBoundedFeltTraits>,
+}
+impl<TBoundedFeltTraits: BoundedFeltTraits> NoGenericArgsGenericType
+    for BoundedFeltType<TBoundedFeltTraits>
+{
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = TBoundedFeltTraits::GENERIC_TYPE_ID;
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 1,
+            },
+        }
+    }
+}
+
+/// LibFunc for converting a felt into a bounded felt type.
+/// Branches to the first branch with the value if it is smaller than 2^251, and to the second
+/// branch otherwise.
+#[derive(Default)]
+pub struct BoundedFeltTryFromFeltLibFunc<TBoundedFeltTraits: BoundedFeltTraits> {
+    _phantom: PhantomData<TBoundedFeltTraits>,
+}
+impl<TBoundedFeltTraits: BoundedFeltTraits> NoGenericArgsGenericLibFunc
+    for BoundedFeltTryFromFeltLibFunc<TBoundedFeltTraits>
+{
+    const ID: GenericLibFuncId = TBoundedFeltTraits::TRY_FROM_FELT;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        range_checked_branch_signature(
+            context,
+            vec![context.get_concrete_type(FeltType::id(), &[])?],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(BoundedFeltType::<TBoundedFeltTraits>::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+            }],
+        )
+    }
+}
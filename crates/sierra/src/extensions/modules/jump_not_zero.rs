@@ -1,14 +1,18 @@
 use std::marker::PhantomData;
 
+use super::felt::FeltType;
+use super::integer::bounded_int_bit_width;
 use super::non_zero::NonZeroType;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     BranchSignature, LibFuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
-    SignatureSpecializationContext,
+    SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
 };
 use crate::extensions::{
     NamedType, NoGenericArgsGenericLibFunc, OutputVarReferenceInfo, SpecializationError,
 };
-use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
 
 /// Trait for implementing a JumpNotZero library function for a type.
 pub trait JumpNotZeroTraits: Default {
@@ -34,21 +38,51 @@ impl<TJumpNotZeroTraits: JumpNotZeroTraits> NoGenericArgsGenericLibFunc
         context: &dyn SignatureSpecializationContext,
     ) -> Result<LibFuncSignature, SpecializationError> {
         let ty = context.get_concrete_type(TJumpNotZeroTraits::GENERIC_TYPE_ID, &[])?;
-        Ok(LibFuncSignature {
-            param_signatures: vec![ParamSignature::new(ty.clone())],
-            branch_signatures: vec![
-                // Zero.
-                BranchSignature { vars: vec![], ap_change: SierraApChange::Known(0) },
-                // NonZero.
-                BranchSignature {
-                    vars: vec![OutputVarInfo {
-                        ty: context.get_wrapped_concrete_type(NonZeroType::id(), ty)?,
-                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
-                    }],
-                    ap_change: SierraApChange::Known(0),
-                },
-            ],
-            fallthrough: Some(0),
-        })
+        jump_not_zero_signature(context, ty)
     }
 }
+
+/// LibFunc for jump non-zero on a value of any integer type (felt or an unsigned integer),
+/// returning the value wrapped as `NonZero<T>` in case of success.
+#[derive(Default)]
+pub struct GenericJumpNotZeroLibFunc {}
+impl SignatureOnlyGenericLibFunc for GenericJumpNotZeroLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("jump_nz");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let generic_id = context.get_type_info(ty.clone())?.long_id.generic_id;
+        if generic_id != FeltType::ID && bounded_int_bit_width(&generic_id).is_none() {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        jump_not_zero_signature(context, ty)
+    }
+}
+
+/// Returns the signature of a jump non-zero on a value of type `ty`: falling through on zero, and
+/// jumping with the value wrapped as `NonZero<ty>` otherwise.
+fn jump_not_zero_signature(
+    context: &dyn SignatureSpecializationContext,
+    ty: ConcreteTypeId,
+) -> Result<LibFuncSignature, SpecializationError> {
+    Ok(LibFuncSignature {
+        param_signatures: vec![ParamSignature::new(ty.clone())],
+        branch_signatures: vec![
+            // Zero.
+            BranchSignature { vars: vec![], ap_change: SierraApChange::Known(0) },
+            // NonZero.
+            BranchSignature {
+                vars: vec![OutputVarInfo {
+                    ty: context.get_wrapped_concrete_type(NonZeroType::id(), ty)?,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                }],
+                ap_change: SierraApChange::Known(0),
+            },
+        ],
+        fallthrough: Some(0),
+    })
+}
@@ -1,13 +1,14 @@
-use super::as_single_type;
 use super::uninitialized::UninitializedType;
 use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
-    LibFuncSignature, OutputVarInfo, ParamSignature, SierraApChange, SignatureOnlyGenericLibFunc,
-    SignatureSpecializationContext, SpecializationContext,
+    LibFuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
+    SignatureAndTypeConcreteLibFunc, SignatureAndTypeGenericLibFunc, SignatureOnlyGenericLibFunc,
+    SignatureSpecializationContext, WrapSignatureAndTypeGenericLibFunc,
 };
+use crate::extensions::types::TypeInfo;
 use crate::extensions::{
-    NamedLibFunc, NamedType, NoGenericArgsGenericLibFunc, OutputVarReferenceInfo,
-    SignatureBasedConcreteLibFunc, SpecializationError,
+    NamedType, NoGenericArgsGenericLibFunc, OutputVarReferenceInfo, SpecializationError,
 };
 use crate::ids::{ConcreteTypeId, GenericLibFuncId};
 use crate::program::GenericArg;
@@ -23,100 +24,76 @@ define_libfunc_hierarchy! {
     }, MemConcreteLibFunc
 }
 
+/// Returns the info of the given type, validating that it can be stored.
+fn get_storable_type_info(
+    context: &dyn SignatureSpecializationContext,
+    ty: &ConcreteTypeId,
+) -> Result<TypeInfo, SpecializationError> {
+    let info = context.get_type_info(ty.clone())?;
+    if info.storable {
+        Ok(info)
+    } else {
+        Err(SpecializationError::TypeNotStorable(ty.clone()))
+    }
+}
+
 /// LibFunc for storing a value into temporary memory.
 #[derive(Default)]
-pub struct StoreTempLibFunc {}
-impl NamedLibFunc for StoreTempLibFunc {
-    type Concrete = StoreTempConcreteLibFunc;
+pub struct StoreTempLibFuncWrapped {}
+impl SignatureAndTypeGenericLibFunc for StoreTempLibFuncWrapped {
     const ID: GenericLibFuncId = GenericLibFuncId::new_inline("store_temp");
 
     fn specialize_signature(
         &self,
         context: &dyn SignatureSpecializationContext,
-        args: &[GenericArg],
+        ty: ConcreteTypeId,
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
-        let type_size = context.as_type_specialization_context().get_type_info(ty.clone())?.size;
+        let type_size = get_storable_type_info(context, &ty)?.size;
+        // A zero-sized value occupies no cells, so storing it is a no-op.
+        let ref_info = if type_size == 0 {
+            OutputVarReferenceInfo::SameAsParam { param_idx: 0 }
+        } else {
+            OutputVarReferenceInfo::NewTempVar { idx: 0 }
+        };
         Ok(LibFuncSignature::new_non_branch_ex(
             vec![ParamSignature { ty: ty.clone(), allow_deferred: true, allow_add_const: true }],
-            vec![OutputVarInfo { ty, ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 } }],
+            vec![OutputVarInfo { ty, ref_info }],
             SierraApChange::Known(type_size),
         ))
     }
-
-    fn specialize(
-        &self,
-        context: &dyn SpecializationContext,
-        args: &[GenericArg],
-    ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
-        Ok(StoreTempConcreteLibFunc {
-            ty,
-            signature: self.specialize_signature(context.upcast(), args)?,
-        })
-    }
-}
-
-pub struct StoreTempConcreteLibFunc {
-    pub ty: ConcreteTypeId,
-    pub signature: LibFuncSignature,
-}
-impl SignatureBasedConcreteLibFunc for StoreTempConcreteLibFunc {
-    fn signature(&self) -> &LibFuncSignature {
-        &self.signature
-    }
 }
+pub type StoreTempLibFunc = WrapSignatureAndTypeGenericLibFunc<StoreTempLibFuncWrapped>;
+pub type StoreTempConcreteLibFunc = SignatureAndTypeConcreteLibFunc;
 
 /// LibFunc for aligning the temporary buffer for flow control merge.
 #[derive(Default)]
-pub struct AlignTempsLibFunc {}
-impl NamedLibFunc for AlignTempsLibFunc {
-    type Concrete = AlignTempsConcreteLibFunc;
+pub struct AlignTempsLibFuncWrapped {}
+impl SignatureAndTypeGenericLibFunc for AlignTempsLibFuncWrapped {
     const ID: GenericLibFuncId = GenericLibFuncId::new_inline("align_temps");
 
     fn specialize_signature(
         &self,
         _context: &dyn SignatureSpecializationContext,
-        _args: &[GenericArg],
+        _ty: ConcreteTypeId,
     ) -> Result<LibFuncSignature, SpecializationError> {
         Ok(LibFuncSignature::new_non_branch(vec![], vec![], SierraApChange::NotImplemented))
     }
-
-    fn specialize(
-        &self,
-        context: &dyn SpecializationContext,
-        args: &[GenericArg],
-    ) -> Result<Self::Concrete, SpecializationError> {
-        Ok(AlignTempsConcreteLibFunc {
-            ty: as_single_type(args)?,
-            signature: self.specialize_signature(context.upcast(), args)?,
-        })
-    }
-}
-
-pub struct AlignTempsConcreteLibFunc {
-    pub ty: ConcreteTypeId,
-    pub signature: LibFuncSignature,
-}
-impl SignatureBasedConcreteLibFunc for AlignTempsConcreteLibFunc {
-    fn signature(&self) -> &LibFuncSignature {
-        &self.signature
-    }
 }
+pub type AlignTempsLibFunc = WrapSignatureAndTypeGenericLibFunc<AlignTempsLibFuncWrapped>;
+pub type AlignTempsConcreteLibFunc = SignatureAndTypeConcreteLibFunc;
 
 /// LibFunc for storing a value into local memory.
 #[derive(Default)]
-pub struct StoreLocalLibFunc {}
-impl NamedLibFunc for StoreLocalLibFunc {
-    type Concrete = StoreLocalConcreteLibFunc;
+pub struct StoreLocalLibFuncWrapped {}
+impl SignatureAndTypeGenericLibFunc for StoreLocalLibFuncWrapped {
     const ID: GenericLibFuncId = GenericLibFuncId::new_inline("store_local");
 
     fn specialize_signature(
         &self,
         context: &dyn SignatureSpecializationContext,
-        args: &[GenericArg],
+        ty: ConcreteTypeId,
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        get_storable_type_info(context, &ty)?;
         let uninitialized_type =
             context.get_wrapped_concrete_type(UninitializedType::id(), ty.clone())?;
         Ok(LibFuncSignature::new_non_branch_ex(
@@ -128,29 +105,9 @@ impl NamedLibFunc for StoreLocalLibFunc {
             SierraApChange::Known(0),
         ))
     }
-
-    fn specialize(
-        &self,
-        context: &dyn SpecializationContext,
-        args: &[GenericArg],
-    ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
-        Ok(StoreLocalConcreteLibFunc {
-            ty,
-            signature: self.specialize_signature(context.upcast(), args)?,
-        })
-    }
-}
-
-pub struct StoreLocalConcreteLibFunc {
-    pub ty: ConcreteTypeId,
-    pub signature: LibFuncSignature,
-}
-impl SignatureBasedConcreteLibFunc for StoreLocalConcreteLibFunc {
-    fn signature(&self) -> &LibFuncSignature {
-        &self.signature
-    }
 }
+pub type StoreLocalLibFunc = WrapSignatureAndTypeGenericLibFunc<StoreLocalLibFuncWrapped>;
+pub type StoreLocalConcreteLibFunc = SignatureAndTypeConcreteLibFunc;
 
 /// LibFunc for finalizing the locals for current function.
 #[derive(Default)]
@@ -167,27 +124,16 @@ impl NoGenericArgsGenericLibFunc for FinalizeLocalsLibFunc {
 }
 
 /// LibFunc for allocating locals for later stores.
-pub struct AllocLocalConcreteLibFunc {
-    pub ty: ConcreteTypeId,
-    pub signature: LibFuncSignature,
-}
-impl SignatureBasedConcreteLibFunc for AllocLocalConcreteLibFunc {
-    fn signature(&self) -> &LibFuncSignature {
-        &self.signature
-    }
-}
 #[derive(Default)]
-pub struct AllocLocalLibFunc {}
-impl NamedLibFunc for AllocLocalLibFunc {
-    type Concrete = AllocLocalConcreteLibFunc;
+pub struct AllocLocalLibFuncWrapped {}
+impl SignatureAndTypeGenericLibFunc for AllocLocalLibFuncWrapped {
     const ID: GenericLibFuncId = GenericLibFuncId::new_inline("alloc_local");
 
     fn specialize_signature(
         &self,
         context: &dyn SignatureSpecializationContext,
-        args: &[GenericArg],
+        ty: ConcreteTypeId,
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![],
             vec![OutputVarInfo {
@@ -197,19 +143,9 @@ impl NamedLibFunc for AllocLocalLibFunc {
             SierraApChange::Known(0),
         ))
     }
-
-    fn specialize(
-        &self,
-        context: &dyn SpecializationContext,
-        args: &[GenericArg],
-    ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
-        Ok(AllocLocalConcreteLibFunc {
-            ty,
-            signature: self.specialize_signature(context.upcast(), args)?,
-        })
-    }
 }
+pub type AllocLocalLibFunc = WrapSignatureAndTypeGenericLibFunc<AllocLocalLibFuncWrapped>;
+pub type AllocLocalConcreteLibFunc = SignatureAndTypeConcreteLibFunc;
 
 /// LibFunc for renaming an identifier - used to align identities for flow control merge.
 #[derive(Default)]
@@ -222,7 +158,7 @@ impl SignatureOnlyGenericLibFunc for RenameLibFunc {
         _context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![ty.clone()],
             vec![OutputVarInfo {
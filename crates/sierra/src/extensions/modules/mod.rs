@@ -1,31 +1,47 @@
-use super::SpecializationError;
-use crate::ids::ConcreteTypeId;
-use crate::program::GenericArg;
-
 pub mod ap_tracking;
 pub mod array;
+pub mod bitwise;
+pub mod boolean;
 pub mod boxing;
+pub mod branch_align;
+pub mod bytes31;
+pub mod casts;
+pub mod circuit;
+pub mod consts;
+pub mod coupon;
+pub mod debug;
 pub mod dict_felt_to;
 pub mod drop;
 pub mod duplicate;
+pub mod ec;
+pub mod ecdsa;
 pub mod enm;
+pub mod event;
 pub mod felt;
 pub mod function_call;
 pub mod gas;
 pub mod integer;
+pub mod interoperability;
 pub mod jump_not_zero;
 pub mod mem;
 pub mod non_zero;
+pub mod nullable;
+pub mod option;
+pub mod panic;
+pub mod pedersen;
+pub mod poseidon;
+pub mod pow;
 pub mod range_check;
+pub mod result;
+pub mod secp256;
+pub mod segment_arena;
+pub mod snapshot;
+pub mod span;
+pub mod storage;
 pub mod strct;
+pub mod syscalls;
+pub mod uint;
+pub mod uint256;
 pub mod unconditional_jump;
 pub mod uninitialized;
-
-/// Helper for extracting the type from the template arguments.
-fn as_single_type(args: &[GenericArg]) -> Result<ConcreteTypeId, SpecializationError> {
-    match args {
-        [GenericArg::Type(ty)] => Ok(ty.clone()),
-        [_] => Err(SpecializationError::UnsupportedGenericArg),
-        _ => Err(SpecializationError::WrongNumberOfGenericArgs),
-    }
-}
+pub mod unit;
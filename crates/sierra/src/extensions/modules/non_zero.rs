@@ -1,4 +1,4 @@
-use super::as_single_type;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     LibFuncSignature, OutputVarInfo, SierraApChange, SignatureOnlyGenericLibFunc,
     SignatureSpecializationContext,
@@ -9,7 +9,8 @@ use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, Special
 use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
 use crate::program::GenericArg;
 
-/// Type wrapping a value as non zero.
+/// Type wrapping a value as non zero. Has the same representation as the wrapped value, which is
+/// known to be non zero, making it usable as a divisor.
 #[derive(Default)]
 pub struct NonZeroType {}
 impl NamedType for NonZeroType {
@@ -21,12 +22,20 @@ impl NamedType for NonZeroType {
         context: &dyn TypeSpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self::Concrete, SpecializationError> {
-        let ty = as_single_type(args)?;
-        Ok(NonZeroConcreteType { info: context.get_type_info(ty.clone())?, ty })
+        let ty = args_as_single_type(args)?;
+        let info = context.get_type_info(ty.clone())?;
+        if !info.storable {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        Ok(NonZeroConcreteType {
+            info: TypeInfo { long_id: Self::concrete_type_long_id(args), ..info },
+            ty,
+        })
     }
 }
 pub struct NonZeroConcreteType {
     pub info: TypeInfo,
+    /// The wrapped type.
     pub ty: ConcreteTypeId,
 }
 impl ConcreteType for NonZeroConcreteType {
@@ -39,14 +48,14 @@ impl ConcreteType for NonZeroConcreteType {
 #[derive(Default)]
 pub struct UnwrapNonZeroLibFunc {}
 impl SignatureOnlyGenericLibFunc for UnwrapNonZeroLibFunc {
-    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("unwrap_nz");
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("unwrap_non_zero");
 
     fn specialize_signature(
         &self,
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let ty = as_single_type(args)?;
+        let ty = args_as_single_type(args)?;
         Ok(LibFuncSignature::new_non_branch(
             vec![context.get_wrapped_concrete_type(NonZeroType::id(), ty.clone())?],
             vec![OutputVarInfo {
@@ -0,0 +1,138 @@
+use super::boxing::BoxType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
+use crate::extensions::lib_func::{
+    BranchSignature, LibFuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
+    SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::TypeInfo;
+use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type representing a boxed value which may be null.
+#[derive(Default)]
+pub struct NullableType {}
+impl NamedType for NullableType {
+    type Concrete = NullableConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Nullable");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let info = context.get_type_info(ty.clone())?;
+        Ok(NullableConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                storable: true,
+                droppable: info.droppable,
+                duplicatable: info.duplicatable,
+                size: 1,
+            },
+            ty,
+        })
+    }
+}
+
+pub struct NullableConcreteType {
+    pub info: TypeInfo,
+    pub ty: ConcreteTypeId,
+}
+impl ConcreteType for NullableConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum NullableLibFunc {
+        Null(NullLibFunc),
+        FromBox(NullableFromBoxLibFunc),
+        Match(MatchNullableLibFunc),
+    }, NullableConcreteLibFunc
+}
+
+/// LibFunc for creating a null `Nullable<T>`.
+#[derive(Default)]
+pub struct NullLibFunc {}
+impl SignatureOnlyGenericLibFunc for NullLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("null");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: context.get_wrapped_concrete_type(NullableType::id(), ty)?,
+                ref_info: OutputVarReferenceInfo::Const,
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for converting a `Box<T>` into a non-null `Nullable<T>`.
+#[derive(Default)]
+pub struct NullableFromBoxLibFunc {}
+impl SignatureOnlyGenericLibFunc for NullableFromBoxLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("nullable_from_box");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![context.get_wrapped_concrete_type(BoxType::id(), ty.clone())?],
+            vec![OutputVarInfo {
+                ty: context.get_wrapped_concrete_type(NullableType::id(), ty)?,
+                ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for matching a `Nullable<T>`.
+/// Branches to the first branch if the value is null, and to the second branch with the boxed
+/// value otherwise.
+#[derive(Default)]
+pub struct MatchNullableLibFunc {}
+impl SignatureOnlyGenericLibFunc for MatchNullableLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("match_nullable");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(
+                context.get_wrapped_concrete_type(NullableType::id(), ty.clone())?,
+            )],
+            branch_signatures: vec![
+                // Null:
+                BranchSignature { vars: vec![], ap_change: SierraApChange::Known(0) },
+                // Not null:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: context.get_wrapped_concrete_type(BoxType::id(), ty)?,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                    }],
+                    ap_change: SierraApChange::Known(0),
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
@@ -0,0 +1,79 @@
+//! Helpers for the canonical option enum, `Enum<ut@Option, T, Struct<ut@Tuple>>`, sparing users
+//! from spelling out its variant indices.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type Tuple<> = Struct<ut@Tuple>;
+//! type OptionFelt = Enum<ut@Option, felt, Tuple<>>;
+//! libfunc option_some = option_some<felt>;
+//! libfunc option_none = option_none<felt>;
+//! libfunc option_match = enum_match<OptionFelt>;
+//! ...
+//! option_some(x) -> (some);
+//! option_none() -> (none);
+//! option_match(some) { fallthrough(x) 1000(unit) };
+//! ```
+
+use super::enm::get_option_type;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+};
+use crate::extensions::{OutputVarReferenceInfo, SpecializationError};
+use crate::ids::GenericLibFuncId;
+use crate::program::GenericArg;
+
+define_libfunc_hierarchy! {
+    pub enum OptionLibFunc {
+        Some(OptionSomeLibFunc),
+        None(OptionNoneLibFunc),
+    }, OptionConcreteLibFunc
+}
+
+/// LibFunc for wrapping a value in the `Some` variant of an option.
+#[derive(Default)]
+pub struct OptionSomeLibFunc {}
+impl SignatureOnlyGenericLibFunc for OptionSomeLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("option_some");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![ty.clone()],
+            vec![OutputVarInfo {
+                ty: get_option_type(context, ty)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for creating the `None` variant of an option.
+#[derive(Default)]
+pub struct OptionNoneLibFunc {}
+impl SignatureOnlyGenericLibFunc for OptionNoneLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("option_none");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: get_option_type(context, args_as_single_type(args)?)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
@@ -0,0 +1,182 @@
+//! Panics, terminating the current function with an array of felts describing the error.
+//!
+//! A function that may panic is panic-wrapped: it returns a single `Panicable<T>`, holding either
+//! its result or the panic data. `Panicable<T>` cannot be dropped, so callers must match on it and
+//! either handle the panic or propagate it by panicking themselves.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type ArrayFelt = Array<felt>;
+//! type PanicableFelt = Panicable<felt>;
+//! libfunc panic = panic;
+//! libfunc panicable_wrap = panicable_wrap<felt>;
+//! libfunc match_panicable = match_panicable<felt>;
+//! libfunc call_inner = function_call<user@inner>;
+//! ...
+//! call_inner(x) -> (result);
+//! match_panicable(result) { fallthrough(value) 7(data) };
+//! panicable_wrap(value) -> (result);
+//! return(result);
+//! panic(data) { };
+//! ```
+
+use std::cmp;
+
+use super::array::ArrayType;
+use super::felt::FeltType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::TypeInfo;
+use crate::extensions::{
+    ConcreteType, NamedType, NoGenericArgsGenericLibFunc, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Returns the type of the panic data, an array of felts.
+fn get_panic_data_type(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+    context.get_wrapped_concrete_type(ArrayType::id(), felt_type)
+}
+
+/// Type for the result of a panic-wrapped function, holding either a value of the wrapped type or
+/// the panic data.
+#[derive(Default)]
+pub struct PanicableType {}
+impl NamedType for PanicableType {
+    type Concrete = PanicableConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Panicable");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let info = context.get_type_info(ty.clone())?;
+        if !info.storable {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        Ok(PanicableConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                // The variant index, followed by the larger of the value and the panic data array.
+                size: 1 + cmp::max(info.size, 2),
+            },
+            ty,
+        })
+    }
+}
+
+pub struct PanicableConcreteType {
+    pub info: TypeInfo,
+    pub ty: ConcreteTypeId,
+}
+impl ConcreteType for PanicableConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum PanicLibFunc {
+        Panic(PanicTerminatorLibFunc),
+        Wrap(PanicableWrapLibFunc),
+        Match(PanicableMatchLibFunc),
+    }, PanicConcreteLibFunc
+}
+
+/// LibFunc for panicking with the given panic data.
+/// Has no branches, as it terminates the current function, which returns the panic data.
+#[derive(Default)]
+pub struct PanicTerminatorLibFunc {}
+impl NoGenericArgsGenericLibFunc for PanicTerminatorLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("panic");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(get_panic_data_type(context)?)],
+            branch_signatures: vec![],
+            fallthrough: None,
+        })
+    }
+}
+
+/// LibFunc for wrapping the result of a panic-wrapped function that did not panic.
+#[derive(Default)]
+pub struct PanicableWrapLibFunc {}
+impl SignatureOnlyGenericLibFunc for PanicableWrapLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("panicable_wrap");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![ty.clone()],
+            vec![OutputVarInfo {
+                ty: context.get_wrapped_concrete_type(PanicableType::id(), ty)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for matching on the result of a panic-wrapped function.
+/// Branches to the first branch with the value if the function did not panic, and to the second
+/// branch with the panic data otherwise.
+#[derive(Default)]
+pub struct PanicableMatchLibFunc {}
+impl SignatureOnlyGenericLibFunc for PanicableMatchLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("match_panicable");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(
+                context.get_wrapped_concrete_type(PanicableType::id(), ty.clone())?,
+            )],
+            branch_signatures: vec![
+                // Success:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty,
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Panic:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: get_panic_data_type(context)?,
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
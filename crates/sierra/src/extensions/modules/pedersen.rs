@@ -0,0 +1,70 @@
+// Module providing the Pedersen builtin related extensions.
+use super::felt::FeltType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+
+/// Type for Pedersen builtin.
+#[derive(Default)]
+pub struct PedersenType {}
+impl NoGenericArgsGenericType for PedersenType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Pedersen");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum PedersenLibFunc {
+        Hash(PedersenHashLibFunc),
+    }, PedersenConcreteLibFunc
+}
+
+/// LibFunc for computing the Pedersen hash of two felts.
+#[derive(Default)]
+pub struct PedersenHashLibFunc {}
+impl NoGenericArgsGenericLibFunc for PedersenHashLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("pedersen_hash");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let pedersen_type = context.get_concrete_type(PedersenType::id(), &[])?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![pedersen_type.clone(), felt_type.clone(), felt_type.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: pedersen_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: felt_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
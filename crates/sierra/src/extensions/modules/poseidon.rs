@@ -0,0 +1,96 @@
+// Module providing the Poseidon builtin related extensions.
+use super::felt::FeltType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureOnlyConcreteLibFunc, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    GenericLibFunc, NamedType, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type for Poseidon builtin.
+#[derive(Default)]
+pub struct PoseidonType {}
+impl NoGenericArgsGenericType for PoseidonType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Poseidon");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum PoseidonLibFunc {
+        HadesPermutation(HadesPermutationLibFunc),
+    }, PoseidonConcreteLibFunc
+}
+
+/// LibFunc for applying the Hades permutation of the Poseidon hash to a state of three felts.
+// The name of this libfunc is too long for an inline id, so it can't be a `NamedLibFunc`.
+pub struct HadesPermutationLibFunc {}
+impl GenericLibFunc for HadesPermutationLibFunc {
+    type Concrete = SignatureOnlyConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        if id == &GenericLibFuncId::from("poseidon_hades_permutation") {
+            Some(Self {})
+        } else {
+            None
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        let poseidon_type = context.get_concrete_type(PoseidonType::id(), &[])?;
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let felt_output = || OutputVarInfo {
+            ty: felt_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        Ok(LibFuncSignature::new_non_branch(
+            vec![poseidon_type.clone(), felt_type.clone(), felt_type.clone(), felt_type.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: poseidon_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                felt_output(),
+                felt_output(),
+                felt_output(),
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(SignatureOnlyConcreteLibFunc {
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type uint128 = uint128;
+//! libfunc felt_cube = pow<felt, 3>;
+//! libfunc uint128_square = pow<uint128, 2>;
+//! ...
+//! felt_cube(x) -> (x_cubed);
+//! uint128_square(rc, y) { fallthrough(rc, y_squared) overflow(rc) };
+//! ```
+
+use num_bigint::BigInt;
+use num_traits::Signed;
+
+use super::felt::FeltType;
+use super::integer::Uint128Type;
+use super::range_check::RangeCheckType;
+use crate::extensions::args::args_as_type_and_value;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::{
+    NamedLibFunc, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId};
+use crate::program::GenericArg;
+
+/// The types `pow` can be applied to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PowBase {
+    /// Exponentiation modulo the prime, which never fails.
+    Felt,
+    /// Exponentiation with an overflow branch, taken if the result does not fit in 128 bits.
+    Uint128,
+}
+
+/// Returns the type, its kind and the exponent, given the libfunc's generic args.
+fn extract_pow_args(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, PowBase, BigInt), SpecializationError> {
+    let (ty, exponent) = args_as_type_and_value(args)?;
+    if exponent.is_negative() {
+        return Err(SpecializationError::unsupported_arg(args[1].clone()));
+    }
+    let generic_id = context.get_type_info(ty.clone())?.long_id.generic_id;
+    let base = if generic_id == FeltType::ID {
+        PowBase::Felt
+    } else if generic_id == Uint128Type::ID {
+        PowBase::Uint128
+    } else {
+        return Err(SpecializationError::unsupported_arg(args[0].clone()));
+    };
+    Ok((ty.clone(), base, exponent.clone()))
+}
+
+/// LibFunc for raising a value to a constant non-negative power, computed by repeated squaring.
+#[derive(Default)]
+pub struct PowLibFunc {}
+impl NamedLibFunc for PowLibFunc {
+    type Concrete = PowConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("pow");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (ty, base, _) = extract_pow_args(context, args)?;
+        let result_output = OutputVarInfo {
+            ty: ty.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        Ok(match base {
+            PowBase::Felt => LibFuncSignature::new_non_branch(
+                vec![ty],
+                vec![result_output],
+                SierraApChange::NotImplemented,
+            ),
+            PowBase::Uint128 => {
+                let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+                let range_check_output = || OutputVarInfo {
+                    ty: range_check_type.clone(),
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                };
+                LibFuncSignature {
+                    param_signatures: vec![
+                        ParamSignature::new(range_check_type.clone()),
+                        ParamSignature::new(ty),
+                    ],
+                    branch_signatures: vec![
+                        BranchSignature {
+                            vars: vec![range_check_output(), result_output],
+                            ap_change: SierraApChange::NotImplemented,
+                        },
+                        BranchSignature {
+                            vars: vec![range_check_output()],
+                            ap_change: SierraApChange::NotImplemented,
+                        },
+                    ],
+                    fallthrough: Some(0),
+                }
+            }
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (_, base, exponent) = extract_pow_args(context.upcast(), args)?;
+        Ok(PowConcreteLibFunc {
+            base,
+            exponent,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct PowConcreteLibFunc {
+    pub base: PowBase,
+    /// The non-negative exponent.
+    pub exponent: BigInt,
+    pub signature: LibFuncSignature,
+}
+impl PowConcreteLibFunc {
+    /// Returns the number of multiplications repeated squaring performs for the exponent.
+    pub fn n_multiplications(&self) -> usize {
+        let bits = self.exponent.bits() as usize;
+        let set_bits = (0..self.exponent.bits()).filter(|i| self.exponent.bit(*i)).count();
+        // A squaring per bit after the leading one, and a multiplication per set bit after it.
+        (bits + set_bits).saturating_sub(2)
+    }
+}
+impl SignatureBasedConcreteLibFunc for PowConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
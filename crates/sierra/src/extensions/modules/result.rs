@@ -0,0 +1,168 @@
+//! Helpers for the canonical result enum, `Enum<ut@Result, T, E>`, sparing users from spelling
+//! out its variant indices.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type uint128 = uint128;
+//! type Result = Enum<ut@Result, felt, uint128>;
+//! libfunc result_ok = result_ok<felt, uint128>;
+//! libfunc result_err = result_err<felt, uint128>;
+//! libfunc result_match = enum_match<Result>;
+//! libfunc result_map_err = result_map_err<felt, user@uint128_to_felt>;
+//! ...
+//! result_ok(x) -> (ok);
+//! result_err(e) -> (err);
+//! result_match(ok) { fallthrough(x) 1000(e) };
+//! result_map_err(err) -> (mapped);
+//! ```
+
+use super::enm::get_result_type;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_two_types;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureOnlyGenericLibFunc, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::{
+    GenericArgKind, NamedLibFunc, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, FunctionId, GenericLibFuncId};
+use crate::program::{Function, GenericArg};
+
+define_libfunc_hierarchy! {
+    pub enum ResultLibFunc {
+        Ok(ResultOkLibFunc),
+        Err(ResultErrLibFunc),
+        MapErr(ResultMapErrLibFunc),
+    }, ResultConcreteLibFunc
+}
+
+/// Returns the signature of a libfunc wrapping a value of type `ty` into the given result type.
+fn wrap_signature(
+    context: &dyn SignatureSpecializationContext,
+    ty: ConcreteTypeId,
+    ok_ty: ConcreteTypeId,
+    err_ty: ConcreteTypeId,
+) -> Result<LibFuncSignature, SpecializationError> {
+    Ok(LibFuncSignature::new_non_branch(
+        vec![ty],
+        vec![OutputVarInfo {
+            ty: get_result_type(context, ok_ty, err_ty)?,
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        }],
+        SierraApChange::Known(0),
+    ))
+}
+
+/// LibFunc for wrapping a value in the `Ok` variant of a result.
+#[derive(Default)]
+pub struct ResultOkLibFunc {}
+impl SignatureOnlyGenericLibFunc for ResultOkLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("result_ok");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (ok_ty, err_ty) = args_as_two_types(args)?;
+        wrap_signature(context, ok_ty.clone(), ok_ty, err_ty)
+    }
+}
+
+/// LibFunc for wrapping an error in the `Err` variant of a result.
+#[derive(Default)]
+pub struct ResultErrLibFunc {}
+impl SignatureOnlyGenericLibFunc for ResultErrLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("result_err");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (ok_ty, err_ty) = args_as_two_types(args)?;
+        wrap_signature(context, err_ty.clone(), ok_ty, err_ty)
+    }
+}
+
+/// LibFunc for mapping the error of a result by calling a user function on it, keeping an `Ok`
+/// value as is. Given the ok type `T` and a function from `E` to `F`, converts `Result<T, E>` into
+/// `Result<T, F>`.
+#[derive(Default)]
+pub struct ResultMapErrLibFunc {}
+impl NamedLibFunc for ResultMapErrLibFunc {
+    type Concrete = ResultMapErrConcreteLibFunc;
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("result_map_err");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let (ok_ty, _, err_ty, mapped_err_ty) = get_map_err_types(context, args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![get_result_type(context, ok_ty.clone(), err_ty)?],
+            vec![OutputVarInfo {
+                ty: get_result_type(context, ok_ty, mapped_err_ty)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Unknown,
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (ok_ty, function_id, err_ty, mapped_err_ty) =
+            get_map_err_types(context.upcast(), args)?;
+        Ok(ResultMapErrConcreteLibFunc {
+            function: context.get_function(&function_id)?,
+            ok_ty,
+            err_ty,
+            mapped_err_ty,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Returns the ok type, the mapping function, and the error types before and after the mapping, of
+/// `result_map_err`, as deduced from its generic args and the signature of the mapping function.
+fn get_map_err_types(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, FunctionId, ConcreteTypeId, ConcreteTypeId), SpecializationError> {
+    let (ok_ty, function_id) = match args {
+        [GenericArg::Type(ok_ty), GenericArg::UserFunc(function_id)] => (ok_ty, function_id),
+        [GenericArg::Type(_), arg] => {
+            return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::UserFunc));
+        }
+        [arg, _] => return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type)),
+        _ => return Err(SpecializationError::wrong_arg_count(2, args.len())),
+    };
+    let signature = context.get_function_signature(function_id)?;
+    match (&signature.param_types[..], &signature.ret_types[..]) {
+        ([err_ty], [mapped_err_ty]) => {
+            Ok((ok_ty.clone(), function_id.clone(), err_ty.clone(), mapped_err_ty.clone()))
+        }
+        _ => Err(SpecializationError::unsupported_arg(args[1].clone())),
+    }
+}
+
+pub struct ResultMapErrConcreteLibFunc {
+    /// The function mapping the error.
+    pub function: Function,
+    pub ok_ty: ConcreteTypeId,
+    pub err_ty: ConcreteTypeId,
+    pub mapped_err_ty: ConcreteTypeId,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for ResultMapErrConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
@@ -0,0 +1,177 @@
+//! Points on the secp256k1 and secp256r1 curves, which are managed by the system and operated on
+//! through syscalls.
+//!
+//! Sierra example:
+//! ```ignore
+//! type u256 = u256;
+//! type Secp256k1Point = Secp256k1Point;
+//! libfunc secp256k1_new = secp256k1_new;
+//! libfunc secp256k1_mul = secp256k1_mul;
+//! ...
+//! secp256k1_new(system, x, y) { fallthrough(system, p) 7(system, reason) };
+//! secp256k1_mul(system, p, scalar) { fallthrough(system, q) 7(system, reason) };
+//! ```
+
+use super::syscalls::syscall_signature;
+use super::uint256::Uint256Type;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SignatureSpecializationContext,
+    SpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    GenericLibFunc, GenericType, NamedType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::{ConcreteTypeLongId, GenericArg};
+
+/// The secp256 curves supported by the system.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Secp256Curve {
+    K1,
+    R1,
+}
+impl Secp256Curve {
+    /// Returns the id of the type of points on the curve.
+    pub fn point_type_id(&self) -> GenericTypeId {
+        match self {
+            Secp256Curve::K1 => GenericTypeId::new_inline("Secp256k1Point"),
+            Secp256Curve::R1 => GenericTypeId::new_inline("Secp256r1Point"),
+        }
+    }
+}
+
+/// Type for a point on a secp256 curve, represented by a pointer to the point managed by the
+/// system.
+pub struct Secp256PointType {
+    pub curve: Secp256Curve,
+}
+impl GenericType for Secp256PointType {
+    type Concrete = InfoOnlyConcreteType;
+
+    fn by_id(id: &GenericTypeId) -> Option<Self> {
+        [Secp256Curve::K1, Secp256Curve::R1]
+            .into_iter()
+            .find(|curve| &curve.point_type_id() == id)
+            .map(|curve| Self { curve })
+    }
+
+    fn specialize(
+        &self,
+        _context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        Ok(InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: ConcreteTypeLongId {
+                    generic_id: self.curve.point_type_id(),
+                    generic_args: vec![],
+                },
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 1,
+            },
+        })
+    }
+}
+
+/// The operations on points of a secp256 curve.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Secp256Operation {
+    /// Creates a point from its u256 coordinates, failing if it is not on the curve.
+    New,
+    /// Adds two points.
+    Add,
+    /// Multiplies a point by a u256 scalar.
+    Mul,
+}
+
+/// LibFunc for an operation on points of a secp256 curve, made through a syscall. Branches to the
+/// failure branch with a revert reason if the syscall fails.
+pub struct Secp256LibFunc {
+    pub curve: Secp256Curve,
+    pub operation: Secp256Operation,
+}
+impl Secp256LibFunc {
+    /// Returns the id of the libfunc applying the operation on the curve.
+    fn id(curve: Secp256Curve, operation: Secp256Operation) -> GenericLibFuncId {
+        match (curve, operation) {
+            (Secp256Curve::K1, Secp256Operation::New) => "secp256k1_new",
+            (Secp256Curve::K1, Secp256Operation::Add) => "secp256k1_add",
+            (Secp256Curve::K1, Secp256Operation::Mul) => "secp256k1_mul",
+            (Secp256Curve::R1, Secp256Operation::New) => "secp256r1_new",
+            (Secp256Curve::R1, Secp256Operation::Add) => "secp256r1_add",
+            (Secp256Curve::R1, Secp256Operation::Mul) => "secp256r1_mul",
+        }
+        .into()
+    }
+}
+impl GenericLibFunc for Secp256LibFunc {
+    type Concrete = Secp256ConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        [Secp256Curve::K1, Secp256Curve::R1]
+            .into_iter()
+            .flat_map(|curve| {
+                [Secp256Operation::New, Secp256Operation::Add, Secp256Operation::Mul]
+                    .map(|operation| Self { curve, operation })
+            })
+            .find(|libfunc| &Self::id(libfunc.curve, libfunc.operation) == id)
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        let point_type = context.get_concrete_type(self.curve.point_type_id(), &[])?;
+        let uint256_type = context.get_concrete_type(Uint256Type::id(), &[])?;
+        let params = match self.operation {
+            // The x and y coordinates:
+            Secp256Operation::New => vec![uint256_type.clone(), uint256_type],
+            Secp256Operation::Add => vec![point_type.clone(), point_type.clone()],
+            // The point and the scalar:
+            Secp256Operation::Mul => vec![point_type.clone(), uint256_type],
+        };
+        syscall_signature(
+            context,
+            params,
+            vec![OutputVarInfo {
+                ty: point_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(Secp256ConcreteLibFunc {
+            curve: self.curve,
+            operation: self.operation,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct Secp256ConcreteLibFunc {
+    pub curve: Secp256Curve,
+    pub operation: Secp256Operation,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for Secp256ConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
@@ -0,0 +1,25 @@
+use crate::extensions::types::{InfoOnlyConcreteType, NamedType, TypeInfo};
+use crate::extensions::NoGenericArgsGenericType;
+use crate::ids::GenericTypeId;
+
+/// Type for the Segment Arena builtin, which tracks the memory segments allocated for dicts.
+/// Like other builtins, it can be neither duplicated nor dropped, so it must be threaded linearly
+/// through every dict allocation and squash.
+#[derive(Default)]
+pub struct SegmentArenaType {}
+impl NoGenericArgsGenericType for SegmentArenaType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("SegmentArena");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::extensions::args::args_as_single_type;
+use crate::extensions::lib_func::{
+    LibFuncSignature, OutputVarInfo, SierraApChange, SignatureOnlyGenericLibFunc,
+    SignatureSpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::TypeInfo;
+use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type representing an immutable view of a value. Has the same representation as the value, but
+/// can always be duplicated and dropped, as it does not own the value.
+#[derive(Default)]
+pub struct SnapshotType {}
+impl NamedType for SnapshotType {
+    type Concrete = SnapshotConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Snapshot");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let info = context.get_type_info(ty.clone())?;
+        Ok(SnapshotConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(args),
+                storable: info.storable,
+                droppable: true,
+                duplicatable: true,
+                size: info.size,
+            },
+            ty,
+        })
+    }
+}
+
+pub struct SnapshotConcreteType {
+    pub info: TypeInfo,
+    pub ty: ConcreteTypeId,
+}
+impl ConcreteType for SnapshotConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+/// LibFunc for taking a snapshot of a value, returning the original value alongside it.
+#[derive(Default)]
+pub struct SnapshotTakeLibFunc {}
+impl SignatureOnlyGenericLibFunc for SnapshotTakeLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("snapshot_take");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![ty.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: ty.clone(),
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: context.get_wrapped_concrete_type(SnapshotType::id(), ty)?,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+            ],
+            SierraApChange::Known(0),
+        ))
+    }
+}
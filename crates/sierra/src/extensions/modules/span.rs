@@ -0,0 +1,172 @@
+use super::array::ArrayType;
+use super::felt::FeltType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::TypeInfo;
+use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type representing a read-only view over the elements of an array.
+/// As the viewed elements are never modified, a span can be freely duplicated and dropped.
+#[derive(Default)]
+pub struct SpanType {}
+impl NamedType for SpanType {
+    type Concrete = SpanConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Span");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        if context.get_type_info(ty.clone())?.storable {
+            Ok(SpanConcreteType {
+                info: TypeInfo {
+                    long_id: Self::concrete_type_long_id(args),
+                    duplicatable: true,
+                    droppable: true,
+                    storable: true,
+                    size: 2,
+                },
+                ty,
+            })
+        } else {
+            Err(SpecializationError::unsupported_arg(args[0].clone()))
+        }
+    }
+}
+
+pub struct SpanConcreteType {
+    pub info: TypeInfo,
+    pub ty: ConcreteTypeId,
+}
+impl ConcreteType for SpanConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum SpanLibFunc {
+        FromArray(ArraySpanLibFunc),
+        PopFront(SpanPopFrontLibFunc),
+        Len(SpanLenLibFunc),
+    }, SpanConcreteLibFunc
+}
+
+/// LibFunc for creating a span over the current elements of an array, keeping the array.
+#[derive(Default)]
+pub struct ArraySpanLibFunc {}
+impl SignatureOnlyGenericLibFunc for ArraySpanLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("array_span");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty.clone())?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![arr_ty.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: arr_ty,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: context.get_wrapped_concrete_type(SpanType::id(), ty)?,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+            ],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for popping the first element of a span.
+/// Branches to the first branch with the rest of the span and a copy of the element if the span
+/// is not empty, and to the second branch with the empty span otherwise.
+#[derive(Default)]
+pub struct SpanPopFrontLibFunc {}
+impl SignatureOnlyGenericLibFunc for SpanPopFrontLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("span_pop_front");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        // The element stays in the backing array, so returning it duplicates it.
+        if !context.get_type_info(ty.clone())?.duplicatable {
+            return Err(SpecializationError::unsupported_arg(args[0].clone()));
+        }
+        let span_ty = context.get_wrapped_concrete_type(SpanType::id(), ty.clone())?;
+        Ok(LibFuncSignature {
+            param_signatures: vec![ParamSignature::new(span_ty.clone())],
+            branch_signatures: vec![
+                // Non-empty:
+                BranchSignature {
+                    vars: vec![
+                        OutputVarInfo {
+                            ty: span_ty.clone(),
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                        OutputVarInfo {
+                            ty,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Empty:
+                BranchSignature {
+                    vars: vec![OutputVarInfo {
+                        ty: span_ty,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                    }],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
+/// LibFunc for getting the number of elements in a span.
+#[derive(Default)]
+pub struct SpanLenLibFunc {}
+impl SignatureOnlyGenericLibFunc for SpanLenLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("span_len");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let ty = args_as_single_type(args)?;
+        let span_ty = context.get_wrapped_concrete_type(SpanType::id(), ty)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![span_ty.clone()],
+            vec![
+                OutputVarInfo {
+                    ty: span_ty,
+                    ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+                },
+                OutputVarInfo {
+                    ty: context.get_concrete_type(FeltType::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
@@ -0,0 +1,176 @@
+//! Storage addresses, made of a base address and an offset from it, so that values spanning
+//! several consecutive storage cells share a single base address.
+//!
+//! Sierra example:
+//! ```ignore
+//! type felt = felt;
+//! type uint8 = uint8;
+//! type StorageBaseAddress = StorageBaseAddress;
+//! type StorageAddress = StorageAddress;
+//! libfunc storage_base_address_from_felt = storage_base_address_from_felt;
+//! libfunc storage_address_from_base_and_offset = storage_address_from_base_and_offset;
+//! libfunc storage_address_from_base_and_2 = storage_address_from_base_and_offset<2>;
+//! ...
+//! storage_base_address_from_felt(rc, x) -> (rc, base);
+//! storage_address_from_base_and_offset(base, offset) -> (address);
+//! storage_address_from_base_and_2(base) -> (address);
+//! ```
+
+use num_bigint::BigInt;
+use num_traits::One;
+
+use super::felt::FeltType;
+use super::range_check::RangeCheckType;
+use super::uint::Uint8Type;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::args::optional_value_args_error;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedLibFunc, NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType,
+    OutputVarReferenceInfo, SignatureBasedConcreteLibFunc, SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::{ConcreteTypeLongId, GenericArg};
+
+/// Returns the exclusive upper bound of storage base addresses, 2^251 - 256, so that adding any
+/// uint8 offset to a base address results in an address smaller than 2^251.
+pub fn storage_base_address_bound() -> BigInt {
+    (BigInt::one() << 251) - 256
+}
+
+/// Returns the info of a storage address type, represented by a single felt.
+fn storage_address_info(long_id: ConcreteTypeLongId) -> InfoOnlyConcreteType {
+    InfoOnlyConcreteType {
+        info: TypeInfo { long_id, storable: true, droppable: true, duplicatable: true, size: 1 },
+    }
+}
+
+/// Type for a storage base address, a felt smaller than 2^251 - 256.
+#[derive(Default)]
+pub struct StorageBaseAddressType {}
+impl NoGenericArgsGenericType for StorageBaseAddressType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("StorageBaseAddress");
+
+    fn specialize(&self) -> Self::Concrete {
+        storage_address_info(Self::concrete_type_long_id(&[]))
+    }
+}
+
+/// Type for a storage address, a felt smaller than 2^251.
+#[derive(Default)]
+pub struct StorageAddressType {}
+impl NoGenericArgsGenericType for StorageAddressType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("StorageAddress");
+
+    fn specialize(&self) -> Self::Concrete {
+        storage_address_info(Self::concrete_type_long_id(&[]))
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum StorageAddressLibFunc {
+        BaseFromFelt(StorageBaseAddressFromFeltLibFunc),
+        FromBaseAndOffset(StorageAddressFromBaseAndOffsetLibFunc),
+    }, StorageAddressConcreteLibFunc
+}
+
+/// LibFunc for converting a felt into a storage base address, by reducing it modulo the bound of
+/// base addresses.
+#[derive(Default)]
+pub struct StorageBaseAddressFromFeltLibFunc {}
+impl NoGenericArgsGenericLibFunc for StorageBaseAddressFromFeltLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("storage_base_address_from_felt");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![range_check_type.clone(), context.get_concrete_type(FeltType::id(), &[])?],
+            vec![
+                OutputVarInfo {
+                    ty: range_check_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                        param_idx: 0,
+                    }),
+                },
+                OutputVarInfo {
+                    ty: context.get_concrete_type(StorageBaseAddressType::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+            SierraApChange::NotImplemented,
+        ))
+    }
+}
+
+/// LibFunc for computing a storage address from a base address and a uint8 offset, given either as
+/// an argument or as a constant generic arg.
+#[derive(Default)]
+pub struct StorageAddressFromBaseAndOffsetLibFunc {}
+impl NamedLibFunc for StorageAddressFromBaseAndOffsetLibFunc {
+    type Concrete = StorageAddressFromBaseAndOffsetConcreteLibFunc;
+    const ID: GenericLibFuncId =
+        GenericLibFuncId::new_inline("storage_address_from_base_and_offset");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let base_type = context.get_concrete_type(StorageBaseAddressType::id(), &[])?;
+        let params = match extract_offset(args)? {
+            None => vec![base_type, context.get_concrete_type(Uint8Type::id(), &[])?],
+            Some(_) => vec![base_type],
+        };
+        Ok(LibFuncSignature::new_non_branch(
+            params,
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(StorageAddressType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(StorageAddressFromBaseAndOffsetConcreteLibFunc {
+            offset: extract_offset(args)?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+/// Extracts the optional constant offset from the generic args, rejecting offsets that do not fit
+/// in a uint8.
+fn extract_offset(args: &[GenericArg]) -> Result<Option<u8>, SpecializationError> {
+    match args {
+        [] => Ok(None),
+        [GenericArg::Value(offset)] => u8::try_from(offset)
+            .map(Some)
+            .map_err(|_| SpecializationError::unsupported_arg(args[0].clone())),
+        _ => Err(optional_value_args_error(args)),
+    }
+}
+
+pub struct StorageAddressFromBaseAndOffsetConcreteLibFunc {
+    /// The constant offset, if given as a generic arg.
+    pub offset: Option<u8>,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for StorageAddressFromBaseAndOffsetConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
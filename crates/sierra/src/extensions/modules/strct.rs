@@ -13,15 +13,17 @@
 
 use utils::try_extract_matches;
 
-use super::as_single_type;
 use crate::define_libfunc_hierarchy;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::lib_func::{
     DeferredOutputKind, LibFuncSignature, OutputVarInfo, SierraApChange,
     SignatureOnlyGenericLibFunc, SignatureSpecializationContext,
 };
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
-use crate::extensions::{ConcreteType, NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::extensions::{
+    ArgCount, ConcreteType, GenericArgKind, NamedType, OutputVarReferenceInfo, SpecializationError,
+};
 use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId};
 use crate::program::{ConcreteTypeLongId, GenericArg};
 
@@ -46,26 +48,34 @@ pub struct StructConcreteType {
     pub members: Vec<ConcreteTypeId>,
 }
 impl StructConcreteType {
-    fn new(
+    pub(crate) fn new(
         context: &dyn TypeSpecializationContext,
         args: &[GenericArg],
     ) -> Result<Self, SpecializationError> {
         let mut args_iter = args.iter();
-        args_iter
-            .next()
-            .and_then(|arg| try_extract_matches!(arg, GenericArg::UserType))
-            .ok_or(SpecializationError::UnsupportedGenericArg)?;
+        match args_iter.next() {
+            Some(GenericArg::UserType(_)) => {}
+            Some(arg) => {
+                return Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::UserType));
+            }
+            None => {
+                return Err(SpecializationError::WrongNumberOfGenericArgs {
+                    expected: ArgCount::AtLeast(1),
+                    actual: 0,
+                });
+            }
+        }
         let mut duplicatable = true;
         let mut droppable = true;
         let mut members: Vec<ConcreteTypeId> = Vec::new();
         let mut size = 0;
         for arg in args_iter {
             let ty = try_extract_matches!(arg, GenericArg::Type)
-                .ok_or(SpecializationError::UnsupportedGenericArg)?
+                .ok_or_else(|| SpecializationError::wrong_arg_kind(arg, GenericArgKind::Type))?
                 .clone();
             let info = context.get_type_info(ty.clone())?;
             if !info.storable {
-                return Err(SpecializationError::UnsupportedGenericArg);
+                return Err(SpecializationError::unsupported_arg(arg.clone()));
             }
             if !info.duplicatable {
                 duplicatable = false;
@@ -115,7 +125,7 @@ impl SignatureOnlyGenericLibFunc for StructConstructLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let struct_type = as_single_type(args)?;
+        let struct_type = args_as_single_type(args)?;
         let generic_args = context.get_type_info(struct_type.clone())?.long_id.generic_args;
         let member_types =
             StructConcreteType::new(context.as_type_specialization_context(), &generic_args)?
@@ -142,7 +152,7 @@ impl SignatureOnlyGenericLibFunc for StructDeconstructLibFunc {
         context: &dyn SignatureSpecializationContext,
         args: &[GenericArg],
     ) -> Result<LibFuncSignature, SpecializationError> {
-        let struct_type = as_single_type(args)?;
+        let struct_type = args_as_single_type(args)?;
         let generic_args = context.get_type_info(struct_type.clone())?.long_id.generic_args;
         let member_types =
             StructConcreteType::new(context.as_type_specialization_context(), &generic_args)?
@@ -0,0 +1,381 @@
+use super::array::ArrayType;
+use super::boolean::BoolType;
+use super::boxing::BoxType;
+use super::felt::FeltType;
+use super::interoperability::{ClassHashType, ContractAddressType};
+use super::storage::StorageAddressType;
+use super::strct::StructType;
+use super::uint::Uint64Type;
+use super::uint256::Uint256Type;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, NoGenericArgsGenericLibFunc,
+    OutputVarInfo, ParamSignature, SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericType, OutputVarReferenceInfo, SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericLibFuncId, GenericTypeId, UserTypeId};
+use crate::program::GenericArg;
+
+/// Type for the system builtin, through which syscalls are made.
+#[derive(Default)]
+pub struct SystemType {}
+impl NoGenericArgsGenericType for SystemType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("System");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: false,
+                duplicatable: false,
+                size: 1,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum SyscallLibFunc {
+        StorageRead(StorageReadLibFunc),
+        StorageWrite(StorageWriteLibFunc),
+        CallContract(CallContractLibFunc),
+        Deploy(DeployLibFunc),
+        LibraryCall(LibraryCallLibFunc),
+        ReplaceClass(ReplaceClassLibFunc),
+        SendMessageToL1(SendMessageToL1LibFunc),
+        Keccak(KeccakLibFunc),
+        GetExecutionInfo(GetExecutionInfoLibFunc),
+        GetBlockHash(GetBlockHashLibFunc),
+    }, SyscallConcreteLibFunc
+}
+
+/// LibFunc for reading a value from the contract's storage at a given address.
+#[derive(Default)]
+pub struct StorageReadLibFunc {}
+impl NoGenericArgsGenericLibFunc for StorageReadLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("storage_read");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let addr_type = context.get_concrete_type(StorageAddressType::id(), &[])?;
+        syscall_signature(
+            context,
+            // Address:
+            vec![addr_type],
+            // Value:
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(FeltType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// LibFunc for writing a value to the contract's storage at a given address.
+#[derive(Default)]
+pub struct StorageWriteLibFunc {}
+impl NoGenericArgsGenericLibFunc for StorageWriteLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("storage_write");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        syscall_signature(
+            context,
+            // Address and value:
+            vec![
+                context.get_concrete_type(StorageAddressType::id(), &[])?,
+                context.get_concrete_type(FeltType::id(), &[])?,
+            ],
+            vec![],
+        )
+    }
+}
+
+/// LibFunc for calling a function of another contract, given the contract's address, the selector
+/// of the function and the calldata. Returns the data returned by the called function.
+#[derive(Default)]
+pub struct CallContractLibFunc {}
+impl NoGenericArgsGenericLibFunc for CallContractLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("call_contract");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let felt_array_type =
+            context.get_wrapped_concrete_type(ArrayType::id(), felt_type.clone())?;
+        syscall_signature(
+            context,
+            // Address, selector and calldata:
+            vec![felt_type.clone(), felt_type, felt_array_type.clone()],
+            // Returned data:
+            vec![OutputVarInfo {
+                ty: felt_array_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// LibFunc for deploying a contract of a given class, given the class hash, a salt, the calldata of
+/// the constructor, and whether to deploy as if from the zero address. Returns the address of the
+/// deployed contract and the data returned by the constructor.
+#[derive(Default)]
+pub struct DeployLibFunc {}
+impl NoGenericArgsGenericLibFunc for DeployLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("deploy");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let felt_array_type =
+            context.get_wrapped_concrete_type(ArrayType::id(), felt_type.clone())?;
+        syscall_signature(
+            context,
+            // Class hash, salt, calldata and deploy from zero:
+            vec![
+                context.get_concrete_type(ClassHashType::id(), &[])?,
+                felt_type,
+                felt_array_type.clone(),
+                context.get_concrete_type(BoolType::id(), &[])?,
+            ],
+            // Deployed address and returned data:
+            vec![
+                OutputVarInfo {
+                    ty: context.get_concrete_type(ContractAddressType::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+                OutputVarInfo {
+                    ty: felt_array_type,
+                    ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                },
+            ],
+        )
+    }
+}
+
+/// LibFunc for calling a function of a class in the context of the calling contract, given the
+/// class hash, the selector of the function and the calldata. Returns the data returned by the
+/// called function.
+#[derive(Default)]
+pub struct LibraryCallLibFunc {}
+impl NoGenericArgsGenericLibFunc for LibraryCallLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("library_call");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let felt_array_type =
+            context.get_wrapped_concrete_type(ArrayType::id(), felt_type.clone())?;
+        syscall_signature(
+            context,
+            // Class hash, selector and calldata:
+            vec![
+                context.get_concrete_type(ClassHashType::id(), &[])?,
+                felt_type,
+                felt_array_type.clone(),
+            ],
+            // Returned data:
+            vec![OutputVarInfo {
+                ty: felt_array_type,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// LibFunc for replacing the class of the current contract with the class of a given hash.
+#[derive(Default)]
+pub struct ReplaceClassLibFunc {}
+impl NoGenericArgsGenericLibFunc for ReplaceClassLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("replace_class");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        syscall_signature(
+            context,
+            // Class hash:
+            vec![context.get_concrete_type(ClassHashType::id(), &[])?],
+            vec![],
+        )
+    }
+}
+
+/// LibFunc for sending a message to L1, given the L1 address to send it to and the payload.
+#[derive(Default)]
+pub struct SendMessageToL1LibFunc {}
+impl NoGenericArgsGenericLibFunc for SendMessageToL1LibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("send_message_to_l1");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+        let felt_array_type =
+            context.get_wrapped_concrete_type(ArrayType::id(), felt_type.clone())?;
+        syscall_signature(
+            context,
+            // Address and payload:
+            vec![felt_type, felt_array_type],
+            vec![],
+        )
+    }
+}
+
+/// LibFunc for computing the keccak-256 hash of an input given as an array of uint64 words in
+/// little-endian order, already padded to a multiple of the keccak rate of 17 words.
+/// Returns the hash as a u256, whose halves are the little-endian reading of the hash bytes.
+#[derive(Default)]
+pub struct KeccakLibFunc {}
+impl NoGenericArgsGenericLibFunc for KeccakLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("keccak");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let uint64_type = context.get_concrete_type(Uint64Type::id(), &[])?;
+        syscall_signature(
+            context,
+            // Input words:
+            vec![context.get_wrapped_concrete_type(ArrayType::id(), uint64_type)?],
+            // Hash:
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(Uint256Type::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// Returns the type of the execution info struct, whose members are the block number, the block
+/// timestamp and the sequencer address of the current block, the hash of the current transaction,
+/// the chain id, and the caller address, the contract address and the entry point selector of the
+/// current call.
+pub fn get_execution_info_type(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+    let uint64_type = context.get_concrete_type(Uint64Type::id(), &[])?;
+    let address_type = context.get_concrete_type(ContractAddressType::id(), &[])?;
+    context.get_concrete_type(
+        StructType::id(),
+        &[
+            GenericArg::UserType(UserTypeId::from("ExecutionInfo")),
+            GenericArg::Type(uint64_type.clone()),
+            GenericArg::Type(uint64_type),
+            GenericArg::Type(address_type.clone()),
+            GenericArg::Type(felt_type.clone()),
+            GenericArg::Type(felt_type.clone()),
+            GenericArg::Type(address_type.clone()),
+            GenericArg::Type(address_type),
+            GenericArg::Type(felt_type),
+        ],
+    )
+}
+
+/// LibFunc for getting the information of the current execution, as a boxed execution info struct.
+#[derive(Default)]
+pub struct GetExecutionInfoLibFunc {}
+impl NoGenericArgsGenericLibFunc for GetExecutionInfoLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("get_execution_info");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let execution_info_type = get_execution_info_type(context)?;
+        syscall_signature(
+            context,
+            vec![],
+            vec![OutputVarInfo {
+                ty: context.get_wrapped_concrete_type(BoxType::id(), execution_info_type)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// LibFunc for getting the hash of a past block, given its number. Fails if the block is not old
+/// enough for its hash to be available.
+#[derive(Default)]
+pub struct GetBlockHashLibFunc {}
+impl NoGenericArgsGenericLibFunc for GetBlockHashLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("get_block_hash");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        syscall_signature(
+            context,
+            // Block number:
+            vec![context.get_concrete_type(Uint64Type::id(), &[])?],
+            // Block hash:
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(FeltType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+        )
+    }
+}
+
+/// Returns the signature of a syscall, taking the system builtin followed by `params`.
+/// On success, returns the system builtin followed by `success_vars`.
+/// On failure, returns the system builtin followed by the revert reason, as an array of felts.
+pub(super) fn syscall_signature(
+    context: &dyn SignatureSpecializationContext,
+    params: Vec<ConcreteTypeId>,
+    success_vars: Vec<OutputVarInfo>,
+) -> Result<LibFuncSignature, SpecializationError> {
+    let system_type = context.get_concrete_type(SystemType::id(), &[])?;
+    let felt_type = context.get_concrete_type(FeltType::id(), &[])?;
+    let felt_array_type = context.get_wrapped_concrete_type(ArrayType::id(), felt_type)?;
+    let system_output = || OutputVarInfo {
+        ty: system_type.clone(),
+        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst { param_idx: 0 }),
+    };
+    Ok(LibFuncSignature {
+        param_signatures: [system_type.clone()]
+            .into_iter()
+            .chain(params)
+            .map(ParamSignature::new)
+            .collect(),
+        branch_signatures: vec![
+            // Success:
+            BranchSignature {
+                vars: [system_output()].into_iter().chain(success_vars).collect(),
+                ap_change: SierraApChange::NotImplemented,
+            },
+            // Failure:
+            BranchSignature {
+                vars: vec![
+                    system_output(),
+                    OutputVarInfo {
+                        ty: felt_array_type,
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    },
+                ],
+                ap_change: SierraApChange::NotImplemented,
+            },
+        ],
+        fallthrough: Some(0),
+    })
+}
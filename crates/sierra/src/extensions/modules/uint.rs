@@ -0,0 +1,402 @@
+use std::marker::PhantomData;
+
+use num_bigint::BigInt;
+use num_traits::{One, Signed};
+
+use super::integer::IntOperator;
+use super::range_check::RangeCheckType;
+use crate::define_concrete_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureOnlyConcreteLibFunc, SignatureSpecializationContext,
+    SpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    GenericArgKind, GenericLibFunc, NamedLibFunc, NamedType, NoGenericArgsGenericLibFunc,
+    NoGenericArgsGenericType, OutputVarReferenceInfo, SignatureBasedConcreteLibFunc,
+    SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Trait for implementing a bounded unsigned integer type and its library functions.
+pub trait UintTraits: Default {
+    /// The number of bits of the integer type.
+    const BITS: u32;
+    /// The id of the generic type.
+    const GENERIC_TYPE_ID: GenericTypeId;
+    /// The id of the library function creating a constant.
+    const CONST: GenericLibFuncId;
+    /// The id of the library function adding two values, branching on overflow.
+    const ADD: GenericLibFuncId;
+    /// The id of the library function subtracting two values, branching on underflow.
+    const SUB: GenericLibFuncId;
+    /// The id of the library function comparing two values with `<`.
+    const LESS_THAN: GenericLibFuncId;
+    /// The id of the library function comparing two values with `<=`.
+    const LESS_THAN_OR_EQUAL: GenericLibFuncId;
+}
+
+#[derive(Default)]
+pub struct Uint8Traits {}
+impl UintTraits for Uint8Traits {
+    const BITS: u32 = 8;
+    const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("uint8");
+    const CONST: GenericLibFuncId = GenericLibFuncId::new_inline("uint8_const");
+    const ADD: GenericLibFuncId = GenericLibFuncId::new_inline("uint8_add");
+    const SUB: GenericLibFuncId = GenericLibFuncId::new_inline("uint8_sub");
+    const LESS_THAN: GenericLibFuncId = GenericLibFuncId::new_inline("uint8_lt");
+    const LESS_THAN_OR_EQUAL: GenericLibFuncId = GenericLibFuncId::new_inline("uint8_le");
+}
+pub type Uint8Type = UintType<Uint8Traits>;
+pub type Uint8LibFunc = UintLibFunc<Uint8Traits>;
+
+#[derive(Default)]
+pub struct Uint16Traits {}
+impl UintTraits for Uint16Traits {
+    const BITS: u32 = 16;
+    const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("uint16");
+    const CONST: GenericLibFuncId = GenericLibFuncId::new_inline("uint16_const");
+    const ADD: GenericLibFuncId = GenericLibFuncId::new_inline("uint16_add");
+    const SUB: GenericLibFuncId = GenericLibFuncId::new_inline("uint16_sub");
+    const LESS_THAN: GenericLibFuncId = GenericLibFuncId::new_inline("uint16_lt");
+    const LESS_THAN_OR_EQUAL: GenericLibFuncId = GenericLibFuncId::new_inline("uint16_le");
+}
+pub type Uint16Type = UintType<Uint16Traits>;
+pub type Uint16LibFunc = UintLibFunc<Uint16Traits>;
+
+#[derive(Default)]
+pub struct Uint32Traits {}
+impl UintTraits for Uint32Traits {
+    const BITS: u32 = 32;
+    const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("uint32");
+    const CONST: GenericLibFuncId = GenericLibFuncId::new_inline("uint32_const");
+    const ADD: GenericLibFuncId = GenericLibFuncId::new_inline("uint32_add");
+    const SUB: GenericLibFuncId = GenericLibFuncId::new_inline("uint32_sub");
+    const LESS_THAN: GenericLibFuncId = GenericLibFuncId::new_inline("uint32_lt");
+    const LESS_THAN_OR_EQUAL: GenericLibFuncId = GenericLibFuncId::new_inline("uint32_le");
+}
+pub type Uint32Type = UintType<Uint32Traits>;
+pub type Uint32LibFunc = UintLibFunc<Uint32Traits>;
+
+#[derive(Default)]
+pub struct Uint64Traits {}
+impl UintTraits for Uint64Traits {
+    const BITS: u32 = 64;
+    const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("uint64");
+    const CONST: GenericLibFuncId = GenericLibFuncId::new_inline("uint64_const");
+    const ADD: GenericLibFuncId = GenericLibFuncId::new_inline("uint64_add");
+    const SUB: GenericLibFuncId = GenericLibFuncId::new_inline("uint64_sub");
+    const LESS_THAN: GenericLibFuncId = GenericLibFuncId::new_inline("uint64_lt");
+    const LESS_THAN_OR_EQUAL: GenericLibFuncId = GenericLibFuncId::new_inline("uint64_le");
+}
+pub type Uint64Type = UintType<Uint64Traits>;
+pub type Uint64LibFunc = UintLibFunc<Uint64Traits>;
+
+/// Type for a bounded unsigned integer.
+#[derive(Default)]
+pub struct UintType<TUintTraits: UintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: UintTraits> NoGenericArgsGenericType for UintType<TUintTraits> {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = TUintTraits::GENERIC_TYPE_ID;
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 1,
+            },
+        }
+    }
+}
+
+/// The library functions of a bounded unsigned integer type.
+pub enum UintLibFunc<TUintTraits: UintTraits> {
+    Const(UintConstLibFunc<TUintTraits>),
+    Operation(UintOperationLibFunc<TUintTraits>),
+    LessThan(UintLessThanLibFunc<TUintTraits>),
+    LessThanOrEqual(UintLessThanOrEqualLibFunc<TUintTraits>),
+}
+impl<TUintTraits: UintTraits> UintLibFunc<TUintTraits> {
+    /// Returns the name of the variant, e.g. for inspecting programs before specialization.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Const(_) => "Const",
+            Self::Operation(_) => "Operation",
+            Self::LessThan(_) => "LessThan",
+            Self::LessThanOrEqual(_) => "LessThanOrEqual",
+        }
+    }
+}
+impl<TUintTraits: UintTraits> GenericLibFunc for UintLibFunc<TUintTraits> {
+    type Concrete = UintConcrete;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        UintConstLibFunc::by_id(id)
+            .map(Self::Const)
+            .or_else(|| UintOperationLibFunc::by_id(id).map(Self::Operation))
+            .or_else(|| UintLessThanLibFunc::by_id(id).map(Self::LessThan))
+            .or_else(|| UintLessThanOrEqualLibFunc::by_id(id).map(Self::LessThanOrEqual))
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        match self {
+            Self::Const(libfunc) => GenericLibFunc::specialize_signature(libfunc, context, args),
+            Self::Operation(libfunc) => {
+                GenericLibFunc::specialize_signature(libfunc, context, args)
+            }
+            Self::LessThan(libfunc) => GenericLibFunc::specialize_signature(libfunc, context, args),
+            Self::LessThanOrEqual(libfunc) => {
+                GenericLibFunc::specialize_signature(libfunc, context, args)
+            }
+        }
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(match self {
+            Self::Const(libfunc) => {
+                UintConcrete::Const(GenericLibFunc::specialize(libfunc, context, args)?)
+            }
+            Self::Operation(libfunc) => {
+                UintConcrete::Operation(GenericLibFunc::specialize(libfunc, context, args)?)
+            }
+            Self::LessThan(libfunc) => {
+                UintConcrete::LessThan(GenericLibFunc::specialize(libfunc, context, args)?)
+            }
+            Self::LessThanOrEqual(libfunc) => {
+                UintConcrete::LessThanOrEqual(GenericLibFunc::specialize(libfunc, context, args)?)
+            }
+        })
+    }
+}
+
+define_concrete_libfunc_hierarchy! {
+    pub enum UintConcrete {
+        Const(UintConstConcreteLibFunc),
+        Operation(UintOperationConcreteLibFunc),
+        LessThan(SignatureOnlyConcreteLibFunc),
+        LessThanOrEqual(SignatureOnlyConcreteLibFunc),
+    }
+}
+
+/// LibFunc for creating a constant bounded unsigned integer.
+#[derive(Default)]
+pub struct UintConstLibFunc<TUintTraits: UintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: UintTraits> NamedLibFunc for UintConstLibFunc<TUintTraits> {
+    type Concrete = UintConstConcreteLibFunc;
+    const ID: GenericLibFuncId = TUintTraits::CONST;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        as_uint_const::<TUintTraits>(args)?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(TUintTraits::GENERIC_TYPE_ID, &[])?,
+                ref_info: OutputVarReferenceInfo::Const,
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(UintConstConcreteLibFunc {
+            c: as_uint_const::<TUintTraits>(args)?,
+            signature: <Self as NamedLibFunc>::specialize_signature(self, context.upcast(), args)?,
+        })
+    }
+}
+
+/// Returns the constant given as the generic arg of a bounded unsigned integer const libfunc,
+/// checking it is within the bounds of the type.
+fn as_uint_const<TUintTraits: UintTraits>(args: &[GenericArg]) -> Result<u64, SpecializationError> {
+    match args {
+        [GenericArg::Value(c)] if !c.is_negative() && c < &(BigInt::one() << TUintTraits::BITS) => {
+            Ok(u64::try_from(c).unwrap())
+        }
+        [GenericArg::Value(_)] => Err(SpecializationError::unsupported_arg(args[0].clone())),
+        [arg] => Err(SpecializationError::wrong_arg_kind(arg, GenericArgKind::Value)),
+        _ => Err(SpecializationError::wrong_arg_count(1, args.len())),
+    }
+}
+
+pub struct UintConstConcreteLibFunc {
+    pub c: u64,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for UintConstConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for bounded unsigned integer operations, branching on overflow.
+pub struct UintOperationLibFunc<TUintTraits: UintTraits> {
+    pub operator: IntOperator,
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: UintTraits> UintOperationLibFunc<TUintTraits> {
+    fn new(operator: IntOperator) -> Self {
+        Self { operator, _phantom: PhantomData }
+    }
+}
+impl<TUintTraits: UintTraits> GenericLibFunc for UintOperationLibFunc<TUintTraits> {
+    type Concrete = UintOperationConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        match id {
+            id if id == &TUintTraits::ADD => Some(Self::new(IntOperator::Add)),
+            id if id == &TUintTraits::SUB => Some(Self::new(IntOperator::Sub)),
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        let ty = context.get_concrete_type(TUintTraits::GENERIC_TYPE_ID, &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(ty.clone()),
+                ParamSignature::new(ty.clone()),
+            ],
+            branch_signatures: vec![
+                // In range:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        OutputVarInfo {
+                            ty,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Overflow:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(UintOperationConcreteLibFunc {
+            operator: self.operator,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct UintOperationConcreteLibFunc {
+    pub operator: IntOperator,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for UintOperationConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// Returns the signature of bounded unsigned integer comparisons, with a "false" branch followed
+/// by a "true" branch.
+fn get_uint_comparison_signature<TUintTraits: UintTraits>(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<LibFuncSignature, SpecializationError> {
+    let ty = context.get_concrete_type(TUintTraits::GENERIC_TYPE_ID, &[])?;
+    let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+    let branch_signature = || BranchSignature {
+        vars: vec![OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        }],
+        ap_change: SierraApChange::NotImplemented,
+    };
+    Ok(LibFuncSignature {
+        param_signatures: vec![
+            ParamSignature::new(range_check_type.clone()),
+            ParamSignature::new(ty.clone()),
+            ParamSignature::new(ty),
+        ],
+        branch_signatures: vec![branch_signature(), branch_signature()],
+        fallthrough: Some(0),
+    })
+}
+
+/// LibFunc for comparing bounded unsigned integers with `<`.
+#[derive(Default)]
+pub struct UintLessThanLibFunc<TUintTraits: UintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: UintTraits> NoGenericArgsGenericLibFunc for UintLessThanLibFunc<TUintTraits> {
+    const ID: GenericLibFuncId = TUintTraits::LESS_THAN;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        get_uint_comparison_signature::<TUintTraits>(context)
+    }
+}
+
+/// LibFunc for comparing bounded unsigned integers with `<=`.
+#[derive(Default)]
+pub struct UintLessThanOrEqualLibFunc<TUintTraits: UintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: UintTraits> NoGenericArgsGenericLibFunc
+    for UintLessThanOrEqualLibFunc<TUintTraits>
+{
+    const ID: GenericLibFuncId = TUintTraits::LESS_THAN_OR_EQUAL;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        get_uint_comparison_signature::<TUintTraits>(context)
+    }
+}
@@ -0,0 +1,199 @@
+//! The u256 type, backed by a pair of uint128s: the low 128 bits followed by the high 128 bits.
+//!
+//! Sierra example:
+//! ```ignore
+//! type uint128 = uint128;
+//! type u256 = u256;
+//! libfunc u256_construct = u256_construct;
+//! libfunc u256_add = u256_add;
+//! ...
+//! u256_construct(low, high) -> (a);
+//! u256_add(rc, a, b) { fallthrough(rc, sum) overflow(rc) };
+//! ```
+
+use super::integer::Uint128Type;
+use super::range_check::RangeCheckType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    BranchSignature, DeferredOutputKind, LibFuncSignature, OutputVarInfo, ParamSignature,
+    SierraApChange, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    GenericLibFunc, NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType,
+    OutputVarReferenceInfo, SignatureBasedConcreteLibFunc, SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+use crate::program::GenericArg;
+
+/// Type for u256, stored as its low and high uint128 halves.
+#[derive(Default)]
+pub struct Uint256Type {}
+impl NoGenericArgsGenericType for Uint256Type {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("u256");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 2,
+            },
+        }
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum Uint256LibFunc {
+        Operation(Uint256OperationLibFunc),
+        Construct(Uint256ConstructLibFunc),
+        Deconstruct(Uint256DeconstructLibFunc),
+    }, Uint256Concrete
+}
+
+/// Operators for u256s, all of which fail on overflow.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Uint256Operator {
+    Add,
+    Sub,
+    Mul,
+}
+
+/// Libfunc for u256 operations. Branches to the first branch with the result, and to the second
+/// branch on overflow.
+pub struct Uint256OperationLibFunc {
+    pub operator: Uint256Operator,
+}
+impl Uint256OperationLibFunc {
+    fn new(operator: Uint256Operator) -> Self {
+        Self { operator }
+    }
+}
+impl GenericLibFunc for Uint256OperationLibFunc {
+    type Concrete = Uint256OperationConcreteLibFunc;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        const ADD: GenericLibFuncId = GenericLibFuncId::new_inline("u256_add");
+        const SUB: GenericLibFuncId = GenericLibFuncId::new_inline("u256_sub");
+        const MUL: GenericLibFuncId = GenericLibFuncId::new_inline("u256_mul");
+        match id {
+            id if id == &ADD => Some(Self::new(Uint256Operator::Add)),
+            id if id == &SUB => Some(Self::new(Uint256Operator::Sub)),
+            id if id == &MUL => Some(Self::new(Uint256Operator::Mul)),
+            _ => None,
+        }
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        if !args.is_empty() {
+            return Err(SpecializationError::wrong_arg_count(0, args.len()));
+        }
+        let ty = context.get_concrete_type(Uint256Type::id(), &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let range_check_output = || OutputVarInfo {
+            ty: range_check_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::AddConst {
+                param_idx: 0,
+            }),
+        };
+        Ok(LibFuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()),
+                ParamSignature::new(ty.clone()),
+                ParamSignature::new(ty.clone()),
+            ],
+            branch_signatures: vec![
+                // No overflow:
+                BranchSignature {
+                    vars: vec![
+                        range_check_output(),
+                        OutputVarInfo {
+                            ty,
+                            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                        },
+                    ],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+                // Overflow:
+                BranchSignature {
+                    vars: vec![range_check_output()],
+                    ap_change: SierraApChange::NotImplemented,
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(Uint256OperationConcreteLibFunc {
+            operator: self.operator,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+pub struct Uint256OperationConcreteLibFunc {
+    pub operator: Uint256Operator,
+    pub signature: LibFuncSignature,
+}
+impl SignatureBasedConcreteLibFunc for Uint256OperationConcreteLibFunc {
+    fn signature(&self) -> &LibFuncSignature {
+        &self.signature
+    }
+}
+
+/// LibFunc for constructing a u256 from its low and high uint128 halves.
+#[derive(Default)]
+pub struct Uint256ConstructLibFunc {}
+impl NoGenericArgsGenericLibFunc for Uint256ConstructLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("u256_construct");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let uint128_type = context.get_concrete_type(Uint128Type::id(), &[])?;
+        Ok(LibFuncSignature::new_non_branch(
+            vec![uint128_type.clone(), uint128_type],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(Uint256Type::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+/// LibFunc for deconstructing a u256 into its low and high uint128 halves.
+#[derive(Default)]
+pub struct Uint256DeconstructLibFunc {}
+impl NoGenericArgsGenericLibFunc for Uint256DeconstructLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("u256_deconstruct");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let uint128_type = context.get_concrete_type(Uint128Type::id(), &[])?;
+        let uint128_output = || OutputVarInfo {
+            ty: uint128_type.clone(),
+            ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+        };
+        Ok(LibFuncSignature::new_non_branch(
+            vec![context.get_concrete_type(Uint256Type::id(), &[])?],
+            vec![uint128_output(), uint128_output()],
+            SierraApChange::Known(0),
+        ))
+    }
+}
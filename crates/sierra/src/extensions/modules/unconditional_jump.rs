@@ -4,6 +4,8 @@ use crate::extensions::lib_func::{
 use crate::extensions::{NoGenericArgsGenericLibFunc, SpecializationError};
 use crate::ids::GenericLibFuncId;
 
+/// LibFunc for an unconditional jump. Has a single branch, which is never a fallthrough, so an
+/// invocation of it always ends its block.
 #[derive(Default)]
 pub struct UnconditionalJumpLibFunc {}
 impl NoGenericArgsGenericLibFunc for UnconditionalJumpLibFunc {
@@ -1,4 +1,4 @@
-use super::as_single_type;
+use crate::extensions::args::args_as_single_type;
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
 use crate::extensions::{ConcreteType, NamedType, SpecializationError};
@@ -25,7 +25,7 @@ impl NamedType for UninitializedType {
                 duplicatable: false,
                 size: 0,
             },
-            ty: as_single_type(args)?,
+            ty: args_as_single_type(args)?,
         })
     }
 }
@@ -0,0 +1,62 @@
+//! The unit type, holding no value and occupying no memory.
+//!
+//! Sierra example:
+//! ```ignore
+//! type Unit = Unit;
+//! libfunc unit = unit;
+//! libfunc store_temp_unit = store_temp<Unit>;
+//! ...
+//! unit() -> (u);
+//! store_temp_unit(u) -> (u);
+//! ```
+
+use crate::extensions::lib_func::{
+    LibFuncSignature, OutputVarInfo, SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::types::{InfoOnlyConcreteType, TypeInfo};
+use crate::extensions::{
+    NamedType, NoGenericArgsGenericLibFunc, NoGenericArgsGenericType, OutputVarReferenceInfo,
+    SpecializationError,
+};
+use crate::ids::{GenericLibFuncId, GenericTypeId};
+
+/// Type representing the empty value.
+#[derive(Default)]
+pub struct UnitType {}
+impl NoGenericArgsGenericType for UnitType {
+    type Concrete = InfoOnlyConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Unit");
+
+    fn specialize(&self) -> Self::Concrete {
+        InfoOnlyConcreteType {
+            info: TypeInfo {
+                long_id: Self::concrete_type_long_id(&[]),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 0,
+            },
+        }
+    }
+}
+
+/// LibFunc for creating the unit value.
+#[derive(Default)]
+pub struct UnitLibFunc {}
+impl NoGenericArgsGenericLibFunc for UnitLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("unit");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        Ok(LibFuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: context.get_concrete_type(UnitType::id(), &[])?,
+                ref_info: OutputVarReferenceInfo::Const,
+            }],
+            SierraApChange::Known(0),
+        ))
+    }
+}
@@ -0,0 +1,203 @@
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use super::error::{ExtensionError, SpecializationError};
+use super::lib_func::{
+    ConcreteLibFunc, GenericLibFunc, LibFuncSignature, SignatureSpecializationContext,
+    SpecializationContext,
+};
+use crate::ids::GenericLibFuncId;
+use crate::program::{ConcreteLibFuncLongId, GenericArg};
+
+/// A concrete libfunc specialized from a libfunc registered at runtime, which may be downcast back
+/// to its actual type.
+pub trait RegisteredConcreteLibFunc: ConcreteLibFunc {
+    fn as_any(&self) -> &dyn Any;
+}
+impl<T: ConcreteLibFunc + 'static> RegisteredConcreteLibFunc for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// The object safe part of [GenericLibFunc], specializing into boxed concrete libfuncs.
+trait RegisteredGenericLibFunc {
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError>;
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Box<dyn RegisteredConcreteLibFunc>, SpecializationError>;
+}
+impl<T: GenericLibFunc> RegisteredGenericLibFunc for T
+where
+    T::Concrete: 'static,
+{
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        GenericLibFunc::specialize_signature(self, context, args)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Box<dyn RegisteredConcreteLibFunc>, SpecializationError> {
+        Ok(Box::new(GenericLibFunc::specialize(self, context, args)?))
+    }
+}
+
+/// A concrete libfunc of an [ExtensionRegistry], either of the static hierarchy or of a libfunc
+/// registered at runtime.
+pub enum ExtensibleConcreteLibFunc<TConcrete> {
+    Core(TConcrete),
+    Registered(Box<dyn RegisteredConcreteLibFunc>),
+}
+impl<TConcrete> ExtensibleConcreteLibFunc<TConcrete> {
+    /// Returns the concrete libfunc of a registered libfunc, if it is of type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        match self {
+            Self::Core(_) => None,
+            Self::Registered(libfunc) => libfunc.as_any().downcast_ref(),
+        }
+    }
+}
+impl<TConcrete: ConcreteLibFunc> ConcreteLibFunc for ExtensibleConcreteLibFunc<TConcrete> {
+    fn signature(&self) -> &LibFuncSignature {
+        match self {
+            Self::Core(libfunc) => libfunc.signature(),
+            Self::Registered(libfunc) => libfunc.signature(),
+        }
+    }
+}
+
+/// The libfuncs of the static hierarchy `TLibFunc`, specializing into
+/// [ExtensibleConcreteLibFunc]s. Used as the libfunc type of a
+/// [ProgramRegistry](crate::program_registry::ProgramRegistry) created with an [ExtensionRegistry].
+pub struct ExtensibleLibFunc<TLibFunc: GenericLibFunc>(TLibFunc);
+impl<TLibFunc: GenericLibFunc> GenericLibFunc for ExtensibleLibFunc<TLibFunc> {
+    type Concrete = ExtensibleConcreteLibFunc<TLibFunc::Concrete>;
+
+    fn by_id(id: &GenericLibFuncId) -> Option<Self> {
+        TLibFunc::by_id(id).map(Self)
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        self.0.specialize_signature(context, args)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        self.0.specialize(context, args).map(ExtensibleConcreteLibFunc::Core)
+    }
+}
+
+/// Registry of libfuncs provided at runtime, alongside the static libfunc hierarchy `TLibFunc`.
+///
+/// Allows embedders to support additional libfuncs without forking the hierarchy. The ids of the
+/// static hierarchy can not be registered again.
+pub struct ExtensionRegistry<TLibFunc: GenericLibFunc> {
+    libfuncs: HashMap<GenericLibFuncId, Box<dyn RegisteredGenericLibFunc>>,
+    phantom: PhantomData<TLibFunc>,
+}
+impl<TLibFunc: GenericLibFunc> Default for ExtensionRegistry<TLibFunc> {
+    fn default() -> Self {
+        Self { libfuncs: HashMap::new(), phantom: PhantomData }
+    }
+}
+impl<TLibFunc: GenericLibFunc> ExtensionRegistry<TLibFunc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `libfunc` as the libfunc with the given id.
+    pub fn register<T: GenericLibFunc + 'static>(
+        &mut self,
+        id: GenericLibFuncId,
+        libfunc: T,
+    ) -> Result<(), ExtensionError>
+    where
+        T::Concrete: 'static,
+    {
+        if TLibFunc::by_id(&id).is_some() {
+            return Err(ExtensionError::LibFuncIdAlreadyRegistered(id));
+        }
+        match self.libfuncs.entry(id) {
+            Entry::Occupied(entry) => {
+                Err(ExtensionError::LibFuncIdAlreadyRegistered(entry.key().clone()))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Box::new(libfunc));
+                Ok(())
+            }
+        }
+    }
+
+    /// Specializes the signature of the libfunc with the given id, from the static hierarchy or
+    /// from the registered libfuncs.
+    pub fn specialize_signature_by_id(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        libfunc_id: &GenericLibFuncId,
+        generic_args: &[GenericArg],
+    ) -> Result<LibFuncSignature, ExtensionError> {
+        let result = if let Some(libfunc) = TLibFunc::by_id(libfunc_id) {
+            libfunc.specialize_signature(context, generic_args)
+        } else if let Some(libfunc) = self.libfuncs.get(libfunc_id) {
+            libfunc.specialize_signature(context, generic_args)
+        } else {
+            Err(SpecializationError::UnsupportedId)
+        };
+        result.map_err(|error| libfunc_specialization_error(libfunc_id, generic_args, error))
+    }
+
+    /// Specializes the libfunc with the given id, from the static hierarchy or from the
+    /// registered libfuncs.
+    pub fn specialize_by_id(
+        &self,
+        context: &dyn SpecializationContext,
+        libfunc_id: &GenericLibFuncId,
+        generic_args: &[GenericArg],
+    ) -> Result<ExtensibleConcreteLibFunc<TLibFunc::Concrete>, ExtensionError> {
+        let result = if let Some(libfunc) = TLibFunc::by_id(libfunc_id) {
+            libfunc.specialize(context, generic_args).map(ExtensibleConcreteLibFunc::Core)
+        } else if let Some(libfunc) = self.libfuncs.get(libfunc_id) {
+            libfunc.specialize(context, generic_args).map(ExtensibleConcreteLibFunc::Registered)
+        } else {
+            Err(SpecializationError::UnsupportedId)
+        };
+        result.map_err(|error| libfunc_specialization_error(libfunc_id, generic_args, error))
+    }
+}
+
+/// Wraps an error specializing the libfunc with the given id and generic args.
+fn libfunc_specialization_error(
+    libfunc_id: &GenericLibFuncId,
+    generic_args: &[GenericArg],
+    error: SpecializationError,
+) -> ExtensionError {
+    ExtensionError::LibFuncSpecialization {
+        long_id: ConcreteLibFuncLongId {
+            generic_id: libfunc_id.clone(),
+            generic_args: generic_args.to_vec(),
+        },
+        error,
+    }
+}
@@ -3,17 +3,28 @@ use num_bigint::BigInt;
 use test_case::test_case;
 
 use super::core::{CoreLibFunc, CoreType};
-use super::lib_func::{SierraApChange, SignatureSpecializationContext, SpecializationContext};
+use super::error::{ArgCount, GenericArgKind};
+use super::felt::felt_prime;
+use super::lib_func::{
+    ConcreteLibFunc, OutputVarInfo, OutputVarReferenceInfo, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
+};
+use super::mem::MemLibFunc;
 use super::types::TypeInfo;
 use super::SpecializationError::{
-    self, IndexOutOfRange, MissingFunction, UnsupportedGenericArg, UnsupportedId,
-    WrongNumberOfGenericArgs,
+    self, FeltConstOutOfRange, IndexOutOfRange, MissingFunction, ShortStringOverflow,
+    TypeNotDroppable, TypeNotDuplicatable, TypeNotStorable, TypeWasNotDeclared,
+    UnsupportedGenericArg, UnsupportedId, WrongNumberOfGenericArgs,
 };
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
-use crate::extensions::{GenericLibFunc, GenericType};
+use crate::extensions::{
+    ConcreteType, GenericLibFunc, GenericLibFuncEx, GenericType, GenericTypeEx,
+};
 use crate::ids::{ConcreteTypeId, FunctionId, GenericTypeId};
 use crate::program::{ConcreteTypeLongId, Function, FunctionSignature, GenericArg, StatementIdx};
-use crate::test_utils::build_bijective_mapping;
+use crate::test_utils::{
+    build_bijective_mapping, core_libfunc_signature_types, LibFuncSignatureTypes,
+};
 
 fn type_arg(name: &str) -> GenericArg {
     GenericArg::Type(name.into())
@@ -27,6 +38,10 @@ fn value_arg(v: i64) -> GenericArg {
     GenericArg::Value(BigInt::from(v))
 }
 
+fn short_string_arg(s: &str) -> GenericArg {
+    GenericArg::ShortString(s.into())
+}
+
 struct MockSpecializationContext {
     mapping: BiMap<ConcreteTypeId, ConcreteTypeLongId>,
 }
@@ -40,12 +55,18 @@ impl TypeSpecializationContext for MockSpecializationContext {
     fn try_get_type_info(&self, id: ConcreteTypeId) -> Option<TypeInfo> {
         if id == "T".into()
             || id == "felt".into()
+            || id == "bool".into()
+            || id == "uint8".into()
+            || id == "uint64".into()
             || id == "uint128".into()
+            || id == "bytes31".into()
             || id == "Option".into()
+            || id == "Result".into()
             || id == "NonZeroFelt".into()
             || id == "NonZeroInt".into()
             || id == "Tuple<>".into()
             || id == "Uint128AndFelt".into()
+            || id == "FeltPair".into()
         {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
@@ -62,6 +83,14 @@ impl TypeSpecializationContext for MockSpecializationContext {
                 duplicatable: false,
                 size: 2,
             })
+        } else if id == "Unit".into() {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 0,
+            })
         } else if id == "UninitializedFelt".into() || id == "UninitializedUint128".into() {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
@@ -70,7 +99,28 @@ impl TypeSpecializationContext for MockSpecializationContext {
                 duplicatable: false,
                 size: 0,
             })
-        } else if id == "GasBuiltin".into() {
+        } else if id == "In0".into()
+            || id == "In1".into()
+            || id == "In2".into()
+            || id == "Sum".into()
+            || id == "Product".into()
+            || id == "Inverse".into()
+            || id == "Circuit".into()
+            || id == "GappedCircuit".into()
+        {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: false,
+                droppable: true,
+                duplicatable: true,
+                size: 0,
+            })
+        } else if id == "GasBuiltin".into()
+            || id == "SegmentArena".into()
+            || id == "PanicableFelt".into()
+            || id == "AddMod".into()
+            || id == "MulMod".into()
+        {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
                 storable: true,
@@ -127,49 +177,155 @@ impl SpecializationContext for MockSpecializationContext {
 
 #[test_case("NoneExistent", vec![] => Err(UnsupportedId); "NoneExistent")]
 #[test_case("GasBuiltin", vec![] => Ok(()); "GasBuiltin")]
-#[test_case("GasBuiltin", vec![type_arg("T")] => Err(WrongNumberOfGenericArgs); "GasBuiltin<T>")]
+#[test_case("GasBuiltin", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "GasBuiltin<T>")]
 #[test_case("RangeCheck", vec![] => Ok(()); "RangeCheck")]
-#[test_case("RangeCheck", vec![type_arg("T")] => Err(WrongNumberOfGenericArgs); "RangeCheck<T>")]
+#[test_case("RangeCheck", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "RangeCheck<T>")]
+#[test_case("SegmentArena", vec![] => Ok(()); "SegmentArena")]
+#[test_case("Panicable", vec![type_arg("felt")] => Ok(()); "Panicable<felt>")]
+#[test_case("Panicable", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Panicable")]
+#[test_case("Panicable", vec![type_arg("UninitializedFelt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "Panicable<UninitializedFelt>")]
+#[test_case("SegmentArena", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "SegmentArena<T>")]
+#[test_case("Bitwise", vec![] => Ok(()); "Bitwise")]
+#[test_case("Bitwise", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "Bitwise<T>")]
+#[test_case("Pedersen", vec![] => Ok(()); "Pedersen")]
+#[test_case("Poseidon", vec![] => Ok(()); "Poseidon")]
+#[test_case("EcOp", vec![] => Ok(()); "EcOp")]
+#[test_case("Signature", vec![] => Ok(()); "Signature")]
+#[test_case("System", vec![] => Ok(()); "System")]
+#[test_case("System", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "System<T>")]
+#[test_case("EcPoint", vec![] => Ok(()); "EcPoint")]
+#[test_case("EcState", vec![] => Ok(()); "EcState")]
+#[test_case("EcState", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "EcState<T>")]
 #[test_case("felt", vec![] => Ok(()); "felt")]
-#[test_case("felt", vec![type_arg("T")] => Err(WrongNumberOfGenericArgs); "felt<T>")]
+#[test_case("felt", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. }); "felt<T>")]
+#[test_case("bool", vec![] => Ok(()); "bool")]
+#[test_case("bool", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. }); "bool<T>")]
+#[test_case("uint8", vec![] => Ok(()); "uint8")]
+#[test_case("uint64", vec![] => Ok(()); "uint64")]
+#[test_case("uint64", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "uint64<T>")]
 #[test_case("uint128", vec![] => Ok(()); "uint128")]
-#[test_case("uint128", vec![type_arg("T")] => Err(WrongNumberOfGenericArgs); "uint128<T>")]
+#[test_case("uint128", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "uint128<T>")]
+#[test_case("u256", vec![] => Ok(()); "u256")]
+#[test_case("bytes31", vec![] => Ok(()); "bytes31")]
+#[test_case("bytes31", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "bytes31<T>")]
 #[test_case("Array", vec![type_arg("uint128")] => Ok(()); "Array<uint128>")]
-#[test_case("Array", vec![] => Err(WrongNumberOfGenericArgs); "Array")]
-#[test_case("Array", vec![value_arg(5)] => Err(UnsupportedGenericArg); "Array<5>")]
-#[test_case("Array", vec![type_arg("UninitializedFelt")] => Err(UnsupportedGenericArg);
+#[test_case("Array", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Array")]
+#[test_case("Array", vec![value_arg(5)] => matches Err(UnsupportedGenericArg { .. }); "Array<5>")]
+#[test_case("Array", vec![type_arg("UninitializedFelt")]
+            => matches Err(UnsupportedGenericArg { .. });
             "Array<UninitializedFelt>")]
+#[test_case("Span", vec![type_arg("felt")] => Ok(()); "Span<felt>")]
+#[test_case("Span", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Span")]
+#[test_case("Span", vec![type_arg("UninitializedFelt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "Span<UninitializedFelt>")]
 #[test_case("NonZero", vec![type_arg("T")] => Ok(()); "NonZero<T>")]
-#[test_case("NonZero", vec![] => Err(WrongNumberOfGenericArgs); "NonZero")]
-#[test_case("NonZero", vec![value_arg(5)] => Err(UnsupportedGenericArg); "NonZero<5>")]
+#[test_case("NonZero", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "NonZero")]
+#[test_case("NonZero", vec![value_arg(5)] => matches Err(UnsupportedGenericArg { .. });
+            "NonZero<5>")]
+#[test_case("NonZero", vec![type_arg("UninitializedFelt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "NonZero<UninitializedFelt>")]
 #[test_case("Box", vec![type_arg("T")] => Ok(()); "Box<T>")]
-#[test_case("Box", vec![] => Err(WrongNumberOfGenericArgs); "Box<>")]
-#[test_case("Box", vec![value_arg(5)] => Err(UnsupportedGenericArg); "Box<5>")]
+#[test_case("Box", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Box<>")]
+#[test_case("Box", vec![value_arg(5)] => matches Err(UnsupportedGenericArg { .. }); "Box<5>")]
+#[test_case("Nullable", vec![type_arg("T")] => Ok(()); "Nullable<T>")]
+#[test_case("Nullable", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Nullable<>")]
+#[test_case("Snapshot", vec![type_arg("T")] => Ok(()); "Snapshot<T>")]
+#[test_case("Snapshot", vec![type_arg("GasBuiltin")] => Ok(()); "Snapshot<GasBuiltin>")]
+#[test_case("Snapshot", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Snapshot<>")]
 #[test_case("Uninitialized", vec![type_arg("T")] => Ok(()); "Uninitialized<T>")]
 #[test_case("Enum", vec![user_type_arg("name")] => Ok(()); "Enum<name>")]
 #[test_case("Enum", vec![user_type_arg("name"), type_arg("uint128")] => Ok(());
             "Enum<name, uint128>")]
 #[test_case("Enum", vec![user_type_arg("name"), type_arg("uint128"), type_arg("felt")] => Ok(());
             "Enum<name, uint128, felt>")]
-#[test_case("Enum", vec![user_type_arg("name"), value_arg(5)] => Err(UnsupportedGenericArg);
+#[test_case("Enum", vec![user_type_arg("name"), value_arg(5)]
+            => matches Err(UnsupportedGenericArg { .. });
             "Enum<name, 5>")]
 #[test_case("Enum", vec![user_type_arg("name"), type_arg("UninitializedFelt")]
-            => Err(UnsupportedGenericArg);
+            => matches Err(UnsupportedGenericArg { .. });
             "Enum<name, UninitializedFelt>")]
-#[test_case("Enum", vec![type_arg("uint128"), type_arg("felt")] => Err(UnsupportedGenericArg);
+#[test_case("Enum", vec![type_arg("uint128"), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
             "Enum<uint128, felt>")]
 #[test_case("Struct", vec![user_type_arg("Unit")] => Ok(()); "Struct<Unit>")]
 #[test_case("Struct", vec![user_type_arg("Wrap"), type_arg("uint128")] => Ok(());
             "Struct<Wrap, uint128>")]
 #[test_case("Struct", vec![user_type_arg("Pair"), type_arg("uint128"), type_arg("felt")] => Ok(());
             "Struct<Pair, uint128, felt>")]
-#[test_case("Struct", vec![user_type_arg("name"), value_arg(5)] => Err(UnsupportedGenericArg);
+#[test_case("Struct", vec![user_type_arg("name"), value_arg(5)]
+            => matches Err(UnsupportedGenericArg { .. });
             "Struct<name, 5>")]
 #[test_case("Struct", vec![user_type_arg("name"), type_arg("UninitializedFelt")]
-            => Err(UnsupportedGenericArg);
+            => matches Err(UnsupportedGenericArg { .. });
             "Struct<name, UninitializedFelt>")]
-#[test_case("Struct", vec![type_arg("uint128"), type_arg("felt")] => Err(UnsupportedGenericArg);
+#[test_case("Struct", vec![type_arg("uint128"), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
             "Struct<uint128, felt>")]
+#[test_case("DictFeltTo", vec![type_arg("felt")] => Ok(()); "DictFeltTo<felt>")]
+#[test_case("DictFeltTo", vec![type_arg("ArrayFelt")] => matches Err(UnsupportedGenericArg { .. });
+            "DictFeltTo<ArrayFelt>")]
+#[test_case("SquashedDictFeltTo", vec![type_arg("felt")] => Ok(()); "SquashedDictFeltTo<felt>")]
+#[test_case("SquashedDictFeltTo", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "SquashedDictFeltTo")]
+#[test_case("DictFeltToEntry", vec![type_arg("felt")] => Ok(()); "DictFeltToEntry<felt>")]
+#[test_case("DictFeltToEntry", vec![type_arg("ArrayFelt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "DictFeltToEntry<ArrayFelt>")]
+#[test_case("Coupon", vec![GenericArg::UserFunc("RegisteredFunction".into())] => Ok(());
+            "Coupon<&RegisteredFunction>")]
+#[test_case("Coupon", vec![type_arg("felt")] => matches Err(UnsupportedGenericArg { .. });
+            "Coupon<felt>")]
+#[test_case("Coupon", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Coupon")]
+#[test_case("Unit", vec![] => Ok(()); "Unit")]
+#[test_case("Unit", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "Unit<felt>")]
+#[test_case("StorageBaseAddress", vec![] => Ok(()); "StorageBaseAddress")]
+#[test_case("StorageAddress", vec![type_arg("felt")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "StorageAddress<felt>")]
+#[test_case("ContractAddress", vec![] => Ok(()); "ContractAddress")]
+#[test_case("Secp256k1Point", vec![] => Ok(()); "Secp256k1Point")]
+#[test_case("Secp256r1Point", vec![type_arg("u256")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "Secp256r1Point<u256>")]
+#[test_case("ClassHash", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "ClassHash<felt>")]
+#[test_case("AddMod", vec![] => Ok(()); "AddMod")]
+#[test_case("MulMod", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "MulMod<felt>")]
+#[test_case("CircuitInput", vec![value_arg(0)] => Ok(()); "CircuitInput<0>")]
+#[test_case("CircuitInput", vec![value_arg(-1)] => matches Err(UnsupportedGenericArg { .. });
+            "CircuitInput<-1>")]
+#[test_case("AddModGate", vec![type_arg("In0"), type_arg("Sum")] => Ok(());
+            "AddModGate<In0, Sum>")]
+#[test_case("InverseGate", vec![type_arg("In0"), type_arg("In1")]
+            => matches Err(WrongNumberOfGenericArgs { .. }); "InverseGate<In0, In1>")]
+#[test_case("SubModGate", vec![type_arg("In0"), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "SubModGate<In0, felt>")]
+#[test_case("Circuit", vec![type_arg("Product"), type_arg("Inverse")] => Ok(());
+            "Circuit<Product, Inverse>")]
+#[test_case("Circuit", vec![type_arg("In2")] => matches Err(UnsupportedGenericArg { .. });
+            "Circuit<In2>")]
+#[test_case("Circuit", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "Circuit")]
+#[test_case("CircuitInputAccumulator", vec![type_arg("Circuit")] => Ok(());
+            "CircuitInputAccumulator<Circuit>")]
+#[test_case("CircuitData", vec![type_arg("Sum")] => matches Err(UnsupportedGenericArg { .. });
+            "CircuitData<Sum>")]
+#[test_case("CircuitOutputs", vec![type_arg("Circuit")] => Ok(()); "CircuitOutputs<Circuit>")]
 fn find_type_specialization(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -186,22 +342,187 @@ fn find_type_specialization(
             "function_call<&UnregisteredFunction>")]
 #[test_case("function_call", vec![GenericArg::UserFunc("RegisteredFunction".into())]
             => Ok(()); "function_call<&RegisteredFunction>")]
-#[test_case("function_call", vec![] => Err(UnsupportedGenericArg); "function_call")]
-#[test_case("array_new", vec![] => Err(WrongNumberOfGenericArgs); "array_new")]
+#[test_case("function_call", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "function_call")]
+#[test_case("function_call", vec![type_arg("felt")] => matches Err(UnsupportedGenericArg { .. });
+            "function_call<felt>")]
+#[test_case("branch_align", vec![] => Ok(()); "branch_align")]
+#[test_case("storage_base_address_from_felt", vec![] => Ok(()); "storage_base_address_from_felt")]
+#[test_case("storage_address_from_base_and_offset", vec![] => Ok(());
+            "storage_address_from_base_and_offset")]
+#[test_case("storage_address_from_base_and_offset", vec![value_arg(255)] => Ok(());
+            "storage_address_from_base_and_offset<255>")]
+#[test_case("storage_address_from_base_and_offset", vec![value_arg(256)]
+            => matches Err(UnsupportedGenericArg { .. });
+            "storage_address_from_base_and_offset<256>")]
+#[test_case("storage_address_from_base_and_offset", vec![value_arg(-1)]
+            => matches Err(UnsupportedGenericArg { .. });
+            "storage_address_from_base_and_offset<-1>")]
+#[test_case("storage_address_from_base_and_offset", vec![type_arg("uint8")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "storage_address_from_base_and_offset<uint8>")]
+#[test_case("contract_address_try_from_felt252", vec![] => Ok(());
+            "contract_address_try_from_felt252")]
+#[test_case("class_hash_try_from_felt252", vec![type_arg("felt")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "class_hash_try_from_felt252<felt>")]
+#[test_case("branch_align", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "branch_align<T>")]
+#[test_case("coupon_buy", vec![GenericArg::UserFunc("RegisteredFunction".into())] => Ok(());
+            "coupon_buy<&RegisteredFunction>")]
+#[test_case("coupon_buy", vec![GenericArg::UserFunc("UnregisteredFunction".into())]
+            => Err(MissingFunction("UnregisteredFunction".into()));
+            "coupon_buy<&UnregisteredFunction>")]
+#[test_case("coupon_refund", vec![GenericArg::UserFunc("RegisteredFunction".into())] => Ok(());
+            "coupon_refund<&RegisteredFunction>")]
+#[test_case("coupon_refund", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "coupon_refund")]
+#[test_case("array_new", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "array_new")]
 #[test_case("array_new", vec![type_arg("uint128")] => Ok(()); "array_new<uint128>")]
-#[test_case("array_append", vec![] => Err(WrongNumberOfGenericArgs); "array_append")]
+#[test_case("array_append", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "array_append")]
 #[test_case("array_append", vec![type_arg("uint128")] => Ok(()); "array_append<uint128>")]
-#[test_case("get_gas", vec![value_arg(0)] => Err(WrongNumberOfGenericArgs); "get_gas<0>")]
+#[test_case("array_len", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "array_len")]
+#[test_case("array_len", vec![type_arg("uint128")] => Ok(()); "array_len<uint128>")]
+#[test_case("array_pop_front", vec![type_arg("felt")] => Ok(()); "array_pop_front<felt>")]
+#[test_case("array_slice", vec![type_arg("felt")] => Ok(()); "array_slice<felt>")]
+#[test_case("array_slice", vec![type_arg("ArrayFelt")] => matches Err(UnsupportedGenericArg { .. });
+            "array_slice<ArrayFelt>")]
+#[test_case("array_span", vec![type_arg("felt")] => Ok(()); "array_span<felt>")]
+#[test_case("span_pop_front", vec![type_arg("felt")] => Ok(()); "span_pop_front<felt>")]
+#[test_case("span_pop_front", vec![type_arg("ArrayFelt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "span_pop_front<ArrayFelt>")]
+#[test_case("span_len", vec![type_arg("felt")] => Ok(()); "span_len<felt>")]
+#[test_case("span_len", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "span_len")]
+#[test_case("array_at", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "array_at")]
+#[test_case("array_at", vec![type_arg("uint128")] => Ok(()); "array_at<uint128>")]
+#[test_case("array_at", vec![type_arg("ArrayFelt")] => matches Err(UnsupportedGenericArg { .. });
+            "array_at<ArrayFelt>")]
+#[test_case("array_reverse", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "array_reverse")]
+#[test_case("array_reverse", vec![type_arg("uint128")] => Ok(()); "array_reverse<uint128>")]
+#[test_case("array_zip", vec![type_arg("Uint128AndFelt")] => Ok(()); "array_zip<Uint128AndFelt>")]
+#[test_case("array_zip", vec![type_arg("uint128")] => matches Err(UnsupportedGenericArg { .. });
+            "array_zip<uint128>")]
+#[test_case("array_fold", vec![GenericArg::UserFunc("RegisteredFunction".into())]
+            => matches Err(UnsupportedGenericArg { .. }); "array_fold<&RegisteredFunction>")]
+#[test_case("array_fold", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "array_fold")]
+#[test_case("array_eq", vec![type_arg("uint128")] => Ok(()); "array_eq<uint128>")]
+#[test_case("array_eq", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "array_eq")]
+#[test_case("array_find_const", vec![value_arg(3)] => Ok(()); "array_find_const<3>")]
+#[test_case("array_rotate", vec![type_arg("uint128"), value_arg(2)] => Ok(());
+            "array_rotate<uint128, 2>")]
+#[test_case("array_rotate", vec![type_arg("uint128"), value_arg(-1)]
+            => matches Err(UnsupportedGenericArg { .. });
+            "array_rotate<uint128, -1>")]
+#[test_case("array_rotate", vec![type_arg("uint128")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "array_rotate<uint128>")]
+#[test_case("array_find_const", vec![type_arg("felt")] => matches Err(UnsupportedGenericArg { .. });
+            "array_find_const<felt>")]
+#[test_case("emit_event", vec![] => Ok(()); "emit_event")]
+#[test_case("emit_event", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "emit_event<felt>")]
+#[test_case("get_gas", vec![value_arg(0)] => matches Err(WrongNumberOfGenericArgs { .. });
+            "get_gas<0>")]
 #[test_case("get_gas", vec![] => Ok(()); "get_gas")]
-#[test_case("refund_gas", vec![value_arg(0)] => Err(WrongNumberOfGenericArgs); "refund_gas<0>")]
+#[test_case("refund_gas", vec![value_arg(0)] => matches Err(WrongNumberOfGenericArgs { .. });
+            "refund_gas<0>")]
 #[test_case("refund_gas", vec![] => Ok(()); "refund_gas")]
+#[test_case("gas_mark", vec![] => Ok(()); "gas_mark")]
+#[test_case("gas_elapsed", vec![value_arg(0)] => matches Err(WrongNumberOfGenericArgs { .. });
+            "gas_elapsed<0>")]
+#[test_case("gas_elapsed", vec![] => Ok(()); "gas_elapsed")]
 #[test_case("felt_add", vec![] => Ok(()); "felt_add")]
 #[test_case("felt_add", vec![value_arg(0)] =>  Ok(()); "felt_add<0>")]
+#[test_case("felt_add", vec![GenericArg::Value(felt_prime() - 1)] => Ok(()); "felt_add<prime - 1>")]
+#[test_case("felt_add", vec![GenericArg::Value(felt_prime())]
+            => Err(FeltConstOutOfRange(felt_prime())); "felt_add<prime>")]
+#[test_case("felt_const", vec![value_arg(5)] => Ok(()); "felt_const<5>")]
+#[test_case("felt_const", vec![value_arg(-5)] => Ok(()); "felt_const<minus 5>")]
+#[test_case("felt_const", vec![GenericArg::Value(felt_prime() - 1)] => Ok(()); "felt_const<prime - 1>")]
+#[test_case("felt_const", vec![GenericArg::Value(1 - felt_prime())] => Ok(()); "felt_const<1 - prime>")]
+#[test_case("felt_const", vec![GenericArg::Value(felt_prime())]
+            => Err(FeltConstOutOfRange(felt_prime())); "felt_const<prime>")]
+#[test_case("felt_const", vec![GenericArg::Value(-felt_prime())]
+            => Err(FeltConstOutOfRange(-felt_prime())); "felt_const<minus prime>")]
+#[test_case("felt_const", vec![short_string_arg("hello")] => Ok(()); "felt_const<'hello'>")]
+#[test_case("felt_const", vec![short_string_arg(&"a".repeat(31))] => Ok(()); "felt_const<31 chars>")]
+#[test_case("felt_const", vec![short_string_arg(&"a".repeat(32))]
+             => Err(ShortStringOverflow("a".repeat(32)));
+            "felt_const<32 chars>")]
+#[test_case("felt_const", vec![short_string_arg("caf\u{e9}")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "felt_const<non ascii>")]
+#[test_case("felt_const", vec![type_arg("felt")] => matches Err(UnsupportedGenericArg { .. });
+            "felt_const<felt>")]
+#[test_case("const_as_immediate", vec![type_arg("felt"), value_arg(5)] => Ok(());
+            "const_as_immediate<felt, 5>")]
+#[test_case("const_as_immediate", vec![type_arg("felt"), short_string_arg("abc")] => Ok(());
+            "const_as_immediate<felt, 'abc'>")]
+#[test_case("const_as_immediate", vec![type_arg("FeltPair"), value_arg(1), value_arg(2)] => Ok(());
+            "const_as_immediate<FeltPair, 1, 2>")]
+#[test_case("const_as_immediate", vec![type_arg("Option"), value_arg(0), value_arg(7)] => Ok(());
+            "const_as_immediate<Option, 0, 7>")]
+#[test_case("const_as_immediate", vec![type_arg("Option"), value_arg(1)] => Ok(());
+            "const_as_immediate<Option, 1>")]
+#[test_case("const_as_immediate", vec![type_arg("Tuple<>")] => Ok(());
+            "const_as_immediate<Tuple<>>")]
+#[test_case("const_as_immediate", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "const_as_immediate")]
+#[test_case("const_as_immediate", vec![value_arg(5)] => matches Err(UnsupportedGenericArg { .. });
+            "const_as_immediate<5>")]
+#[test_case("const_as_immediate", vec![type_arg("felt")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "const_as_immediate<felt>")]
+#[test_case("const_as_immediate", vec![type_arg("FeltPair"), value_arg(1)]
+             => matches Err(WrongNumberOfGenericArgs { .. }); "const_as_immediate<FeltPair, 1>")]
+#[test_case("const_as_immediate", vec![type_arg("felt"), value_arg(1), value_arg(2)]
+             => matches Err(WrongNumberOfGenericArgs { .. }); "const_as_immediate<felt, 1, 2>")]
+#[test_case("const_as_immediate", vec![type_arg("felt"), type_arg("felt")]
+             => matches Err(UnsupportedGenericArg { .. }); "const_as_immediate<felt, felt>")]
+#[test_case("const_as_immediate", vec![type_arg("Option"), value_arg(2)]
+             => Err(IndexOutOfRange { index: BigInt::from(2), range_size: 2 });
+            "const_as_immediate<Option, 2>")]
+#[test_case("const_as_immediate", vec![type_arg("Uint128AndFelt"), value_arg(1), value_arg(2)]
+             => matches Err(UnsupportedGenericArg { .. });
+             "const_as_immediate<Uint128AndFelt, 1, 2>")]
+#[test_case("felt_div", vec![] => Ok(()); "felt_div")]
+#[test_case("felt_div", vec![value_arg(2)] => Ok(()); "felt_div<2>")]
+#[test_case("felt_div", vec![value_arg(0)] => matches Err(UnsupportedGenericArg { .. });
+            "felt_div<0>")]
+#[test_case("felt_inv", vec![] => Ok(()); "felt_inv")]
+#[test_case("felt_inv", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "felt_inv<felt>")]
 #[test_case("felt_mul", vec![] => Ok(()); "felt_mul")]
 #[test_case("felt_mul", vec![value_arg(0)] =>  Ok(()); "felt_mul<0>")]
 #[test_case("felt_jump_nz", vec![] => Ok(()); "felt_jump_nz<>")]
 #[test_case("felt_jump_nz", vec![type_arg("felt")]
-            => Err(WrongNumberOfGenericArgs); "felt_jump_nz<int>")]
+            => matches Err(WrongNumberOfGenericArgs { .. }); "felt_jump_nz<int>")]
+#[test_case("jump_nz", vec![type_arg("felt")] => Ok(()); "jump_nz<felt>")]
+#[test_case("jump_nz", vec![type_arg("uint8")] => Ok(()); "jump_nz<uint8>")]
+#[test_case("jump_nz", vec![type_arg("uint128")] => Ok(()); "jump_nz<uint128>")]
+#[test_case("jump_nz", vec![type_arg("bool")] => matches Err(UnsupportedGenericArg { .. });
+            "jump_nz<bool>")]
+#[test_case("jump_nz", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "jump_nz")]
+#[test_case("felt_is_zero", vec![] => Ok(()); "felt_is_zero")]
+#[test_case("felt_is_zero", vec![type_arg("felt")]
+            => matches Err(WrongNumberOfGenericArgs { .. }); "felt_is_zero<felt>")]
+#[test_case("felt_assert_lt_const", vec![value_arg(10)] => Ok(()); "felt_assert_lt_const<10>")]
+#[test_case("felt_assert_lt_const", vec![value_arg(0)] => matches Err(UnsupportedGenericArg { .. });
+            "felt_assert_lt_const<0>")]
+#[test_case("felt_assert_lt_const", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "felt_assert_lt_const")]
+#[test_case("felt_popcount", vec![value_arg(252)] => Ok(()); "felt_popcount<252>")]
+#[test_case("felt_popcount", vec![value_arg(253)] => matches Err(UnsupportedGenericArg { .. });
+            "felt_popcount<253>")]
+#[test_case("felt_popcount", vec![value_arg(0)] => matches Err(UnsupportedGenericArg { .. });
+            "felt_popcount<0>")]
+#[test_case("felt_popcount", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "felt_popcount")]
+#[test_case("pack_felts", vec![value_arg(8)] => Ok(()); "pack_felts<8>")]
+#[test_case("pack_felts", vec![value_arg(253)] => matches Err(UnsupportedGenericArg { .. });
+            "pack_felts<253>")]
 #[test_case("uint128_wrapping_add", vec![] => Ok(()); "uint128_wrapping_add")]
 #[test_case("uint128_wrapping_sub", vec![] => Ok(()); "uint128_wrapping_sub")]
 #[test_case("uint128_wrapping_mul", vec![] => Ok(()); "uint128_wrapping_mul")]
@@ -211,38 +532,239 @@ fn find_type_specialization(
 #[test_case("uint128_wrapping_sub", vec![value_arg(5)] => Ok(()); "int_sub<5>")]
 #[test_case("uint128_wrapping_mul", vec![value_arg(7)] => Ok(()); "int_mul<7>")]
 #[test_case("uint128_div", vec![value_arg(9)] => Ok(()); "uint128_div<9>")]
-#[test_case("uint128_div", vec![value_arg(0)] => Err(UnsupportedGenericArg); "uint128_div<0>")]
+#[test_case("uint128_div", vec![value_arg(0)] => matches Err(UnsupportedGenericArg { .. });
+            "uint128_div<0>")]
+#[test_case("uint128_div", vec![GenericArg::Value(BigInt::from(u128::MAX) + 1)]
+            => matches Err(UnsupportedGenericArg { .. }); "uint128_div<2**128>")]
 #[test_case("uint128_mod", vec![value_arg(1)] => Ok(()); "uint128_mod<1>")]
-#[test_case("uint128_mod", vec![value_arg(0)] => Err(UnsupportedGenericArg); "uint128_mod<0>")]
+#[test_case("uint128_mod", vec![value_arg(0)] => matches Err(UnsupportedGenericArg { .. });
+            "uint128_mod<0>")]
+#[test_case("u128_safe_divmod", vec![] => Ok(()); "u128_safe_divmod")]
+#[test_case("u128_safe_divmod", vec![value_arg(2)] => matches Err(WrongNumberOfGenericArgs { .. });
+            "u128_safe_divmod<2>")]
+#[test_case("u128_wide_mul", vec![] => Ok(()); "u128_wide_mul")]
+#[test_case("u128_sqrt", vec![] => Ok(()); "u128_sqrt")]
+#[test_case("u128_sqrt", vec![type_arg("uint128")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "u128_sqrt<uint128>")]
+#[test_case("pow", vec![type_arg("felt"), value_arg(3)] => Ok(()); "pow<felt, 3>")]
+#[test_case("pow", vec![type_arg("uint128"), value_arg(0)] => Ok(()); "pow<uint128, 0>")]
+#[test_case("pow", vec![type_arg("felt"), value_arg(-1)]
+            => matches Err(UnsupportedGenericArg { .. });
+            "pow<felt, -1>")]
+#[test_case("pow", vec![type_arg("bool"), value_arg(2)]
+            => matches Err(UnsupportedGenericArg { .. });
+            "pow<bool, 2>")]
+#[test_case("pow", vec![value_arg(2), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "pow<2, felt>")]
+#[test_case("pow", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "pow<felt>")]
+#[test_case("u128_shl", vec![] => Ok(()); "u128_shl")]
+#[test_case("u128_shl", vec![value_arg(127)] => Ok(()); "u128_shl<127>")]
+#[test_case("u128_shl", vec![value_arg(128)] => matches Err(UnsupportedGenericArg { .. });
+            "u128_shl<128>")]
+#[test_case("u128_shl", vec![value_arg(-1)] => matches Err(UnsupportedGenericArg { .. });
+            "u128_shl<-1>")]
+#[test_case("u128_shr", vec![] => Ok(()); "u128_shr")]
+#[test_case("u128_shr", vec![value_arg(0)] => Ok(()); "u128_shr<0>")]
+#[test_case("u128_shr", vec![value_arg(200)] => matches Err(UnsupportedGenericArg { .. });
+            "u128_shr<200>")]
+#[test_case("u128_shr", vec![type_arg("uint128")] => matches Err(UnsupportedGenericArg { .. });
+            "u128_shr<uint128>")]
+#[test_case("u128_wide_mul", vec![type_arg("uint128")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "u128_wide_mul<uint128>")]
+#[test_case("uint8_const", vec![value_arg(255)] => Ok(()); "uint8_const<255>")]
+#[test_case("uint8_const", vec![value_arg(256)] => matches Err(UnsupportedGenericArg { .. });
+            "uint8_const<256>")]
+#[test_case("uint8_const", vec![value_arg(-1)] => matches Err(UnsupportedGenericArg { .. });
+            "uint8_const<-1>")]
+#[test_case("uint8_const", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "uint8_const")]
+#[test_case("uint8_add", vec![] => Ok(()); "uint8_add")]
+#[test_case("uint8_add", vec![value_arg(1)] => matches Err(WrongNumberOfGenericArgs { .. });
+            "uint8_add<1>")]
+#[test_case("uint64_sub", vec![] => Ok(()); "uint64_sub")]
+#[test_case("uint64_lt", vec![] => Ok(()); "uint64_lt")]
+#[test_case("uint8_le", vec![] => Ok(()); "uint8_le")]
 #[test_case("uint128_const", vec![value_arg(8)] => Ok(()); "uint128_const<8>")]
-#[test_case("uint128_const", vec![] => Err(UnsupportedGenericArg); "uint128_const")]
+#[test_case("uint128_const", vec![GenericArg::Value(BigInt::from(u128::MAX))] => Ok(());
+            "uint128_const<u128::MAX>")]
+#[test_case("uint128_const", vec![GenericArg::Value(BigInt::from(u128::MAX) + 1)]
+            => matches Err(UnsupportedGenericArg { .. }); "uint128_const<2**128>")]
+#[test_case("uint128_const", vec![value_arg(-1)] => matches Err(UnsupportedGenericArg { .. });
+            "uint128_const<minus 1>")]
+#[test_case("uint128_const", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "uint128_const")]
+#[test_case("felt_clamp", vec![type_arg("uint128")] => Ok(()); "felt_clamp<uint128>")]
+#[test_case("felt_clamp", vec![type_arg("uint8")] => Ok(()); "felt_clamp<uint8>")]
+#[test_case("upcast", vec![type_arg("uint8"), type_arg("uint64")] => Ok(());
+            "upcast<uint8, uint64>")]
+#[test_case("upcast", vec![type_arg("uint128"), type_arg("felt")] => Ok(());
+            "upcast<uint128, felt>")]
+#[test_case("upcast", vec![type_arg("uint64"), type_arg("uint8")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "upcast<uint64, uint8>")]
+#[test_case("upcast", vec![type_arg("uint8"), type_arg("uint8")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "upcast<uint8, uint8>")]
+#[test_case("upcast", vec![type_arg("bool"), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "upcast<bool, felt>")]
+#[test_case("upcast", vec![type_arg("uint8")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "upcast<uint8>")]
+#[test_case("downcast", vec![type_arg("uint64"), type_arg("uint8")] => Ok(());
+            "downcast<uint64, uint8>")]
+#[test_case("downcast", vec![type_arg("felt"), type_arg("uint128")] => Ok(());
+            "downcast<felt, uint128>")]
+#[test_case("downcast", vec![type_arg("uint8"), type_arg("uint64")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "downcast<uint8, uint64>")]
+#[test_case("downcast", vec![type_arg("felt"), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "downcast<felt, felt>")]
+#[test_case("felt_clamp", vec![type_arg("felt")] => matches Err(UnsupportedGenericArg { .. });
+            "felt_clamp<felt>")]
+#[test_case("felt_clamp", vec![value_arg(8)] => matches Err(UnsupportedGenericArg { .. });
+            "felt_clamp<8>")]
+#[test_case("felt_clamp", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "felt_clamp")]
+#[test_case("uint128_array_sum", vec![] => Ok(()); "uint128_array_sum")]
+#[test_case("u256_add", vec![] => Ok(()); "u256_add")]
+#[test_case("u256_sub", vec![] => Ok(()); "u256_sub")]
+#[test_case("u256_mul", vec![] => Ok(()); "u256_mul")]
+#[test_case("u256_add", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "u256_add<felt>")]
+#[test_case("u256_construct", vec![] => Ok(()); "u256_construct")]
+#[test_case("u256_deconstruct", vec![] => Ok(()); "u256_deconstruct")]
+#[test_case("bytes31_try_from_felt", vec![] => Ok(()); "bytes31_try_from_felt")]
+#[test_case("bytes31_to_felt", vec![] => Ok(()); "bytes31_to_felt")]
+#[test_case("bytes31_concat", vec![] => Ok(()); "bytes31_concat")]
+#[test_case("bytes31_split", vec![] => Ok(()); "bytes31_split")]
+#[test_case("bytes31_concat", vec![type_arg("felt")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "bytes31_concat<felt>")]
+#[test_case("uint128_array_sum", vec![type_arg("uint128")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "uint128_array_sum<uint128>")]
+#[test_case("bitwise_and", vec![] => Ok(()); "bitwise_and")]
+#[test_case("bitwise_or", vec![] => Ok(()); "bitwise_or")]
+#[test_case("bitwise_xor", vec![] => Ok(()); "bitwise_xor")]
+#[test_case("bitwise_xor", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "bitwise_xor<felt>")]
+#[test_case("pedersen_hash", vec![] => Ok(()); "pedersen_hash")]
+#[test_case("pedersen_hash", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "pedersen_hash<felt>")]
+#[test_case("poseidon_hades_permutation", vec![] => Ok(()); "poseidon_hades_permutation")]
+#[test_case("poseidon_hades_permutation", vec![type_arg("felt")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "poseidon_hades_permutation<felt>")]
+#[test_case("ec_point_try_new", vec![] => Ok(()); "ec_point_try_new")]
+#[test_case("ec_state_init", vec![] => Ok(()); "ec_state_init")]
+#[test_case("ec_state_add_mul", vec![] => Ok(()); "ec_state_add_mul")]
+#[test_case("ec_state_finalize", vec![] => Ok(()); "ec_state_finalize")]
+#[test_case("ec_state_finalize", vec![type_arg("EcState")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "ec_state_finalize<EcState>")]
+#[test_case("ecdsa_verify", vec![] => Ok(()); "ecdsa_verify")]
+#[test_case("storage_read", vec![] => Ok(()); "storage_read")]
+#[test_case("storage_write", vec![] => Ok(()); "storage_write")]
+#[test_case("call_contract", vec![] => Ok(()); "call_contract")]
+#[test_case("deploy", vec![] => Ok(()); "deploy")]
+#[test_case("library_call", vec![] => Ok(()); "library_call")]
+#[test_case("replace_class", vec![] => Ok(()); "replace_class")]
+#[test_case("send_message_to_l1", vec![] => Ok(()); "send_message_to_l1")]
+#[test_case("keccak", vec![] => Ok(()); "keccak")]
+#[test_case("get_execution_info", vec![] => Ok(()); "get_execution_info")]
+#[test_case("get_block_hash", vec![] => Ok(()); "get_block_hash")]
+#[test_case("secp256k1_new", vec![] => Ok(()); "secp256k1_new")]
+#[test_case("secp256k1_add", vec![] => Ok(()); "secp256k1_add")]
+#[test_case("secp256r1_mul", vec![] => Ok(()); "secp256r1_mul")]
+#[test_case("secp256r1_new", vec![type_arg("u256")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "secp256r1_new<u256>")]
+#[test_case("storage_write", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "storage_write<felt>")]
+#[test_case("ecdsa_verify", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "ecdsa_verify<felt>")]
+#[test_case("bool_and", vec![] => Ok(()); "bool_and")]
+#[test_case("bool_or", vec![] => Ok(()); "bool_or")]
+#[test_case("bool_xor", vec![] => Ok(()); "bool_xor")]
+#[test_case("bool_xor", vec![type_arg("bool")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "bool_xor<bool>")]
+#[test_case("bool_not", vec![] => Ok(()); "bool_not")]
+#[test_case("uint128_from_felt", vec![] => Ok(()); "uint128_from_felt")]
+#[test_case("uint128_to_felt", vec![] => Ok(()); "uint128_to_felt")]
+#[test_case("bool_to_felt", vec![] => Ok(()); "bool_to_felt")]
+#[test_case("bool_from_felt", vec![] => Ok(()); "bool_from_felt")]
+#[test_case("drop", vec![type_arg("bool")] => Ok(()); "drop<bool>")]
+#[test_case("null", vec![type_arg("felt")] => Ok(()); "null<felt>")]
+#[test_case("null", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "null")]
+#[test_case("nullable_from_box", vec![type_arg("felt")] => Ok(()); "nullable_from_box<felt>")]
+#[test_case("match_nullable", vec![type_arg("felt")] => Ok(()); "match_nullable<felt>")]
+#[test_case("match_nullable", vec![type_arg("uint128")]
+             => Err(TypeWasNotDeclared("Nullable".into(), vec![type_arg("uint128")]));
+            "match_nullable<uint128>")]
+#[test_case("snapshot_take", vec![type_arg("felt")] => Ok(()); "snapshot_take<felt>")]
+#[test_case("snapshot_take", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "snapshot_take")]
 #[test_case("drop", vec![type_arg("uint128")] => Ok(()); "drop<uint128>")]
-#[test_case("drop", vec![] => Err(WrongNumberOfGenericArgs); "drop<>")]
-#[test_case("drop", vec![type_arg("GasBuiltin")] => Err(UnsupportedGenericArg); "drop<GasBuiltin>")]
+#[test_case("drop", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "drop<>")]
+#[test_case("drop", vec![type_arg("GasBuiltin")] => Err(TypeNotDroppable("GasBuiltin".into()));
+            "drop<GasBuiltin>")]
+#[test_case("drop", vec![type_arg("SegmentArena")] => Err(TypeNotDroppable("SegmentArena".into()));
+            "drop<SegmentArena>")]
+#[test_case("debug_print", vec![type_arg("uint128")] => Ok(()); "debug_print<uint128>")]
+#[test_case("debug_print", vec![type_arg("GasBuiltin")]
+            => Err(TypeNotDroppable("GasBuiltin".into()));
+            "debug_print<GasBuiltin>")]
 #[test_case("dup", vec![type_arg("uint128")] => Ok(()); "dup<uint128>")]
-#[test_case("dup", vec![] => Err(WrongNumberOfGenericArgs); "dup<>")]
-#[test_case("dup", vec![type_arg("GasBuiltin")] => Err(UnsupportedGenericArg); "dup<GasBuiltin>")]
+#[test_case("dup", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "dup<>")]
+#[test_case("dup", vec![type_arg("GasBuiltin")] => Err(TypeNotDuplicatable("GasBuiltin".into()));
+            "dup<GasBuiltin>")]
+#[test_case("dup", vec![type_arg("SegmentArena")]
+             => Err(TypeNotDuplicatable("SegmentArena".into()));
+            "dup<SegmentArena>")]
+#[test_case("dict_felt_to_new", vec![type_arg("felt")] => Ok(()); "dict_felt_to_new<felt>")]
+#[test_case("panic", vec![] => Ok(()); "panic")]
+#[test_case("panic", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "panic<felt>")]
+#[test_case("panicable_wrap", vec![type_arg("felt")] => Ok(()); "panicable_wrap<felt>")]
+#[test_case("panicable_wrap", vec![type_arg("uint128")]
+             => Err(TypeWasNotDeclared("Panicable".into(), vec![type_arg("uint128")]));
+            "panicable_wrap<uint128>")]
+#[test_case("match_panicable", vec![type_arg("felt")] => Ok(()); "match_panicable<felt>")]
+#[test_case("drop", vec![type_arg("PanicableFelt")]
+             => Err(TypeNotDroppable("PanicableFelt".into()));
+            "drop<PanicableFelt>")]
 #[test_case("uint128_jump_nz", vec![] => Ok(()); "uint128_jump_nz<>")]
 #[test_case("uint128_jump_nz", vec![type_arg("uint128")]
-            => Err(WrongNumberOfGenericArgs); "uint128_jump_nz<uint128>")]
-#[test_case("unwrap_nz", vec![type_arg("uint128")] => Ok(()); "unwrap_nz<uint128>")]
-#[test_case("unwrap_nz", vec![] => Err(WrongNumberOfGenericArgs); "unwrap_nz")]
+            => matches Err(WrongNumberOfGenericArgs { .. }); "uint128_jump_nz<uint128>")]
+#[test_case("unwrap_non_zero", vec![type_arg("uint128")] => Ok(()); "unwrap_non_zero<uint128>")]
+#[test_case("unwrap_non_zero", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "unwrap_non_zero")]
 #[test_case("store_temp", vec![type_arg("uint128")] => Ok(()); "store_temp<uint128>")]
-#[test_case("store_temp", vec![] => Err(WrongNumberOfGenericArgs); "store_temp")]
+#[test_case("store_temp", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "store_temp")]
+#[test_case("store_temp", vec![type_arg("Unit")] => Ok(()); "store_temp<Unit>")]
+#[test_case("store_temp", vec![type_arg("UninitializedFelt")]
+            => Err(TypeNotStorable("UninitializedFelt".into())); "store_temp<UninitializedFelt>")]
 #[test_case("align_temps", vec![type_arg("uint128")] => Ok(()); "align_temps<uint128>")]
-#[test_case("align_temps", vec![value_arg(3)] => Err(UnsupportedGenericArg); "align_temps<3>")]
-#[test_case("align_temps", vec![] => Err(WrongNumberOfGenericArgs); "align_temps")]
+#[test_case("align_temps", vec![value_arg(3)] => matches Err(UnsupportedGenericArg { .. });
+            "align_temps<3>")]
+#[test_case("align_temps", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "align_temps")]
 #[test_case("store_local", vec![type_arg("uint128")] => Ok(()); "store_local<uint128>")]
-#[test_case("store_local", vec![] => Err(WrongNumberOfGenericArgs); "store_local")]
+#[test_case("store_local", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "store_local")]
+#[test_case("store_local", vec![type_arg("UninitializedFelt")]
+            => Err(TypeNotStorable("UninitializedFelt".into())); "store_local<UninitializedFelt>")]
 #[test_case("finalize_locals", vec![] => Ok(()); "finalize_locals")]
 #[test_case("finalize_locals", vec![type_arg("uint128")]
-            => Err(WrongNumberOfGenericArgs); "finalize_locals<uint128>")]
+            => matches Err(WrongNumberOfGenericArgs { .. }); "finalize_locals<uint128>")]
 #[test_case("alloc_local", vec![type_arg("uint128")] => Ok(()); "alloc_local<uint128>")]
-#[test_case("alloc_local", vec![] => Err(WrongNumberOfGenericArgs); "alloc_local<>")]
+#[test_case("alloc_local", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "alloc_local<>")]
 #[test_case("rename", vec![type_arg("uint128")] => Ok(()); "rename<uint128>")]
-#[test_case("rename", vec![] => Err(WrongNumberOfGenericArgs); "rename")]
+#[test_case("rename", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "rename")]
 #[test_case("jump", vec![] => Ok(()); "jump")]
-#[test_case("jump", vec![type_arg("T")] => Err(WrongNumberOfGenericArgs); "jump<T>")]
+#[test_case("unit", vec![] => Ok(()); "unit")]
+#[test_case("unit", vec![type_arg("Unit")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "unit<Unit>")]
+#[test_case("jump", vec![type_arg("T")] => matches Err(WrongNumberOfGenericArgs { .. }); "jump<T>")]
 #[test_case("revoke_ap_tracking", vec![] => Ok(()); "revoke_ap_tracking")]
 #[test_case("enum_init", vec![type_arg("Option"), value_arg(0)] => Ok(()); "enum_init<Option,0>")]
 #[test_case("enum_init", vec![type_arg("Option"), value_arg(1)] => Ok(());"enum_init<Option,1>")]
@@ -251,26 +773,89 @@ fn find_type_specialization(
 #[test_case("enum_init", vec![type_arg("Option"), value_arg(-3)]
             => Err(IndexOutOfRange{index: BigInt::from(-3), range_size: 2}); "enum_init<Option,-3>")]
 #[test_case("enum_init", vec![type_arg("Option")]
-            => Err(WrongNumberOfGenericArgs); "enum_init<Option>")]
-#[test_case("enum_init", vec![value_arg(0)] => Err(WrongNumberOfGenericArgs); "enum_init<0>")]
-#[test_case("enum_init", vec![] => Err(WrongNumberOfGenericArgs); "enum_init")]
+            => matches Err(WrongNumberOfGenericArgs { .. }); "enum_init<Option>")]
+#[test_case("enum_init", vec![value_arg(0)] => matches Err(WrongNumberOfGenericArgs { .. });
+            "enum_init<0>")]
+#[test_case("enum_init", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "enum_init")]
 #[test_case("enum_init", vec![value_arg(0),type_arg("Option")]
-            => Err(UnsupportedGenericArg); "enum_init<0,Option>")]
+            => matches Err(UnsupportedGenericArg { .. }); "enum_init<0,Option>")]
 #[test_case("enum_init", vec![type_arg("Option"), type_arg("Option")]
-            => Err(UnsupportedGenericArg); "enum_init<Option,Option>")]
+            => matches Err(UnsupportedGenericArg { .. }); "enum_init<Option,Option>")]
 #[test_case("enum_init", vec![value_arg(0), value_arg(0)]
-            => Err(UnsupportedGenericArg); "enum_init<0,0>")]
+            => matches Err(UnsupportedGenericArg { .. }); "enum_init<0,0>")]
 #[test_case("enum_match", vec![type_arg("Option")] => Ok(()); "enum_match<Option>")]
-#[test_case("enum_match", vec![value_arg(4)] => Err(UnsupportedGenericArg); "enum_match<4>")]
-#[test_case("enum_match", vec![] => Err(WrongNumberOfGenericArgs); "enum_match")]
+#[test_case("enum_match", vec![value_arg(4)] => matches Err(UnsupportedGenericArg { .. });
+            "enum_match<4>")]
+#[test_case("enum_match", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "enum_match")]
+#[test_case("option_some", vec![type_arg("felt")] => Ok(()); "option_some<felt>")]
+#[test_case("option_some", vec![type_arg("uint128")]
+            => Err(TypeWasNotDeclared("Enum".into(), vec![
+                user_type_arg("Option"), type_arg("uint128"), type_arg("Tuple<>")]));
+            "option_some<uint128>")]
+#[test_case("option_some", vec![] => matches Err(WrongNumberOfGenericArgs { .. }); "option_some")]
+#[test_case("option_none", vec![type_arg("felt")] => Ok(()); "option_none<felt>")]
+#[test_case("option_none", vec![value_arg(0)] => matches Err(UnsupportedGenericArg { .. });
+            "option_none<0>")]
+#[test_case("result_ok", vec![type_arg("felt"), type_arg("uint128")] => Ok(());
+            "result_ok<felt, uint128>")]
+#[test_case("result_err", vec![type_arg("felt"), type_arg("uint128")] => Ok(());
+            "result_err<felt, uint128>")]
+#[test_case("result_err", vec![type_arg("uint128"), type_arg("felt")]
+            => Err(TypeWasNotDeclared("Enum".into(), vec![
+                user_type_arg("Result"), type_arg("uint128"), type_arg("felt")]));
+            "result_err<uint128, felt>")]
+#[test_case("result_ok", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "result_ok<felt>")]
+#[test_case("result_ok", vec![type_arg("felt"), value_arg(0)]
+            => matches Err(UnsupportedGenericArg { .. });
+            "result_ok<felt, 0>")]
+#[test_case("result_map_err",
+            vec![type_arg("felt"), GenericArg::UserFunc("RegisteredFunction".into())]
+            => matches Err(UnsupportedGenericArg { .. });
+            "result_map_err<felt, &RegisteredFunction>")]
+#[test_case("result_map_err",
+            vec![type_arg("felt"), GenericArg::UserFunc("UnregisteredFunction".into())]
+            => Err(MissingFunction("UnregisteredFunction".into()));
+            "result_map_err<felt, &UnregisteredFunction>")]
+#[test_case("result_map_err", vec![type_arg("felt"), type_arg("felt")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "result_map_err<felt, felt>")]
+#[test_case("result_map_err", vec![type_arg("felt")] => matches Err(WrongNumberOfGenericArgs { .. });
+            "result_map_err<felt>")]
 #[test_case("struct_construct", vec![type_arg("Uint128AndFelt")] => Ok(());
             "struct_construct<Uint128AndFelt>")]
-#[test_case("struct_construct", vec![value_arg(4)] => Err(UnsupportedGenericArg);
+#[test_case("struct_construct", vec![value_arg(4)] => matches Err(UnsupportedGenericArg { .. });
             "struct_construct<4>")]
 #[test_case("struct_deconstruct", vec![type_arg("Uint128AndFelt")] => Ok(());
             "struct_deconstruct<Uint128AndFelt>")]
-#[test_case("struct_deconstruct", vec![value_arg(4)] => Err(UnsupportedGenericArg);
+#[test_case("struct_deconstruct", vec![value_arg(4)] => matches Err(UnsupportedGenericArg { .. });
             "struct_deconstruct<4>")]
+#[test_case("dict_felt_to_squash", vec![type_arg("felt")] => Ok(()); "dict_felt_to_squash<felt>")]
+#[test_case("dict_entry_get", vec![type_arg("felt")] => Ok(()); "dict_entry_get<felt>")]
+#[test_case("dict_entry_get", vec![type_arg("uint128")]
+            => Err(TypeWasNotDeclared("DictFeltTo".into(), vec![type_arg("uint128")]));
+            "dict_entry_get<uint128>")]
+#[test_case("dict_entry_finalize", vec![type_arg("felt")] => Ok(()); "dict_entry_finalize<felt>")]
+#[test_case("dict_entry_finalize", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "dict_entry_finalize")]
+#[test_case("init_circuit_data", vec![type_arg("Circuit")] => Ok(()); "init_circuit_data<Circuit>")]
+#[test_case("init_circuit_data", vec![type_arg("GappedCircuit")]
+            => matches Err(UnsupportedGenericArg { .. });
+            "init_circuit_data<GappedCircuit>")]
+#[test_case("add_circuit_input", vec![type_arg("Circuit")] => Ok(()); "add_circuit_input<Circuit>")]
+#[test_case("eval_circuit", vec![type_arg("Circuit")] => Ok(()); "eval_circuit<Circuit>")]
+#[test_case("eval_circuit", vec![type_arg("felt")] => matches Err(UnsupportedGenericArg { .. });
+            "eval_circuit<felt>")]
+#[test_case("get_circuit_output", vec![type_arg("Circuit"), value_arg(1)] => Ok(());
+            "get_circuit_output<Circuit, 1>")]
+#[test_case("get_circuit_output", vec![type_arg("Circuit"), value_arg(2)]
+            => Err(IndexOutOfRange { index: BigInt::from(2), range_size: 2 });
+            "get_circuit_output<Circuit, 2>")]
+#[test_case("get_circuit_output", vec![type_arg("Circuit")]
+            => matches Err(WrongNumberOfGenericArgs { .. });
+            "get_circuit_output<Circuit>")]
+#[test_case("dict_felt_to_squash", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "dict_felt_to_squash")]
 fn find_libfunc_specialization(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -280,3 +865,235 @@ fn find_libfunc_specialization(
         .specialize(&MockSpecializationContext::new(), &generic_args)
         .map(|_| ())
 }
+
+#[test_case("store_temp", vec![value_arg(5)]
+            => Err(UnsupportedGenericArg {
+                arg: value_arg(5),
+                expected: Some(GenericArgKind::Type),
+            });
+            "store_temp<5>")]
+#[test_case("array_new", vec![]
+            => Err(WrongNumberOfGenericArgs { expected: ArgCount::Exactly(1), actual: 0 });
+            "array_new")]
+#[test_case("felt_add", vec![value_arg(1), value_arg(2)]
+            => Err(WrongNumberOfGenericArgs { expected: ArgCount::AtMost(1), actual: 2 });
+            "felt_add<1, 2>")]
+#[test_case("felt_div", vec![value_arg(0)]
+            => Err(UnsupportedGenericArg { arg: value_arg(0), expected: None });
+            "felt_div<0>")]
+#[test_case("const_as_immediate", vec![type_arg("FeltPair"), value_arg(1)]
+            => Err(WrongNumberOfGenericArgs { expected: ArgCount::AtLeast(3), actual: 2 });
+            "const_as_immediate<FeltPair, 1>")]
+#[test_case("const_as_immediate", vec![type_arg("felt"), value_arg(1), value_arg(2)]
+            => Err(WrongNumberOfGenericArgs { expected: ArgCount::Exactly(2), actual: 3 });
+            "const_as_immediate<felt, 1, 2>")]
+fn generic_arg_error_details(
+    id: &str,
+    generic_args: Vec<GenericArg>,
+) -> Result<(), SpecializationError> {
+    CoreLibFunc::by_id(&id.into())
+        .ok_or(UnsupportedId)?
+        .specialize(&MockSpecializationContext::new(), &generic_args)
+        .map(|_| ())
+}
+
+/// Returns the param types and the output types of each branch of the specialized libfunc.
+#[test_case("dup", vec![type_arg("felt")]
+            => Ok((vec!["felt".into()], vec![vec!["felt".into(), "felt".into()]])); "dup<felt>")]
+#[test_case("drop", vec![type_arg("felt")] => Ok((vec!["felt".into()], vec![vec![]])); "drop<felt>")]
+#[test_case("rename", vec![type_arg("felt")] => Ok((vec!["felt".into()], vec![vec!["felt".into()]]));
+            "rename<felt>")]
+#[test_case("store_temp", vec![type_arg("felt")]
+            => Ok((vec!["felt".into()], vec![vec!["felt".into()]])); "store_temp<felt>")]
+#[test_case("store_temp", vec![value_arg(5)] => matches Err(UnsupportedGenericArg { .. });
+            "store_temp<5>")]
+#[test_case("felt_add", vec![]
+            => Ok((vec!["felt".into(), "felt".into()], vec![vec!["felt".into()]])); "felt_add")]
+#[test_case("felt_is_zero", vec![]
+            => Ok((vec!["felt".into()], vec![vec![], vec!["NonZeroFelt".into()]])); "felt_is_zero")]
+#[test_case("unwrap_non_zero", vec![type_arg("felt")]
+            => Ok((vec!["NonZeroFelt".into()], vec![vec!["felt".into()]])); "unwrap_non_zero<felt>")]
+#[test_case("uint128_const", vec![value_arg(5)] => Ok((vec![], vec![vec!["uint128".into()]]));
+            "uint128_const<5>")]
+#[test_case("uint128_const", vec![] => matches Err(WrongNumberOfGenericArgs { .. });
+            "uint128_const")]
+fn find_libfunc_signature(
+    id: &str,
+    generic_args: Vec<GenericArg>,
+) -> Result<LibFuncSignatureTypes, SpecializationError> {
+    core_libfunc_signature_types(&MockSpecializationContext::new(), id, &generic_args)
+}
+
+#[test]
+fn libfunc_variant_by_id() {
+    let libfunc = CoreLibFunc::by_id(&"store_temp".into()).unwrap();
+    assert_eq!(libfunc.variant_name(), "Mem");
+    match libfunc {
+        CoreLibFunc::Mem(mem_libfunc) => {
+            assert!(matches!(mem_libfunc, MemLibFunc::StoreTemp(_)));
+            assert_eq!(mem_libfunc.variant_name(), "StoreTemp");
+        }
+        _ => panic!("Expected a memory libfunc."),
+    }
+    assert!(CoreLibFunc::by_id(&"not_a_libfunc".into()).is_none());
+}
+
+#[test]
+fn zero_sized_store_temp_is_a_no_op() {
+    let libfunc = CoreLibFunc::by_id(&"store_temp".into())
+        .unwrap()
+        .specialize(&MockSpecializationContext::new(), &[type_arg("Unit")])
+        .unwrap();
+    let branches = libfunc.branch_signatures();
+    assert_eq!(branches.len(), 1);
+    assert!(matches!(branches[0].ap_change, SierraApChange::Known(0)));
+    assert!(matches!(
+        &branches[0].vars[..],
+        [OutputVarInfo { ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 }, .. }]
+    ));
+}
+
+#[test]
+fn empty_types_are_zero_sized() {
+    let size = |id: &str, generic_args: &[GenericArg]| {
+        CoreType::by_id(&id.into())
+            .unwrap()
+            .specialize(&MockSpecializationContext::new(), generic_args)
+            .unwrap()
+            .info()
+            .size
+    };
+    assert_eq!(size("Unit", &[]), 0);
+    assert_eq!(size("Struct", &[user_type_arg("Empty")]), 0);
+    assert_eq!(size("Enum", &[user_type_arg("Never")]), 0);
+    assert_eq!(size("Enum", &[user_type_arg("Single"), type_arg("Unit")]), 1);
+}
+
+#[test]
+fn ec_libfuncs_output_types() {
+    let output_types = |id: &str| {
+        core_libfunc_signature_types(&MockSpecializationContext::new(), id, &[]).unwrap().1
+    };
+    let ec_point: ConcreteTypeId = "EcPoint".into();
+    assert_eq!(output_types("ec_point_try_new"), vec![vec![ec_point.clone()], vec![]]);
+    assert_eq!(output_types("ec_state_init"), vec![vec!["EcState".into()]]);
+    assert_eq!(output_types("ec_state_add_mul"), vec![vec!["EcOp".into(), "EcState".into()]]);
+    assert_eq!(output_types("ec_state_finalize"), vec![vec![ec_point], vec![]]);
+}
+
+#[test]
+fn branch_align_metadata() {
+    let is_branch_align = |id: &str| {
+        CoreLibFunc::by_id(&id.into())
+            .unwrap()
+            .specialize(&MockSpecializationContext::new(), &[])
+            .unwrap()
+            .is_branch_align()
+    };
+    assert!(is_branch_align("branch_align"));
+    assert!(!is_branch_align("revoke_ap_tracking"));
+    assert!(!is_branch_align("felt_is_zero"));
+}
+
+#[test]
+fn revoke_ap_tracking_metadata() {
+    let revokes_ap_tracking_with_args = |id: &str, args: &[GenericArg]| {
+        CoreLibFunc::by_id(&id.into())
+            .unwrap()
+            .specialize(&MockSpecializationContext::new(), args)
+            .unwrap()
+            .revokes_ap_tracking()
+    };
+    let revokes_ap_tracking = |id: &str| revokes_ap_tracking_with_args(id, &[]);
+    assert!(revokes_ap_tracking("revoke_ap_tracking"));
+    assert!(!revokes_ap_tracking("branch_align"));
+    assert!(!revokes_ap_tracking("finalize_locals"));
+    // A not implemented ap change is not known to be unknown, so it is not a revocation.
+    assert!(!revokes_ap_tracking_with_args("align_temps", &[type_arg("uint128")]));
+}
+
+#[test]
+fn concrete_libfunc_signature() {
+    let libfunc = CoreLibFunc::by_id(&"dup".into())
+        .unwrap()
+        .specialize(&MockSpecializationContext::new(), &[type_arg("felt")])
+        .unwrap();
+    let signature = libfunc.signature();
+    assert_eq!(signature.param_signatures.len(), 1);
+    assert_eq!(signature.fallthrough, Some(0));
+    assert_eq!(signature.branch_signatures.len(), 1);
+    assert!(matches!(
+        &signature.branch_signatures[0].vars[..],
+        [
+            OutputVarInfo { ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 }, .. },
+            OutputVarInfo { ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 }, .. },
+        ]
+    ));
+}
+
+#[test]
+fn felt_is_zero_branches() {
+    let libfunc = CoreLibFunc::by_id(&"felt_is_zero".into())
+        .unwrap()
+        .specialize(&MockSpecializationContext::new(), &[])
+        .unwrap();
+    assert_eq!(libfunc.fallthrough(), Some(0));
+    assert_eq!(libfunc.output_types(), vec![vec![], vec!["NonZeroFelt".into()]]);
+}
+
+#[test]
+fn division_requires_non_zero_divisor() {
+    let divisor_type = |id: &str| {
+        let libfunc = CoreLibFunc::by_id(&id.into())
+            .unwrap()
+            .specialize(&MockSpecializationContext::new(), &[])
+            .unwrap();
+        libfunc.param_signatures().last().unwrap().ty.clone()
+    };
+    assert_eq!(divisor_type("felt_div"), "NonZeroFelt".into());
+    assert_eq!(divisor_type("uint128_div"), "NonZeroUint128".into());
+    assert_eq!(divisor_type("uint128_mod"), "NonZeroUint128".into());
+    assert_eq!(divisor_type("u128_safe_divmod"), "NonZeroUint128".into());
+}
+
+#[test]
+fn non_zero_has_own_long_id() {
+    let info = CoreType::by_id(&"NonZero".into())
+        .unwrap()
+        .specialize(&MockSpecializationContext::new(), &[type_arg("felt")])
+        .unwrap()
+        .info()
+        .clone();
+    assert_eq!(
+        info.long_id,
+        ConcreteTypeLongId { generic_id: "NonZero".into(), generic_args: vec![type_arg("felt")] }
+    );
+    assert_eq!(info.size, 1);
+}
+
+#[test]
+fn extension_error_display() {
+    let libfunc_error = CoreLibFunc::specialize_by_id(
+        &MockSpecializationContext::new(),
+        &"store_temp".into(),
+        &[value_arg(5)],
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        libfunc_error.to_string(),
+        "Could not specialize libfunc `store_temp<5>`: Generic arg `5` is unsupported, expected a \
+         type"
+    );
+    let type_error = CoreType::specialize_by_id(
+        &MockSpecializationContext::new(),
+        &"NonZero".into(),
+        &[type_arg("felt"), type_arg("felt")],
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        type_error.to_string(),
+        "Could not specialize type `NonZero<felt, felt>`: Expected 1 generic arguments, got 2"
+    );
+}
@@ -32,13 +32,19 @@ impl<TGenericType: GenericType> GenericTypeEx for TGenericType {
         args: &[GenericArg],
     ) -> Result<TGenericType::Concrete, ExtensionError> {
         Self::by_id(type_id)
-            .ok_or_else(move || ExtensionError::TypeSpecialization {
-                type_id: type_id.clone(),
+            .ok_or_else(|| ExtensionError::TypeSpecialization {
+                long_id: ConcreteTypeLongId {
+                    generic_id: type_id.clone(),
+                    generic_args: args.to_vec(),
+                },
                 error: SpecializationError::UnsupportedId,
             })?
             .specialize(context, args)
-            .map_err(move |error| ExtensionError::TypeSpecialization {
-                type_id: type_id.clone(),
+            .map_err(|error| ExtensionError::TypeSpecialization {
+                long_id: ConcreteTypeLongId {
+                    generic_id: type_id.clone(),
+                    generic_args: args.to_vec(),
+                },
                 error,
             })
     }
@@ -97,7 +103,7 @@ impl<T: NoGenericArgsGenericType> NamedType for T {
         if args.is_empty() {
             Ok(self.specialize())
         } else {
-            Err(SpecializationError::WrongNumberOfGenericArgs)
+            Err(SpecializationError::wrong_arg_count(0, args.len()))
         }
     }
 }
@@ -146,21 +152,26 @@ impl ConcreteType for InfoOnlyConcreteType {
 ///     }, MyTypeConcrete
 /// }
 /// ```
+/// Variants may carry attributes, e.g. `#[cfg(feature = "...")]`.
 #[macro_export]
 macro_rules! define_type_hierarchy {
-    (pub enum $name:ident { $($variant_name:ident ($variant:ty),)* },
-    $concrete_name:ident) => {
+    (pub enum $name:ident {
+        $($(#[$attr:meta])* $variant_name:ident ($variant:ty),)*
+    }, $concrete_name:ident) => {
         #[allow(clippy::enum_variant_names)]
         pub enum $name {
-            $($variant_name ($variant)),*
+            $($(#[$attr])* $variant_name ($variant)),*
         }
 
         impl $crate::extensions::types::GenericType for $name {
             type Concrete = $concrete_name;
             fn by_id(id: &$crate::ids::GenericTypeId) -> Option<Self> {
                 $(
-                    if let Some(res) = <$variant>::by_id(id){
-                        return Some(Self::$variant_name(res));
+                    $(#[$attr])*
+                    {
+                        if let Some(res) = <$variant>::by_id(id){
+                            return Some(Self::$variant_name(res));
+                        }
                     }
                 )*
                 None
@@ -172,6 +183,7 @@ macro_rules! define_type_hierarchy {
             ) -> Result<Self::Concrete, $crate::extensions::SpecializationError>{
                 match self {
                     $(
+                        $(#[$attr])*
                         Self::$variant_name(value) => {
                             Ok(Self::Concrete::$variant_name(
                                 <$variant as $crate::extensions::GenericType>::specialize(
@@ -186,12 +198,15 @@ macro_rules! define_type_hierarchy {
         }
 
         pub enum $concrete_name {
-            $($variant_name (<$variant as $crate::extensions::GenericType> ::Concrete),)*
+            $(
+                $(#[$attr])*
+                $variant_name (<$variant as $crate::extensions::GenericType> ::Concrete),
+            )*
         }
         impl $crate::extensions::ConcreteType for $concrete_name {
             fn info(&self) -> &$crate::extensions::types::TypeInfo {
                 match self {
-                    $(Self::$variant_name(value) => value.info()),*
+                    $($(#[$attr])* Self::$variant_name(value) => value.info()),*
                 }
             }
         }
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::fmt;
 
+use num_bigint::{BigInt, Sign};
 use utils::write_comma_separated;
 
 use crate::ids::{
@@ -8,31 +10,186 @@ use crate::ids::{
 };
 use crate::program::{
     ConcreteLibFuncLongId, ConcreteTypeLongId, Function, GenBranchInfo, GenBranchTarget,
-    GenInvocation, GenStatement, GenericArg, LibFuncDeclaration, Param, Program, StatementIdx,
-    TypeDeclaration,
+    GenInvocation, GenStatement, GenericArg, LibFuncDeclaration, Param, Program, Statement,
+    StatementIdx, TypeDeclaration,
 };
 
+/// Options for printing a program.
+#[derive(Clone, Debug, Default)]
+pub struct PrintOptions {
+    /// Values with more bits than this are printed in hex. If not set, all values are printed in
+    /// decimal.
+    pub hex_threshold_bits: Option<u64>,
+    /// Whether values printed in hex are defined once in a const table at the top of the program,
+    /// and referenced by name.
+    pub const_table: bool,
+    /// Whether statements jumped to are labeled, and branch targets reference these labels instead
+    /// of statement indices.
+    pub labels: bool,
+}
+
+/// A program along with the options for printing it.
+pub struct ProgramDisplay<'a> {
+    program: &'a Program,
+    options: &'a PrintOptions,
+}
+
+impl Program {
+    /// Returns a displayable version of the program, printed according to the given options.
+    pub fn display_with_options<'a>(&'a self, options: &'a PrintOptions) -> ProgramDisplay<'a> {
+        ProgramDisplay { program: self, options }
+    }
+}
+
 impl fmt::Display for Program {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for declaration in &self.type_declarations {
-            writeln!(f, "{declaration};")?;
+        write!(f, "{}", self.display_with_options(&PrintOptions::default()))
+    }
+}
+
+impl fmt::Display for ProgramDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let program = self.program;
+        let mut consts: Vec<&BigInt> = vec![];
+        if self.options.const_table {
+            let args = program
+                .type_declarations
+                .iter()
+                .flat_map(|declaration| &declaration.long_id.generic_args)
+                .chain(
+                    program
+                        .libfunc_declarations
+                        .iter()
+                        .flat_map(|declaration| &declaration.long_id.generic_args),
+                );
+            for arg in args {
+                if let GenericArg::Value(value) = arg {
+                    if self.is_hex(value) && !consts.contains(&value) {
+                        consts.push(value);
+                    }
+                }
+            }
+            for (idx, value) in consts.iter().enumerate() {
+                write!(f, "const c{idx} = ")?;
+                write_hex(f, value)?;
+                writeln!(f, ";")?;
+            }
+            if !consts.is_empty() {
+                writeln!(f)?;
+            }
+        }
+        for declaration in &program.type_declarations {
+            write!(f, "type {} = {}", declaration.id, declaration.long_id.generic_id)?;
+            self.write_template_args(f, &declaration.long_id.generic_args, &consts)?;
+            writeln!(f, ";")?;
         }
         writeln!(f)?;
-        for declaration in &self.libfunc_declarations {
-            writeln!(f, "{declaration};")?;
+        for declaration in &program.libfunc_declarations {
+            write!(f, "libfunc {} = {}", declaration.id, declaration.long_id.generic_id)?;
+            self.write_template_args(f, &declaration.long_id.generic_args, &consts)?;
+            writeln!(f, ";")?;
         }
         writeln!(f)?;
-        for statement in &self.statements {
-            writeln!(f, "{statement};")?;
+        if self.options.labels {
+            let targets: HashSet<StatementIdx> = program
+                .statements
+                .iter()
+                .flat_map(|statement| match statement {
+                    GenStatement::Invocation(invocation) => invocation.branches.as_slice(),
+                    GenStatement::Return(_) => &[],
+                })
+                .filter_map(|branch| match branch.target {
+                    GenBranchTarget::Fallthrough => None,
+                    GenBranchTarget::Statement(idx) => Some(idx),
+                })
+                .collect();
+            for (idx, statement) in program.statements.iter().enumerate() {
+                if targets.contains(&StatementIdx(idx)) {
+                    write!(f, "{}: ", StatementLabel(StatementIdx(idx)))?;
+                }
+                writeln!(f, "{};", with_labels(statement))?;
+            }
+        } else {
+            for statement in &program.statements {
+                writeln!(f, "{statement};")?;
+            }
         }
         writeln!(f)?;
-        for func in &self.funcs {
+        for func in &program.funcs {
             writeln!(f, "{func};")?;
         }
         Ok(())
     }
 }
 
+impl ProgramDisplay<'_> {
+    /// Returns whether the value should be printed in hex.
+    fn is_hex(&self, value: &BigInt) -> bool {
+        matches!(self.options.hex_threshold_bits, Some(bits) if value.bits() > bits)
+    }
+
+    /// Writes the template args, referencing values found in `consts` by their name.
+    fn write_template_args(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        args: &[GenericArg],
+        consts: &[&BigInt],
+    ) -> fmt::Result {
+        if args.is_empty() {
+            return Ok(());
+        }
+        write!(f, "<")?;
+        for (i, arg) in args.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            match arg {
+                GenericArg::Value(value) if self.is_hex(value) => {
+                    match consts.iter().position(|c| *c == value) {
+                        Some(idx) => write!(f, "const@c{idx}")?,
+                        None => write_hex(f, value)?,
+                    }
+                }
+                _ => write!(f, "{arg}")?,
+            }
+        }
+        write!(f, ">")
+    }
+}
+
+/// The label of a statement, used when printing programs with labels.
+struct StatementLabel(StatementIdx);
+
+impl fmt::Display for StatementLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "L{}", self.0 .0)
+    }
+}
+
+/// Returns the statement with its branch targets replaced by the labels of the targeted statements.
+fn with_labels(statement: &Statement) -> GenStatement<StatementLabel> {
+    match statement {
+        GenStatement::Invocation(invocation) => GenStatement::Invocation(GenInvocation {
+            libfunc_id: invocation.libfunc_id.clone(),
+            args: invocation.args.clone(),
+            branches: invocation
+                .branches
+                .iter()
+                .map(|branch| GenBranchInfo {
+                    target: match branch.target {
+                        GenBranchTarget::Fallthrough => GenBranchTarget::Fallthrough,
+                        GenBranchTarget::Statement(idx) => {
+                            GenBranchTarget::Statement(StatementLabel(idx))
+                        }
+                    },
+                    results: branch.results.clone(),
+                })
+                .collect(),
+        }),
+        GenStatement::Return(ids) => GenStatement::Return(ids.clone()),
+    }
+}
+
 impl fmt::Display for TypeDeclaration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "type {} = {}", self.id, self.long_id)
@@ -102,6 +259,7 @@ impl fmt::Display for GenericArg {
             GenericArg::Type(id) => write!(f, "{id}"),
             GenericArg::UserType(id) => write!(f, "ut@{id}"),
             GenericArg::Value(v) => write!(f, "{v}"),
+            GenericArg::ShortString(s) => write!(f, "'{s}'"),
             GenericArg::UserFunc(id) => write!(f, "user@{id}"),
             GenericArg::LibFunc(id) => write!(f, "lib@{id}"),
         }
@@ -171,3 +329,8 @@ fn write_template_args(f: &mut fmt::Formatter<'_>, args: &[GenericArg]) -> fmt::
         write!(f, ">")
     }
 }
+
+fn write_hex(f: &mut fmt::Formatter<'_>, value: &BigInt) -> fmt::Result {
+    let sign = if value.sign() == Sign::Minus { "-" } else { "" };
+    write!(f, "{sign}0x{}", value.magnitude().to_str_radix(16))
+}
@@ -11,16 +11,19 @@
 
 use lalrpop_util::lalrpop_mod;
 
+pub mod analysis;
 pub mod edit_state;
 pub mod extensions;
 pub mod fmt;
 pub mod ids;
+pub mod parser_utils;
 pub mod program;
 pub mod program_registry;
 pub mod serialization;
 pub mod simulation;
 #[cfg(test)]
 mod test_utils;
+pub mod validate;
 
 lalrpop_mod!(
     #[allow(clippy::all, unused_extern_crates)]
@@ -0,0 +1,113 @@
+//! Helpers for the program parser, resolving named references found while parsing.
+
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use thiserror::Error;
+
+use crate::program::{
+    GenBranchInfo, GenBranchTarget, GenInvocation, GenStatement, GenericArg, Statement,
+    StatementIdx,
+};
+
+/// Errors found in a syntactically valid program text.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    #[error("const `{0}` is not defined")]
+    UndefinedConst(String),
+    #[error("const `{0}` is defined more than once")]
+    DuplicateConst(String),
+    #[error("label `{0}` is not defined")]
+    UndefinedLabel(String),
+    #[error("label `{0}` is defined more than once")]
+    DuplicateLabel(String),
+}
+
+/// A generic argument as parsed, possibly referencing a value from the const table.
+pub enum ParsedGenericArg {
+    Arg(GenericArg),
+    Const(String),
+}
+
+/// The const table of a program, mapping const names to their values.
+pub struct ConstTable {
+    values: HashMap<String, BigInt>,
+}
+impl ConstTable {
+    /// Builds the table from the const declarations of a program.
+    pub fn new(consts: Vec<(String, BigInt)>) -> Result<Self, ParseError> {
+        let mut values = HashMap::new();
+        for (name, value) in consts {
+            if values.contains_key(&name) {
+                return Err(ParseError::DuplicateConst(name));
+            }
+            values.insert(name, value);
+        }
+        Ok(Self { values })
+    }
+
+    /// Resolves the parsed generic arguments, replacing const references with their values.
+    pub fn resolve(&self, args: Vec<ParsedGenericArg>) -> Result<Vec<GenericArg>, ParseError> {
+        args.into_iter()
+            .map(|arg| match arg {
+                ParsedGenericArg::Arg(arg) => Ok(arg),
+                ParsedGenericArg::Const(name) => match self.values.get(&name) {
+                    Some(value) => Ok(GenericArg::Value(value.clone())),
+                    None => Err(ParseError::UndefinedConst(name)),
+                },
+            })
+            .collect()
+    }
+}
+
+/// A reference to a statement, either by its index or by the label attached to it.
+pub enum StatementRef {
+    Idx(StatementIdx),
+    Label(String),
+}
+
+/// A statement as parsed, whose branch targets may reference labels.
+pub type ParsedStatement = GenStatement<StatementRef>;
+
+/// Resolves the labels referenced by the parsed statements, given along with the optional label
+/// attached to each of them.
+pub fn resolve_labels(
+    statements: Vec<(Option<String>, ParsedStatement)>,
+) -> Result<Vec<Statement>, ParseError> {
+    let mut labels = HashMap::new();
+    for (idx, (label, _)) in statements.iter().enumerate() {
+        if let Some(label) = label {
+            if labels.insert(label.clone(), StatementIdx(idx)).is_some() {
+                return Err(ParseError::DuplicateLabel(label.clone()));
+            }
+        }
+    }
+    let resolve_target = |target| match target {
+        GenBranchTarget::Fallthrough => Ok(GenBranchTarget::Fallthrough),
+        GenBranchTarget::Statement(StatementRef::Idx(idx)) => Ok(GenBranchTarget::Statement(idx)),
+        GenBranchTarget::Statement(StatementRef::Label(label)) => match labels.get(&label) {
+            Some(idx) => Ok(GenBranchTarget::Statement(*idx)),
+            None => Err(ParseError::UndefinedLabel(label)),
+        },
+    };
+    statements
+        .into_iter()
+        .map(|(_, statement)| match statement {
+            GenStatement::Invocation(invocation) => Ok(GenStatement::Invocation(GenInvocation {
+                libfunc_id: invocation.libfunc_id,
+                args: invocation.args,
+                branches: invocation
+                    .branches
+                    .into_iter()
+                    .map(|branch| {
+                        Ok(GenBranchInfo {
+                            target: resolve_target(branch.target)?,
+                            results: branch.results,
+                        })
+                    })
+                    .collect::<Result<_, ParseError>>()?,
+            })),
+            GenStatement::Return(ids) => Ok(GenStatement::Return(ids)),
+        })
+        .collect()
+}
@@ -123,6 +123,8 @@ pub enum GenericArg {
     UserType(UserTypeId),
     Type(ConcreteTypeId),
     Value(BigInt),
+    /// A string of up to 31 ASCII characters, packed into a felt when specialized.
+    ShortString(String),
     UserFunc(FunctionId),
     LibFunc(ConcreteLibFuncId),
 }
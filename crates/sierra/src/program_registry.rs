@@ -1,18 +1,24 @@
+use std::cell::RefCell;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use thiserror::Error;
 
 use crate::extensions::lib_func::{
-    SierraApChange, SignatureSpecializationContext, SpecializationContext,
+    LibFuncSignature, SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
+use crate::extensions::registry::ExtensibleLibFunc;
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
 use crate::extensions::{
-    ConcreteType, ExtensionError, GenericLibFunc, GenericLibFuncEx, GenericType, GenericTypeEx,
+    ConcreteType, ExtensionError, ExtensionRegistry, GenericLibFunc, GenericLibFuncEx, GenericType,
+    GenericTypeEx,
 };
-use crate::ids::{ConcreteLibFuncId, ConcreteTypeId, FunctionId, GenericTypeId};
+use crate::ids::{ConcreteLibFuncId, ConcreteTypeId, FunctionId, GenericLibFuncId, GenericTypeId};
 use crate::program::{Function, FunctionSignature, GenericArg, Program, TypeDeclaration};
+use crate::validate::{
+    check_linear_threading, check_panic_wrapping, LinearityError, UnwrappedPanicError,
+};
 
 #[cfg(test)]
 #[path = "program_registry_test.rs"]
@@ -39,14 +45,22 @@ pub enum ProgramRegistryError {
     LibFuncConcreteIdAlreadyExists(ConcreteLibFuncId),
     #[error("Could not find the requested libfunc")]
     MissingLibFunc(ConcreteLibFuncId),
+    #[error("panicking function is not panic-wrapped")]
+    UnwrappedPanic(UnwrappedPanicError),
+    #[error("value that may not be dropped is not threaded linearly")]
+    NonLinearThreading { function_id: FunctionId, error: LinearityError },
 }
 
 type TypeMap<TType> = HashMap<ConcreteTypeId, TType>;
 type LibFuncMap<TLibFunc> = HashMap<ConcreteLibFuncId, TLibFunc>;
-type FunctionMap = HashMap<FunctionId, Function>;
+type FunctionMap<TFunction = Function> = HashMap<FunctionId, TFunction>;
+/// Mapping from concrete libfunc ids to their specialized signatures.
+pub type LibFuncSignatureMap = HashMap<ConcreteLibFuncId, LibFuncSignature>;
 /// Mapping from the arguments for generating a concrete type (the generic-id and the arguments) to
 /// the concrete-id that points to it.
 type ConcreteTypeIdMap<'a> = HashMap<(GenericTypeId, &'a [GenericArg]), ConcreteTypeId>;
+/// Mapping from concrete libfunc ids to the concrete types referenced while specializing them.
+pub type LibFuncTypeDependencies = HashMap<ConcreteLibFuncId, HashSet<ConcreteTypeId>>;
 
 /// Registry for the data of the compiler, for all program specific data.
 pub struct ProgramRegistry<TType: GenericType, TLibFunc: GenericLibFunc> {
@@ -56,31 +70,89 @@ pub struct ProgramRegistry<TType: GenericType, TLibFunc: GenericLibFunc> {
     concrete_types: TypeMap<TType::Concrete>,
     /// Mapping ids to the concrete libfuncs reperesented by them.
     concrete_libfuncs: LibFuncMap<TLibFunc::Concrete>,
+    /// The concrete types referenced by each libfunc's specialization, if recorded.
+    type_dependencies: Option<LibFuncTypeDependencies>,
 }
 impl<TType: GenericType, TLibFunc: GenericLibFunc> ProgramRegistry<TType, TLibFunc> {
     /// Create a registry for the program.
     pub fn with_ap_change(
         program: &Program,
         function_ap_change: HashMap<FunctionId, SierraApChange>,
+    ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
+        Self::build(program, function_ap_change, false)
+    }
+
+    pub fn new(
+        program: &Program,
+    ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
+        Self::with_ap_change(program, HashMap::default())
+    }
+
+    /// Create a registry for the program, after validating that every function that may panic,
+    /// directly or through the functions it calls, is panic-wrapped, and that the values that may
+    /// not be dropped, such as the builtin pointers, are threaded linearly through every function.
+    pub fn new_validated(
+        program: &Program,
+    ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
+        let registry = Self::new(program)?;
+        for function in &program.funcs {
+            check_panic_wrapping(program, function)
+                .map_err(ProgramRegistryError::UnwrappedPanic)?;
+            check_linear_threading(program, function, &registry).map_err(|error| {
+                ProgramRegistryError::NonLinearThreading { function_id: function.id.clone(), error }
+            })?;
+        }
+        Ok(registry)
+    }
+
+    /// Create a registry for the program, recording the concrete types referenced by the
+    /// specialization of each libfunc.
+    pub fn with_type_dependencies(
+        program: &Program,
+    ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
+        Self::build(program, HashMap::default(), true)
+    }
+
+    fn build(
+        program: &Program,
+        function_ap_change: HashMap<FunctionId, SierraApChange>,
+        record_type_dependencies: bool,
+    ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
+        Self::build_with_specializer(
+            program,
+            function_ap_change,
+            record_type_dependencies,
+            TLibFunc::specialize_by_id,
+        )
+    }
+
+    /// Creates the registry, specializing the libfuncs using `specialize_libfunc`.
+    fn build_with_specializer(
+        program: &Program,
+        function_ap_change: HashMap<FunctionId, SierraApChange>,
+        record_type_dependencies: bool,
+        specialize_libfunc: impl Fn(
+            &dyn SpecializationContext,
+            &GenericLibFuncId,
+            &[GenericArg],
+        ) -> Result<TLibFunc::Concrete, ExtensionError>,
     ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
         let functions = get_functions(program)?;
         let (concrete_types, concrete_type_ids) = get_concrete_types_maps::<TType>(program)?;
-        let concrete_libfuncs = get_concrete_libfuncs::<TType, TLibFunc>(
+        let mut type_dependencies = record_type_dependencies.then(LibFuncTypeDependencies::default);
+        let concrete_libfuncs = get_concrete_libfuncs::<TType, TLibFunc::Concrete>(
             program,
             &SpecializationContextForRegistry {
                 functions: &functions,
                 concrete_type_ids: &concrete_type_ids,
                 concrete_types: &concrete_types,
                 function_ap_change,
+                referenced_types: record_type_dependencies.then(RefCell::default),
             },
+            specialize_libfunc,
+            type_dependencies.as_mut(),
         )?;
-        Ok(ProgramRegistry { functions, concrete_types, concrete_libfuncs })
-    }
-
-    pub fn new(
-        program: &Program,
-    ) -> Result<ProgramRegistry<TType, TLibFunc>, Box<ProgramRegistryError>> {
-        Self::with_ap_change(program, HashMap::default())
+        Ok(ProgramRegistry { functions, concrete_types, concrete_libfuncs, type_dependencies })
     }
     /// Get a function from the input program.
     pub fn get_function<'a>(
@@ -109,17 +181,119 @@ impl<TType: GenericType, TLibFunc: GenericLibFunc> ProgramRegistry<TType, TLibFu
             .get(id)
             .ok_or_else(|| Box::new(ProgramRegistryError::MissingLibFunc(id.clone())))
     }
+    /// Get the concrete types referenced by the specialization of each libfunc, if the registry
+    /// was created with [Self::with_type_dependencies].
+    pub fn type_dependencies(&self) -> Option<&LibFuncTypeDependencies> {
+        self.type_dependencies.as_ref()
+    }
+}
+
+impl<TType: GenericType, TLibFunc: GenericLibFunc>
+    ProgramRegistry<TType, ExtensibleLibFunc<TLibFunc>>
+{
+    /// Create a registry for the program, supporting the libfuncs registered in `extensions` in
+    /// addition to the ones of `TLibFunc`. Records the concrete types referenced by the
+    /// specialization of each libfunc if `record_type_dependencies` is set.
+    pub fn with_extensions(
+        program: &Program,
+        extensions: &ExtensionRegistry<TLibFunc>,
+        function_ap_change: HashMap<FunctionId, SierraApChange>,
+        record_type_dependencies: bool,
+    ) -> Result<Self, Box<ProgramRegistryError>> {
+        Self::build_with_specializer(
+            program,
+            function_ap_change,
+            record_type_dependencies,
+            |context, id, args| extensions.specialize_by_id(context, id, args),
+        )
+    }
+}
+
+/// Specializes the signatures of all the libfuncs of the program, without specializing the
+/// libfuncs themselves. Only requires the signatures of the program's functions, so it suits code
+/// that only needs the signatures, such as validators and analyzers.
+pub fn get_libfunc_signatures<TType: GenericType, TLibFunc: GenericLibFunc>(
+    program: &Program,
+    function_ap_change: HashMap<FunctionId, SierraApChange>,
+) -> Result<LibFuncSignatureMap, Box<ProgramRegistryError>> {
+    get_libfunc_signatures_with_specializer::<TType>(
+        program,
+        function_ap_change,
+        TLibFunc::specialize_signature_by_id,
+    )
+}
+
+/// Specializes the signatures of all the libfuncs of the program, as [get_libfunc_signatures],
+/// supporting the libfuncs registered in `extensions` in addition to the ones of `TLibFunc`.
+pub fn get_libfunc_signatures_with_extensions<TType: GenericType, TLibFunc: GenericLibFunc>(
+    program: &Program,
+    extensions: &ExtensionRegistry<TLibFunc>,
+    function_ap_change: HashMap<FunctionId, SierraApChange>,
+) -> Result<LibFuncSignatureMap, Box<ProgramRegistryError>> {
+    get_libfunc_signatures_with_specializer::<TType>(
+        program,
+        function_ap_change,
+        |context, id, args| extensions.specialize_signature_by_id(context, id, args),
+    )
+}
+
+/// Specializes the signatures of all the libfuncs of the program using `specialize_signature`.
+fn get_libfunc_signatures_with_specializer<TType: GenericType>(
+    program: &Program,
+    function_ap_change: HashMap<FunctionId, SierraApChange>,
+    specialize_signature: impl Fn(
+        &dyn SignatureSpecializationContext,
+        &GenericLibFuncId,
+        &[GenericArg],
+    ) -> Result<LibFuncSignature, ExtensionError>,
+) -> Result<LibFuncSignatureMap, Box<ProgramRegistryError>> {
+    let function_signatures = get_functions_map(program, |func| func.signature.clone())?;
+    let (concrete_types, concrete_type_ids) = get_concrete_types_maps::<TType>(program)?;
+    let context = SpecializationContextForRegistry::<TType, FunctionSignature> {
+        functions: &function_signatures,
+        concrete_type_ids: &concrete_type_ids,
+        concrete_types: &concrete_types,
+        function_ap_change,
+        referenced_types: None,
+    };
+    let mut signatures = LibFuncSignatureMap::new();
+    for declaration in &program.libfunc_declarations {
+        let signature = specialize_signature(
+            &context,
+            &declaration.long_id.generic_id,
+            &declaration.long_id.generic_args,
+        )
+        .map_err(|error| ProgramRegistryError::LibFuncSpecialization {
+            concrete_id: declaration.id.clone(),
+            error,
+        })?;
+        match signatures.entry(declaration.id.clone()) {
+            Entry::Occupied(_) => {
+                Err(ProgramRegistryError::LibFuncConcreteIdAlreadyExists(declaration.id.clone()))
+            }
+            Entry::Vacant(entry) => Ok(entry.insert(signature)),
+        }?;
+    }
+    Ok(signatures)
 }
 
 /// Creates the functions map.
 fn get_functions(program: &Program) -> Result<FunctionMap, Box<ProgramRegistryError>> {
+    get_functions_map(program, Function::clone)
+}
+
+/// Creates a map from the id of each function to the value `to_value` extracts from it.
+fn get_functions_map<TFunction>(
+    program: &Program,
+    to_value: impl Fn(&Function) -> TFunction,
+) -> Result<FunctionMap<TFunction>, Box<ProgramRegistryError>> {
     let mut functions = FunctionMap::new();
     for func in &program.funcs {
         match functions.entry(func.id.clone()) {
             Entry::Occupied(_) => {
                 Err(ProgramRegistryError::FunctionIdAlreadyExists(func.id.clone()))
             }
-            Entry::Vacant(entry) => Ok(entry.insert(func.clone())),
+            Entry::Vacant(entry) => Ok(entry.insert(to_value(func))),
         }?;
     }
     Ok(functions)
@@ -173,32 +347,75 @@ fn get_concrete_types_maps<TType: GenericType>(
     Ok((concrete_types, concrete_type_ids))
 }
 
-/// Context required for specialization process.
-pub struct SpecializationContextForRegistry<'a, TType: GenericType> {
-    pub functions: &'a FunctionMap,
+/// A function as known to a [SpecializationContextForRegistry]. Knowing only the signature of the
+/// function suffices for specializing signatures.
+pub trait RegistryFunction {
+    fn signature(&self) -> &FunctionSignature;
+}
+impl RegistryFunction for Function {
+    fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+}
+impl RegistryFunction for FunctionSignature {
+    fn signature(&self) -> &FunctionSignature {
+        self
+    }
+}
+
+/// Context required for specialization process. Supports the full specialization only if the
+/// complete functions are known, and otherwise only the specialization of signatures.
+pub struct SpecializationContextForRegistry<
+    'a,
+    TType: GenericType,
+    TFunction: RegistryFunction = Function,
+> {
+    pub functions: &'a FunctionMap<TFunction>,
     pub concrete_type_ids: &'a ConcreteTypeIdMap<'a>,
     pub concrete_types: &'a TypeMap<TType::Concrete>,
     /// AP changes information for Sierra user functions.
     pub function_ap_change: HashMap<FunctionId, SierraApChange>,
+    /// The concrete types referenced during the current specialization, if recorded.
+    pub referenced_types: Option<RefCell<HashSet<ConcreteTypeId>>>,
+}
+impl<TType: GenericType, TFunction: RegistryFunction>
+    SpecializationContextForRegistry<'_, TType, TFunction>
+{
+    /// Records a reference to the given concrete type, if references are recorded.
+    fn record_reference(&self, id: &ConcreteTypeId) {
+        if let Some(referenced_types) = &self.referenced_types {
+            referenced_types.borrow_mut().insert(id.clone());
+        }
+    }
 }
-impl<TType: GenericType> TypeSpecializationContext for SpecializationContextForRegistry<'_, TType> {
+impl<TType: GenericType, TFunction: RegistryFunction> TypeSpecializationContext
+    for SpecializationContextForRegistry<'_, TType, TFunction>
+{
     fn try_get_type_info(&self, id: ConcreteTypeId) -> Option<TypeInfo> {
-        self.concrete_types.get(&id).map(|ty| ty.info().clone())
+        let info = self.concrete_types.get(&id).map(|ty| ty.info().clone());
+        if info.is_some() {
+            self.record_reference(&id);
+        }
+        info
     }
 }
-impl<TType: GenericType> SignatureSpecializationContext
-    for SpecializationContextForRegistry<'_, TType>
+impl<TType: GenericType, TFunction: RegistryFunction> SignatureSpecializationContext
+    for SpecializationContextForRegistry<'_, TType, TFunction>
 {
     fn try_get_concrete_type(
         &self,
         id: GenericTypeId,
         generic_args: &[GenericArg],
     ) -> Option<ConcreteTypeId> {
-        self.concrete_type_ids.get(&(id, generic_args)).cloned()
+        let concrete_id = self.concrete_type_ids.get(&(id, generic_args)).cloned();
+        if let Some(concrete_id) = &concrete_id {
+            self.record_reference(concrete_id);
+        }
+        concrete_id
     }
 
     fn try_get_function_signature(&self, function_id: &FunctionId) -> Option<FunctionSignature> {
-        self.try_get_function(function_id).map(|f| f.signature)
+        self.functions.get(function_id).map(|f| f.signature().clone())
     }
 
     fn as_type_specialization_context(&self) -> &dyn TypeSpecializationContext {
@@ -222,14 +439,22 @@ impl<TType: GenericType> SpecializationContext for SpecializationContextForRegis
     }
 }
 
-/// Creates the libfuncs map.
-fn get_concrete_libfuncs<TType: GenericType, TLibFunc: GenericLibFunc>(
+/// Creates the libfuncs map, specializing each libfunc using `specialize_libfunc`. Fills
+/// `type_dependencies` with the types referenced by each libfunc, if given and the context records
+/// referenced types.
+fn get_concrete_libfuncs<TType: GenericType, TConcrete>(
     program: &Program,
     context: &SpecializationContextForRegistry<'_, TType>,
-) -> Result<LibFuncMap<TLibFunc::Concrete>, Box<ProgramRegistryError>> {
+    specialize_libfunc: impl Fn(
+        &dyn SpecializationContext,
+        &GenericLibFuncId,
+        &[GenericArg],
+    ) -> Result<TConcrete, ExtensionError>,
+    mut type_dependencies: Option<&mut LibFuncTypeDependencies>,
+) -> Result<LibFuncMap<TConcrete>, Box<ProgramRegistryError>> {
     let mut concrete_libfuncs = HashMap::new();
     for declaration in &program.libfunc_declarations {
-        let concrete_libfunc = TLibFunc::specialize_by_id(
+        let concrete_libfunc = specialize_libfunc(
             context,
             &declaration.long_id.generic_id,
             &declaration.long_id.generic_args,
@@ -244,6 +469,11 @@ fn get_concrete_libfuncs<TType: GenericType, TLibFunc: GenericLibFunc>(
             }
             Entry::Vacant(entry) => Ok(entry.insert(concrete_libfunc)),
         }?;
+        if let (Some(type_dependencies), Some(referenced_types)) =
+            (type_dependencies.as_mut(), &context.referenced_types)
+        {
+            type_dependencies.insert(declaration.id.clone(), referenced_types.take());
+        }
     }
     Ok(concrete_libfuncs)
 }
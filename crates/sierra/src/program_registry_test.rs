@@ -1,9 +1,14 @@
+use std::collections::{HashMap, HashSet};
+
 use indoc::indoc;
 use test_log::test;
 
 use crate::extensions::core::{CoreLibFunc, CoreType};
-use crate::program::{ConcreteTypeLongId, TypeDeclaration};
-use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+use crate::extensions::felt::felt_prime;
+use crate::extensions::{ConcreteLibFunc, ExtensionError, SpecializationError};
+use crate::program::{ConcreteTypeLongId, StatementIdx, TypeDeclaration};
+use crate::program_registry::{get_libfunc_signatures, ProgramRegistry, ProgramRegistryError};
+use crate::validate::UnwrappedPanicError;
 use crate::ProgramParser;
 
 #[test]
@@ -95,3 +100,116 @@ fn libfunc_id_double_declaration() {
         Err(Box::new(ProgramRegistryError::LibFuncConcreteIdAlreadyExists("used_id".into())))
     );
 }
+
+#[test]
+fn records_type_dependencies() {
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::with_type_dependencies(
+        &ProgramParser::new()
+            .parse(indoc! {"
+                type felt = felt;
+                type uint128 = uint128;
+                libfunc store_temp_felt = store_temp<felt>;
+                libfunc uint128_const = uint128_const<3>;
+            "})
+            .unwrap(),
+    )
+    .unwrap();
+    let type_dependencies = registry.type_dependencies().unwrap();
+    assert_eq!(type_dependencies[&"store_temp_felt".into()], HashSet::from(["felt".into()]));
+    assert_eq!(type_dependencies[&"uint128_const".into()], HashSet::from(["uint128".into()]));
+    assert!(ProgramRegistry::<CoreType, CoreLibFunc>::new(
+        &ProgramParser::new().parse("").unwrap()
+    )
+    .unwrap()
+    .type_dependencies()
+    .is_none());
+}
+
+#[test]
+fn libfunc_signatures_without_registry() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            libfunc store_temp_felt = store_temp<felt>;
+            libfunc call_foo = function_call<user@foo>;
+            foo@0(a: felt) -> (felt);
+        "})
+        .unwrap();
+    let signatures =
+        get_libfunc_signatures::<CoreType, CoreLibFunc>(&program, HashMap::default()).unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    for id in ["store_temp_felt", "call_foo"] {
+        let id = id.into();
+        let libfunc = registry.get_libfunc(&id).unwrap();
+        assert_eq!(signatures[&id].param_signatures.len(), libfunc.param_signatures().len());
+        assert_eq!(
+            signatures[&id].branch_signatures.iter().map(|b| b.vars.len()).collect::<Vec<_>>(),
+            libfunc.branch_signatures().iter().map(|b| b.vars.len()).collect::<Vec<_>>(),
+        );
+    }
+}
+
+#[test]
+fn libfunc_signatures_missing_function() {
+    assert!(matches!(
+        get_libfunc_signatures::<CoreType, CoreLibFunc>(
+            &ProgramParser::new().parse("libfunc call_foo = function_call<user@foo>;").unwrap(),
+            HashMap::default(),
+        )
+        .map_err(|error| *error),
+        Err(ProgramRegistryError::LibFuncSpecialization { .. })
+    ));
+}
+
+#[test]
+fn libfunc_signatures_felt_const_out_of_range() {
+    let program = ProgramParser::new()
+        .parse(&format!(
+            "type felt = felt;\nlibfunc felt_const_prime = felt_const<{}>;",
+            felt_prime()
+        ))
+        .unwrap();
+    assert!(matches!(
+        get_libfunc_signatures::<CoreType, CoreLibFunc>(&program, HashMap::default())
+            .map_err(|error| *error),
+        Err(ProgramRegistryError::LibFuncSpecialization {
+            error: ExtensionError::LibFuncSpecialization {
+                error: SpecializationError::FeltConstOutOfRange(_),
+                ..
+            },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn validated_registry_rejects_unwrapped_panic() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            type PanicableFelt = Panicable<felt>;
+            libfunc panic = panic;
+            libfunc match_panicable = match_panicable<felt>;
+            libfunc array_new = array_new<felt>;
+            libfunc call_inner = function_call<user@inner>;
+            array_new() -> (data);
+            panic(data) { };
+            call_inner() -> (r);
+            match_panicable(r) { fallthrough(v) 5(data) };
+            return(v);
+            panic(data) { };
+            inner@0() -> (PanicableFelt);
+            outer@2() -> (felt);
+        "})
+        .unwrap();
+    // The unvalidated registry accepts the program.
+    assert!(ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).is_ok());
+    assert_eq!(
+        ProgramRegistry::<CoreType, CoreLibFunc>::new_validated(&program).map(|_| ()),
+        Err(Box::new(ProgramRegistryError::UnwrappedPanic(UnwrappedPanicError {
+            function_id: "outer".into(),
+            statement_idx: StatementIdx(5),
+        })))
+    );
+}
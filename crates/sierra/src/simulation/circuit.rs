@@ -0,0 +1,62 @@
+//! Modular arithmetic over an arbitrary modulus, used for simulating the circuit libfuncs.
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+
+use super::field::canonical;
+use crate::extensions::circuit::{CircuitGate, CircuitInfo, GateOperator};
+
+/// Returns the representative of the value in the range `[0, modulus)`.
+fn reduce(value: &BigInt, modulus: &BigInt) -> BigInt {
+    ((value % modulus) + modulus) % modulus
+}
+
+/// Returns the inverse of the value modulo the modulus, or `None` if they are not coprime.
+fn inverse(value: &BigInt, modulus: &BigInt) -> Option<BigInt> {
+    // Extended Euclid, keeping only the coefficients of `value`.
+    let (mut r0, mut r1) = (modulus.clone(), reduce(value, modulus));
+    let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+    while !r1.is_zero() {
+        let quotient = &r0 / &r1;
+        (r0, r1) = (r1.clone(), &r0 - &quotient * &r1);
+        (t0, t1) = (t1.clone(), &t0 - &quotient * &t1);
+    }
+    if r0.is_one() {
+        Some(reduce(&t0, modulus))
+    } else {
+        None
+    }
+}
+
+/// Evaluates a gate given the values of the circuit's inputs.
+/// Returns `None` if an inverse gate was applied to a value with no inverse.
+fn eval_gate(gate: &CircuitGate, inputs: &[BigInt], modulus: &BigInt) -> Option<BigInt> {
+    let (operator, operands) = match gate {
+        // The inputs are felts, so they are taken by their value in `[0, prime)` before being
+        // reduced modulo the modulus.
+        CircuitGate::Input(index) => return Some(reduce(&canonical(&inputs[*index]), modulus)),
+        CircuitGate::Operation { operator, operands } => (operator, operands),
+    };
+    let operands = operands
+        .iter()
+        .map(|operand| eval_gate(operand, inputs, modulus))
+        .collect::<Option<Vec<_>>>()?;
+    Some(match (operator, &operands[..]) {
+        (GateOperator::Add, [lhs, rhs]) => reduce(&(lhs + rhs), modulus),
+        (GateOperator::Sub, [lhs, rhs]) => reduce(&(lhs - rhs), modulus),
+        (GateOperator::Mul, [lhs, rhs]) => reduce(&(lhs * rhs), modulus),
+        (GateOperator::Inverse, [value]) => inverse(value, modulus)?,
+        _ => unreachable!("Gate arity is validated on specialization."),
+    })
+}
+
+/// Evaluates the outputs of a circuit modulo a positive modulus, given the felt values of its
+/// inputs.
+/// Returns `None` if an inverse gate was applied to a value with no inverse.
+pub fn eval_circuit(
+    circuit: &CircuitInfo,
+    inputs: &[BigInt],
+    modulus: &BigInt,
+) -> Option<Vec<BigInt>> {
+    assert!(modulus.is_positive(), "The modulus must be positive.");
+    circuit.outputs.iter().map(|output| eval_gate(output, inputs, modulus)).collect()
+}
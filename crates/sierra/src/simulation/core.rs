@@ -1,34 +1,100 @@
 use std::collections::HashMap;
 
-use num_bigint::ToBigInt;
-use num_traits::Zero;
+use num_bigint::{BigInt, Sign, ToBigInt};
+use num_traits::{One, Zero};
 use utils::extract_matches;
 
+use super::circuit::eval_circuit;
+use super::ec::{ec_add, ec_mul, is_on_curve};
+use super::field::{canonical, felt_prime, reduce, FieldBackend};
+use super::hashes::{hades_permutation, keccak_u64_words, pedersen_hash, KECCAK_RATE_IN_WORDS};
+use super::signature::SignatureVerifier;
+use super::syscalls::SyscallHandler;
 use super::value::CoreValue;
-use super::LibFuncSimulationError;
-use crate::extensions::array::ArrayConcreteLibFunc;
+use super::{Event, L1Message, LibFuncSimulationError};
+use crate::extensions::array::{
+    ArrayConcreteLibFunc, ArrayFindConstConcreteLibFunc, ArrayFoldConcreteLibFunc,
+    ArrayRotateConcreteLibFunc,
+};
+use crate::extensions::bitwise::{BitwiseConcreteLibFunc, BitwiseOperator};
+use crate::extensions::boolean::{BoolConcreteLibFunc, BoolOperationConcreteLibFunc, BoolOperator};
+use crate::extensions::bytes31::{Bytes31ConcreteLibFunc, BYTES31_MAX_LEN};
+use crate::extensions::casts::{CastConcreteLibFunc, CastConcreteLibFuncInfo, CastType};
+use crate::extensions::circuit::{
+    AddCircuitInputConcreteLibFunc, CircuitConcreteLibFunc, EvalCircuitConcreteLibFunc,
+    GetCircuitOutputConcreteLibFunc,
+};
+use crate::extensions::consts::{ConstAsImmediateConcreteLibFunc, ConstValue};
 use crate::extensions::core::CoreConcreteLibFunc::{
-    self, ApTracking, Array, Drop, Dup, Enum, Felt, FunctionCall, Gas, Mem, Struct, Uint128,
-    UnconditionalJump, UnwrapNonZero,
+    self, ApTracking, Array, Bitwise, Bool, BranchAlign, Bytes31, Cast, Circuit,
+    ClassHashTryFromFelt, ConstAsImmediate, ContractAddressTryFromFelt, Coupon, DebugPrint, Drop,
+    Dup, Ec, EcdsaVerify, EmitEvent, Enum, Felt, FunctionCall, Gas, JumpNotZero, Mem, Nullable,
+    Panic, Pedersen, Poseidon, Pow, Secp256, SnapshotTake, Span, StorageAddress, Struct, Syscall,
+    Uint128, Uint16, Uint256, Uint32, Uint64, Uint8, UnconditionalJump, Unit, UnwrapNonZero,
 };
+use crate::extensions::coupon::CouponConcreteLibFunc;
 use crate::extensions::dict_felt_to::DictFeltToConcreteLibFunc;
-use crate::extensions::enm::{EnumConcreteLibFunc, EnumInitConcreteLibFunc};
+use crate::extensions::ec::EcConcreteLibFunc;
+use crate::extensions::enm::{
+    EnumConcreteLibFunc, EnumInitConcreteLibFunc, OPTION_NONE_VARIANT_INDEX,
+    OPTION_SOME_VARIANT_INDEX, RESULT_ERR_VARIANT_INDEX, RESULT_OK_VARIANT_INDEX,
+};
 use crate::extensions::felt::{
-    FeltBinaryOperationConcreteLibFunc, FeltConcrete, FeltConstConcreteLibFunc,
-    FeltOperationConcreteLibFunc, FeltOperationWithConstConcreteLibFunc, FeltOperator,
+    FeltAssertLtConstConcreteLibFunc, FeltBinaryOperationConcreteLibFunc, FeltClampConcreteLibFunc,
+    FeltConcrete, FeltConstConcreteLibFunc, FeltOperationConcreteLibFunc,
+    FeltOperationWithConstConcreteLibFunc, FeltOperator, FeltPackConcreteLibFunc,
+    FeltPopcountConcreteLibFunc,
 };
 use crate::extensions::function_call::FunctionCallConcreteLibFunc;
-use crate::extensions::gas::GasConcreteLibFunc::{BurnGas, GetGas, RefundGas};
+use crate::extensions::gas::GasConcreteLibFunc::{BurnGas, GasElapsed, GasMark, GetGas, RefundGas};
 use crate::extensions::integer::{
-    IntOperator, Uint128BinaryOperationConcreteLibFunc, Uint128Concrete,
+    IntOperator, ShiftOperator, Uint128BinaryOperationConcreteLibFunc, Uint128Concrete,
     Uint128ConstConcreteLibFunc, Uint128OperationConcreteLibFunc,
-    Uint128OperationWithConstConcreteLibFunc,
+    Uint128OperationWithConstConcreteLibFunc, Uint128ShiftBinaryConcreteLibFunc,
+    Uint128ShiftConcreteLibFunc, Uint128ShiftWithConstConcreteLibFunc,
 };
+use crate::extensions::interoperability::BOUNDED_FELT_BITS;
 use crate::extensions::mem::MemConcreteLibFunc::{
     AlignTemps, AllocLocal, FinalizeLocals, Rename, StoreLocal, StoreTemp,
 };
+use crate::extensions::nullable::NullableConcreteLibFunc;
+use crate::extensions::option::OptionConcreteLibFunc;
+use crate::extensions::panic::PanicConcreteLibFunc;
+use crate::extensions::pedersen::PedersenConcreteLibFunc;
+use crate::extensions::poseidon::PoseidonConcreteLibFunc;
+use crate::extensions::pow::{PowBase, PowConcreteLibFunc};
+use crate::extensions::result::{ResultConcreteLibFunc, ResultMapErrConcreteLibFunc};
+use crate::extensions::secp256::{Secp256ConcreteLibFunc, Secp256Operation};
+use crate::extensions::span::SpanConcreteLibFunc;
+use crate::extensions::storage::{
+    storage_base_address_bound, StorageAddressConcreteLibFunc,
+    StorageAddressFromBaseAndOffsetConcreteLibFunc,
+};
 use crate::extensions::strct::StructConcreteLibFunc;
-use crate::ids::FunctionId;
+use crate::extensions::syscalls::SyscallConcreteLibFunc;
+use crate::extensions::uint::{
+    Uint16Traits, Uint32Traits, Uint64Traits, Uint8Traits, UintConcrete, UintConstConcreteLibFunc,
+    UintOperationConcreteLibFunc, UintTraits,
+};
+use crate::extensions::uint256::{
+    Uint256Concrete, Uint256OperationConcreteLibFunc, Uint256Operator,
+};
+use crate::ids::{ConcreteTypeId, FunctionId};
+
+/// The handlers of the effects of libfuncs beyond their outputs, provided by the simulation
+/// context.
+pub struct SideEffects<'a> {
+    /// Records a printed value of the given type.
+    pub debug_print: &'a dyn Fn(&ConcreteTypeId, &CoreValue),
+    /// Records an emitted event.
+    pub emit_event: &'a dyn Fn(Event),
+    /// Records a message sent to L1.
+    pub send_message_to_l1: &'a dyn Fn(L1Message),
+    /// Verifies ECDSA signatures.
+    pub signature_verifier: &'a dyn SignatureVerifier,
+    /// Handles the syscalls made by the syscall libfuncs.
+    pub syscall_handler: &'a dyn SyscallHandler,
+}
 
 // TODO(spapini): Proper errors when converting from bigint to u128.
 /// Simulates the run of a single libfunc. Returns the value representations of the outputs, and
@@ -36,27 +102,69 @@ use crate::ids::FunctionId;
 ///
 /// `simulate_function` is a function that simulates running of a user function. It is provided here
 /// for the case where the extensions need to use it.
+///
+/// `field` is the backend used for the felt arithmetic.
+///
+/// `side_effects` handles the printed values, events, L1 messages, signature verifications and
+/// syscalls of the libfunc.
 pub fn simulate<
+    Field: FieldBackend,
     GetStatementGasInfo: Fn() -> Option<i64>,
     SimulateFunction: Fn(&FunctionId, Vec<CoreValue>) -> Result<Vec<CoreValue>, LibFuncSimulationError>,
 >(
     libfunc: &CoreConcreteLibFunc,
     inputs: Vec<CoreValue>,
+    field: &Field,
     get_statement_gas_info: GetStatementGasInfo,
     simulate_function: SimulateFunction,
+    side_effects: &SideEffects<'_>,
 ) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
     match libfunc {
+        DebugPrint(libfunc) => match &inputs[..] {
+            [value] => {
+                (side_effects.debug_print)(&libfunc.signature.param_signatures[0].ty, value);
+                Ok((vec![], 0))
+            }
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        EmitEvent(_) => match &inputs[..] {
+            [CoreValue::System, CoreValue::Array(keys), CoreValue::Array(data)] => {
+                (side_effects.emit_event)(Event { keys: as_felts(keys)?, data: as_felts(data)? });
+                Ok((vec![CoreValue::System], 0))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        EcdsaVerify(_) => match &inputs[..] {
+            [CoreValue::Signature, CoreValue::Felt(message), CoreValue::Felt(public_key), CoreValue::Felt(r), CoreValue::Felt(s)] =>
+            {
+                let valid = side_effects.signature_verifier.verify(message, public_key, r, s)?;
+                Ok((vec![CoreValue::Signature], if valid { 0 } else { 1 }))
+            }
+            [_, _, _, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Drop(_) => match &inputs[..] {
             [_] => Ok((vec![], 0)),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
-        Dup(_) => match &inputs[..] {
+        Dup(_) | SnapshotTake(_) => match &inputs[..] {
             [value] => Ok((vec![value.clone(), value.clone()], 0)),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
         FunctionCall(FunctionCallConcreteLibFunc { function, .. }) => {
             Ok((simulate_function(&function.id, inputs)?, 0))
         }
+        // The cost of a coupon is accounted for by the gas of the statement buying it.
+        Coupon(CouponConcreteLibFunc::Buy(_)) => match &inputs[..] {
+            [] => Ok((vec![CoreValue::Coupon], 0)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Coupon(CouponConcreteLibFunc::Refund(_)) => match &inputs[..] {
+            [CoreValue::Coupon] => Ok((vec![], 0)),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Gas(GetGas(_)) => {
             let count = get_statement_gas_info()
                 .ok_or(LibFuncSimulationError::UnresolvedStatementGasInfo)?;
@@ -87,6 +195,27 @@ pub fn simulate<
             get_statement_gas_info().ok_or(LibFuncSimulationError::UnresolvedStatementGasInfo)?;
             Ok((vec![], 0))
         }
+        Gas(GasMark(_)) => match &inputs[..] {
+            [CoreValue::GasBuiltin(value)] => {
+                let mark = u128::try_from(*value)
+                    .map_err(|_| LibFuncSimulationError::BuiltinValueOutOfRange)?;
+                Ok((vec![CoreValue::GasBuiltin(*value), CoreValue::Uint128(mark)], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Gas(GasElapsed(_)) => match &inputs[..] {
+            [CoreValue::GasBuiltin(value), CoreValue::Uint128(mark)] => {
+                let counter = u128::try_from(*value)
+                    .map_err(|_| LibFuncSimulationError::BuiltinValueOutOfRange)?;
+                // The counter only decreases, so a mark below it was not taken from it.
+                let elapsed =
+                    mark.checked_sub(counter).ok_or(LibFuncSimulationError::InvalidGasMark)?;
+                Ok((vec![CoreValue::GasBuiltin(*value), CoreValue::Uint128(elapsed)], 0))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Array(ArrayConcreteLibFunc::New(_)) => {
             if inputs.is_empty() {
                 Ok((vec![CoreValue::Array(vec![])], 0))
@@ -94,6 +223,117 @@ pub fn simulate<
                 Err(LibFuncSimulationError::WrongNumberOfArgs)
             }
         }
+        Array(ArrayConcreteLibFunc::Reverse(_)) => match &inputs[..] {
+            [CoreValue::Array(values)] => {
+                Ok((vec![CoreValue::Array(values.iter().rev().cloned().collect())], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibFunc::Zip(_)) => match &inputs[..] {
+            [CoreValue::Array(first), CoreValue::Array(second)] => {
+                if first.len() == second.len() {
+                    let pairs = first
+                        .iter()
+                        .zip(second.iter())
+                        .map(|(a, b)| CoreValue::Struct(vec![a.clone(), b.clone()]))
+                        .collect();
+                    Ok((vec![CoreValue::Array(pairs)], 0))
+                } else {
+                    Ok((inputs, 1))
+                }
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibFunc::Fold(ArrayFoldConcreteLibFunc { function, .. })) => {
+            match &inputs[..] {
+                [CoreValue::Array(values), accumulator] => {
+                    let mut accumulator = accumulator.clone();
+                    for value in values {
+                        accumulator = match &simulate_function(
+                            &function.id,
+                            vec![accumulator, value.clone()],
+                        )?[..]
+                        {
+                            [accumulator] => accumulator.clone(),
+                            _ => return Err(LibFuncSimulationError::WrongNumberOfArgs),
+                        };
+                    }
+                    Ok((vec![accumulator], 0))
+                }
+                [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+            }
+        }
+        Array(ArrayConcreteLibFunc::Eq(_)) => match &inputs[..] {
+            [CoreValue::Array(first), CoreValue::Array(second)] => {
+                // `all` stops at the first differing element.
+                let equal = first.len() == second.len()
+                    && first.iter().zip(second.iter()).all(|(a, b)| a == b);
+                Ok((inputs, if equal { 0 } else { 1 }))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibFunc::FindConst(ArrayFindConstConcreteLibFunc {
+            target, ..
+        })) => match &inputs[..] {
+            [CoreValue::RangeCheck, CoreValue::Array(values)] => {
+                let prime = felt_prime();
+                let mut index = None;
+                for (i, value) in values.iter().enumerate() {
+                    match value {
+                        CoreValue::Felt(value) if ((value - target) % &prime).is_zero() => {
+                            index = Some(i);
+                            break;
+                        }
+                        CoreValue::Felt(_) => {}
+                        _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                    }
+                }
+                let array = CoreValue::Array(values.clone());
+                Ok(match index {
+                    Some(i) => (vec![CoreValue::RangeCheck, array, CoreValue::Felt(i.into())], 0),
+                    None => (vec![CoreValue::RangeCheck, array], 1),
+                })
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibFunc::Rotate(ArrayRotateConcreteLibFunc { offset, .. })) => {
+            match &inputs[..] {
+                [CoreValue::Array(values)] => {
+                    let mut values = values.clone();
+                    if !values.is_empty() {
+                        let len = values.len();
+                        values.rotate_left(offset % len);
+                    }
+                    Ok((vec![CoreValue::Array(values)], 0))
+                }
+                [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+            }
+        }
+        Array(ArrayConcreteLibFunc::Len(_)) => match &inputs[..] {
+            [CoreValue::Array(values)] => {
+                let len = CoreValue::Felt(values.len().into());
+                Ok((vec![CoreValue::Array(values.clone()), len], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibFunc::At(_)) => match &inputs[..] {
+            [CoreValue::RangeCheck, CoreValue::Array(values), CoreValue::Felt(index)] => {
+                let array = CoreValue::Array(values.clone());
+                Ok(match usize::try_from(index).ok().and_then(|index| values.get(index)) {
+                    Some(value) => (vec![CoreValue::RangeCheck, array, value.clone()], 0),
+                    None => (vec![CoreValue::RangeCheck, array], 1),
+                })
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Array(ArrayConcreteLibFunc::Append(_)) => match &inputs[..] {
             [CoreValue::Array(_), _] => {
                 let mut iter = inputs.into_iter();
@@ -104,13 +344,186 @@ pub fn simulate<
             [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
+        Array(ArrayConcreteLibFunc::PopFront(_)) => match &inputs[..] {
+            [CoreValue::Array(values)] if values.is_empty() => Ok((inputs, 1)),
+            [CoreValue::Array(values)] => {
+                let mut values = values.clone();
+                let value = values.remove(0);
+                Ok((vec![CoreValue::Array(values), value], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibFunc::Slice(_)) => match &inputs[..] {
+            [CoreValue::RangeCheck, CoreValue::Array(values), CoreValue::Felt(start), CoreValue::Felt(length)] =>
+            {
+                let array = CoreValue::Array(values.clone());
+                let slice = usize::try_from(start)
+                    .ok()
+                    .zip(usize::try_from(length).ok())
+                    .and_then(|(start, length)| values.get(start..start.checked_add(length)?));
+                Ok(match slice {
+                    Some(slice) => {
+                        (vec![CoreValue::RangeCheck, array, CoreValue::Array(slice.to_vec())], 0)
+                    }
+                    None => (vec![CoreValue::RangeCheck, array], 1),
+                })
+            }
+            [_, _, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Span(SpanConcreteLibFunc::FromArray(_)) => match &inputs[..] {
+            [CoreValue::Array(values)] => {
+                let span =
+                    CoreValue::Span { elements: values.clone(), offset: 0, len: values.len() };
+                Ok((vec![CoreValue::Array(values.clone()), span], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Span(SpanConcreteLibFunc::PopFront(_)) => match &inputs[..] {
+            [CoreValue::Span { len: 0, .. }] => Ok((inputs, 1)),
+            [CoreValue::Span { elements, offset, len }] => match elements.get(*offset) {
+                Some(value) => Ok((
+                    vec![
+                        CoreValue::Span {
+                            elements: elements.clone(),
+                            offset: offset + 1,
+                            len: len - 1,
+                        },
+                        value.clone(),
+                    ],
+                    0,
+                )),
+                None => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            },
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Span(SpanConcreteLibFunc::Len(_)) => match &inputs[..] {
+            [CoreValue::Span { len, .. }] => {
+                let len = CoreValue::Felt((*len).into());
+                Ok((vec![inputs[0].clone(), len], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Bitwise(BitwiseConcreteLibFunc { operator, .. }) => match &inputs[..] {
+            [CoreValue::Bitwise, CoreValue::Felt(lhs), CoreValue::Felt(rhs)] => {
+                // The builtin only supports values of up to 251 bits.
+                let bound = BigInt::one() << 251;
+                let (lhs, rhs) = (canonical(lhs), canonical(rhs));
+                if lhs >= bound || rhs >= bound {
+                    return Err(LibFuncSimulationError::BuiltinValueOutOfRange);
+                }
+                let result = match operator {
+                    BitwiseOperator::And => lhs & rhs,
+                    BitwiseOperator::Or => lhs | rhs,
+                    BitwiseOperator::Xor => lhs ^ rhs,
+                };
+                Ok((vec![CoreValue::Bitwise, CoreValue::Felt(reduce(result))], 0))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Pedersen(PedersenConcreteLibFunc::Hash(_)) => match &inputs[..] {
+            [CoreValue::Pedersen, CoreValue::Felt(lhs), CoreValue::Felt(rhs)] => {
+                Ok((vec![CoreValue::Pedersen, CoreValue::Felt(pedersen_hash(lhs, rhs)?)], 0))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Poseidon(PoseidonConcreteLibFunc::HadesPermutation(_)) => match &inputs[..] {
+            [CoreValue::Poseidon, CoreValue::Felt(s0), CoreValue::Felt(s1), CoreValue::Felt(s2)] => {
+                let [s0, s1, s2] = hades_permutation([s0, s1, s2])?;
+                Ok((
+                    vec![
+                        CoreValue::Poseidon,
+                        CoreValue::Felt(s0),
+                        CoreValue::Felt(s1),
+                        CoreValue::Felt(s2),
+                    ],
+                    0,
+                ))
+            }
+            [_, _, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Ec(libfunc) => simulate_ec_libfunc(libfunc, &inputs),
+        Syscall(SyscallConcreteLibFunc::SendMessageToL1(_)) => match &inputs[..] {
+            [CoreValue::System, CoreValue::Felt(to_address), CoreValue::Array(payload)] => {
+                (side_effects.send_message_to_l1)(L1Message {
+                    to_address: to_address.clone(),
+                    payload: as_felts(payload)?,
+                });
+                Ok((vec![CoreValue::System], 0))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Syscall(libfunc) => {
+            simulate_syscall_libfunc(libfunc, &inputs, side_effects.syscall_handler)
+        }
+        Secp256(libfunc) => {
+            simulate_secp256_libfunc(libfunc, &inputs, side_effects.syscall_handler)
+        }
+        Bool(libfunc) => simulate_bool_libfunc(libfunc, &inputs),
+        Uint8(libfunc) => simulate_uint_libfunc(Uint8Traits::BITS, libfunc, &inputs),
+        Uint16(libfunc) => simulate_uint_libfunc(Uint16Traits::BITS, libfunc, &inputs),
+        Uint32(libfunc) => simulate_uint_libfunc(Uint32Traits::BITS, libfunc, &inputs),
+        Uint64(libfunc) => simulate_uint_libfunc(Uint64Traits::BITS, libfunc, &inputs),
         Uint128(libfunc) => simulate_integer_libfunc(libfunc, &inputs),
-        Felt(libfunc) => simulate_felt_libfunc(libfunc, &inputs),
+        Uint256(libfunc) => simulate_uint256_libfunc(libfunc, &inputs),
+        Bytes31(libfunc) => simulate_bytes31_libfunc(libfunc, &inputs),
+        Cast(libfunc) => simulate_cast_libfunc(libfunc, &inputs),
+        Felt(libfunc) => simulate_felt_libfunc(libfunc, &inputs, field),
+        ConstAsImmediate(ConstAsImmediateConcreteLibFunc { value, .. }) => {
+            if inputs.is_empty() {
+                Ok((vec![const_core_value(value)], 0))
+            } else {
+                Err(LibFuncSimulationError::WrongNumberOfArgs)
+            }
+        }
+        Pow(libfunc) => simulate_pow_libfunc(libfunc, &inputs, field),
+        Circuit(libfunc) => simulate_circuit_libfunc(libfunc, &inputs),
+        StorageAddress(libfunc) => simulate_storage_address_libfunc(libfunc, &inputs),
+        ContractAddressTryFromFelt(_) => {
+            simulate_bounded_felt_try_from_felt(&inputs, CoreValue::ContractAddress)
+        }
+        ClassHashTryFromFelt(_) => {
+            simulate_bounded_felt_try_from_felt(&inputs, CoreValue::ClassHash)
+        }
+        JumpNotZero(_) => match &inputs[..] {
+            [value] => {
+                let is_zero = match value {
+                    CoreValue::Felt(value) => value.is_zero(),
+                    CoreValue::Uint8(value) => *value == 0,
+                    CoreValue::Uint16(value) => *value == 0,
+                    CoreValue::Uint32(value) => *value == 0,
+                    CoreValue::Uint64(value) => *value == 0,
+                    CoreValue::Uint128(value) => *value == 0,
+                    _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                };
+                if is_zero {
+                    // Zero - jumping to the failure branch.
+                    Ok((vec![], 0))
+                } else {
+                    // Non-zero - jumping to the success branch and providing a NonZero wrap to the
+                    // given value.
+                    Ok((vec![CoreValue::NonZero(Box::new(value.clone()))], 1))
+                }
+            }
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         UnwrapNonZero(_) => match &inputs[..] {
             [CoreValue::NonZero(value)] => Ok((vec![*value.clone()], 0)),
             [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
+        Unit(_) => match &inputs[..] {
+            [] => Ok((vec![CoreValue::Unit], 0)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Mem(Rename(_) | StoreTemp(_)) | CoreConcreteLibFunc::Box(_) => {
             if inputs.len() == 1 {
                 Ok((inputs, 0))
@@ -118,7 +531,11 @@ pub fn simulate<
                 Err(LibFuncSimulationError::WrongNumberOfArgs)
             }
         }
-        Mem(AlignTemps(_)) | Mem(FinalizeLocals(_)) | UnconditionalJump(_) | ApTracking(_) => {
+        Mem(AlignTemps(_))
+        | Mem(FinalizeLocals(_))
+        | UnconditionalJump(_)
+        | ApTracking(_)
+        | BranchAlign(_) => {
             if inputs.is_empty() {
                 Ok((inputs, 0))
             } else {
@@ -137,6 +554,20 @@ pub fn simulate<
                 Err(LibFuncSimulationError::WrongNumberOfArgs)
             }
         }
+        Panic(libfunc) => simulate_panic_libfunc(libfunc, &inputs),
+        Nullable(NullableConcreteLibFunc::Null(_)) => match &inputs[..] {
+            [] => Ok((vec![CoreValue::Null], 0)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Nullable(NullableConcreteLibFunc::FromBox(_)) => match &inputs[..] {
+            [value] => Ok((vec![value.clone()], 0)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Nullable(NullableConcreteLibFunc::Match(_)) => match &inputs[..] {
+            [CoreValue::Null] => Ok((vec![], 0)),
+            [value] => Ok((vec![value.clone()], 1)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Enum(EnumConcreteLibFunc::Init(EnumInitConcreteLibFunc { index, .. })) => {
             match &inputs[..] {
                 [input] => {
@@ -151,6 +582,10 @@ pub fn simulate<
             [_] => Err(LibFuncSimulationError::WrongArgType),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
+        CoreConcreteLibFunc::Option(libfunc) => simulate_option_libfunc(libfunc, &inputs),
+        CoreConcreteLibFunc::Result(libfunc) => {
+            simulate_result_libfunc(libfunc, &inputs, &simulate_function)
+        }
         Struct(StructConcreteLibFunc::Construct(_)) => Ok((vec![CoreValue::Struct(inputs)], 0)),
         Struct(StructConcreteLibFunc::Deconstruct(_)) => match &inputs[..] {
             [CoreValue::Struct(_)] => {
@@ -160,36 +595,151 @@ pub fn simulate<
             [_] => Err(LibFuncSimulationError::WrongArgType),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
-        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::New(_)) => {
-            if inputs.is_empty() {
-                Ok((vec![CoreValue::Dict(HashMap::new())], 0))
-            } else {
-                Err(LibFuncSimulationError::WrongNumberOfArgs)
+        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::New(_)) => match &inputs[..] {
+            [CoreValue::SegmentArena, _] => {
+                // The default value is of the dict's value type, and is returned for missing keys.
+                let default_value = Box::new(inputs.into_iter().nth(1).unwrap());
+                Ok((
+                    vec![
+                        CoreValue::SegmentArena,
+                        CoreValue::Dict {
+                            entries: HashMap::new(),
+                            accesses: vec![],
+                            default_value,
+                        },
+                    ],
+                    0,
+                ))
             }
-        }
-        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::Read(_)) => {
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::Read(_)) => match &inputs[..] {
+            [dict @ CoreValue::Dict { .. }, CoreValue::Felt(key)] => {
+                // Reading a key is an access as well, so it is recorded for squashing.
+                let value = dict_value(dict, key)?;
+                let dict = dict_access(dict, key, value.clone())?;
+                Ok((vec![dict, value], 0))
+            }
+            [_, _] => Err(LibFuncSimulationError::WrongArgType),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::Write(_)) => match &inputs[..] {
+            [dict @ CoreValue::Dict { .. }, CoreValue::Felt(key), value] => {
+                Ok((vec![dict_access(dict, key, value.clone())?], 0))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::EntryGet(_)) => {
             match &inputs[..] {
-                [CoreValue::Dict(map), CoreValue::Felt(key)] => {
-                    // Returns 0 as a defualt value.
-                    // TODO(Gil): correct this behaviour when dict behaviour is decided on key not
-                    // found.
-                    Ok((vec![map.get(key).map_or(CoreValue::Felt(0.into()), |x| x.clone())], 0))
+                [dict @ CoreValue::Dict { .. }, CoreValue::Felt(key)] => {
+                    // The access is recorded for squashing when the entry is finalized.
+                    let value = dict_value(dict, key)?;
+                    Ok((
+                        vec![
+                            CoreValue::DictEntry { dict: Box::new(dict.clone()), key: key.clone() },
+                            value,
+                        ],
+                        0,
+                    ))
                 }
                 [_, _] => Err(LibFuncSimulationError::WrongArgType),
                 _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
             }
         }
-        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::Write(_)) => match &inputs[..] {
-            [CoreValue::Dict(_), CoreValue::Felt(_), _] => {
-                let mut iter = inputs.into_iter();
-                let mut dict = extract_matches!(iter.next().unwrap(), CoreValue::Dict);
-                let key = extract_matches!(iter.next().unwrap(), CoreValue::Felt);
-                dict.insert(key, iter.next().unwrap());
-                Ok((vec![CoreValue::Dict(dict)], 0))
+        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::EntryFinalize(_)) => {
+            match &inputs[..] {
+                [CoreValue::DictEntry { dict, key }, value] => {
+                    Ok((vec![dict_access(dict, key, value.clone())?], 0))
+                }
+                // The dict itself is given, so the entry was already finalized, or never taken.
+                [CoreValue::Dict { .. }, _] => Err(LibFuncSimulationError::NoPendingDictEntry),
+                [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
             }
-            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
-            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        }
+        CoreConcreteLibFunc::DictFeltTo(DictFeltToConcreteLibFunc::Squash(_)) => {
+            match &inputs[..] {
+                [CoreValue::RangeCheck, CoreValue::SegmentArena, CoreValue::Dict { accesses, .. }] =>
+                {
+                    // Sorting by the canonical key, as keys are felts. The sort is stable, so the
+                    // accesses to each key stay in order.
+                    let mut accesses = accesses.clone();
+                    accesses.sort_by_cached_key(|(key, _, _)| canonical(key));
+                    let accesses = accesses
+                        .into_iter()
+                        .map(|(key, prev_value, new_value)| {
+                            CoreValue::Struct(vec![CoreValue::Felt(key), prev_value, new_value])
+                        })
+                        .collect();
+                    Ok((
+                        vec![
+                            CoreValue::RangeCheck,
+                            CoreValue::SegmentArena,
+                            CoreValue::Array(accesses),
+                        ],
+                        0,
+                    ))
+                }
+                [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+            }
+        }
+    }
+}
+
+/// Returns the current value of a key of a dict, or its default value if it was never written.
+/// Keys are felts, so they are looked up by their reduced value.
+fn dict_value(dict: &CoreValue, key: &BigInt) -> Result<CoreValue, LibFuncSimulationError> {
+    match dict {
+        CoreValue::Dict { entries, default_value, .. } => {
+            Ok(entries.get(&reduce(key.clone())).unwrap_or(default_value).clone())
+        }
+        _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+    }
+}
+
+/// Returns the dict after an access setting the value of `key` to `value`, recording the access
+/// for squashing.
+fn dict_access(
+    dict: &CoreValue,
+    key: &BigInt,
+    value: CoreValue,
+) -> Result<CoreValue, LibFuncSimulationError> {
+    let prev_value = dict_value(dict, key)?;
+    let CoreValue::Dict { entries, accesses, default_value } = dict else {
+        return Err(LibFuncSimulationError::MemoryLayoutMismatch);
+    };
+    let (mut entries, mut accesses) = (entries.clone(), accesses.clone());
+    let key = reduce(key.clone());
+    // Accesses keeping the value, such as reads, leave the entries unchanged.
+    if value != prev_value {
+        entries.insert(key.clone(), value.clone());
+    }
+    accesses.push((key, prev_value, value));
+    Ok(CoreValue::Dict { entries, accesses, default_value: default_value.clone() })
+}
+
+/// Simulates shifting a uint128, returning the outputs and the chosen branch.
+fn simulate_uint128_shift(
+    operator: ShiftOperator,
+    value: u128,
+    amount: u32,
+) -> (Vec<CoreValue>, usize) {
+    match operator {
+        ShiftOperator::Shl => match value.checked_shl(amount) {
+            // Shifting left overflows if any set bit is shifted out.
+            Some(result) if result >> amount == value => {
+                (vec![CoreValue::RangeCheck, CoreValue::Uint128(result)], 0)
+            }
+            None if value == 0 => (vec![CoreValue::RangeCheck, CoreValue::Uint128(0)], 0),
+            _ => (vec![CoreValue::RangeCheck], 1),
         },
+        ShiftOperator::Shr => (
+            vec![CoreValue::RangeCheck, CoreValue::Uint128(value.checked_shr(amount).unwrap_or(0))],
+            0,
+        ),
     }
 }
 
@@ -215,7 +765,7 @@ fn simulate_integer_libfunc(
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
         Uint128Concrete::ToFelt(_) => match inputs {
-            [CoreValue::RangeCheck, CoreValue::Uint128(value)] => {
+            [CoreValue::Uint128(value)] => {
                 Ok((vec![CoreValue::Felt(value.to_bigint().unwrap())], 0))
             }
             [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
@@ -315,6 +865,37 @@ fn simulate_integer_libfunc(
             [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
+        Uint128Concrete::Sqrt(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint128(value)] => {
+                let root = u64::try_from(BigInt::from(*value).sqrt()).unwrap();
+                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint64(root)], 0))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint128Concrete::Shift(Uint128ShiftConcreteLibFunc::Binary(
+            Uint128ShiftBinaryConcreteLibFunc { operator, .. },
+        )) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint128(value), CoreValue::Uint128(amount)] => {
+                // Amounts beyond `u32` shift out all bits, same as any amount of 128 or more.
+                Ok(simulate_uint128_shift(
+                    *operator,
+                    *value,
+                    u32::try_from(*amount).unwrap_or(u32::MAX),
+                ))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint128Concrete::Shift(Uint128ShiftConcreteLibFunc::Const(
+            Uint128ShiftWithConstConcreteLibFunc { operator, amount, .. },
+        )) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint128(value)] => {
+                Ok(simulate_uint128_shift(*operator, *value, *amount))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
         Uint128Concrete::JumpNotZero(_) => {
             match inputs {
                 [CoreValue::Uint128(value)] if *value == 0 => {
@@ -348,63 +929,736 @@ fn simulate_integer_libfunc(
             [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
+        Uint128Concrete::ArraySum(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Array(values)] => {
+                let mut sum: u128 = 0;
+                for value in values {
+                    let value = match value {
+                        CoreValue::Uint128(value) => *value,
+                        _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                    };
+                    sum = match sum.checked_add(value) {
+                        Some(sum) => sum,
+                        None => return Ok((vec![CoreValue::RangeCheck], 1)),
+                    };
+                }
+                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint128(sum)], 0))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint128Concrete::SafeDivMod(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint128(lhs), CoreValue::NonZero(non_zero)] => {
+                match **non_zero {
+                    // The hint computes both values at once, as `divmod(lhs, rhs)`.
+                    CoreValue::Uint128(rhs) if rhs != 0 => Ok((
+                        vec![
+                            CoreValue::RangeCheck,
+                            CoreValue::Uint128(lhs / rhs),
+                            CoreValue::Uint128(lhs % rhs),
+                        ],
+                        0,
+                    )),
+                    _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                }
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint128Concrete::WideMul(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint128(lhs), CoreValue::Uint128(rhs)] => {
+                let (high, low) = wide_mul(*lhs, *rhs);
+                Ok((
+                    vec![CoreValue::RangeCheck, CoreValue::Uint128(high), CoreValue::Uint128(low)],
+                    0,
+                ))
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
     }
 }
 
-/// Simulate felt library functions.
-fn simulate_felt_libfunc(
-    libfunc: &FeltConcrete,
+/// Simulates the behavior of the cast libfuncs.
+fn simulate_cast_libfunc(
+    libfunc: &CastConcreteLibFunc,
     inputs: &[CoreValue],
 ) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
     match libfunc {
-        FeltConcrete::Const(FeltConstConcreteLibFunc { c, .. }) => {
-            if inputs.is_empty() {
-                Ok((vec![CoreValue::Felt(c.to_bigint().unwrap())], 0))
-            } else {
-                Err(LibFuncSimulationError::WrongNumberOfArgs)
+        CastConcreteLibFunc::Upcast(CastConcreteLibFuncInfo { from, to, .. }) => match inputs {
+            [value] => {
+                // Only bounded integers can be upcast, and they always fit in the wider type.
+                let value = cast_value(*from, value)
+                    .and_then(|value| u128::try_from(value).ok())
+                    .ok_or(LibFuncSimulationError::MemoryLayoutMismatch)?;
+                Ok((vec![cast_result(*to, value).unwrap()], 0))
             }
-        }
-        FeltConcrete::Operation(FeltOperationConcreteLibFunc::Binary(
-            FeltBinaryOperationConcreteLibFunc { operator, .. },
-        )) => match (inputs, operator) {
-            (
-                [CoreValue::Felt(lhs), CoreValue::Felt(rhs)],
-                FeltOperator::Add | FeltOperator::Sub | FeltOperator::Mul,
-            ) => Ok((
-                vec![CoreValue::Felt(match operator {
-                    FeltOperator::Add => lhs + rhs,
-                    FeltOperator::Sub => lhs - rhs,
-                    FeltOperator::Mul => lhs * rhs,
-                    _ => unreachable!("Arm only handles these cases."),
-                })],
-                0,
-            )),
-            ([CoreValue::Felt(_lhs), CoreValue::NonZero(non_zero)], FeltOperator::Div) => {
-                if let CoreValue::Felt(_rhs) = *non_zero.clone() {
-                    todo!("Support felt_div operation.")
-                } else {
-                    Err(LibFuncSimulationError::MemoryLayoutMismatch)
-                }
-            }
-            ([_, _], _) => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        CastConcreteLibFunc::Downcast(CastConcreteLibFuncInfo { from, to, .. }) => match inputs {
+            [CoreValue::RangeCheck, value] => {
+                let value =
+                    cast_value(*from, value).ok_or(LibFuncSimulationError::MemoryLayoutMismatch)?;
+                Ok(match u128::try_from(value).ok().and_then(|value| cast_result(*to, value)) {
+                    Some(result) => (vec![CoreValue::RangeCheck, result], 0),
+                    None => (vec![CoreValue::RangeCheck], 1),
+                })
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Returns the value of a cast source of the given cast type.
+fn cast_value(ty: CastType, value: &CoreValue) -> Option<BigInt> {
+    match (ty, value) {
+        (CastType::Felt, CoreValue::Felt(value)) => Some(value.clone()),
+        (CastType::Uint(bits), value) => as_uint(bits, value).map(BigInt::from),
+        _ => None,
+    }
+}
+
+/// Returns the result of a cast into the given cast type, or `None` if the value does not fit.
+fn cast_result(ty: CastType, value: u128) -> Option<CoreValue> {
+    match ty {
+        CastType::Felt => Some(CoreValue::Felt(BigInt::from(value))),
+        CastType::Uint(bits) => uint_value(bits, value),
+    }
+}
+
+/// Returns the value of a bounded unsigned integer of the given bit width.
+fn as_uint(bits: u32, value: &CoreValue) -> Option<u128> {
+    match (bits, value) {
+        (8, CoreValue::Uint8(value)) => Some((*value).into()),
+        (16, CoreValue::Uint16(value)) => Some((*value).into()),
+        (32, CoreValue::Uint32(value)) => Some((*value).into()),
+        (64, CoreValue::Uint64(value)) => Some((*value).into()),
+        (128, CoreValue::Uint128(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Returns a bounded unsigned integer of the given bit width, or `None` if the value does not fit.
+fn uint_value(bits: u32, value: u128) -> Option<CoreValue> {
+    match bits {
+        8 => u8::try_from(value).ok().map(CoreValue::Uint8),
+        16 => u16::try_from(value).ok().map(CoreValue::Uint16),
+        32 => u32::try_from(value).ok().map(CoreValue::Uint32),
+        64 => u64::try_from(value).ok().map(CoreValue::Uint64),
+        128 => Some(CoreValue::Uint128(value)),
+        _ => None,
+    }
+}
+
+/// Simulate bounded unsigned integer library functions, for an integer type of the given bit
+/// width.
+fn simulate_uint_libfunc(
+    bits: u32,
+    libfunc: &UintConcrete,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match libfunc {
+        UintConcrete::Const(UintConstConcreteLibFunc { c, .. }) => {
+            if inputs.is_empty() {
+                Ok((vec![uint_value(bits, (*c).into()).unwrap()], 0))
+            } else {
+                Err(LibFuncSimulationError::WrongNumberOfArgs)
+            }
+        }
+        UintConcrete::Operation(UintOperationConcreteLibFunc { operator, .. }) => match inputs {
+            [CoreValue::RangeCheck, lhs, rhs] => {
+                let (lhs, rhs) = match (as_uint(bits, lhs), as_uint(bits, rhs)) {
+                    (Some(lhs), Some(rhs)) => (lhs, rhs),
+                    _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                };
+                let result = match operator {
+                    IntOperator::Add => lhs.checked_add(rhs),
+                    IntOperator::Sub => lhs.checked_sub(rhs),
+                    _ => unreachable!("Only addition and subtraction are supported."),
+                };
+                Ok(match result.and_then(|result| uint_value(bits, result)) {
+                    Some(result) => (vec![CoreValue::RangeCheck, result], 0),
+                    None => (vec![CoreValue::RangeCheck], 1),
+                })
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        UintConcrete::LessThan(_) | UintConcrete::LessThanOrEqual(_) => match inputs {
+            [CoreValue::RangeCheck, a, b] => match (as_uint(bits, a), as_uint(bits, b)) {
+                // "False" branch (branch 0) is the case the comparison does not hold.
+                // "True" branch (branch 1) is the case the comparison holds.
+                (Some(a), Some(b)) => {
+                    let holds = match libfunc {
+                        UintConcrete::LessThan(_) => a < b,
+                        _ => a <= b,
+                    };
+                    Ok((vec![CoreValue::RangeCheck], usize::from(holds)))
+                }
+                _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            },
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Returns the low and high uint128 halves of a u256 value.
+fn as_uint256(value: &CoreValue) -> Option<(u128, u128)> {
+    match value {
+        CoreValue::Struct(halves) => match &halves[..] {
+            [CoreValue::Uint128(low), CoreValue::Uint128(high)] => Some((*low, *high)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns a u256 value given its low and high uint128 halves.
+fn uint256_value(low: u128, high: u128) -> CoreValue {
+    CoreValue::Struct(vec![CoreValue::Uint128(low), CoreValue::Uint128(high)])
+}
+
+/// Returns the high and low uint128 halves of the full product of two uint128s.
+fn wide_mul(lhs: u128, rhs: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (lhs_low, lhs_high) = (lhs & MASK, lhs >> 64);
+    let (rhs_low, rhs_high) = (rhs & MASK, rhs >> 64);
+    let low_low = lhs_low * rhs_low;
+    // The sum of the middle products may exceed 128 bits, so its carry is kept aside.
+    let (middle, middle_carry) = (lhs_low * rhs_high).overflowing_add(lhs_high * rhs_low);
+    let (low, low_carry) = low_low.overflowing_add(middle << 64);
+    let high = lhs_high * rhs_high
+        + (middle >> 64)
+        + (u128::from(middle_carry) << 64)
+        + u128::from(low_carry);
+    (high, low)
+}
+
+/// Applies a u256 operator on values given as their low and high uint128 halves, returning `None`
+/// on overflow.
+fn apply_uint256_operator(
+    operator: Uint256Operator,
+    (lhs_low, lhs_high): (u128, u128),
+    (rhs_low, rhs_high): (u128, u128),
+) -> Option<(u128, u128)> {
+    match operator {
+        Uint256Operator::Add => {
+            let (low, carry) = lhs_low.overflowing_add(rhs_low);
+            let high = lhs_high.checked_add(rhs_high)?.checked_add(carry.into())?;
+            Some((low, high))
+        }
+        Uint256Operator::Sub => {
+            let (low, borrow) = lhs_low.overflowing_sub(rhs_low);
+            let high = lhs_high.checked_sub(rhs_high)?.checked_sub(borrow.into())?;
+            Some((low, high))
+        }
+        Uint256Operator::Mul => {
+            if lhs_high != 0 && rhs_high != 0 {
+                return None;
+            }
+            let (low_high, low) = wide_mul(lhs_low, rhs_low);
+            let (lhs_cross_high, lhs_cross) = wide_mul(lhs_low, rhs_high);
+            let (rhs_cross_high, rhs_cross) = wide_mul(lhs_high, rhs_low);
+            if lhs_cross_high != 0 || rhs_cross_high != 0 {
+                return None;
+            }
+            let high = low_high.checked_add(lhs_cross)?.checked_add(rhs_cross)?;
+            Some((low, high))
+        }
+    }
+}
+
+/// Simulate u256 library functions.
+fn simulate_uint256_libfunc(
+    libfunc: &Uint256Concrete,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match libfunc {
+        Uint256Concrete::Operation(Uint256OperationConcreteLibFunc { operator, .. }) => {
+            match inputs {
+                [CoreValue::RangeCheck, lhs, rhs] => {
+                    let (lhs, rhs) = match (as_uint256(lhs), as_uint256(rhs)) {
+                        (Some(lhs), Some(rhs)) => (lhs, rhs),
+                        _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                    };
+                    Ok(match apply_uint256_operator(*operator, lhs, rhs) {
+                        Some((low, high)) => {
+                            (vec![CoreValue::RangeCheck, uint256_value(low, high)], 0)
+                        }
+                        None => (vec![CoreValue::RangeCheck], 1),
+                    })
+                }
+                [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+            }
+        }
+        Uint256Concrete::Construct(_) => match inputs {
+            [CoreValue::Uint128(low), CoreValue::Uint128(high)] => {
+                Ok((vec![uint256_value(*low, *high)], 0))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint256Concrete::Deconstruct(_) => match inputs {
+            [value] => {
+                let (low, high) =
+                    as_uint256(value).ok_or(LibFuncSimulationError::MemoryLayoutMismatch)?;
+                Ok((vec![CoreValue::Uint128(low), CoreValue::Uint128(high)], 0))
+            }
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Returns the value representation of a constant.
+fn const_core_value(value: &ConstValue) -> CoreValue {
+    match value {
+        ConstValue::Felt(value) => CoreValue::Felt(reduce(value.clone())),
+        ConstValue::Struct(members) => {
+            CoreValue::Struct(members.iter().map(const_core_value).collect())
+        }
+        ConstValue::Enum { index, value } => {
+            CoreValue::Enum { value: Box::new(const_core_value(value)), index: *index }
+        }
+    }
+}
+
+/// Returns a bytes31 holding the given value, or `None` if the value does not fit in 31 bytes.
+fn bytes31_value(value: BigInt) -> Option<CoreValue> {
+    if value >= BigInt::zero() && value < BigInt::one() << (8 * BYTES31_MAX_LEN) {
+        Some(CoreValue::Bytes31(value))
+    } else {
+        None
+    }
+}
+
+/// Simulate bytes31 library functions.
+fn simulate_bytes31_libfunc(
+    libfunc: &Bytes31ConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    // Returns the outputs of the first branch if the result is valid, and of the second otherwise.
+    let range_checked = |result: Option<Vec<CoreValue>>| {
+        Ok(match result {
+            Some(outputs) => ([vec![CoreValue::RangeCheck], outputs].concat(), 0),
+            None => (vec![CoreValue::RangeCheck], 1),
+        })
+    };
+    match libfunc {
+        Bytes31ConcreteLibFunc::TryFromFelt(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Felt(value)] => {
+                range_checked(bytes31_value(value.clone()).map(|value| vec![value]))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Bytes31ConcreteLibFunc::ToFelt(_) => match inputs {
+            [CoreValue::Bytes31(value)] => Ok((vec![CoreValue::Felt(value.clone())], 0)),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Bytes31ConcreteLibFunc::Concat(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Bytes31(a), CoreValue::Bytes31(b), CoreValue::Uint8(b_len)] =>
+            {
+                let shift = 8 * usize::from(*b_len);
+                range_checked(if *b < BigInt::one() << shift {
+                    bytes31_value((a << shift) + b).map(|value| vec![value])
+                } else {
+                    None
+                })
+            }
+            [_, _, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        Bytes31ConcreteLibFunc::Split(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Bytes31(value), CoreValue::Uint8(n)] => {
+                range_checked(if u32::from(*n) <= BYTES31_MAX_LEN {
+                    let shift = 8 * usize::from(*n);
+                    let low = value & ((BigInt::one() << shift) - 1);
+                    Some(vec![CoreValue::Bytes31(value >> shift), CoreValue::Bytes31(low)])
+                } else {
+                    None
+                })
+            }
+            [_, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Simulate panic library functions.
+fn simulate_panic_libfunc(
+    libfunc: &PanicConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match libfunc {
+        PanicConcreteLibFunc::Panic(_) => match inputs {
+            [CoreValue::Array(data)] => Err(LibFuncSimulationError::Panicked(data.clone())),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        PanicConcreteLibFunc::Wrap(_) => match inputs {
+            [value] => Ok((vec![CoreValue::Enum { value: Box::new(value.clone()), index: 0 }], 0)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        // The first variant holds the value and the second the panic data, so the variant index
+        // is also the chosen branch.
+        PanicConcreteLibFunc::Match(_) => match inputs {
+            [CoreValue::Enum { value, index: index @ (0 | 1) }] => {
+                Ok((vec![*value.clone()], *index))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Simulate boolean library functions.
+fn simulate_bool_libfunc(
+    libfunc: &BoolConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match libfunc {
+        BoolConcreteLibFunc::Operation(BoolOperationConcreteLibFunc { operator, .. }) => {
+            match inputs {
+                [CoreValue::Bool(lhs), CoreValue::Bool(rhs)] => {
+                    let result = match operator {
+                        BoolOperator::And => lhs & rhs,
+                        BoolOperator::Or => lhs | rhs,
+                        BoolOperator::Xor => lhs ^ rhs,
+                    };
+                    Ok((vec![CoreValue::Bool(result)], 0))
+                }
+                [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+            }
+        }
+        BoolConcreteLibFunc::Not(_) => match inputs {
+            [CoreValue::Bool(value)] => Ok((vec![CoreValue::Bool(!value)], 0)),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        BoolConcreteLibFunc::ToFelt(_) => match inputs {
+            [CoreValue::Bool(value)] => Ok((vec![CoreValue::Felt(BigInt::from(*value as u8))], 0)),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        BoolConcreteLibFunc::FromFelt(_) => match inputs {
+            [CoreValue::Felt(value)] => Ok(if value.is_zero() {
+                (vec![CoreValue::Bool(false)], 0)
+            } else if value.is_one() {
+                (vec![CoreValue::Bool(true)], 0)
+            } else {
+                (vec![], 1)
+            }),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Simulate syscall library functions.
+fn simulate_syscall_libfunc(
+    libfunc: &SyscallConcreteLibFunc,
+    inputs: &[CoreValue],
+    syscall_handler: &dyn SyscallHandler,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    let result = match (libfunc, inputs) {
+        (
+            SyscallConcreteLibFunc::StorageRead(_),
+            [CoreValue::System, CoreValue::StorageAddress(address)],
+        ) => {
+            syscall_handler.storage_read(address).map(|value| vec![CoreValue::Felt(reduce(value))])
+        }
+        (
+            SyscallConcreteLibFunc::StorageWrite(_),
+            [CoreValue::System, CoreValue::StorageAddress(address), CoreValue::Felt(value)],
+        ) => syscall_handler.storage_write(address, value).map(|()| vec![]),
+        (
+            SyscallConcreteLibFunc::CallContract(_),
+            [CoreValue::System, CoreValue::Felt(address), CoreValue::Felt(selector), CoreValue::Array(calldata)],
+        ) => syscall_handler
+            .call_contract(address, selector, &as_felts(calldata)?)
+            .map(|retdata| vec![CoreValue::Array(felt_values(retdata))]),
+        (
+            SyscallConcreteLibFunc::Deploy(_),
+            [CoreValue::System, CoreValue::ClassHash(class_hash), CoreValue::Felt(salt), CoreValue::Array(calldata), CoreValue::Bool(deploy_from_zero)],
+        ) => syscall_handler.deploy(class_hash, salt, &as_felts(calldata)?, *deploy_from_zero).map(
+            |(address, retdata)| {
+                vec![CoreValue::ContractAddress(address), CoreValue::Array(felt_values(retdata))]
+            },
+        ),
+        (
+            SyscallConcreteLibFunc::LibraryCall(_),
+            [CoreValue::System, CoreValue::ClassHash(class_hash), CoreValue::Felt(selector), CoreValue::Array(calldata)],
+        ) => syscall_handler
+            .library_call(class_hash, selector, &as_felts(calldata)?)
+            .map(|retdata| vec![CoreValue::Array(felt_values(retdata))]),
+        (
+            SyscallConcreteLibFunc::ReplaceClass(_),
+            [CoreValue::System, CoreValue::ClassHash(class_hash)],
+        ) => syscall_handler.replace_class(class_hash).map(|()| vec![]),
+        (SyscallConcreteLibFunc::Keccak(_), [CoreValue::System, CoreValue::Array(input)]) => {
+            let words = input
+                .iter()
+                .map(|word| match word {
+                    CoreValue::Uint64(word) => Ok(*word),
+                    _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if words.len() % KECCAK_RATE_IN_WORDS == 0 {
+                let (low, high) = keccak_u64_words(&words)?;
+                Ok(vec![uint256_value(low, high)])
+            } else {
+                Err(vec![BigInt::from_bytes_be(Sign::Plus, b"Invalid input length")])
+            }
+        }
+        (SyscallConcreteLibFunc::GetExecutionInfo(_), [CoreValue::System]) => {
+            syscall_handler.get_execution_info().map(|info| {
+                // The box is represented by the boxed struct itself.
+                vec![CoreValue::Struct(vec![
+                    CoreValue::Uint64(info.block_number),
+                    CoreValue::Uint64(info.block_timestamp),
+                    CoreValue::ContractAddress(info.sequencer_address),
+                    CoreValue::Felt(reduce(info.transaction_hash)),
+                    CoreValue::Felt(reduce(info.chain_id)),
+                    CoreValue::ContractAddress(info.caller_address),
+                    CoreValue::ContractAddress(info.contract_address),
+                    CoreValue::Felt(reduce(info.entry_point_selector)),
+                ])]
+            })
+        }
+        (
+            SyscallConcreteLibFunc::GetBlockHash(_),
+            [CoreValue::System, CoreValue::Uint64(number)],
+        ) => {
+            syscall_handler.get_block_hash(*number).map(|hash| vec![CoreValue::Felt(reduce(hash))])
+        }
+        (SyscallConcreteLibFunc::GetExecutionInfo(_), [_])
+        | (SyscallConcreteLibFunc::StorageRead(_), [_, _])
+        | (SyscallConcreteLibFunc::ReplaceClass(_), [_, _])
+        | (SyscallConcreteLibFunc::GetBlockHash(_), [_, _])
+        | (SyscallConcreteLibFunc::Keccak(_), [_, _])
+        | (SyscallConcreteLibFunc::StorageWrite(_), [_, _, _])
+        | (SyscallConcreteLibFunc::CallContract(_), [_, _, _, _])
+        | (SyscallConcreteLibFunc::LibraryCall(_), [_, _, _, _])
+        | (SyscallConcreteLibFunc::Deploy(_), [_, _, _, _, _]) => {
+            return Err(LibFuncSimulationError::MemoryLayoutMismatch);
+        }
+        _ => return Err(LibFuncSimulationError::WrongNumberOfArgs),
+    };
+    Ok(match result {
+        Ok(outputs) => ([CoreValue::System].into_iter().chain(outputs).collect(), 0),
+        Err(revert_reason) => (
+            vec![
+                CoreValue::System,
+                CoreValue::Array(revert_reason.into_iter().map(CoreValue::Felt).collect()),
+            ],
+            1,
+        ),
+    })
+}
+
+/// Simulate the secp256 libfuncs through the syscall handler, with the same branches as the other
+/// syscalls.
+fn simulate_secp256_libfunc(
+    libfunc: &Secp256ConcreteLibFunc,
+    inputs: &[CoreValue],
+    syscall_handler: &dyn SyscallHandler,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    let as_point = |value: &CoreValue| match value {
+        CoreValue::Secp256Point(x, y) => Ok((x.clone(), y.clone())),
+        _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+    };
+    let as_u256 = |value: &CoreValue| {
+        as_uint256(value)
+            .map(|(low, high)| (BigInt::from(high) << 128) + low)
+            .ok_or(LibFuncSimulationError::MemoryLayoutMismatch)
+    };
+    let curve = libfunc.curve;
+    let result = match (libfunc.operation, inputs) {
+        (Secp256Operation::New, [CoreValue::System, x, y]) => {
+            syscall_handler.secp256_new(curve, as_u256(x)?, as_u256(y)?)
+        }
+        (Secp256Operation::Add, [CoreValue::System, lhs, rhs]) => {
+            syscall_handler.secp256_add(curve, &as_point(lhs)?, &as_point(rhs)?)
+        }
+        (Secp256Operation::Mul, [CoreValue::System, point, scalar]) => {
+            syscall_handler.secp256_mul(curve, &as_point(point)?, &as_u256(scalar)?)
+        }
+        (_, [_, _, _]) => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+        _ => return Err(LibFuncSimulationError::WrongNumberOfArgs),
+    };
+    Ok(match result {
+        Ok((x, y)) => (vec![CoreValue::System, CoreValue::Secp256Point(x, y)], 0),
+        Err(revert_reason) => (
+            vec![
+                CoreValue::System,
+                CoreValue::Array(revert_reason.into_iter().map(CoreValue::Felt).collect()),
+            ],
+            1,
+        ),
+    })
+}
+
+/// Simulate elliptic curve library functions.
+fn simulate_ec_libfunc(
+    libfunc: &EcConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match libfunc {
+        EcConcreteLibFunc::TryNew(_) => match inputs {
+            [CoreValue::Felt(x), CoreValue::Felt(y)] => {
+                let (x, y) = (canonical(x), canonical(y));
+                Ok(if is_on_curve(&x, &y) {
+                    (vec![CoreValue::EcPoint(x, y)], 0)
+                } else {
+                    (vec![], 1)
+                })
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        EcConcreteLibFunc::StateInit(_) => match inputs {
+            [] => Ok((vec![CoreValue::EcState(None)], 0)),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        EcConcreteLibFunc::StateAddMul(_) => match inputs {
+            [CoreValue::EcOp, CoreValue::EcState(state), CoreValue::Felt(scalar), CoreValue::EcPoint(x, y)] =>
+            {
+                let product = ec_mul(&Some((x.clone(), y.clone())), scalar);
+                Ok((vec![CoreValue::EcOp, CoreValue::EcState(ec_add(state, &product))], 0))
+            }
+            [_, _, _, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        EcConcreteLibFunc::StateFinalize(_) => match inputs {
+            [CoreValue::EcState(Some((x, y)))] => {
+                Ok((vec![CoreValue::EcPoint(x.clone(), y.clone())], 0))
+            }
+            [CoreValue::EcState(None)] => Ok((vec![], 1)),
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+    }
+}
+
+/// Simulate felt library functions.
+fn simulate_felt_libfunc<Field: FieldBackend>(
+    libfunc: &FeltConcrete,
+    inputs: &[CoreValue],
+    field: &Field,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match libfunc {
+        FeltConcrete::Const(FeltConstConcreteLibFunc { c, .. }) => {
+            if inputs.is_empty() {
+                // Reduced to the representative closest to zero, as the results of the felt
+                // operations, so that equal felts are held by equal values.
+                Ok((vec![CoreValue::Felt(reduce(c.clone()))], 0))
+            } else {
+                Err(LibFuncSimulationError::WrongNumberOfArgs)
+            }
+        }
+        FeltConcrete::Operation(FeltOperationConcreteLibFunc::Binary(
+            FeltBinaryOperationConcreteLibFunc { operator, .. },
+        )) => match (inputs, operator) {
+            (
+                [CoreValue::Felt(lhs), CoreValue::Felt(rhs)],
+                FeltOperator::Add | FeltOperator::Sub | FeltOperator::Mul,
+            ) => Ok((vec![CoreValue::Felt(apply_felt_operator(field, operator, lhs, rhs)?)], 0)),
+            ([CoreValue::Felt(lhs), CoreValue::NonZero(non_zero)], FeltOperator::Div) => {
+                if let CoreValue::Felt(rhs) = &**non_zero {
+                    Ok((vec![CoreValue::Felt(apply_felt_operator(field, operator, lhs, rhs)?)], 0))
+                } else {
+                    Err(LibFuncSimulationError::MemoryLayoutMismatch)
+                }
+            }
+            ([_, _], _) => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        FeltConcrete::Inverse(_) => match inputs {
+            [CoreValue::NonZero(non_zero)] => match &**non_zero {
+                CoreValue::Felt(value) => {
+                    let inverse = field
+                        .inv(&field.from_bigint(value))
+                        .ok_or(LibFuncSimulationError::FeltDivisionByZero)?;
+                    Ok((
+                        vec![CoreValue::NonZero(Box::new(CoreValue::Felt(
+                            field.to_bigint(&inverse),
+                        )))],
+                        0,
+                    ))
+                }
+                _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            },
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
         FeltConcrete::Operation(FeltOperationConcreteLibFunc::Const(
             FeltOperationWithConstConcreteLibFunc { operator, c, .. },
         )) => match inputs {
-            [CoreValue::Felt(value)] => Ok((
-                vec![CoreValue::Felt(match operator {
-                    FeltOperator::Add => value + c.clone(),
-                    FeltOperator::Sub => value - c.clone(),
-                    FeltOperator::Mul => value * c.clone(),
-                    FeltOperator::Div => todo!("Support full felt operations."),
-                })],
-                0,
-            )),
+            [CoreValue::Felt(value)] => {
+                Ok((vec![CoreValue::Felt(apply_felt_operator(field, operator, value, c)?)], 0))
+            }
             [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
         },
-        FeltConcrete::JumpNotZero(_) => {
+        FeltConcrete::AssertLtConst(FeltAssertLtConstConcreteLibFunc { bound, .. }) => match inputs
+        {
+            [CoreValue::RangeCheck, CoreValue::Felt(value)] => {
+                Ok((vec![CoreValue::RangeCheck], if &canonical(value) < bound { 0 } else { 1 }))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        FeltConcrete::Popcount(FeltPopcountConcreteLibFunc { width, .. }) => match inputs {
+            [CoreValue::Felt(value)] => {
+                let mask: BigInt = (BigInt::one() << *width) - 1;
+                let (_, bytes) = (canonical(value) & mask).to_bytes_le();
+                let count: u32 = bytes.iter().map(|byte| byte.count_ones()).sum();
+                Ok((vec![CoreValue::Felt(BigInt::from(count))], 0))
+            }
+            [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        FeltConcrete::Pack(FeltPackConcreteLibFunc { bits, .. }) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Array(values)] => {
+                let prime = felt_prime();
+                let bound = BigInt::one() << *bits;
+                let mut packed = BigInt::zero();
+                // Packing from the last element, so that the first element is at the lowest bits.
+                for value in values.iter().rev() {
+                    let value = match value {
+                        CoreValue::Felt(value) => canonical(value),
+                        _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+                    };
+                    if value >= bound {
+                        return Ok((vec![CoreValue::RangeCheck], 1));
+                    }
+                    packed = (packed << *bits) + value;
+                }
+                if packed < prime {
+                    Ok((vec![CoreValue::RangeCheck, CoreValue::Felt(reduce(packed))], 0))
+                } else {
+                    Ok((vec![CoreValue::RangeCheck], 1))
+                }
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        FeltConcrete::Clamp(FeltClampConcreteLibFunc { bit_width, .. }) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Felt(value)] => {
+                let max: BigInt = (BigInt::one() << *bit_width) - 1;
+                // The clamped value fits in the target type by construction.
+                let clamped = u128::try_from(canonical(value).min(max)).unwrap();
+                Ok((vec![CoreValue::RangeCheck, uint_value(*bit_width, clamped).unwrap()], 0))
+            }
+            [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+        },
+        FeltConcrete::JumpNotZero(_) | FeltConcrete::IsZero(_) => {
             match inputs {
                 [CoreValue::Felt(value)] if value.is_zero() => {
                     // Zero - jumping to the failure branch.
@@ -421,3 +1675,262 @@ fn simulate_felt_libfunc(
         }
     }
 }
+
+/// Extracts the felt values of the elements of a felt array.
+fn as_felts(values: &[CoreValue]) -> Result<Vec<BigInt>, LibFuncSimulationError> {
+    values
+        .iter()
+        .map(|value| match value {
+            CoreValue::Felt(value) => Ok(value.clone()),
+            _ => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+        })
+        .collect()
+}
+
+/// Returns the reduced felt values of the given felts, such as a syscall's return data.
+fn felt_values(values: Vec<BigInt>) -> Vec<CoreValue> {
+    values.into_iter().map(|value| CoreValue::Felt(reduce(value))).collect()
+}
+
+/// Applies a felt operator on the given values, using the field backend.
+fn apply_felt_operator<Field: FieldBackend>(
+    field: &Field,
+    operator: &FeltOperator,
+    lhs: &BigInt,
+    rhs: &BigInt,
+) -> Result<BigInt, LibFuncSimulationError> {
+    let lhs = field.from_bigint(lhs);
+    let rhs = field.from_bigint(rhs);
+    let result = match operator {
+        FeltOperator::Add => field.add(&lhs, &rhs),
+        FeltOperator::Sub => field.sub(&lhs, &rhs),
+        FeltOperator::Mul => field.mul(&lhs, &rhs),
+        FeltOperator::Div => {
+            field.mul(&lhs, &field.inv(&rhs).ok_or(LibFuncSimulationError::FeltDivisionByZero)?)
+        }
+    };
+    Ok(field.to_bigint(&result))
+}
+
+/// Simulate the pow library function, by repeated squaring over the exponent's bits.
+fn simulate_pow_libfunc<Field: FieldBackend>(
+    libfunc: &PowConcreteLibFunc,
+    inputs: &[CoreValue],
+    field: &Field,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    let PowConcreteLibFunc { base, exponent, .. } = libfunc;
+    let mut exponent_bits = (0..exponent.bits()).rev().map(|i| exponent.bit(i));
+    match (base, inputs) {
+        (PowBase::Felt, [CoreValue::Felt(value)]) => {
+            let value = field.from_bigint(value);
+            let result = exponent_bits.fold(field.from_bigint(&BigInt::one()), |acc, bit| {
+                let acc = field.mul(&acc, &acc);
+                if bit {
+                    field.mul(&acc, &value)
+                } else {
+                    acc
+                }
+            });
+            Ok((vec![CoreValue::Felt(field.to_bigint(&result))], 0))
+        }
+        (PowBase::Uint128, [CoreValue::RangeCheck, CoreValue::Uint128(value)]) => {
+            let result = exponent_bits.try_fold(1u128, |acc, bit| {
+                let acc = acc.checked_mul(acc)?;
+                if bit {
+                    acc.checked_mul(*value)
+                } else {
+                    Some(acc)
+                }
+            });
+            Ok(match result {
+                Some(result) => (vec![CoreValue::RangeCheck, CoreValue::Uint128(result)], 0),
+                None => (vec![CoreValue::RangeCheck], 1),
+            })
+        }
+        (PowBase::Felt, [_]) | (PowBase::Uint128, [_, _]) => {
+            Err(LibFuncSimulationError::MemoryLayoutMismatch)
+        }
+        _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+    }
+}
+
+/// Simulate storage address related library functions.
+fn simulate_storage_address_libfunc(
+    libfunc: &StorageAddressConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match (libfunc, inputs) {
+        (
+            StorageAddressConcreteLibFunc::BaseFromFelt(_),
+            [CoreValue::RangeCheck, CoreValue::Felt(value)],
+        ) => {
+            let base = canonical(value) % storage_base_address_bound();
+            Ok((vec![CoreValue::RangeCheck, CoreValue::StorageBaseAddress(base)], 0))
+        }
+        (
+            StorageAddressConcreteLibFunc::FromBaseAndOffset(
+                StorageAddressFromBaseAndOffsetConcreteLibFunc { offset, .. },
+            ),
+            _,
+        ) => {
+            let (base, offset) = match (offset, inputs) {
+                (None, [CoreValue::StorageBaseAddress(base), CoreValue::Uint8(offset)])
+                | (Some(offset), [CoreValue::StorageBaseAddress(base)]) => (base, offset),
+                (None, [_, _]) | (Some(_), [_]) => {
+                    return Err(LibFuncSimulationError::MemoryLayoutMismatch);
+                }
+                _ => return Err(LibFuncSimulationError::WrongNumberOfArgs),
+            };
+            Ok((vec![CoreValue::StorageAddress(base + *offset)], 0))
+        }
+        (StorageAddressConcreteLibFunc::BaseFromFelt(_), [_, _]) => {
+            Err(LibFuncSimulationError::MemoryLayoutMismatch)
+        }
+        (StorageAddressConcreteLibFunc::BaseFromFelt(_), _) => {
+            Err(LibFuncSimulationError::WrongNumberOfArgs)
+        }
+    }
+}
+
+/// Simulate circuit related library functions.
+fn simulate_circuit_libfunc(
+    libfunc: &CircuitConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match (libfunc, inputs) {
+        (CircuitConcreteLibFunc::InitCircuitData(_), []) => {
+            Ok((vec![CoreValue::CircuitInputs(vec![])], 0))
+        }
+        (
+            CircuitConcreteLibFunc::AddInput(AddCircuitInputConcreteLibFunc { n_inputs, .. }),
+            [CoreValue::CircuitInputs(values), CoreValue::Felt(value)],
+        ) => {
+            if values.len() >= *n_inputs {
+                return Err(LibFuncSimulationError::MemoryLayoutMismatch);
+            }
+            let mut values = values.clone();
+            values.push(value.clone());
+            // Branches to the second branch once the last input was provided.
+            let branch = if values.len() == *n_inputs { 1 } else { 0 };
+            Ok((vec![CoreValue::CircuitInputs(values)], branch))
+        }
+        (
+            CircuitConcreteLibFunc::Eval(EvalCircuitConcreteLibFunc { circuit, .. }),
+            [CoreValue::AddMod, CoreValue::MulMod, CoreValue::CircuitInputs(values), CoreValue::NonZero(modulus)],
+        ) => {
+            let modulus = match modulus.as_ref() {
+                CoreValue::Felt(modulus) => canonical(modulus),
+                _ => return Err(LibFuncSimulationError::MemoryLayoutMismatch),
+            };
+            if values.len() != circuit.n_inputs || modulus.is_zero() {
+                return Err(LibFuncSimulationError::MemoryLayoutMismatch);
+            }
+            Ok(match eval_circuit(circuit, values, &modulus) {
+                Some(outputs) => (
+                    vec![CoreValue::AddMod, CoreValue::MulMod, CoreValue::CircuitOutputs(outputs)],
+                    0,
+                ),
+                None => (vec![CoreValue::AddMod, CoreValue::MulMod], 1),
+            })
+        }
+        (
+            CircuitConcreteLibFunc::GetOutput(GetCircuitOutputConcreteLibFunc { index, .. }),
+            [CoreValue::CircuitOutputs(outputs)],
+        ) => match outputs.get(*index) {
+            Some(output) => Ok((vec![CoreValue::Felt(reduce(output.clone()))], 0)),
+            None => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+        },
+        (CircuitConcreteLibFunc::AddInput(_), [_, _])
+        | (CircuitConcreteLibFunc::Eval(_), [_, _, _, _])
+        | (CircuitConcreteLibFunc::GetOutput(_), [_]) => {
+            Err(LibFuncSimulationError::MemoryLayoutMismatch)
+        }
+        _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+    }
+}
+
+/// Simulate option helper library functions.
+fn simulate_option_libfunc(
+    libfunc: &OptionConcreteLibFunc,
+    inputs: &[CoreValue],
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    let (value, index) = match (libfunc, inputs) {
+        (OptionConcreteLibFunc::Some(_), [value]) => (value.clone(), OPTION_SOME_VARIANT_INDEX),
+        (OptionConcreteLibFunc::None(_), []) => {
+            (CoreValue::Struct(vec![]), OPTION_NONE_VARIANT_INDEX)
+        }
+        _ => return Err(LibFuncSimulationError::WrongNumberOfArgs),
+    };
+    Ok((vec![CoreValue::Enum { value: Box::new(value), index }], 0))
+}
+
+/// Simulate result helper library functions.
+fn simulate_result_libfunc(
+    libfunc: &ResultConcreteLibFunc,
+    inputs: &[CoreValue],
+    simulate_function: impl Fn(
+        &FunctionId,
+        Vec<CoreValue>,
+    ) -> Result<Vec<CoreValue>, LibFuncSimulationError>,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    let index = match libfunc {
+        ResultConcreteLibFunc::Ok(_) => RESULT_OK_VARIANT_INDEX,
+        ResultConcreteLibFunc::Err(_) => RESULT_ERR_VARIANT_INDEX,
+        ResultConcreteLibFunc::MapErr(ResultMapErrConcreteLibFunc { function, .. }) => {
+            return simulate_result_map_err(&function.id, inputs, simulate_function);
+        }
+    };
+    match inputs {
+        [value] => Ok((vec![CoreValue::Enum { value: Box::new(value.clone()), index }], 0)),
+        _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+    }
+}
+
+/// Simulates mapping the error of a result by calling the given function on it. An `Ok` value is
+/// kept as is.
+fn simulate_result_map_err(
+    function_id: &FunctionId,
+    inputs: &[CoreValue],
+    simulate_function: impl Fn(
+        &FunctionId,
+        Vec<CoreValue>,
+    ) -> Result<Vec<CoreValue>, LibFuncSimulationError>,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match inputs {
+        [CoreValue::Enum { value, index: RESULT_ERR_VARIANT_INDEX }] => {
+            match &simulate_function(function_id, vec![value.as_ref().clone()])?[..] {
+                [mapped] => Ok((
+                    vec![CoreValue::Enum {
+                        value: Box::new(mapped.clone()),
+                        index: RESULT_ERR_VARIANT_INDEX,
+                    }],
+                    0,
+                )),
+                _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+            }
+        }
+        [CoreValue::Enum { index: RESULT_OK_VARIANT_INDEX, .. }] => Ok((inputs.to_vec(), 0)),
+        [_] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+        _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+    }
+}
+
+/// Simulates the conversion of a felt into a felt type bounded by 2^251, branching to the failure
+/// branch if the felt is out of range.
+fn simulate_bounded_felt_try_from_felt(
+    inputs: &[CoreValue],
+    wrap: fn(BigInt) -> CoreValue,
+) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    match inputs {
+        [CoreValue::RangeCheck, CoreValue::Felt(value)] => {
+            let value = canonical(value);
+            if value < (BigInt::one() << BOUNDED_FELT_BITS) {
+                Ok((vec![CoreValue::RangeCheck, wrap(value)], 0))
+            } else {
+                Ok((vec![CoreValue::RangeCheck], 1))
+            }
+        }
+        [_, _] => Err(LibFuncSimulationError::MemoryLayoutMismatch),
+        _ => Err(LibFuncSimulationError::WrongNumberOfArgs),
+    }
+}
@@ -0,0 +1,64 @@
+//! Arithmetic over the STARK curve `y^2 = x^3 + x + beta`, used for simulating the EC libfuncs.
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+use super::field::{canonical, felt_prime};
+
+/// A point on the curve, given by its canonical coordinates, or `None` for the point at infinity.
+pub type EcPoint = Option<(BigInt, BigInt)>;
+
+/// Returns the `beta` coefficient of the curve.
+fn beta() -> BigInt {
+    BigInt::parse_bytes(
+        b"3141592653589793238462643383279502884197169399375105820974944592307816406665",
+        10,
+    )
+    .unwrap()
+}
+
+/// Returns the inverse of a non-zero canonical value.
+fn inverse(value: &BigInt) -> BigInt {
+    let prime = felt_prime();
+    value.modpow(&(&prime - BigInt::from(2)), &prime)
+}
+
+/// Returns whether the given coordinates are of a point on the curve.
+pub fn is_on_curve(x: &BigInt, y: &BigInt) -> bool {
+    canonical(&(y * y)) == canonical(&(x * x * x + x + beta()))
+}
+
+/// Adds two points on the curve.
+pub fn ec_add(lhs: &EcPoint, rhs: &EcPoint) -> EcPoint {
+    let ((x1, y1), (x2, y2)) = match (lhs, rhs) {
+        (None, _) => return rhs.clone(),
+        (_, None) => return lhs.clone(),
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+    };
+    let slope = if x1 == x2 {
+        if y1 != y2 || y1.is_zero() {
+            // The points are opposite, or a point of order 2 is doubled.
+            return None;
+        }
+        canonical(&((BigInt::from(3) * x1 * x1 + 1) * inverse(&canonical(&(BigInt::from(2) * y1)))))
+    } else {
+        canonical(&((y2 - y1) * inverse(&canonical(&(x2 - x1)))))
+    };
+    let x3 = canonical(&(&slope * &slope - x1 - x2));
+    let y3 = canonical(&(slope * (x1 - &x3) - y1));
+    Some((x3, y3))
+}
+
+/// Multiplies a point on the curve by the canonical representative of a scalar.
+pub fn ec_mul(point: &EcPoint, scalar: &BigInt) -> EcPoint {
+    let mut scalar = canonical(scalar);
+    let mut power = point.clone();
+    let mut result = None;
+    while !scalar.is_zero() {
+        if scalar.bit(0) {
+            result = ec_add(&result, &power);
+        }
+        power = ec_add(&power, &power);
+        scalar >>= 1;
+    }
+    result
+}
@@ -0,0 +1,80 @@
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+pub use crate::extensions::felt::felt_prime;
+
+/// Arithmetic over the field of felts, used for simulating the felt libfuncs. Allows plugging
+/// faster implementations, such as Montgomery form arithmetic.
+pub trait FieldBackend {
+    /// The representation of a field element in this backend.
+    type Element: Clone;
+
+    /// Converts a felt value to its representation in this backend.
+    // Takes `self`, as the conversion may depend on the backend's parameters.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_bigint(&self, value: &BigInt) -> Self::Element;
+    /// Converts a field element back to a felt value.
+    fn to_bigint(&self, element: &Self::Element) -> BigInt;
+    fn add(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element;
+    fn sub(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element;
+    fn mul(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element;
+    /// Returns the multiplicative inverse of the element, or None if it is zero.
+    fn inv(&self, element: &Self::Element) -> Option<Self::Element>;
+}
+
+/// The default backend, computing directly on `BigInt` values.
+/// Results are reduced to the representative closest to zero, so small negative values stay
+/// readable.
+#[derive(Default)]
+pub struct BigIntBackend {}
+impl FieldBackend for BigIntBackend {
+    type Element = BigInt;
+
+    fn from_bigint(&self, value: &BigInt) -> BigInt {
+        value.clone()
+    }
+
+    fn to_bigint(&self, element: &BigInt) -> BigInt {
+        element.clone()
+    }
+
+    fn add(&self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        reduce(lhs + rhs)
+    }
+
+    fn sub(&self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        reduce(lhs - rhs)
+    }
+
+    fn mul(&self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        reduce(lhs * rhs)
+    }
+
+    fn inv(&self, element: &BigInt) -> Option<BigInt> {
+        let prime = felt_prime();
+        let value = canonical(element);
+        if value.is_zero() {
+            None
+        } else {
+            // By Fermat's little theorem, `value^(p-2)` is the inverse of `value`.
+            Some(reduce(value.modpow(&(&prime - BigInt::from(2)), &prime)))
+        }
+    }
+}
+
+/// Returns the representative of the value in the range `[0, prime)`.
+pub(crate) fn canonical(value: &BigInt) -> BigInt {
+    let prime = felt_prime();
+    ((value % &prime) + &prime) % &prime
+}
+
+/// Reduces the value modulo the prime, to the representative closest to zero.
+pub(crate) fn reduce(value: BigInt) -> BigInt {
+    let prime = felt_prime();
+    let value = canonical(&value);
+    if BigInt::from(2) * &value > prime {
+        value - prime
+    } else {
+        value
+    }
+}
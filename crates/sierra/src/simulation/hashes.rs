@@ -0,0 +1,78 @@
+//! Simulation of the hash builtins and the keccak syscall. The actual hash functions are only
+//! available with the `pedersen`, `poseidon` and `keccak` features, as they are not implemented in
+//! this crate.
+use num_bigint::BigInt;
+#[cfg(any(feature = "pedersen", feature = "poseidon"))]
+use starknet_crypto::FieldElement;
+
+use super::LibFuncSimulationError;
+
+/// Converts a felt to a field element of the StarkNet curve.
+#[cfg(any(feature = "pedersen", feature = "poseidon"))]
+fn to_field_element(value: &BigInt) -> FieldElement {
+    let (_, bytes) = super::field::canonical(value).to_bytes_be();
+    let mut padded = [0; 32];
+    padded[32 - bytes.len()..].copy_from_slice(&bytes);
+    FieldElement::from_bytes_be(&padded).unwrap()
+}
+
+/// Converts a field element of the StarkNet curve back to a felt, reduced as the simulation holds
+/// felts.
+#[cfg(any(feature = "pedersen", feature = "poseidon"))]
+fn from_field_element(element: &FieldElement) -> BigInt {
+    super::field::reduce(BigInt::from_bytes_be(num_bigint::Sign::Plus, &element.to_bytes_be()))
+}
+
+/// Computes the StarkNet Pedersen hash of two felts.
+#[cfg(feature = "pedersen")]
+pub fn pedersen_hash(lhs: &BigInt, rhs: &BigInt) -> Result<BigInt, LibFuncSimulationError> {
+    Ok(from_field_element(&starknet_crypto::pedersen_hash(
+        &to_field_element(lhs),
+        &to_field_element(rhs),
+    )))
+}
+
+/// Computes the StarkNet Pedersen hash of two felts.
+#[cfg(not(feature = "pedersen"))]
+pub fn pedersen_hash(_lhs: &BigInt, _rhs: &BigInt) -> Result<BigInt, LibFuncSimulationError> {
+    Err(LibFuncSimulationError::MissingFeature("pedersen"))
+}
+
+/// Applies the Hades permutation used by the StarkNet Poseidon hash to a state of three felts.
+#[cfg(feature = "poseidon")]
+pub fn hades_permutation(state: [&BigInt; 3]) -> Result<[BigInt; 3], LibFuncSimulationError> {
+    let mut state = state.map(to_field_element);
+    starknet_crypto::poseidon_permute_comp(&mut state);
+    Ok(state.map(|element| from_field_element(&element)))
+}
+
+/// Applies the Hades permutation used by the StarkNet Poseidon hash to a state of three felts.
+#[cfg(not(feature = "poseidon"))]
+pub fn hades_permutation(_state: [&BigInt; 3]) -> Result<[BigInt; 3], LibFuncSimulationError> {
+    Err(LibFuncSimulationError::MissingFeature("poseidon"))
+}
+
+/// The number of uint64 words absorbed by each round of keccak-256.
+pub const KECCAK_RATE_IN_WORDS: usize = 17;
+
+/// Computes keccak-256 over input words already padded to a multiple of the rate, returning the
+/// low and high halves of the hash as read in little-endian order.
+#[cfg(feature = "keccak")]
+pub fn keccak_u64_words(words: &[u64]) -> Result<(u128, u128), LibFuncSimulationError> {
+    let mut state = [0_u64; 25];
+    for block in words.chunks(KECCAK_RATE_IN_WORDS) {
+        for (lane, word) in state.iter_mut().zip(block) {
+            *lane ^= word;
+        }
+        keccak::f1600(&mut state);
+    }
+    let half = |low: u64, high: u64| u128::from(low) | (u128::from(high) << 64);
+    Ok((half(state[0], state[1]), half(state[2], state[3])))
+}
+
+/// Computes keccak-256 over input words already padded to a multiple of the rate, returning the
+/// low and high halves of the hash as read in little-endian order.
+#[cfg(not(feature = "keccak"))]
+pub fn keccak_u64_words(_words: &[u64]) -> Result<(u128, u128), LibFuncSimulationError> {
+    Err(LibFuncSimulationError::MissingFeature("keccak"))
+}
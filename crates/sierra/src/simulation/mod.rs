@@ -1,18 +1,34 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 
 use itertools::izip;
+use num_bigint::BigInt;
 use thiserror::Error;
 
+use self::field::{BigIntBackend, FieldBackend};
+use self::signature::{MissingSignatureVerifier, SignatureVerifier};
+use self::syscalls::{InMemorySyscallHandler, SyscallHandler, SyscallState};
+use self::trace::{Trace, TraceStep, TracedRunError};
 use self::value::CoreValue;
 use crate::edit_state::{put_results, take_args, EditStateError};
-use crate::extensions::core::{CoreConcreteLibFunc, CoreLibFunc, CoreType};
-use crate::ids::{FunctionId, VarId};
-use crate::program::{Program, Statement, StatementIdx};
+use crate::extensions::core::{CoreConcreteLibFunc, CoreLibFunc, CoreType, CoreTypeConcrete};
+use crate::extensions::types::TypeInfo;
+use crate::ids::{ConcreteTypeId, FunctionId, VarId};
+use crate::program::{GenericArg, Program, Statement, StatementIdx};
 use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+use crate::validate::{returns_panicable, UnwrappedPanicError};
 
+mod circuit;
 pub mod core;
+mod ec;
+pub mod field;
+mod hashes;
+pub mod secp256;
+pub mod signature;
+pub mod syscalls;
 #[cfg(test)]
 mod test;
+pub mod trace;
 pub mod value;
 
 /// Error occurring while simulating a libfunc.
@@ -26,8 +42,22 @@ pub enum LibFuncSimulationError {
     MemoryLayoutMismatch,
     #[error("Could not resolve requested symbol value")]
     UnresolvedStatementGasInfo,
+    #[error("Division by a felt equal to zero modulo the prime")]
+    FeltDivisionByZero,
+    #[error("Value is out of the range supported by the builtin")]
+    BuiltinValueOutOfRange,
+    #[error("Gas mark is below the current gas counter")]
+    InvalidGasMark,
+    #[error("Simulating this libfunc requires the `{0}` feature")]
+    MissingFeature(&'static str),
+    #[error("No signature verifier was provided for the simulation")]
+    MissingSignatureVerifier,
     #[error("Error occurred during user function call")]
     FunctionSimulationError(FunctionId, Box<SimulationError>),
+    #[error("Expected a dict entry pending finalization")]
+    NoPendingDictEntry,
+    #[error("Panicked with the given panic data")]
+    Panicked(Vec<CoreValue>),
 }
 
 /// Error occurring while simulating a program function.
@@ -45,6 +75,8 @@ pub enum SimulationError {
     FunctionArgumentCountMismatch { function_id: FunctionId, expected: usize, actual: usize },
     #[error("identifiers left at function return")]
     FunctionDidNotConsumeAllArgs(FunctionId, StatementIdx),
+    #[error("panicking function is not panic-wrapped")]
+    UnwrappedPanic(#[from] UnwrappedPanicError),
 }
 
 /// Runs a function from the program with the given inputs.
@@ -54,21 +86,165 @@ pub fn run(
     function_id: &FunctionId,
     inputs: Vec<CoreValue>,
 ) -> Result<Vec<CoreValue>, SimulationError> {
-    let context = SimulationContext {
-        program,
-        statement_gas_info,
-        registry: &ProgramRegistry::new(program)?,
-    };
-    context.simulate_function(function_id, inputs)
+    SimulationContext::new(program, statement_gas_info)?.run(function_id, inputs)
+}
+
+/// Runs a function from the program with the given inputs. Returns the outputs alongside the values
+/// printed by `debug_print` during the run, in order.
+pub fn run_with_debug_output(
+    program: &Program,
+    statement_gas_info: &HashMap<StatementIdx, i64>,
+    function_id: &FunctionId,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, Vec<String>), SimulationError> {
+    let context = SimulationContext::new(program, statement_gas_info)?;
+    let outputs = context.run(function_id, inputs)?;
+    Ok((outputs, context.debug_output()))
+}
+
+/// An event emitted by `emit_event` during the simulation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Event {
+    pub keys: Vec<BigInt>,
+    pub data: Vec<BigInt>,
+}
+
+/// A message sent to L1 by `send_message_to_l1` during the simulation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct L1Message {
+    pub to_address: BigInt,
+    pub payload: Vec<BigInt>,
+}
+
+/// A snapshot of the mutable state of a simulation context, allowing to roll back to it. Includes
+/// the state of the syscall handler, as given by [SyscallHandler::state].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Checkpoint {
+    debug_output: Vec<String>,
+    events: Vec<Event>,
+    l1_messages: Vec<L1Message>,
+    trace: Option<Trace>,
+    syscall_state: SyscallState,
 }
 
 /// Helper class for runing the simulation.
-struct SimulationContext<'a> {
-    pub program: &'a Program,
-    pub statement_gas_info: &'a HashMap<StatementIdx, i64>,
-    pub registry: &'a ProgramRegistry<CoreType, CoreLibFunc>,
+pub struct SimulationContext<'a, Field: FieldBackend = BigIntBackend> {
+    program: &'a Program,
+    statement_gas_info: &'a HashMap<StatementIdx, i64>,
+    registry: ProgramRegistry<CoreType, CoreLibFunc>,
+    /// The backend for the felt arithmetic.
+    field: Field,
+    /// The values printed by `debug_print` so far.
+    debug_output: RefCell<Vec<String>>,
+    /// The events emitted by `emit_event` so far.
+    events: RefCell<Vec<Event>>,
+    /// The messages sent to L1 by `send_message_to_l1` so far.
+    l1_messages: RefCell<Vec<L1Message>>,
+    /// The trace of the current run, if recorded.
+    trace: RefCell<Option<Trace>>,
+    /// The verifier used for simulating `ecdsa_verify`.
+    signature_verifier: Box<dyn SignatureVerifier + 'a>,
+    /// The handler of the syscalls made by the syscall libfuncs.
+    syscall_handler: Box<dyn SyscallHandler + 'a>,
 }
-impl SimulationContext<'_> {
+impl<'a> SimulationContext<'a> {
+    /// Creates a context for simulating the program, using the default felt arithmetic.
+    pub fn new(
+        program: &'a Program,
+        statement_gas_info: &'a HashMap<StatementIdx, i64>,
+    ) -> Result<Self, SimulationError> {
+        Self::with_field_backend(program, statement_gas_info, BigIntBackend::default())
+    }
+}
+impl<'a, Field: FieldBackend> SimulationContext<'a, Field> {
+    /// Creates a context for simulating the program, using the given backend for the felt
+    /// arithmetic.
+    pub fn with_field_backend(
+        program: &'a Program,
+        statement_gas_info: &'a HashMap<StatementIdx, i64>,
+        field: Field,
+    ) -> Result<Self, SimulationError> {
+        Ok(Self {
+            program,
+            statement_gas_info,
+            registry: ProgramRegistry::new(program)?,
+            field,
+            debug_output: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
+            l1_messages: RefCell::new(vec![]),
+            trace: RefCell::new(None),
+            signature_verifier: Box::new(MissingSignatureVerifier::default()),
+            syscall_handler: Box::new(InMemorySyscallHandler::default()),
+        })
+    }
+    /// Sets the verifier used for simulating `ecdsa_verify`.
+    pub fn with_signature_verifier(mut self, verifier: impl SignatureVerifier + 'a) -> Self {
+        self.signature_verifier = Box::new(verifier);
+        self
+    }
+    /// Sets the handler of the syscalls made by the syscall libfuncs. By default, an in-memory
+    /// storage is used.
+    pub fn with_syscall_handler(mut self, handler: impl SyscallHandler + 'a) -> Self {
+        self.syscall_handler = Box::new(handler);
+        self
+    }
+    /// Runs a function from the program with the given inputs.
+    pub fn run(
+        &self,
+        function_id: &FunctionId,
+        inputs: Vec<CoreValue>,
+    ) -> Result<Vec<CoreValue>, SimulationError> {
+        self.simulate_function(function_id, inputs)
+    }
+    /// Runs a function from the program with the given inputs, recording the trace of the run.
+    /// If the run fails, the trace of the steps run until the failure is returned with the error.
+    pub fn run_with_trace(
+        &self,
+        function_id: &FunctionId,
+        inputs: Vec<CoreValue>,
+    ) -> Result<(Vec<CoreValue>, Trace), TracedRunError> {
+        *self.trace.borrow_mut() = Some(Trace { inputs: inputs.clone(), steps: vec![] });
+        let result = self.simulate_function(function_id, inputs);
+        let trace = self.trace.borrow_mut().take().unwrap_or_default();
+        match result {
+            Ok(outputs) => Ok((outputs, trace)),
+            Err(error) => Err(TracedRunError { error, trace }),
+        }
+    }
+    /// Returns the backend used for the felt arithmetic.
+    pub fn field_backend(&self) -> &Field {
+        &self.field
+    }
+    /// Returns the values printed by `debug_print` so far, in order.
+    pub fn debug_output(&self) -> Vec<String> {
+        self.debug_output.borrow().clone()
+    }
+    /// Returns the events emitted by `emit_event` so far, in order.
+    pub fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+    /// Returns the messages sent to L1 by `send_message_to_l1` so far, in order.
+    pub fn l1_messages(&self) -> Vec<L1Message> {
+        self.l1_messages.borrow().clone()
+    }
+    /// Returns a snapshot of the state of the context, to later be restored by `restore`.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            debug_output: self.debug_output(),
+            events: self.events(),
+            l1_messages: self.l1_messages(),
+            trace: self.trace.borrow().clone(),
+            syscall_state: self.syscall_handler.state(),
+        }
+    }
+    /// Restores the state of the context to the given snapshot, discarding all changes since.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        *self.debug_output.get_mut() = checkpoint.debug_output;
+        *self.events.get_mut() = checkpoint.events;
+        *self.l1_messages.get_mut() = checkpoint.l1_messages;
+        *self.trace.get_mut() = checkpoint.trace;
+        self.syscall_handler.restore_state(checkpoint.syscall_state);
+    }
     /// Simulates the run of a function, even recursively.
     fn simulate_function(
         &self,
@@ -99,6 +275,14 @@ impl SimulationContext<'_> {
                         SimulationError::EditStateError(error, current_statement_id)
                     })?;
                     return if remaining.is_empty() {
+                        if self.is_recording() {
+                            self.record_step(TraceStep {
+                                statement_idx: current_statement_id.0,
+                                branch: None,
+                                inputs: outputs.clone(),
+                                outputs: vec![],
+                            });
+                        }
                         Ok(outputs)
                     } else {
                         Err(SimulationError::FunctionDidNotConsumeAllArgs(
@@ -113,12 +297,53 @@ impl SimulationContext<'_> {
                             SimulationError::EditStateError(error, current_statement_id)
                         })?;
                     let libfunc = self.registry.get_libfunc(&invocation.libfunc_id)?;
-                    let (outputs, chosen_branch) = self.simulate_libfunc(
+                    let recorded_inputs = self.is_recording().then(|| inputs.clone());
+                    let (outputs, chosen_branch) = match self.simulate_libfunc(
                         &current_statement_id,
                         libfunc,
                         inputs,
                         current_statement_id,
-                    )?;
+                    ) {
+                        // Panicking terminates the function, which returns the panic data.
+                        Err(SimulationError::LibFuncSimulationError(
+                            LibFuncSimulationError::Panicked(data),
+                            _,
+                        )) => {
+                            if !returns_panicable(self.program, func) {
+                                return Err(SimulationError::UnwrappedPanic(UnwrappedPanicError {
+                                    function_id: func.id.clone(),
+                                    statement_idx: current_statement_id,
+                                }));
+                            }
+                            if !remaining.is_empty() {
+                                return Err(SimulationError::FunctionDidNotConsumeAllArgs(
+                                    func.id.clone(),
+                                    current_statement_id,
+                                ));
+                            }
+                            if let Some(inputs) = recorded_inputs {
+                                self.record_step(TraceStep {
+                                    statement_idx: current_statement_id.0,
+                                    branch: None,
+                                    inputs,
+                                    outputs: vec![],
+                                });
+                            }
+                            return Ok(vec![CoreValue::Enum {
+                                value: Box::new(CoreValue::Array(data)),
+                                index: 1,
+                            }]);
+                        }
+                        result => result?,
+                    };
+                    if let Some(inputs) = recorded_inputs {
+                        self.record_step(TraceStep {
+                            statement_idx: current_statement_id.0,
+                            branch: Some(chosen_branch),
+                            inputs,
+                            outputs: outputs.clone(),
+                        });
+                    }
                     let branch_info = &invocation.branches[chosen_branch];
                     state = put_results(
                         remaining,
@@ -132,6 +357,16 @@ impl SimulationContext<'_> {
             }
         }
     }
+    /// Returns whether the trace of the current run is recorded.
+    fn is_recording(&self) -> bool {
+        self.trace.borrow().is_some()
+    }
+    /// Adds a step to the trace of the current run, if recorded.
+    fn record_step(&self, step: TraceStep) {
+        if let Some(trace) = self.trace.borrow_mut().as_mut() {
+            trace.steps.push(step);
+        }
+    }
     /// Simulates the run of libfuncs. Returns the memory reperesentations of the outputs given the
     /// inputs.
     fn simulate_libfunc(
@@ -144,6 +379,7 @@ impl SimulationContext<'_> {
         core::simulate(
             libfunc,
             inputs,
+            &self.field,
             || self.statement_gas_info.get(idx).copied(),
             |function_id, inputs| {
                 self.simulate_function(function_id, inputs).map_err(|error| {
@@ -153,7 +389,66 @@ impl SimulationContext<'_> {
                     )
                 })
             },
+            &core::SideEffects {
+                debug_print: &|ty, value| {
+                    let formatted = self.format_value(ty, value);
+                    self.debug_output.borrow_mut().push(formatted);
+                },
+                emit_event: &|event| self.events.borrow_mut().push(event),
+                send_message_to_l1: &|message| self.l1_messages.borrow_mut().push(message),
+                signature_verifier: self.signature_verifier.as_ref(),
+                syscall_handler: self.syscall_handler.as_ref(),
+            },
         )
         .map_err(|error| SimulationError::LibFuncSimulationError(error, current_statement_id))
     }
+    /// Formats a value of the given type for debug printing, using the type's layout to render
+    /// structs, enums and arrays.
+    fn format_value(&self, ty: &ConcreteTypeId, value: &CoreValue) -> String {
+        match (self.registry.get_type(ty), value) {
+            (Ok(CoreTypeConcrete::Struct(info)), CoreValue::Struct(members))
+                if info.members.len() == members.len() =>
+            {
+                let members = info
+                    .members
+                    .iter()
+                    .zip(members.iter())
+                    .map(|(ty, value)| self.format_value(ty, value))
+                    .collect::<Vec<_>>();
+                format!("{}({})", user_type_name(&info.info), members.join(", "))
+            }
+            (Ok(CoreTypeConcrete::Enum(info)), CoreValue::Enum { value, index })
+                if *index < info.variants.len() =>
+            {
+                format!(
+                    "{}::{index}({})",
+                    user_type_name(&info.info),
+                    self.format_value(&info.variants[*index], value)
+                )
+            }
+            (Ok(CoreTypeConcrete::Array(info)), CoreValue::Array(values)) => {
+                let values = values
+                    .iter()
+                    .map(|value| self.format_value(&info.ty, value))
+                    .collect::<Vec<_>>();
+                format!("[{}]", values.join(", "))
+            }
+            (_, CoreValue::Felt(value)) => value.to_string(),
+            (_, CoreValue::Bool(value)) => value.to_string(),
+            (_, CoreValue::Uint8(value)) => value.to_string(),
+            (_, CoreValue::Uint16(value)) => value.to_string(),
+            (_, CoreValue::Uint32(value)) => value.to_string(),
+            (_, CoreValue::Uint64(value)) => value.to_string(),
+            (_, CoreValue::Uint128(value)) => value.to_string(),
+            (_, value) => format!("{value:?}"),
+        }
+    }
+}
+
+/// Returns the name of a user defined type, falling back to its long id.
+fn user_type_name(info: &TypeInfo) -> String {
+    match info.long_id.generic_args.first() {
+        Some(GenericArg::UserType(id)) => id.to_string(),
+        _ => info.long_id.to_string(),
+    }
 }
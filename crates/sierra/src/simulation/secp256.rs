@@ -0,0 +1,104 @@
+//! Arithmetic over the secp256k1 and secp256r1 curves, used by the default handling of the secp256
+//! syscalls. Points are given by their affine coordinates, where `(0, 0)` is the point at
+//! infinity, as it is on neither curve.
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+use crate::extensions::secp256::Secp256Curve;
+
+/// A point on a secp256 curve, given by its canonical coordinates.
+pub type Secp256Point = (BigInt, BigInt);
+
+/// The parameters of a curve `y^2 = x^3 + ax + b` over the prime field of `prime`.
+struct CurveParams {
+    prime: BigInt,
+    a: BigInt,
+    b: BigInt,
+}
+
+/// Returns the parameters of the given curve.
+fn curve_params(curve: Secp256Curve) -> CurveParams {
+    let hex = |value: &[u8]| BigInt::parse_bytes(value, 16).unwrap();
+    match curve {
+        Secp256Curve::K1 => CurveParams {
+            prime: hex(b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+            a: BigInt::zero(),
+            b: BigInt::from(7),
+        },
+        Secp256Curve::R1 => CurveParams {
+            prime: hex(b"ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"),
+            a: hex(b"ffffffff00000001000000000000000000000000fffffffffffffffffffffffc"),
+            b: hex(b"5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+        },
+    }
+}
+impl CurveParams {
+    /// Returns the representative of the value in the range `[0, prime)`.
+    fn reduce(&self, value: &BigInt) -> BigInt {
+        ((value % &self.prime) + &self.prime) % &self.prime
+    }
+
+    /// Returns the inverse of a non-zero reduced value.
+    fn inverse(&self, value: &BigInt) -> BigInt {
+        value.modpow(&(&self.prime - BigInt::from(2)), &self.prime)
+    }
+}
+
+/// Returns the point at infinity.
+pub fn infinity() -> Secp256Point {
+    (BigInt::zero(), BigInt::zero())
+}
+
+/// Returns whether the coordinates are of a point on the curve, or of the point at infinity.
+/// Coordinates out of the range of the curve's field are rejected.
+pub fn is_valid_point(curve: Secp256Curve, (x, y): &Secp256Point) -> bool {
+    let params = curve_params(curve);
+    let in_range = |value: &BigInt| value >= &BigInt::zero() && value < &params.prime;
+    if !in_range(x) || !in_range(y) {
+        return false;
+    }
+    (x, y) == (&BigInt::zero(), &BigInt::zero())
+        || params.reduce(&(y * y)) == params.reduce(&(x * x * x + &params.a * x + &params.b))
+}
+
+/// Adds two valid points on the curve.
+pub fn secp256_add(curve: Secp256Curve, lhs: &Secp256Point, rhs: &Secp256Point) -> Secp256Point {
+    if lhs == &infinity() {
+        return rhs.clone();
+    }
+    if rhs == &infinity() {
+        return lhs.clone();
+    }
+    let params = curve_params(curve);
+    let ((x1, y1), (x2, y2)) = (lhs, rhs);
+    let slope = if x1 == x2 {
+        if y1 != y2 || y1.is_zero() {
+            // The points are opposite, or a point of order 2 is doubled.
+            return infinity();
+        }
+        params.reduce(
+            &((BigInt::from(3) * x1 * x1 + &params.a)
+                * params.inverse(&params.reduce(&(BigInt::from(2) * y1)))),
+        )
+    } else {
+        params.reduce(&((y2 - y1) * params.inverse(&params.reduce(&(x2 - x1)))))
+    };
+    let x3 = params.reduce(&(&slope * &slope - x1 - x2));
+    let y3 = params.reduce(&(slope * (x1 - &x3) - y1));
+    (x3, y3)
+}
+
+/// Multiplies a valid point on the curve by a non-negative scalar.
+pub fn secp256_mul(curve: Secp256Curve, point: &Secp256Point, scalar: &BigInt) -> Secp256Point {
+    let mut scalar = scalar.clone();
+    let mut power = point.clone();
+    let mut result = infinity();
+    while !scalar.is_zero() {
+        if scalar.bit(0) {
+            result = secp256_add(curve, &result, &power);
+        }
+        power = secp256_add(curve, &power, &power);
+        scalar >>= 1;
+    }
+    result
+}
@@ -0,0 +1,43 @@
+use num_bigint::BigInt;
+
+use super::LibFuncSimulationError;
+
+/// Verifies ECDSA signatures when simulating `ecdsa_verify`. Allows plugging a real
+/// implementation, or a mock for tests.
+pub trait SignatureVerifier {
+    /// Returns whether `(r, s)` is a valid signature of `message` by `public_key`.
+    fn verify(
+        &self,
+        message: &BigInt,
+        public_key: &BigInt,
+        r: &BigInt,
+        s: &BigInt,
+    ) -> Result<bool, LibFuncSimulationError>;
+}
+
+impl<F: Fn(&BigInt, &BigInt, &BigInt, &BigInt) -> bool> SignatureVerifier for F {
+    fn verify(
+        &self,
+        message: &BigInt,
+        public_key: &BigInt,
+        r: &BigInt,
+        s: &BigInt,
+    ) -> Result<bool, LibFuncSimulationError> {
+        Ok(self(message, public_key, r, s))
+    }
+}
+
+/// The verifier used when none is provided, failing any signature verification.
+#[derive(Default)]
+pub struct MissingSignatureVerifier {}
+impl SignatureVerifier for MissingSignatureVerifier {
+    fn verify(
+        &self,
+        _message: &BigInt,
+        _public_key: &BigInt,
+        _r: &BigInt,
+        _s: &BigInt,
+    ) -> Result<bool, LibFuncSimulationError> {
+        Err(LibFuncSimulationError::MissingSignatureVerifier)
+    }
+}
@@ -0,0 +1,365 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+use super::secp256::{is_valid_point, secp256_add, secp256_mul, Secp256Point};
+use crate::extensions::secp256::Secp256Curve;
+
+/// The result of a syscall: its outputs on success, or the revert reason on failure.
+pub type SyscallResult<T> = Result<T, Vec<BigInt>>;
+
+/// A mock of a contract, called with the selector and the calldata of each call to it.
+pub type MockContract = Box<dyn Fn(&BigInt, &[BigInt]) -> SyscallResult<Vec<BigInt>>>;
+
+/// Provides the hashes of past blocks, by block number, or `None` for unknown blocks.
+pub type BlockHashProvider = Box<dyn Fn(u64) -> Option<BigInt>>;
+
+/// The number of most recent blocks whose hashes are not available to `get_block_hash`.
+pub const BLOCK_HASH_BUFFER: u64 = 10;
+
+/// The information of the current execution, returned by `get_execution_info`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionInfo {
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    pub sequencer_address: BigInt,
+    pub transaction_hash: BigInt,
+    pub chain_id: BigInt,
+    pub caller_address: BigInt,
+    pub contract_address: BigInt,
+    pub entry_point_selector: BigInt,
+}
+
+/// The mutable state of a syscall handler, captured by the checkpoints of the simulation.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SyscallState {
+    /// The written storage, by address.
+    pub storage: HashMap<BigInt, BigInt>,
+    /// The class hashes of the contracts deployed or whose class was replaced, by address.
+    pub deployed: HashMap<BigInt, BigInt>,
+}
+
+/// Handles the syscalls made when simulating the syscall libfuncs. Allows plugging a handler
+/// backed by a real state, or a mock for tests.
+pub trait SyscallHandler {
+    /// Returns the value stored at `address`.
+    fn storage_read(&self, address: &BigInt) -> SyscallResult<BigInt>;
+    /// Stores `value` at `address`.
+    fn storage_write(&self, address: &BigInt, value: &BigInt) -> SyscallResult<()>;
+    /// Calls the function with the given selector of the contract at `address`, returning the data
+    /// returned by the function.
+    fn call_contract(
+        &self,
+        address: &BigInt,
+        selector: &BigInt,
+        calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>>;
+    /// Deploys a contract of the class `class_hash`, calling its constructor with `calldata`.
+    /// Returns the address of the deployed contract and the data returned by the constructor.
+    fn deploy(
+        &self,
+        class_hash: &BigInt,
+        salt: &BigInt,
+        calldata: &[BigInt],
+        deploy_from_zero: bool,
+    ) -> SyscallResult<(BigInt, Vec<BigInt>)>;
+    /// Calls the function with the given selector of the class `class_hash` in the context of the
+    /// calling contract, returning the data returned by the function.
+    fn library_call(
+        &self,
+        class_hash: &BigInt,
+        selector: &BigInt,
+        calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>>;
+    /// Replaces the class of the current contract with the class `class_hash`.
+    fn replace_class(&self, class_hash: &BigInt) -> SyscallResult<()>;
+    /// Returns the information of the current execution.
+    fn get_execution_info(&self) -> SyscallResult<ExecutionInfo>;
+    /// Returns the hash of the block with the given number, reverting if it is out of range.
+    fn get_block_hash(&self, block_number: u64) -> SyscallResult<BigInt>;
+    /// Returns a snapshot of the state of the handler, to later be restored by `restore_state`.
+    /// Defaults to an empty state, for handlers without a state of their own.
+    fn state(&self) -> SyscallState {
+        SyscallState::default()
+    }
+    /// Restores the state of the handler to the given snapshot, discarding all changes since.
+    /// Defaults to doing nothing, for handlers without a state of their own.
+    fn restore_state(&self, _state: SyscallState) {}
+    /// Returns the point of the curve with the given coordinates, reverting if there is no such
+    /// point. Defaults to the curve arithmetic of the simulator.
+    fn secp256_new(
+        &self,
+        curve: Secp256Curve,
+        x: BigInt,
+        y: BigInt,
+    ) -> SyscallResult<Secp256Point> {
+        let point = (x, y);
+        if is_valid_point(curve, &point) {
+            Ok(point)
+        } else {
+            Err(vec![BigInt::from_bytes_be(Sign::Plus, b"Invalid point")])
+        }
+    }
+    /// Adds two points of the curve. Defaults to the curve arithmetic of the simulator.
+    fn secp256_add(
+        &self,
+        curve: Secp256Curve,
+        lhs: &Secp256Point,
+        rhs: &Secp256Point,
+    ) -> SyscallResult<Secp256Point> {
+        Ok(secp256_add(curve, lhs, rhs))
+    }
+    /// Multiplies a point of the curve by a scalar. Defaults to the curve arithmetic of the
+    /// simulator.
+    fn secp256_mul(
+        &self,
+        curve: Secp256Curve,
+        point: &Secp256Point,
+        scalar: &BigInt,
+    ) -> SyscallResult<Secp256Point> {
+        Ok(secp256_mul(curve, point, scalar))
+    }
+}
+
+impl<T: SyscallHandler + ?Sized> SyscallHandler for &T {
+    fn storage_read(&self, address: &BigInt) -> SyscallResult<BigInt> {
+        (**self).storage_read(address)
+    }
+
+    fn storage_write(&self, address: &BigInt, value: &BigInt) -> SyscallResult<()> {
+        (**self).storage_write(address, value)
+    }
+
+    fn call_contract(
+        &self,
+        address: &BigInt,
+        selector: &BigInt,
+        calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>> {
+        (**self).call_contract(address, selector, calldata)
+    }
+
+    fn deploy(
+        &self,
+        class_hash: &BigInt,
+        salt: &BigInt,
+        calldata: &[BigInt],
+        deploy_from_zero: bool,
+    ) -> SyscallResult<(BigInt, Vec<BigInt>)> {
+        (**self).deploy(class_hash, salt, calldata, deploy_from_zero)
+    }
+
+    fn library_call(
+        &self,
+        class_hash: &BigInt,
+        selector: &BigInt,
+        calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>> {
+        (**self).library_call(class_hash, selector, calldata)
+    }
+
+    fn replace_class(&self, class_hash: &BigInt) -> SyscallResult<()> {
+        (**self).replace_class(class_hash)
+    }
+
+    fn get_execution_info(&self) -> SyscallResult<ExecutionInfo> {
+        (**self).get_execution_info()
+    }
+
+    fn get_block_hash(&self, block_number: u64) -> SyscallResult<BigInt> {
+        (**self).get_block_hash(block_number)
+    }
+
+    fn state(&self) -> SyscallState {
+        (**self).state()
+    }
+
+    fn restore_state(&self, state: SyscallState) {
+        (**self).restore_state(state)
+    }
+
+    fn secp256_new(
+        &self,
+        curve: Secp256Curve,
+        x: BigInt,
+        y: BigInt,
+    ) -> SyscallResult<Secp256Point> {
+        (**self).secp256_new(curve, x, y)
+    }
+
+    fn secp256_add(
+        &self,
+        curve: Secp256Curve,
+        lhs: &Secp256Point,
+        rhs: &Secp256Point,
+    ) -> SyscallResult<Secp256Point> {
+        (**self).secp256_add(curve, lhs, rhs)
+    }
+
+    fn secp256_mul(
+        &self,
+        curve: Secp256Curve,
+        point: &Secp256Point,
+        scalar: &BigInt,
+    ) -> SyscallResult<Secp256Point> {
+        (**self).secp256_mul(curve, point, scalar)
+    }
+}
+
+/// A syscall handler keeping the storage in memory, where addresses that were never written hold
+/// zero. Calls to other contracts are handled by the mock contracts registered by address, or by
+/// the mock class of contracts deployed during the simulation, and revert with
+/// `CONTRACT_NOT_DEPLOYED` for any other address. The execution info is the default one, unless
+/// configured.
+///
+/// Contracts are deployed at the address given by their salt, and their constructor is modelled by
+/// calling their class with a zero selector. Replacing the class of the current contract, given by
+/// the execution info, makes it handled by the new mock class from then on.
+///
+/// Block hashes are given by the configured provider, and are only available for blocks at least
+/// `BLOCK_HASH_BUFFER` blocks older than the current block of the execution info.
+#[derive(Default)]
+pub struct InMemorySyscallHandler {
+    storage: RefCell<HashMap<BigInt, BigInt>>,
+    contracts: HashMap<BigInt, MockContract>,
+    /// The mock classes, by class hash.
+    classes: HashMap<BigInt, MockContract>,
+    /// The class hashes of the contracts deployed or whose class was replaced during the
+    /// simulation, by address.
+    deployed: RefCell<HashMap<BigInt, BigInt>>,
+    execution_info: ExecutionInfo,
+    block_hash_provider: Option<BlockHashProvider>,
+}
+impl InMemorySyscallHandler {
+    /// Registers a mock contract handling the calls to `address`.
+    pub fn with_contract(
+        mut self,
+        address: BigInt,
+        contract: impl Fn(&BigInt, &[BigInt]) -> SyscallResult<Vec<BigInt>> + 'static,
+    ) -> Self {
+        self.contracts.insert(address, Box::new(contract));
+        self
+    }
+    /// Registers a mock class, handling the calls to the contracts of class `class_hash` and the
+    /// library calls to it.
+    pub fn with_class(
+        mut self,
+        class_hash: BigInt,
+        class: impl Fn(&BigInt, &[BigInt]) -> SyscallResult<Vec<BigInt>> + 'static,
+    ) -> Self {
+        self.classes.insert(class_hash, Box::new(class));
+        self
+    }
+    /// Sets the execution info returned by `get_execution_info`.
+    pub fn with_execution_info(mut self, execution_info: ExecutionInfo) -> Self {
+        self.execution_info = execution_info;
+        self
+    }
+    /// Sets the provider of the hashes returned by `get_block_hash`.
+    pub fn with_block_hash_provider(
+        mut self,
+        provider: impl Fn(u64) -> Option<BigInt> + 'static,
+    ) -> Self {
+        self.block_hash_provider = Some(Box::new(provider));
+        self
+    }
+    /// Returns the current storage, including only the written addresses.
+    pub fn storage(&self) -> HashMap<BigInt, BigInt> {
+        self.storage.borrow().clone()
+    }
+    /// Returns the class hashes of the contracts deployed so far, by address.
+    pub fn deployed(&self) -> HashMap<BigInt, BigInt> {
+        self.deployed.borrow().clone()
+    }
+    /// Returns the mock class with the given hash, or the revert reason if it was not registered.
+    fn class(&self, class_hash: &BigInt) -> SyscallResult<&MockContract> {
+        self.classes
+            .get(class_hash)
+            .ok_or_else(|| vec![BigInt::from_bytes_be(Sign::Plus, b"CLASS_HASH_NOT_DECLARED")])
+    }
+}
+impl SyscallHandler for InMemorySyscallHandler {
+    fn storage_read(&self, address: &BigInt) -> SyscallResult<BigInt> {
+        Ok(self.storage.borrow().get(address).cloned().unwrap_or_else(BigInt::zero))
+    }
+
+    fn storage_write(&self, address: &BigInt, value: &BigInt) -> SyscallResult<()> {
+        self.storage.borrow_mut().insert(address.clone(), value.clone());
+        Ok(())
+    }
+
+    fn call_contract(
+        &self,
+        address: &BigInt,
+        selector: &BigInt,
+        calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>> {
+        let class_hash = self.deployed.borrow().get(address).cloned();
+        if let Some(class_hash) = class_hash {
+            return self.class(&class_hash)?(selector, calldata);
+        }
+        match self.contracts.get(address) {
+            Some(contract) => contract(selector, calldata),
+            None => Err(vec![BigInt::from_bytes_be(Sign::Plus, b"CONTRACT_NOT_DEPLOYED")]),
+        }
+    }
+
+    fn deploy(
+        &self,
+        class_hash: &BigInt,
+        salt: &BigInt,
+        calldata: &[BigInt],
+        _deploy_from_zero: bool,
+    ) -> SyscallResult<(BigInt, Vec<BigInt>)> {
+        let class = self.class(class_hash)?;
+        if self.contracts.contains_key(salt) || self.deployed.borrow().contains_key(salt) {
+            return Err(vec![BigInt::from_bytes_be(Sign::Plus, b"CONTRACT_ALREADY_DEPLOYED")]);
+        }
+        let retdata = class(&BigInt::zero(), calldata)?;
+        self.deployed.borrow_mut().insert(salt.clone(), class_hash.clone());
+        Ok((salt.clone(), retdata))
+    }
+
+    fn library_call(
+        &self,
+        class_hash: &BigInt,
+        selector: &BigInt,
+        calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>> {
+        self.class(class_hash)?(selector, calldata)
+    }
+
+    fn replace_class(&self, class_hash: &BigInt) -> SyscallResult<()> {
+        self.class(class_hash)?;
+        self.deployed
+            .borrow_mut()
+            .insert(self.execution_info.contract_address.clone(), class_hash.clone());
+        Ok(())
+    }
+
+    fn get_execution_info(&self) -> SyscallResult<ExecutionInfo> {
+        Ok(self.execution_info.clone())
+    }
+
+    fn get_block_hash(&self, block_number: u64) -> SyscallResult<BigInt> {
+        let in_range = matches!(
+            block_number.checked_add(BLOCK_HASH_BUFFER),
+            Some(bound) if bound <= self.execution_info.block_number
+        );
+        in_range
+            .then(|| self.block_hash_provider.as_ref().and_then(|provider| provider(block_number)))
+            .flatten()
+            .ok_or_else(|| vec![BigInt::from_bytes_be(Sign::Plus, b"BLOCK_NUMBER_OUT_OF_RANGE")])
+    }
+
+    fn state(&self) -> SyscallState {
+        SyscallState { storage: self.storage(), deployed: self.deployed() }
+    }
+
+    fn restore_state(&self, state: SyscallState) {
+        *self.storage.borrow_mut() = state.storage;
+        *self.deployed.borrow_mut() = state.deployed;
+    }
+}
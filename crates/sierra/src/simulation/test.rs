@@ -1,14 +1,28 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+
 use bimap::BiMap;
-use num_bigint::BigInt;
+use indoc::indoc;
+use num_bigint::{BigInt, Sign};
 use test_case::test_case;
 
+use super::field::{felt_prime, reduce, BigIntBackend, FieldBackend};
+use super::syscalls::{ExecutionInfo, InMemorySyscallHandler, SyscallHandler, SyscallResult};
+use super::trace::{replay, ReplayError, TracedRunError};
 use super::value::CoreValue::{
-    self, Array, GasBuiltin, NonZero, RangeCheck, Uint128, Uninitialized,
+    self, AddMod, Array, Bitwise, Bool, Bytes31, CircuitInputs, CircuitOutputs, ClassHash,
+    ContractAddress, Coupon, DictEntry, EcOp, EcPoint, EcState, Enum, Felt, GasBuiltin, MulMod,
+    NonZero, Null, Pedersen, Poseidon, RangeCheck, Secp256Point, SegmentArena, Signature,
+    StorageAddress, StorageBaseAddress, Struct, System, Uint128, Uint64, Uint8, Uninitialized,
+    Unit,
 };
 use super::LibFuncSimulationError::{
-    self, FunctionSimulationError, MemoryLayoutMismatch, WrongNumberOfArgs,
+    self, BuiltinValueOutOfRange, FunctionSimulationError, InvalidGasMark, MemoryLayoutMismatch,
+    NoPendingDictEntry, WrongNumberOfArgs,
+};
+use super::{
+    core, run, run_with_debug_output, Event, L1Message, SimulationContext, SimulationError,
 };
-use super::{core, SimulationError};
 use crate::extensions::core::CoreLibFunc;
 use crate::extensions::lib_func::{
     SierraApChange, SignatureSpecializationContext, SpecializationContext,
@@ -17,8 +31,12 @@ use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
 use crate::extensions::GenericLibFunc;
 use crate::ids::{ConcreteTypeId, FunctionId, GenericTypeId};
-use crate::program::{ConcreteTypeLongId, Function, FunctionSignature, GenericArg, StatementIdx};
+use crate::program::{
+    ConcreteTypeLongId, Function, FunctionSignature, GenericArg, Program, StatementIdx,
+};
 use crate::test_utils::build_bijective_mapping;
+use crate::validate::UnwrappedPanicError;
+use crate::ProgramParser;
 
 fn type_arg(name: &str) -> GenericArg {
     GenericArg::Type(name.into())
@@ -32,6 +50,34 @@ fn user_func_arg(name: &str) -> GenericArg {
     GenericArg::UserFunc(name.into())
 }
 
+fn felt_dict(entries: &[(i64, i64)], default_value: i64) -> CoreValue {
+    felt_dict_with_accesses(entries, &[], default_value)
+}
+
+fn felt_dict_with_accesses(
+    entries: &[(i64, i64)],
+    accesses: &[(i64, i64, i64)],
+    default_value: i64,
+) -> CoreValue {
+    let felt = |v: &i64| Felt(BigInt::from(*v));
+    CoreValue::Dict {
+        entries: entries.iter().map(|(k, v)| (BigInt::from(*k), felt(v))).collect(),
+        accesses: accesses
+            .iter()
+            .map(|(k, prev, new)| (BigInt::from(*k), felt(prev), felt(new)))
+            .collect(),
+        default_value: Box::new(felt(&default_value)),
+    }
+}
+
+fn uint128_span(elements: &[u128], offset: usize, len: usize) -> CoreValue {
+    CoreValue::Span {
+        elements: elements.iter().map(|value| Uint128(*value)).collect(),
+        offset,
+        len,
+    }
+}
+
 struct MockSpecializationContext {
     mapping: BiMap<ConcreteTypeId, ConcreteTypeLongId>,
 }
@@ -56,7 +102,12 @@ impl SpecializationContext for MockSpecializationContext {
 }
 impl TypeSpecializationContext for MockSpecializationContext {
     fn try_get_type_info(&self, id: ConcreteTypeId) -> Option<TypeInfo> {
-        if id == "uint128".into() || id == "NonZeroInt".into() {
+        if id == "uint8".into()
+            || id == "uint64".into()
+            || id == "uint128".into()
+            || id == "felt".into()
+            || id == "NonZeroInt".into()
+        {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
                 storable: true,
@@ -64,6 +115,48 @@ impl TypeSpecializationContext for MockSpecializationContext {
                 duplicatable: true,
                 size: 1,
             })
+        } else if id == "Unit".into() {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 0,
+            })
+        } else if id == "Uint128AndFelt".into()
+            || id == "FeltPair".into()
+            || id == "Option".into()
+            || id == "Result".into()
+        {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 2,
+            })
+        } else if id == "Tuple<>".into() {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                size: 0,
+            })
+        } else if id == "In0".into()
+            || id == "In1".into()
+            || id == "Sum".into()
+            || id == "Product".into()
+            || id == "Inverse".into()
+            || id == "Circuit".into()
+        {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: false,
+                droppable: true,
+                duplicatable: true,
+                size: 0,
+            })
         } else if id == "UninitializedInt".into() {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
@@ -110,12 +203,16 @@ fn simulate(
     generic_args: Vec<GenericArg>,
     inputs: Vec<CoreValue>,
 ) -> Result<(Vec<CoreValue>, usize), LibFuncSimulationError> {
+    // Accepts only signatures with `r + s` equal to the message, to avoid real cryptography.
+    let signature_verifier =
+        |message: &BigInt, _public_key: &BigInt, r: &BigInt, s: &BigInt| &(r + s) == message;
     core::simulate(
         &CoreLibFunc::by_id(&id.into())
             .unwrap()
             .specialize(&MockSpecializationContext::new(), &generic_args)
             .unwrap(),
         inputs,
+        &BigIntBackend::default(),
         || Some(4),
         |id, inputs| {
             if id == &"drop_all_inputs".into() {
@@ -129,6 +226,13 @@ fn simulate(
                 ))
             }
         },
+        &core::SideEffects {
+            debug_print: &|_, _| {},
+            emit_event: &|_| {},
+            send_message_to_l1: &|_| {},
+            signature_verifier: &signature_verifier,
+            syscall_handler: &InMemorySyscallHandler::default(),
+        },
     )
 }
 
@@ -138,7 +242,133 @@ fn simulate(
              => Ok((vec![RangeCheck, GasBuiltin(2)], 1)); "get_gas(2)")]
 #[test_case("uint128_jump_nz", vec![], vec![Uint128(2)] => Ok((vec![NonZero(Box::new(Uint128(2)))], 1)); "uint128_jump_nz(2)")]
 #[test_case("uint128_jump_nz", vec![], vec![Uint128(0)] => Ok((vec![], 0)); "uint128_jump_nz(0)")]
+#[test_case("storage_read", vec![], vec![System, StorageAddress(BigInt::from(3))]
+             => Ok((vec![System, Felt(BigInt::from(0))], 0)); "storage_read(3)")]
+#[test_case("emit_event", vec![], vec![System, Array(vec![]), Array(vec![])]
+             => Ok((vec![System], 0)); "emit_event([], [])")]
+#[test_case("storage_write", vec![],
+             vec![System, StorageAddress(BigInt::from(3)), Felt(BigInt::from(4))]
+             => Ok((vec![System], 0)); "storage_write(3, 4)")]
+#[test_case("library_call", vec![], vec![System, ClassHash(BigInt::from(1)), Felt(BigInt::from(2)), Array(vec![])]
+             => Ok((vec![
+                 System,
+                 Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, b"CLASS_HASH_NOT_DECLARED"))]),
+             ], 1));
+            "library_call(1, 2, [])")]
+#[test_case("keccak", vec![], vec![System, Array(vec![Uint64(1)])]
+             => Ok((vec![
+                 System,
+                 Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, b"Invalid input length"))]),
+             ], 1));
+            "keccak([1])")]
+#[test_case("jump_nz", vec![type_arg("felt")], vec![Felt(BigInt::from(0))] => Ok((vec![], 0));
+            "jump_nz<felt>(0)")]
+#[test_case("jump_nz", vec![type_arg("uint8")], vec![Uint8(7)]
+             => Ok((vec![NonZero(Box::new(Uint8(7)))], 1)); "jump_nz<uint8>(7)")]
+#[test_case("jump_nz", vec![type_arg("uint128")], vec![Uint128(0)] => Ok((vec![], 0));
+            "jump_nz<uint128>(0)")]
+#[test_case("felt_is_zero", vec![], vec![Felt(BigInt::from(0))] => Ok((vec![], 0)); "felt_is_zero(0)")]
+#[test_case("felt_is_zero", vec![], vec![Felt(BigInt::from(3))]
+             => Ok((vec![NonZero(Box::new(Felt(BigInt::from(3))))], 1)); "felt_is_zero(3)")]
 #[test_case("jump", vec![], vec![] => Ok((vec![], 0)); "jump()")]
+#[test_case("felt_assert_lt_const", vec![value_arg(10)], vec![RangeCheck, Felt(BigInt::from(9))]
+             => Ok((vec![RangeCheck], 0)); "felt_assert_lt_const<10>(9)")]
+#[test_case("felt_assert_lt_const", vec![value_arg(10)], vec![RangeCheck, Felt(BigInt::from(10))]
+             => Ok((vec![RangeCheck], 1)); "felt_assert_lt_const<10>(10)")]
+#[test_case("felt_assert_lt_const", vec![value_arg(10)], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck], 1)); "felt_assert_lt_const<10>(-1)")]
+#[test_case("array_zip", vec![type_arg("Uint128AndFelt")],
+             vec![
+                Array(vec![Uint128(1), Uint128(2)]),
+                Array(vec![Felt(BigInt::from(3)), Felt(BigInt::from(4))]),
+             ]
+             => Ok((vec![Array(vec![
+                Struct(vec![Uint128(1), Felt(BigInt::from(3))]),
+                Struct(vec![Uint128(2), Felt(BigInt::from(4))]),
+             ])], 0)); "array_zip([1, 2], [3, 4])")]
+#[test_case("array_zip", vec![type_arg("Uint128AndFelt")],
+             vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Felt(BigInt::from(3))])]
+             => Ok((vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Felt(BigInt::from(3))])], 1));
+             "array_zip([1, 2], [3])")]
+#[test_case("array_eq", vec![type_arg("uint128")],
+             vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Uint128(1), Uint128(2)])], 0));
+             "array_eq([1, 2], [1, 2])")]
+#[test_case("array_eq", vec![type_arg("uint128")],
+             vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Uint128(1)])]
+             => Ok((vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Uint128(1)])], 1));
+             "array_eq([1, 2], [1])")]
+#[test_case("array_eq", vec![type_arg("uint128")],
+             vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Uint128(1), Uint128(3)])]
+             => Ok((vec![Array(vec![Uint128(1), Uint128(2)]), Array(vec![Uint128(1), Uint128(3)])], 1));
+             "array_eq([1, 2], [1, 3])")]
+#[test_case("array_at", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Felt(BigInt::from(1))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Uint128(4)], 0));
+             "array_at([3, 4], 1)")]
+#[test_case("array_at", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Felt(BigInt::from(2))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)])], 1));
+             "array_at([3, 4], 2)")]
+#[test_case("array_at", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3)]), Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3)])], 1));
+             "array_at([3], -1)")]
+#[test_case("array_pop_front", vec![type_arg("uint128")], vec![Array(vec![Uint128(3), Uint128(4)])]
+             => Ok((vec![Array(vec![Uint128(4)]), Uint128(3)], 0)); "array_pop_front([3, 4])")]
+#[test_case("array_pop_front", vec![type_arg("uint128")], vec![Array(vec![])]
+             => Ok((vec![Array(vec![])], 1)); "array_pop_front([])")]
+#[test_case("array_slice", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3), Uint128(4), Uint128(5)]), Felt(BigInt::from(1)), Felt(BigInt::from(2))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3), Uint128(4), Uint128(5)]), Array(vec![Uint128(4), Uint128(5)])], 0));
+             "array_slice([3, 4, 5], 1, 2)")]
+#[test_case("array_slice", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Felt(BigInt::from(2)), Felt(BigInt::from(0))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Array(vec![])], 0));
+             "array_slice([3, 4], 2, 0)")]
+#[test_case("array_slice", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Felt(BigInt::from(1)), Felt(BigInt::from(2))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)])], 1));
+             "array_slice([3, 4], 1, 2)")]
+#[test_case("array_slice", vec![type_arg("uint128")],
+             vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)]), Felt(BigInt::from(-1)), Felt(BigInt::from(1))]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(3), Uint128(4)])], 1));
+             "array_slice([3, 4], -1, 1)")]
+#[test_case("span_pop_front", vec![type_arg("uint128")], vec![uint128_span(&[3, 4], 0, 2)]
+             => Ok((vec![uint128_span(&[3, 4], 1, 1), Uint128(3)], 0)); "span_pop_front([3, 4])")]
+#[test_case("span_pop_front", vec![type_arg("uint128")], vec![uint128_span(&[3, 4], 1, 1)]
+             => Ok((vec![uint128_span(&[3, 4], 2, 0), Uint128(4)], 0)); "span_pop_front([4])")]
+#[test_case("span_pop_front", vec![type_arg("uint128")], vec![uint128_span(&[3, 4], 2, 0)]
+             => Ok((vec![uint128_span(&[3, 4], 2, 0)], 1)); "span_pop_front([])")]
+#[test_case("array_find_const", vec![value_arg(3)],
+             vec![RangeCheck, Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(3)), Felt(BigInt::from(3))])]
+             => Ok((vec![
+                RangeCheck,
+                Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(3)), Felt(BigInt::from(3))]),
+                Felt(BigInt::from(1)),
+             ], 0)); "array_find_const<3>([1, 3, 3])")]
+#[test_case("array_find_const", vec![value_arg(3)],
+             vec![RangeCheck, Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(2))])]
+             => Ok((vec![RangeCheck, Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(2))])], 1));
+             "array_find_const<3>([1, 2])")]
+#[test_case("pack_felts", vec![value_arg(8)],
+             vec![RangeCheck, Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(2))])]
+             => Ok((vec![RangeCheck, Felt(BigInt::from(0x201))], 0)); "pack_felts<8>([1, 2])")]
+#[test_case("pack_felts", vec![value_arg(8)],
+             vec![RangeCheck, Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(256))])]
+             => Ok((vec![RangeCheck], 1)); "pack_felts<8>([1, 256])")]
+#[test_case("pack_felts", vec![value_arg(252)],
+             vec![RangeCheck, Array(vec![Felt(BigInt::from(0)), Felt(BigInt::from(1))])]
+             => Ok((vec![RangeCheck], 1)); "pack_felts<252>([0, 1])")]
+#[test_case("pack_felts", vec![value_arg(252)],
+             vec![RangeCheck, Array(vec![Felt(BigInt::from(-1))])]
+             => Ok((vec![RangeCheck, Felt(BigInt::from(-1))], 0)); "pack_felts<252>([-1])")]
+#[test_case("felt_popcount", vec![value_arg(8)], vec![Felt(BigInt::from(0))]
+             => Ok((vec![Felt(BigInt::from(0))], 0)); "felt_popcount<8>(0)")]
+#[test_case("felt_popcount", vec![value_arg(8)], vec![Felt(BigInt::from(0xff))]
+             => Ok((vec![Felt(BigInt::from(8))], 0)); "felt_popcount<8>(0xff)")]
+#[test_case("felt_popcount", vec![value_arg(8)], vec![Felt(BigInt::from(0x1b5))]
+             => Ok((vec![Felt(BigInt::from(5))], 0)); "felt_popcount<8>(0x1b5)")]
 #[test_case("uint128_add", vec![], vec![RangeCheck, Uint128(2), Uint128(3)] => Ok((vec![RangeCheck, Uint128(5)], 0));
             "uint128_add(2, 3)")]
 #[test_case("uint128_sub", vec![], vec![RangeCheck, Uint128(5), Uint128(3)] => Ok((vec![RangeCheck, Uint128(2)], 0));
@@ -147,6 +377,165 @@ fn simulate(
             "uint128_mul(5, 3)")]
 #[test_case("uint128_sub", vec![], vec![RangeCheck, Uint128(3), Uint128(5)] => Ok((vec![RangeCheck], 1));
             "uint128_sub(3, 5)")]
+#[test_case("felt_clamp", vec![type_arg("uint128")], vec![RangeCheck, Felt(BigInt::from(5))]
+             => Ok((vec![RangeCheck, Uint128(5)], 0)); "felt_clamp<uint128>(5)")]
+#[test_case("felt_clamp", vec![type_arg("uint128")], vec![RangeCheck, Felt(BigInt::from(1) << 130)]
+             => Ok((vec![RangeCheck, Uint128(u128::MAX)], 0)); "felt_clamp<uint128>(2^130)")]
+#[test_case("felt_clamp", vec![type_arg("uint128")], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck, Uint128(u128::MAX)], 0)); "felt_clamp<uint128>(-1)")]
+#[test_case("uint8_add", vec![], vec![RangeCheck, Uint8(200), Uint8(55)] => Ok((vec![RangeCheck, Uint8(255)], 0));
+            "uint8_add(200, 55)")]
+#[test_case("uint8_add", vec![], vec![RangeCheck, Uint8(200), Uint8(56)] => Ok((vec![RangeCheck], 1));
+            "uint8_add(200, 56)")]
+#[test_case("uint64_sub", vec![], vec![RangeCheck, Uint64(5), Uint64(3)] => Ok((vec![RangeCheck, Uint64(2)], 0));
+            "uint64_sub(5, 3)")]
+#[test_case("uint64_sub", vec![], vec![RangeCheck, Uint64(3), Uint64(5)] => Ok((vec![RangeCheck], 1));
+            "uint64_sub(3, 5)")]
+#[test_case("uint8_lt", vec![], vec![RangeCheck, Uint8(2), Uint8(3)] => Ok((vec![RangeCheck], 1));
+            "uint8_lt(2, 3)")]
+#[test_case("uint8_le", vec![], vec![RangeCheck, Uint8(3), Uint8(2)] => Ok((vec![RangeCheck], 0));
+            "uint8_le(3, 2)")]
+#[test_case("uint8_add", vec![], vec![RangeCheck, Uint8(2), Uint64(3)] => Err(MemoryLayoutMismatch);
+            "uint8_add(2, uint64)")]
+#[test_case("downcast", vec![type_arg("uint64"), type_arg("uint8")], vec![RangeCheck, Uint64(255)]
+             => Ok((vec![RangeCheck, Uint8(255)], 0)); "downcast<uint64, uint8>(255)")]
+#[test_case("downcast", vec![type_arg("uint64"), type_arg("uint8")], vec![RangeCheck, Uint64(256)]
+             => Ok((vec![RangeCheck], 1)); "downcast<uint64, uint8>(256)")]
+#[test_case("downcast", vec![type_arg("felt"), type_arg("uint128")], vec![RangeCheck, Felt(BigInt::from(5))]
+             => Ok((vec![RangeCheck, Uint128(5)], 0)); "downcast<felt, uint128>(5)")]
+#[test_case("downcast", vec![type_arg("felt"), type_arg("uint128")], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck], 1)); "downcast<felt, uint128>(-1)")]
+#[test_case("felt_clamp", vec![type_arg("uint8")], vec![RangeCheck, Felt(BigInt::from(300))]
+             => Ok((vec![RangeCheck, Uint8(255)], 0)); "felt_clamp<uint8>(300)")]
+#[test_case("u256_add", vec![],
+             vec![RangeCheck, Struct(vec![Uint128(u128::MAX), Uint128(0)]), Struct(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![RangeCheck, Struct(vec![Uint128(0), Uint128(3)])], 0)); "u256_add(2^128 - 1, 2^129 + 1)")]
+#[test_case("u256_add", vec![],
+             vec![RangeCheck, Struct(vec![Uint128(0), Uint128(u128::MAX)]), Struct(vec![Uint128(0), Uint128(1)])]
+             => Ok((vec![RangeCheck], 1)); "u256_add(overflow)")]
+#[test_case("u256_sub", vec![],
+             vec![RangeCheck, Struct(vec![Uint128(0), Uint128(1)]), Struct(vec![Uint128(1), Uint128(0)])]
+             => Ok((vec![RangeCheck, Struct(vec![Uint128(u128::MAX), Uint128(0)])], 0)); "u256_sub(2^128, 1)")]
+#[test_case("u256_sub", vec![],
+             vec![RangeCheck, Struct(vec![Uint128(1), Uint128(0)]), Struct(vec![Uint128(0), Uint128(1)])]
+             => Ok((vec![RangeCheck], 1)); "u256_sub(underflow)")]
+#[test_case("u256_mul", vec![],
+             vec![RangeCheck, Struct(vec![Uint128(u128::MAX), Uint128(0)]), Struct(vec![Uint128(2), Uint128(0)])]
+             => Ok((vec![RangeCheck, Struct(vec![Uint128(u128::MAX - 1), Uint128(1)])], 0));
+            "u256_mul(2^128 - 1, 2)")]
+#[test_case("u256_mul", vec![],
+             vec![RangeCheck, Struct(vec![Uint128(0), Uint128(1)]), Struct(vec![Uint128(0), Uint128(1)])]
+             => Ok((vec![RangeCheck], 1)); "u256_mul(overflow)")]
+#[test_case("bytes31_try_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(0x616263))]
+             => Ok((vec![RangeCheck, Bytes31(BigInt::from(0x616263))], 0)); "bytes31_try_from_felt('abc')")]
+#[test_case("bytes31_try_from_felt", vec![], vec![RangeCheck, Felt((BigInt::from(1) << 248) - 1)]
+             => Ok((vec![RangeCheck, Bytes31((BigInt::from(1) << 248) - 1)], 0));
+            "bytes31_try_from_felt(2^248 - 1)")]
+#[test_case("bytes31_try_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(1) << 248)]
+             => Ok((vec![RangeCheck], 1)); "bytes31_try_from_felt(2^248)")]
+#[test_case("bytes31_try_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck], 1)); "bytes31_try_from_felt(-1)")]
+#[test_case("bytes31_concat", vec![],
+             vec![RangeCheck, Bytes31(BigInt::from(0x6162)), Bytes31(BigInt::from(0x63)), Uint8(1)]
+             => Ok((vec![RangeCheck, Bytes31(BigInt::from(0x616263))], 0)); "bytes31_concat('ab', 'c', 1)")]
+#[test_case("bytes31_concat", vec![],
+             vec![RangeCheck, Bytes31(BigInt::from(0x61)), Bytes31(BigInt::from(0x63)), Uint8(2)]
+             => Ok((vec![RangeCheck, Bytes31(BigInt::from(0x610063))], 0)); "bytes31_concat('a', '\\0c', 2)")]
+#[test_case("bytes31_concat", vec![],
+             vec![RangeCheck, Bytes31(BigInt::from(0x61)), Bytes31(BigInt::from(0x6263)), Uint8(1)]
+             => Ok((vec![RangeCheck], 1)); "bytes31_concat('a', 'bc', 1)")]
+#[test_case("bytes31_concat", vec![],
+             vec![RangeCheck, Bytes31(BigInt::from(0x61)), Bytes31(BigInt::from(0)), Uint8(31)]
+             => Ok((vec![RangeCheck], 1)); "bytes31_concat(too long)")]
+#[test_case("bytes31_split", vec![], vec![RangeCheck, Bytes31(BigInt::from(0x616263)), Uint8(1)]
+             => Ok((vec![RangeCheck, Bytes31(BigInt::from(0x6162)), Bytes31(BigInt::from(0x63))], 0));
+            "bytes31_split('abc', 1)")]
+#[test_case("bytes31_split", vec![], vec![RangeCheck, Bytes31(BigInt::from(0x616263)), Uint8(31)]
+             => Ok((vec![RangeCheck, Bytes31(BigInt::from(0)), Bytes31(BigInt::from(0x616263))], 0));
+            "bytes31_split('abc', 31)")]
+#[test_case("bytes31_split", vec![], vec![RangeCheck, Bytes31(BigInt::from(0x616263)), Uint8(32)]
+             => Ok((vec![RangeCheck], 1)); "bytes31_split('abc', 32)")]
+#[test_case("contract_address_try_from_felt252", vec![], vec![RangeCheck, Felt(BigInt::from(5))]
+             => Ok((vec![RangeCheck, ContractAddress(BigInt::from(5))], 0));
+            "contract_address_try_from_felt252(5)")]
+#[test_case("contract_address_try_from_felt252", vec![],
+            vec![RangeCheck, Felt((BigInt::from(1) << 251) - 1)]
+             => Ok((vec![RangeCheck, ContractAddress((BigInt::from(1) << 251) - 1)], 0));
+            "contract_address_try_from_felt252(2^251 - 1)")]
+#[test_case("contract_address_try_from_felt252", vec![], vec![RangeCheck, Felt(BigInt::from(1) << 251)]
+             => Ok((vec![RangeCheck], 1)); "contract_address_try_from_felt252(2^251)")]
+#[test_case("class_hash_try_from_felt252", vec![], vec![RangeCheck, Felt(BigInt::from(7))]
+             => Ok((vec![RangeCheck, ClassHash(BigInt::from(7))], 0)); "class_hash_try_from_felt252(7)")]
+#[test_case("class_hash_try_from_felt252", vec![], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck], 1)); "class_hash_try_from_felt252(-1)")]
+#[test_case("contract_address_try_from_felt252", vec![],
+            vec![RangeCheck, Felt((BigInt::from(1) << 251) - 1 - felt_prime())]
+             => Ok((vec![RangeCheck, ContractAddress((BigInt::from(1) << 251) - 1)], 0));
+            "contract_address_try_from_felt252(reduced 2^251 - 1)")]
+#[test_case("class_hash_try_from_felt252", vec![],
+            vec![RangeCheck, Felt((BigInt::from(1) << 251) - felt_prime())]
+             => Ok((vec![RangeCheck], 1)); "class_hash_try_from_felt252(reduced 2^251)")]
+#[test_case("uint128_array_sum", vec![], vec![RangeCheck, Array(vec![Uint128(2), Uint128(3), Uint128(4)])]
+             => Ok((vec![RangeCheck, Uint128(9)], 0)); "uint128_array_sum([2, 3, 4])")]
+#[test_case("uint128_array_sum", vec![], vec![RangeCheck, Array(vec![])]
+             => Ok((vec![RangeCheck, Uint128(0)], 0)); "uint128_array_sum([])")]
+#[test_case("uint128_array_sum", vec![],
+             vec![RangeCheck, Array(vec![Uint128(1), Uint128(u128::MAX), Uint128(2)])]
+             => Ok((vec![RangeCheck], 1)); "uint128_array_sum([1, max, 2])")]
+#[test_case("uint128_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(5))]
+             => Ok((vec![RangeCheck, Uint128(5)], 0)); "uint128_from_felt(5)")]
+#[test_case("uint128_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(u128::MAX))]
+             => Ok((vec![RangeCheck, Uint128(u128::MAX)], 0)); "uint128_from_felt(2^128 - 1)")]
+#[test_case("uint128_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(1) << 128)]
+             => Ok((vec![RangeCheck], 1)); "uint128_from_felt(2^128)")]
+#[test_case("uint128_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok((vec![RangeCheck], 1)); "uint128_from_felt(-1)")]
+#[test_case("u128_shl", vec![], vec![RangeCheck, Uint128(3), Uint128(4)]
+             => Ok((vec![RangeCheck, Uint128(48)], 0)); "u128_shl(3, 4)")]
+#[test_case("u128_shl", vec![], vec![RangeCheck, Uint128(1), Uint128(127)]
+             => Ok((vec![RangeCheck, Uint128(1 << 127)], 0)); "u128_shl(1, 127)")]
+#[test_case("u128_shl", vec![], vec![RangeCheck, Uint128(2), Uint128(127)]
+             => Ok((vec![RangeCheck], 1)); "u128_shl(2, 127)")]
+#[test_case("u128_shl", vec![], vec![RangeCheck, Uint128(1), Uint128(128)]
+             => Ok((vec![RangeCheck], 1)); "u128_shl(1, 128)")]
+#[test_case("u128_shl", vec![], vec![RangeCheck, Uint128(0), Uint128(u128::MAX)]
+             => Ok((vec![RangeCheck, Uint128(0)], 0)); "u128_shl(0, max)")]
+#[test_case("u128_shl", vec![value_arg(4)], vec![RangeCheck, Uint128(3)]
+             => Ok((vec![RangeCheck, Uint128(48)], 0)); "u128_shl<4>(3)")]
+#[test_case("u128_shl", vec![value_arg(127)], vec![RangeCheck, Uint128(3)]
+             => Ok((vec![RangeCheck], 1)); "u128_shl<127>(3)")]
+#[test_case("u128_shr", vec![], vec![RangeCheck, Uint128(48), Uint128(4)]
+             => Ok((vec![RangeCheck, Uint128(3)], 0)); "u128_shr(48, 4)")]
+#[test_case("u128_shr", vec![], vec![RangeCheck, Uint128(u128::MAX), Uint128(128)]
+             => Ok((vec![RangeCheck, Uint128(0)], 0)); "u128_shr(max, 128)")]
+#[test_case("u128_shr", vec![value_arg(127)], vec![RangeCheck, Uint128(u128::MAX)]
+             => Ok((vec![RangeCheck, Uint128(1)], 0)); "u128_shr<127>(max)")]
+#[test_case("pow", vec![type_arg("uint128"), value_arg(3)], vec![RangeCheck, Uint128(5)]
+             => Ok((vec![RangeCheck, Uint128(125)], 0)); "pow<uint128, 3>(5)")]
+#[test_case("pow", vec![type_arg("uint128"), value_arg(200)], vec![RangeCheck, Uint128(1)]
+             => Ok((vec![RangeCheck, Uint128(1)], 0)); "pow<uint128, 200>(1)")]
+#[test_case("pow", vec![type_arg("uint128"), value_arg(2)], vec![RangeCheck, Uint128(1 << 64)]
+             => Ok((vec![RangeCheck], 1)); "pow<uint128, 2>(2^64)")]
+#[test_case("pow", vec![type_arg("uint128"), value_arg(0)], vec![RangeCheck, Uint128(0)]
+             => Ok((vec![RangeCheck, Uint128(1)], 0)); "pow<uint128, 0>(0)")]
+#[test_case("bool_from_felt", vec![], vec![Felt(BigInt::from(1))] => Ok((vec![Bool(true)], 0));
+            "bool_from_felt(1)")]
+#[test_case("bool_from_felt", vec![], vec![Felt(BigInt::from(0))] => Ok((vec![Bool(false)], 0));
+            "bool_from_felt(0)")]
+#[test_case("bool_from_felt", vec![], vec![Felt(BigInt::from(2))] => Ok((vec![], 1));
+            "bool_from_felt(2)")]
+#[test_case("match_nullable", vec![type_arg("felt")], vec![Null] => Ok((vec![], 0));
+            "match_nullable<felt>(null)")]
+#[test_case("match_nullable", vec![type_arg("felt")], vec![Felt(BigInt::from(3))]
+             => Ok((vec![Felt(BigInt::from(3))], 1)); "match_nullable<felt>(3)")]
+#[test_case("ecdsa_verify", vec![],
+             vec![Signature, Felt(BigInt::from(5)), Felt(BigInt::from(7)), Felt(BigInt::from(2)),
+                  Felt(BigInt::from(3))]
+             => Ok((vec![Signature], 0)); "ecdsa_verify(5, 7, 2, 3)")]
+#[test_case("ecdsa_verify", vec![],
+             vec![Signature, Felt(BigInt::from(5)), Felt(BigInt::from(7)), Felt(BigInt::from(2)),
+                  Felt(BigInt::from(4))]
+             => Ok((vec![Signature], 1)); "ecdsa_verify(5, 7, 2, 4)")]
 fn simulate_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -157,9 +546,111 @@ fn simulate_branch(
 
 /// Tests for simulation of a non branch invocations.
 #[test_case("refund_gas", vec![], vec![GasBuiltin(2)] => Ok(vec![GasBuiltin(6)]); "refund_gas(2)")]
+#[test_case("felt_const", vec![value_arg(-5)], vec![] => Ok(vec![Felt(BigInt::from(-5))]);
+            "felt_const<-5>()")]
+#[test_case("felt_const", vec![GenericArg::Value(felt_prime() - 5)], vec![]
+             => Ok(vec![Felt(BigInt::from(-5))]); "felt_const<prime - 5>()")]
+#[test_case("felt_const", vec![GenericArg::Value(5 - felt_prime())], vec![]
+             => Ok(vec![Felt(BigInt::from(5))]); "felt_const<5 - prime>()")]
+#[test_case("felt_const", vec![GenericArg::Value((felt_prime() - 1) / 2)], vec![]
+             => Ok(vec![Felt((felt_prime() - 1) / 2)]); "felt_const<half prime>()")]
+#[test_case("felt_const", vec![GenericArg::Value((felt_prime() + 1) / 2)], vec![]
+             => Ok(vec![Felt((1 - felt_prime()) / 2)]); "felt_const<half prime + 1>()")]
+#[test_case("felt_const", vec![GenericArg::ShortString("abc".into())], vec![]
+             => Ok(vec![Felt(BigInt::from(0x616263))]); "felt_const<'abc'>()")]
+#[test_case("felt_const", vec![GenericArg::ShortString("".into())], vec![]
+             => Ok(vec![Felt(BigInt::from(0))]); "felt_const<''>()")]
+#[test_case("const_as_immediate", vec![type_arg("FeltPair"), GenericArg::Value(BigInt::from(1)),
+                                       GenericArg::ShortString("ab".into())], vec![]
+             => Ok(vec![Struct(vec![Felt(BigInt::from(1)), Felt(BigInt::from(0x6162))])]);
+            "const_as_immediate<FeltPair, 1, 'ab'>()")]
+#[test_case("const_as_immediate", vec![type_arg("Option"), GenericArg::Value(BigInt::from(0)),
+                                       GenericArg::Value(BigInt::from(7))], vec![]
+             => Ok(vec![Enum { value: Box::new(Felt(BigInt::from(7))), index: 0 }]);
+            "const_as_immediate<Option, 0, 7>()")]
+#[test_case("const_as_immediate", vec![type_arg("Option"), GenericArg::Value(BigInt::from(1))], vec![]
+             => Ok(vec![Enum { value: Box::new(Struct(vec![])), index: 1 }]);
+            "const_as_immediate<Option, 1>()")]
+#[test_case("option_some", vec![type_arg("felt")], vec![Felt(BigInt::from(7))]
+             => Ok(vec![Enum { value: Box::new(Felt(BigInt::from(7))), index: 0 }]);
+            "option_some<felt>(7)")]
+#[test_case("option_none", vec![type_arg("felt")], vec![]
+             => Ok(vec![Enum { value: Box::new(Struct(vec![])), index: 1 }]);
+            "option_none<felt>()")]
+#[test_case("result_ok", vec![type_arg("felt"), type_arg("uint128")], vec![Felt(BigInt::from(7))]
+             => Ok(vec![Enum { value: Box::new(Felt(BigInt::from(7))), index: 0 }]);
+            "result_ok<felt, uint128>(7)")]
+#[test_case("result_err", vec![type_arg("felt"), type_arg("uint128")], vec![Uint128(3)]
+             => Ok(vec![Enum { value: Box::new(Uint128(3)), index: 1 }]);
+            "result_err<felt, uint128>(3)")]
+#[test_case("pow", vec![type_arg("felt"), value_arg(3)], vec![Felt(BigInt::from(-2))]
+             => Ok(vec![Felt(BigInt::from(-8))]); "pow<felt, 3>(-2)")]
+#[test_case("pow", vec![type_arg("felt"), value_arg(0)], vec![Felt(BigInt::from(0))]
+             => Ok(vec![Felt(BigInt::from(1))]); "pow<felt, 0>(0)")]
+#[test_case("pow", vec![type_arg("felt"),
+                        GenericArg::Value((BigInt::from(1) << 251) + 17 * (BigInt::from(1) << 192))],
+            vec![Felt(BigInt::from(3))]
+             => Ok(vec![Felt(BigInt::from(1))]); "pow<felt, prime - 1>(3)")]
+#[test_case("felt_add", vec![], vec![Felt(BigInt::from(2)), Felt(BigInt::from(3))]
+             => Ok(vec![Felt(BigInt::from(5))]); "felt_add(2, 3)")]
+#[test_case("felt_sub", vec![], vec![Felt(BigInt::from(2)), Felt(BigInt::from(3))]
+             => Ok(vec![Felt(BigInt::from(-1))]); "felt_sub(2, 3)")]
+#[test_case("felt_div", vec![], vec![Felt(BigInt::from(6)), NonZero(Box::new(Felt(BigInt::from(3))))]
+             => Ok(vec![Felt(BigInt::from(2))]); "felt_div(6, 3)")]
+#[test_case("felt_div", vec![value_arg(2)], vec![Felt(BigInt::from(-8))]
+             => Ok(vec![Felt(BigInt::from(-4))]); "felt_div<2>(-8)")]
+#[test_case("felt_div", vec![], vec![Felt(BigInt::from(1)), NonZero(Box::new(Felt(BigInt::from(2))))]
+             => Ok(vec![Felt((BigInt::from(1) - felt_prime()) / 2)]); "felt_div(1, 2)")]
+#[test_case("felt_inv", vec![], vec![NonZero(Box::new(Felt(BigInt::from(2))))]
+             => Ok(vec![NonZero(Box::new(Felt((BigInt::from(1) - felt_prime()) / 2)))]); "felt_inv(2)")]
+#[test_case("snapshot_take", vec![type_arg("felt")], vec![Felt(BigInt::from(3))]
+             => Ok(vec![Felt(BigInt::from(3)), Felt(BigInt::from(3))]); "snapshot_take<felt>(3)")]
+#[test_case("null", vec![type_arg("felt")], vec![] => Ok(vec![Null]); "null<felt>()")]
+#[test_case("nullable_from_box", vec![type_arg("felt")], vec![Felt(BigInt::from(3))]
+             => Ok(vec![Felt(BigInt::from(3))]); "nullable_from_box<felt>(3)")]
+#[test_case("bool_and", vec![], vec![Bool(true), Bool(false)] => Ok(vec![Bool(false)]);
+            "bool_and(true, false)")]
+#[test_case("bool_or", vec![], vec![Bool(true), Bool(false)] => Ok(vec![Bool(true)]);
+            "bool_or(true, false)")]
+#[test_case("bool_xor", vec![], vec![Bool(true), Bool(true)] => Ok(vec![Bool(false)]);
+            "bool_xor(true, true)")]
+#[test_case("bool_not", vec![], vec![Bool(false)] => Ok(vec![Bool(true)]); "bool_not(false)")]
+#[test_case("uint128_to_felt", vec![], vec![Uint128(u128::MAX)]
+             => Ok(vec![Felt(BigInt::from(u128::MAX))]); "uint128_to_felt(2^128 - 1)")]
+#[test_case("bool_to_felt", vec![], vec![Bool(true)] => Ok(vec![Felt(BigInt::from(1))]);
+            "bool_to_felt(true)")]
+#[test_case("bitwise_and", vec![], vec![Bitwise, Felt(BigInt::from(12)), Felt(BigInt::from(10))]
+             => Ok(vec![Bitwise, Felt(BigInt::from(8))]); "bitwise_and(12, 10)")]
+#[test_case("bitwise_or", vec![], vec![Bitwise, Felt(BigInt::from(12)), Felt(BigInt::from(10))]
+             => Ok(vec![Bitwise, Felt(BigInt::from(14))]); "bitwise_or(12, 10)")]
+#[test_case("bitwise_xor", vec![], vec![Bitwise, Felt(BigInt::from(12)), Felt(BigInt::from(10))]
+             => Ok(vec![Bitwise, Felt(BigInt::from(6))]); "bitwise_xor(12, 10)")]
+#[test_case("gas_mark", vec![], vec![GasBuiltin(7)] => Ok(vec![GasBuiltin(7), Uint128(7)]);
+            "gas_mark(7)")]
+#[test_case("gas_elapsed", vec![], vec![GasBuiltin(3), Uint128(7)]
+             => Ok(vec![GasBuiltin(3), Uint128(4)]); "gas_elapsed(3, 7)")]
+#[test_case("gas_elapsed", vec![], vec![GasBuiltin(7), Uint128(7)]
+             => Ok(vec![GasBuiltin(7), Uint128(0)]); "gas_elapsed(7, 7)")]
 #[test_case("array_new", vec![type_arg("uint128")], vec![] => Ok(vec![Array(vec![])]); "array_new()")]
 #[test_case("array_append", vec![type_arg("uint128")], vec![Array(vec![]), Uint128(4)] =>
             Ok(vec![Array(vec![Uint128(4)])]); "array_append([], 4)")]
+#[test_case("array_rotate", vec![type_arg("uint128"), value_arg(1)],
+            vec![Array(vec![Uint128(1), Uint128(2), Uint128(3)])] =>
+            Ok(vec![Array(vec![Uint128(2), Uint128(3), Uint128(1)])]); "array_rotate<1>([1, 2, 3])")]
+#[test_case("array_rotate", vec![type_arg("uint128"), value_arg(5)],
+            vec![Array(vec![Uint128(1), Uint128(2), Uint128(3)])] =>
+            Ok(vec![Array(vec![Uint128(3), Uint128(1), Uint128(2)])]); "array_rotate<5>([1, 2, 3])")]
+#[test_case("array_rotate", vec![type_arg("uint128"), value_arg(5)], vec![Array(vec![])] =>
+            Ok(vec![Array(vec![])]); "array_rotate<5>([])")]
+#[test_case("array_len", vec![type_arg("uint128")], vec![Array(vec![Uint128(3), Uint128(4)])] =>
+            Ok(vec![Array(vec![Uint128(3), Uint128(4)]), Felt(BigInt::from(2))]); "array_len([3, 4])")]
+#[test_case("array_span", vec![type_arg("uint128")], vec![Array(vec![Uint128(3), Uint128(4)])] =>
+            Ok(vec![Array(vec![Uint128(3), Uint128(4)]), uint128_span(&[3, 4], 0, 2)]);
+            "array_span([3, 4])")]
+#[test_case("span_len", vec![type_arg("uint128")], vec![uint128_span(&[3, 4, 5], 1, 2)] =>
+            Ok(vec![uint128_span(&[3, 4, 5], 1, 2), Felt(BigInt::from(2))]); "span_len([4, 5])")]
+#[test_case("array_reverse", vec![type_arg("uint128")], vec![Array(vec![])] =>
+            Ok(vec![Array(vec![])]); "array_reverse([])")]
 #[test_case("uint128_wrapping_add", vec![], vec![RangeCheck, Uint128(2), Uint128(3)] => Ok(vec![RangeCheck, Uint128(5)]);
             "uint128_wrapping_add(2, 3)")]
 #[test_case("uint128_wrapping_sub", vec![], vec![RangeCheck, Uint128(5), Uint128(3)] => Ok(vec![RangeCheck, Uint128(2)]);
@@ -176,17 +667,71 @@ fn simulate_branch(
             "uint128_wrapping_sub<3>(5)")]
 #[test_case("uint128_wrapping_mul", vec![value_arg(3)], vec![RangeCheck, Uint128(5)] => Ok(vec![RangeCheck, Uint128(15)]);
             "uint128_wrapping_mul<3>(5)")]
+#[test_case("u128_safe_divmod", vec![], vec![RangeCheck, Uint128(32), NonZero(Box::new(Uint128(5)))]
+             => Ok(vec![RangeCheck, Uint128(6), Uint128(2)]); "u128_safe_divmod(32, 5)")]
+#[test_case("u128_safe_divmod", vec![], vec![RangeCheck, Uint128(3), NonZero(Box::new(Uint128(5)))]
+             => Ok(vec![RangeCheck, Uint128(0), Uint128(3)]); "u128_safe_divmod(3, 5)")]
+#[test_case("u128_safe_divmod", vec![],
+            vec![RangeCheck, Uint128(u128::MAX), NonZero(Box::new(Uint128(u128::MAX)))]
+             => Ok(vec![RangeCheck, Uint128(1), Uint128(0)]); "u128_safe_divmod(max, max)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(0)] => Ok(vec![RangeCheck, Uint64(0)]);
+            "u128_sqrt(0)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(1)] => Ok(vec![RangeCheck, Uint64(1)]);
+            "u128_sqrt(1)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(48)] => Ok(vec![RangeCheck, Uint64(6)]);
+            "u128_sqrt(48)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(49)] => Ok(vec![RangeCheck, Uint64(7)]);
+            "u128_sqrt(49)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(50)] => Ok(vec![RangeCheck, Uint64(7)]);
+            "u128_sqrt(50)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(u128::MAX)]
+             => Ok(vec![RangeCheck, Uint64(u64::MAX)]); "u128_sqrt(max)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(u128::from(u64::MAX) * u128::from(u64::MAX))]
+             => Ok(vec![RangeCheck, Uint64(u64::MAX)]); "u128_sqrt((2^64 - 1)^2)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(u128::from(u64::MAX) * u128::from(u64::MAX) - 1)]
+             => Ok(vec![RangeCheck, Uint64(u64::MAX - 1)]); "u128_sqrt((2^64 - 1)^2 - 1)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(0), Uint128(0)]
+             => Ok(vec![RangeCheck, Uint128(0), Uint128(0)]); "u128_wide_mul(0, 0)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(0), Uint128(u128::MAX)]
+             => Ok(vec![RangeCheck, Uint128(0), Uint128(0)]); "u128_wide_mul(0, max)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(1), Uint128(1)]
+             => Ok(vec![RangeCheck, Uint128(0), Uint128(1)]); "u128_wide_mul(1, 1)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(1), Uint128(u128::MAX)]
+             => Ok(vec![RangeCheck, Uint128(0), Uint128(u128::MAX)]); "u128_wide_mul(1, max)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(2), Uint128(u128::MAX)]
+             => Ok(vec![RangeCheck, Uint128(1), Uint128(u128::MAX - 1)]); "u128_wide_mul(2, max)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(u128::MAX), Uint128(u128::MAX)]
+             => Ok(vec![RangeCheck, Uint128(u128::MAX - 1), Uint128(1)]); "u128_wide_mul(max, max)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(1 << 64), Uint128(1 << 64)]
+             => Ok(vec![RangeCheck, Uint128(1), Uint128(0)]); "u128_wide_mul(2^64, 2^64)")]
 #[test_case("uint128_div", vec![value_arg(5)], vec![RangeCheck, Uint128(32)] => Ok(vec![RangeCheck, Uint128(6)]);
             "uint128_div<5>(32)")]
 #[test_case("uint128_mod", vec![value_arg(5)], vec![RangeCheck, Uint128(32)] => Ok(vec![RangeCheck, Uint128(2)]);
             "uint128_mod<5>(32)")]
+#[test_case("bytes31_to_felt", vec![], vec![Bytes31(BigInt::from(0x616263))]
+             => Ok(vec![Felt(BigInt::from(0x616263))]); "bytes31_to_felt('abc')")]
+#[test_case("u256_construct", vec![], vec![Uint128(1), Uint128(2)]
+             => Ok(vec![Struct(vec![Uint128(1), Uint128(2)])]); "u256_construct(1, 2)")]
+#[test_case("u256_deconstruct", vec![], vec![Struct(vec![Uint128(1), Uint128(2)])]
+             => Ok(vec![Uint128(1), Uint128(2)]); "u256_deconstruct(2^128 + 1)")]
+#[test_case("upcast", vec![type_arg("uint8"), type_arg("uint64")], vec![Uint8(200)]
+             => Ok(vec![Uint64(200)]); "upcast<uint8, uint64>(200)")]
+#[test_case("upcast", vec![type_arg("uint128"), type_arg("felt")], vec![Uint128(u128::MAX)]
+             => Ok(vec![Felt(BigInt::from(u128::MAX))]); "upcast<uint128, felt>(2^128 - 1)")]
+#[test_case("uint8_const", vec![value_arg(3)], vec![] => Ok(vec![Uint8(3)]); "uint8_const<3>()")]
+#[test_case("uint64_const", vec![value_arg(3)], vec![] => Ok(vec![Uint64(3)]);
+            "uint64_const<3>()")]
 #[test_case("uint128_const", vec![value_arg(3)], vec![] => Ok(vec![Uint128(3)]);
             "uint128_const<3>()")]
 #[test_case("dup", vec![type_arg("uint128")], vec![Uint128(24)]
              => Ok(vec![Uint128(24), Uint128(24)]); "dup<uint128>(24)")]
 #[test_case("drop", vec![type_arg("uint128")], vec![Uint128(2)] => Ok(vec![]); "drop<uint128>(2)")]
-#[test_case("unwrap_nz", vec![type_arg("uint128")], vec![NonZero(Box::new(Uint128(6)))]
-             => Ok(vec![Uint128(6)]); "unwrap_nz<uint128>(6)")]
+#[test_case("debug_print", vec![type_arg("uint128")], vec![Uint128(2)] => Ok(vec![]);
+            "debug_print<uint128>(2)")]
+#[test_case("unwrap_non_zero", vec![type_arg("uint128")], vec![NonZero(Box::new(Uint128(6)))]
+             => Ok(vec![Uint128(6)]); "unwrap_non_zero<uint128>(6)")]
+#[test_case("unit", vec![], vec![] => Ok(vec![Unit]); "unit()")]
+#[test_case("store_temp", vec![type_arg("Unit")], vec![Unit] => Ok(vec![Unit]); "store_temp<Unit>(())")]
 #[test_case("store_temp", vec![type_arg("uint128")], vec![Uint128(6)] => Ok(vec![Uint128(6)]);
             "store_temp<uint128>(6)")]
 #[test_case("align_temps", vec![type_arg("uint128")], vec![] => Ok(vec![]);
@@ -194,12 +739,70 @@ fn simulate_branch(
 #[test_case("store_local", vec![type_arg("uint128")], vec![Uninitialized, Uint128(6)]
              => Ok(vec![Uint128(6)]); "store_local<uint128>(_, 6)")]
 #[test_case("finalize_locals", vec![], vec![] => Ok(vec![]); "finalize_locals()")]
+#[test_case("branch_align", vec![], vec![] => Ok(vec![]); "branch_align()")]
 #[test_case("rename", vec![type_arg("uint128")], vec![Uint128(6)] => Ok(vec![Uint128(6)]);
             "rename<uint128>(6)")]
 #[test_case("function_call", vec![user_func_arg("drop_all_inputs")], vec![Uint128(3), Uint128(5)]
              => Ok(vec![]); "function_call<drop_all_inputs>()")]
 #[test_case("function_call", vec![user_func_arg("identity")], vec![Uint128(3), Uint128(5)]
              => Ok(vec![Uint128(3), Uint128(5)]); "function_call<identity>()")]
+#[test_case("dict_felt_to_new", vec![type_arg("felt")], vec![SegmentArena, Felt(BigInt::from(0))]
+             => Ok(vec![SegmentArena, felt_dict(&[], 0)]); "dict_felt_to_new<felt>(0)")]
+#[test_case("dict_felt_to_write", vec![type_arg("felt")],
+            vec![felt_dict(&[], 0), Felt(BigInt::from(3)), Felt(BigInt::from(7))]
+             => Ok(vec![felt_dict_with_accesses(&[(3, 7)], &[(3, 0, 7)], 0)]);
+            "dict_felt_to_write<felt>({}, 3, 7)")]
+#[test_case("dict_felt_to_read", vec![type_arg("felt")],
+            vec![felt_dict(&[(3, 7)], 0), Felt(BigInt::from(3))]
+             => Ok(vec![felt_dict_with_accesses(&[(3, 7)], &[(3, 7, 7)], 0), Felt(BigInt::from(7))]);
+            "dict_felt_to_read<felt>({3: 7}, 3)")]
+#[test_case("dict_felt_to_read", vec![type_arg("felt")],
+            vec![felt_dict(&[(3, 7)], 5), Felt(BigInt::from(4))]
+             => Ok(vec![felt_dict_with_accesses(&[(3, 7)], &[(4, 5, 5)], 5), Felt(BigInt::from(5))]);
+            "dict_felt_to_read<felt>({3: 7}, 4)")]
+#[test_case("dict_felt_to_squash", vec![type_arg("felt")],
+            vec![RangeCheck, SegmentArena,
+                 felt_dict_with_accesses(&[(3, 8), (1, 2)], &[(3, 0, 7), (1, 0, 2), (3, 7, 8)], 0)]
+             => Ok(vec![RangeCheck, SegmentArena, Array(vec![
+                 Struct(vec![Felt(BigInt::from(1)), Felt(BigInt::from(0)), Felt(BigInt::from(2))]),
+                 Struct(vec![Felt(BigInt::from(3)), Felt(BigInt::from(0)), Felt(BigInt::from(7))]),
+                 Struct(vec![Felt(BigInt::from(3)), Felt(BigInt::from(7)), Felt(BigInt::from(8))]),
+             ])]);
+            "dict_felt_to_squash<felt>({3: 8, 1: 2})")]
+#[test_case("dict_entry_get", vec![type_arg("felt")],
+            vec![felt_dict(&[(3, 7)], 5), Felt(BigInt::from(4))]
+             => Ok(vec![
+                 DictEntry { dict: Box::new(felt_dict(&[(3, 7)], 5)), key: BigInt::from(4) },
+                 Felt(BigInt::from(5)),
+             ]);
+            "dict_entry_get<felt>({3: 7}, 4)")]
+#[test_case("dict_entry_finalize", vec![type_arg("felt")],
+            vec![DictEntry { dict: Box::new(felt_dict(&[(3, 7)], 0)), key: BigInt::from(3) },
+                 Felt(BigInt::from(8))]
+             => Ok(vec![felt_dict_with_accesses(&[(3, 8)], &[(3, 7, 8)], 0)]);
+            "dict_entry_finalize<felt>({3: 7}[3], 8)")]
+#[test_case("storage_base_address_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(7))]
+             => Ok(vec![RangeCheck, StorageBaseAddress(BigInt::from(7))]);
+            "storage_base_address_from_felt(7)")]
+#[test_case("storage_base_address_from_felt", vec![],
+            vec![RangeCheck, Felt((BigInt::from(1) << 251) - 255)]
+             => Ok(vec![RangeCheck, StorageBaseAddress(BigInt::from(1))]);
+            "storage_base_address_from_felt(2^251 - 255)")]
+#[test_case("storage_base_address_from_felt", vec![], vec![RangeCheck, Felt(BigInt::from(-1))]
+             => Ok(vec![RangeCheck, StorageBaseAddress((BigInt::from(17) << 192) + 256)]);
+            "storage_base_address_from_felt(-1)")]
+#[test_case("storage_address_from_base_and_offset", vec![],
+            vec![StorageBaseAddress(BigInt::from(10)), Uint8(255)]
+             => Ok(vec![StorageAddress(BigInt::from(265))]);
+            "storage_address_from_base_and_offset(10, 255)")]
+#[test_case("storage_address_from_base_and_offset", vec![value_arg(2)],
+            vec![StorageBaseAddress(BigInt::from(10))]
+             => Ok(vec![StorageAddress(BigInt::from(12))]);
+            "storage_address_from_base_and_offset<2>(10)")]
+#[test_case("coupon_buy", vec![user_func_arg("identity")], vec![] => Ok(vec![Coupon]);
+            "coupon_buy<&identity>()")]
+#[test_case("coupon_refund", vec![user_func_arg("identity")], vec![Coupon] => Ok(vec![]);
+            "coupon_refund<&identity>(coupon)")]
 fn simulate_none_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -211,6 +814,221 @@ fn simulate_none_branch(
     })
 }
 
+#[test]
+fn simulate_dict_felt_to_write_then_read() {
+    let (outputs, _) = simulate(
+        "dict_felt_to_new",
+        vec![type_arg("felt")],
+        vec![SegmentArena, Felt(BigInt::from(0))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_write",
+        vec![type_arg("felt")],
+        vec![outputs[1].clone(), Felt(BigInt::from(1)), Felt(BigInt::from(10))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_read",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(BigInt::from(1))],
+    )
+    .unwrap();
+    assert_eq!(outputs[1], Felt(BigInt::from(10)));
+    let (outputs, _) = simulate(
+        "dict_felt_to_read",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(BigInt::from(2))],
+    )
+    .unwrap();
+    assert_eq!(outputs[1], Felt(BigInt::from(0)));
+}
+
+#[test]
+fn simulate_dict_felt_to_read_key_by_its_felt_value() {
+    // The keys -1 and `prime - 1` are the same felt.
+    let (outputs, _) = simulate(
+        "dict_felt_to_write",
+        vec![type_arg("felt")],
+        vec![felt_dict(&[], 0), Felt(BigInt::from(-1)), Felt(BigInt::from(10))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_read",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(felt_prime() - 1)],
+    )
+    .unwrap();
+    assert_eq!(
+        outputs,
+        vec![
+            felt_dict_with_accesses(&[(-1, 10)], &[(-1, 0, 10), (-1, 10, 10)], 0),
+            Felt(BigInt::from(10))
+        ]
+    );
+}
+
+#[test]
+fn simulate_dict_felt_to_read_missing_key_of_non_felt_dict() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type uint128 = uint128;
+            type SegmentArena = SegmentArena;
+            type DictFeltToUint128 = DictFeltTo<uint128>;
+            libfunc uint128_const = uint128_const<5>;
+            libfunc felt_const = felt_const<3>;
+            libfunc dict_felt_to_new = dict_felt_to_new<uint128>;
+            libfunc dict_felt_to_read = dict_felt_to_read<uint128>;
+            libfunc drop_dict = drop<DictFeltToUint128>;
+            uint128_const() -> (default);
+            dict_felt_to_new(arena, default) -> (arena, dict);
+            felt_const() -> (key);
+            dict_felt_to_read(dict, key) -> (dict, value);
+            drop_dict(dict) -> ();
+            return(arena, value);
+            main@0(arena: SegmentArena) -> (SegmentArena, uint128);
+        "})
+        .unwrap();
+    // The missing key is read as the default value, of the dict's value type.
+    assert_eq!(
+        run(&program, &HashMap::new(), &"main".into(), vec![SegmentArena]),
+        Ok(vec![SegmentArena, Uint128(5)])
+    );
+}
+
+#[test]
+fn simulate_dict_entry_update() {
+    let dict = felt_dict(&[(1, 10)], 0);
+    let (outputs, _) =
+        simulate("dict_entry_get", vec![type_arg("felt")], vec![dict, Felt(BigInt::from(1))])
+            .unwrap();
+    assert_eq!(outputs[1], Felt(BigInt::from(10)));
+    let (outputs, _) = simulate(
+        "dict_entry_finalize",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(BigInt::from(11))],
+    )
+    .unwrap();
+    assert_eq!(outputs, vec![felt_dict_with_accesses(&[(1, 11)], &[(1, 10, 11)], 0)]);
+    // Finalizing again, with the dict returned by the first finalization.
+    assert_eq!(
+        simulate(
+            "dict_entry_finalize",
+            vec![type_arg("felt")],
+            vec![outputs[0].clone(), Felt(BigInt::from(12))],
+        ),
+        Err(NoPendingDictEntry)
+    );
+}
+
+#[test]
+fn simulate_circuit_evaluation() {
+    let circuit = || vec![type_arg("Circuit")];
+    let (outputs, branch) = simulate("init_circuit_data", circuit(), vec![]).unwrap();
+    assert_eq!((outputs.clone(), branch), (vec![CircuitInputs(vec![])], 0));
+    let (outputs, branch) =
+        simulate("add_circuit_input", circuit(), vec![outputs[0].clone(), Felt(BigInt::from(2))])
+            .unwrap();
+    assert_eq!(branch, 0);
+    let (outputs, branch) =
+        simulate("add_circuit_input", circuit(), vec![outputs[0].clone(), Felt(BigInt::from(11))])
+            .unwrap();
+    assert_eq!((outputs.clone(), branch), (vec![CircuitInputs(vec![2.into(), 11.into()])], 1));
+    let data = outputs[0].clone();
+    let eval = |modulus: i64| {
+        simulate(
+            "eval_circuit",
+            circuit(),
+            vec![AddMod, MulMod, data.clone(), NonZero(Box::new(Felt(BigInt::from(modulus))))],
+        )
+    };
+    // Modulo 7: (2 + 11) * 11 = 3, and the inverse of 2 is 4.
+    let (outputs, branch) = eval(7).unwrap();
+    assert_eq!(
+        (outputs.clone(), branch),
+        (vec![AddMod, MulMod, CircuitOutputs(vec![3.into(), 4.into()])], 0)
+    );
+    assert_eq!(
+        simulate(
+            "get_circuit_output",
+            vec![type_arg("Circuit"), value_arg(1)],
+            outputs[2..].to_vec()
+        ),
+        Ok((vec![Felt(BigInt::from(4))], 0))
+    );
+    // Modulo 8, 2 has no inverse.
+    assert_eq!(eval(8), Ok((vec![AddMod, MulMod], 1)));
+}
+
+#[test]
+fn simulate_circuit_evaluation_of_negative_felt_input() {
+    let circuit = || vec![type_arg("Circuit")];
+    let data = CircuitInputs(vec![BigInt::from(-1), BigInt::from(1)]);
+    // The felt -1 stands for `prime - 1`, which is 4 modulo 11, rather than 10.
+    // So (4 + 1) * 1 = 5, and the inverse of 4 is 3.
+    assert_eq!(
+        simulate(
+            "eval_circuit",
+            circuit(),
+            vec![AddMod, MulMod, data, NonZero(Box::new(Felt(BigInt::from(11))))],
+        ),
+        Ok((vec![AddMod, MulMod, CircuitOutputs(vec![5.into(), 3.into()])], 0))
+    );
+}
+
+#[test]
+fn simulate_dict_felt_to_squash_after_writes() {
+    let (outputs, _) = simulate(
+        "dict_felt_to_new",
+        vec![type_arg("felt")],
+        vec![SegmentArena, Felt(BigInt::from(5))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_write",
+        vec![type_arg("felt")],
+        vec![outputs[1].clone(), Felt(BigInt::from(2)), Felt(BigInt::from(20))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_write",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(BigInt::from(1)), Felt(BigInt::from(10))],
+    )
+    .unwrap();
+    // A negative key, whose canonical representative is larger than all the other keys.
+    let (outputs, _) = simulate(
+        "dict_felt_to_write",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(BigInt::from(-1)), Felt(BigInt::from(30))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_write",
+        vec![type_arg("felt")],
+        vec![outputs[0].clone(), Felt(BigInt::from(2)), Felt(BigInt::from(21))],
+    )
+    .unwrap();
+    let (outputs, _) = simulate(
+        "dict_felt_to_squash",
+        vec![type_arg("felt")],
+        vec![RangeCheck, SegmentArena, outputs[0].clone()],
+    )
+    .unwrap();
+    let access = |key: i64, prev: i64, new: i64| {
+        Struct(vec![Felt(BigInt::from(key)), Felt(BigInt::from(prev)), Felt(BigInt::from(new))])
+    };
+    assert_eq!(
+        outputs,
+        vec![
+            RangeCheck,
+            SegmentArena,
+            Array(vec![access(1, 5, 10), access(2, 5, 20), access(2, 20, 21), access(-1, 5, 30)])
+        ]
+    );
+}
+
 #[test_case("get_gas", vec![], vec![RangeCheck, Uninitialized] => MemoryLayoutMismatch;
             "get_gas(empty)")]
 #[test_case("get_gas", vec![], vec![] => WrongNumberOfArgs; "get_gas()")]
@@ -225,6 +1043,16 @@ fn simulate_none_branch(
             "uint128_wrapping_mul(1)")]
 #[test_case("uint128_div", vec![], vec![RangeCheck, Uint128(1)] => WrongNumberOfArgs; "uint128_div(1)")]
 #[test_case("uint128_mod", vec![], vec![RangeCheck, Uint128(1)] => WrongNumberOfArgs; "uint128_mod(1)")]
+#[test_case("u128_safe_divmod", vec![], vec![RangeCheck, Uint128(1)] => WrongNumberOfArgs;
+            "u128_safe_divmod(1)")]
+#[test_case("upcast", vec![type_arg("uint8"), type_arg("uint64")], vec![] => WrongNumberOfArgs;
+            "upcast<uint8, uint64>()")]
+#[test_case("array_slice", vec![type_arg("uint128")], vec![RangeCheck, Array(vec![]), Felt(BigInt::from(0))]
+             => WrongNumberOfArgs; "array_slice([], 0)")]
+#[test_case("span_len", vec![type_arg("uint128")], vec![Array(vec![])] => MemoryLayoutMismatch;
+            "span_len(array)")]
+#[test_case("u128_wide_mul", vec![], vec![RangeCheck, Uint128(1)] => WrongNumberOfArgs;
+            "u128_wide_mul(1)")]
 #[test_case("uint128_wrapping_add", vec![value_arg(3)], vec![RangeCheck] => WrongNumberOfArgs;
             "uint128_wrapping_add<3>()")]
 #[test_case("uint128_wrapping_sub", vec![value_arg(3)], vec![RangeCheck] => WrongNumberOfArgs;
@@ -236,10 +1064,31 @@ fn simulate_none_branch(
 #[test_case("uint128_const", vec![value_arg(3)], vec![Uint128(1)] => WrongNumberOfArgs;
             "uint128_const<3>(1)")]
 #[test_case("dup", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs; "dup<uint128>()")]
+#[test_case("bytes31_to_felt", vec![], vec![Felt(BigInt::from(1))] => MemoryLayoutMismatch;
+            "bytes31_to_felt(felt)")]
+#[test_case("bytes31_split", vec![], vec![RangeCheck, Bytes31(BigInt::from(1))] => WrongNumberOfArgs;
+            "bytes31_split(rc, 1)")]
+#[test_case("class_hash_try_from_felt252", vec![], vec![Felt(BigInt::from(1)), Felt(BigInt::from(1))]
+             => MemoryLayoutMismatch; "class_hash_try_from_felt252(1, 1)")]
+#[test_case("bool_and", vec![], vec![Bool(true), Felt(BigInt::from(1))] => MemoryLayoutMismatch;
+            "bool_and(true, 1)")]
+#[test_case("poseidon_hades_permutation", vec![], vec![Poseidon, Felt(BigInt::from(1))]
+             => WrongNumberOfArgs; "poseidon_hades_permutation(1)")]
+#[test_case("pedersen_hash", vec![], vec![Pedersen, Felt(BigInt::from(1))] => WrongNumberOfArgs;
+            "pedersen_hash(1)")]
+#[test_case("bitwise_and", vec![], vec![Bitwise, Felt(BigInt::from(1))] => WrongNumberOfArgs;
+            "bitwise_and(1)")]
+#[test_case("bitwise_and", vec![], vec![Bitwise, Felt(BigInt::from(-1)), Felt(BigInt::from(1))]
+             => BuiltinValueOutOfRange; "bitwise_and(-1, 1)")]
+#[test_case("gas_mark", vec![], vec![GasBuiltin(-1)] => BuiltinValueOutOfRange; "gas_mark(-1)")]
+#[test_case("gas_elapsed", vec![], vec![GasBuiltin(7), Uint128(3)] => InvalidGasMark;
+            "gas_elapsed(7, 3)")]
+#[test_case("debug_print", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs;
+            "debug_print<uint128>()")]
 #[test_case("drop", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs; "drop<uint128>()")]
 #[test_case("uint128_jump_nz", vec![], vec![] => WrongNumberOfArgs; "uint128_jump_nz()")]
-#[test_case("unwrap_nz", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs;
-            "unwrap_nz<uint128>()")]
+#[test_case("unwrap_non_zero", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs;
+            "unwrap_non_zero<uint128>()")]
 #[test_case("store_temp", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs;
             "store_temp<uint128>()")]
 #[test_case("align_temps", vec![type_arg("uint128")], vec![Uint128(1)] => WrongNumberOfArgs;
@@ -247,8 +1096,22 @@ fn simulate_none_branch(
 #[test_case("store_local", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs;
             "store_local<uint128>()")]
 #[test_case("finalize_locals", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "finalize_locals(4)")]
+#[test_case("branch_align", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "branch_align(4)")]
 #[test_case("rename", vec![type_arg("uint128")], vec![] => WrongNumberOfArgs; "rename<uint128>()")]
 #[test_case("jump", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "jump(4)")]
+#[test_case("unit", vec![], vec![Unit] => WrongNumberOfArgs; "unit(())")]
+#[test_case("emit_event", vec![], vec![Array(vec![]), Array(vec![])] => WrongNumberOfArgs;
+            "emit_event([], [])")]
+#[test_case("dict_felt_to_new", vec![type_arg("felt")], vec![Felt(BigInt::from(0))] => WrongNumberOfArgs;
+            "dict_felt_to_new<felt>(0)")]
+#[test_case("dict_felt_to_new", vec![type_arg("felt")], vec![RangeCheck, Felt(BigInt::from(0))]
+             => MemoryLayoutMismatch; "dict_felt_to_new<felt>(RangeCheck, 0)")]
+#[test_case("dict_felt_to_new", vec![type_arg("felt")], vec![] => WrongNumberOfArgs;
+            "dict_felt_to_new<felt>()")]
+#[test_case("dict_felt_to_read", vec![type_arg("felt")], vec![felt_dict(&[], 0)] => WrongNumberOfArgs;
+            "dict_felt_to_read<felt>({})")]
+#[test_case("dict_felt_to_squash", vec![type_arg("felt")], vec![felt_dict(&[], 0)] => WrongNumberOfArgs;
+            "dict_felt_to_squash<felt>({})")]
 #[test_case("function_call", vec![user_func_arg("unimplemented")], vec![] =>
             FunctionSimulationError(
                 "unimplemented".into(),
@@ -261,3 +1124,1216 @@ fn simulate_error(
 ) -> LibFuncSimulationError {
     simulate(id, generic_args, inputs).err().unwrap()
 }
+
+#[cfg(feature = "pedersen")]
+#[test]
+fn simulate_pedersen_hash() {
+    // The hash of (0, 0) is the x coordinate of the Pedersen shift point.
+    let shift_point_x =
+        BigInt::parse_bytes(b"49ee3eba8c1600700ee1b87eb599f16716b0b1022947733551fde4050ca6804", 16)
+            .unwrap();
+    assert_eq!(
+        simulate(
+            "pedersen_hash",
+            vec![],
+            vec![Pedersen, Felt(BigInt::from(0)), Felt(BigInt::from(0))]
+        ),
+        Ok((vec![Pedersen, Felt(shift_point_x - felt_prime())], 0))
+    );
+}
+
+#[cfg(feature = "pedersen")]
+#[test]
+fn simulate_pedersen_hash_equals_felt_operation_result() {
+    // The hash of (0, 0) is above half the prime, so comparing it to a felt operation's result
+    // checks both are held by the same representative.
+    let (outputs, _) = simulate(
+        "pedersen_hash",
+        vec![],
+        vec![Pedersen, Felt(BigInt::from(0)), Felt(BigInt::from(0))],
+    )
+    .unwrap();
+    let hash = outputs[1].clone();
+    let (outputs, _) =
+        simulate("felt_add", vec![], vec![hash.clone(), Felt(BigInt::from(0))]).unwrap();
+    assert_eq!(outputs, vec![hash.clone()]);
+    let arrays = vec![Array(vec![hash]), Array(outputs)];
+    assert_eq!(simulate("array_eq", vec![type_arg("felt")], arrays.clone()), Ok((arrays, 0)));
+}
+
+#[cfg(not(feature = "pedersen"))]
+#[test]
+fn simulate_pedersen_hash_without_feature() {
+    assert_eq!(
+        simulate(
+            "pedersen_hash",
+            vec![],
+            vec![Pedersen, Felt(BigInt::from(0)), Felt(BigInt::from(0))]
+        ),
+        Err(LibFuncSimulationError::MissingFeature("pedersen"))
+    );
+}
+
+#[cfg(feature = "poseidon")]
+#[test]
+fn simulate_poseidon_hades_permutation() {
+    // The Poseidon hash of two felts is the first element of the permutation of `[x, y, 2]`.
+    let (x, y) = (BigInt::from(3), BigInt::from(5));
+    let (outputs, _) = simulate(
+        "poseidon_hades_permutation",
+        vec![],
+        vec![Poseidon, Felt(x), Felt(y), Felt(BigInt::from(2))],
+    )
+    .unwrap();
+    let expected = starknet_crypto::poseidon_hash(
+        starknet_crypto::FieldElement::from(3_u32),
+        starknet_crypto::FieldElement::from(5_u32),
+    );
+    assert_eq!(
+        outputs[1],
+        Felt(reduce(BigInt::from_bytes_be(Sign::Plus, &expected.to_bytes_be())))
+    );
+}
+
+#[cfg(not(feature = "poseidon"))]
+#[test]
+fn simulate_poseidon_hades_permutation_without_feature() {
+    assert_eq!(
+        simulate(
+            "poseidon_hades_permutation",
+            vec![],
+            vec![Poseidon, Felt(BigInt::from(0)), Felt(BigInt::from(0)), Felt(BigInt::from(0))],
+        ),
+        Err(LibFuncSimulationError::MissingFeature("poseidon"))
+    );
+}
+
+/// Returns the keccak input of the empty message, padded to a single block of the rate.
+fn keccak_empty_input() -> CoreValue {
+    let mut words = vec![Uint64(0); 17];
+    words[0] = Uint64(1);
+    words[16] = Uint64(0x8000000000000000);
+    Array(words)
+}
+
+#[cfg(feature = "keccak")]
+#[test]
+fn simulate_keccak() {
+    // keccak-256 of the empty message is
+    // 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470, read in little-endian
+    // order per half.
+    assert_eq!(
+        simulate("keccak", vec![], vec![System, keccak_empty_input()]),
+        Ok((
+            vec![
+                System,
+                Struct(vec![
+                    Uint128(0xc003c7dcb27d7e923c23f7860146d2c5),
+                    Uint128(0x70a4855d04d8fa7b3b2782ca53b600e5),
+                ]),
+            ],
+            0
+        ))
+    );
+}
+
+#[cfg(not(feature = "keccak"))]
+#[test]
+fn simulate_keccak_without_feature() {
+    assert_eq!(
+        simulate("keccak", vec![], vec![System, keccak_empty_input()]),
+        Err(LibFuncSimulationError::MissingFeature("keccak"))
+    );
+}
+
+/// Returns the generator point of the STARK curve.
+fn ec_generator() -> CoreValue {
+    EcPoint(
+        BigInt::parse_bytes(b"1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca", 16)
+            .unwrap(),
+        BigInt::parse_bytes(b"5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f", 16)
+            .unwrap(),
+    )
+}
+
+#[test]
+fn simulate_ec_point_try_new() {
+    let (x, y) = match ec_generator() {
+        EcPoint(x, y) => (x, y),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        simulate("ec_point_try_new", vec![], vec![Felt(x.clone()), Felt(y.clone())]),
+        Ok((vec![ec_generator()], 0))
+    );
+    assert_eq!(simulate("ec_point_try_new", vec![], vec![Felt(x), Felt(y + 1)]), Ok((vec![], 1)));
+}
+
+#[test]
+fn simulate_ec_state() {
+    let add_mul = |state: CoreValue, scalar: BigInt| match simulate(
+        "ec_state_add_mul",
+        vec![],
+        vec![EcOp, state, Felt(scalar), ec_generator()],
+    ) {
+        Ok((outputs, 0)) => match &outputs[..] {
+            [EcOp, state] => state.clone(),
+            _ => panic!("Unexpected outputs."),
+        },
+        _ => panic!("Simulation failed."),
+    };
+    let initial = simulate("ec_state_init", vec![], vec![]).unwrap().0.remove(0);
+    assert_eq!(initial, EcState(None));
+    assert_eq!(simulate("ec_state_finalize", vec![], vec![initial.clone()]), Ok((vec![], 1)));
+
+    // Adding the generator twice is the same as adding its double.
+    let doubled = add_mul(initial.clone(), BigInt::from(2));
+    assert_eq!(add_mul(add_mul(initial.clone(), BigInt::from(1)), BigInt::from(1)), doubled);
+    let (x, y) = (
+        BigInt::parse_bytes(
+            b"3324833730090626974525872402899302150520188025637965566623476530814354734325",
+            10,
+        )
+        .unwrap(),
+        BigInt::parse_bytes(
+            b"3147007486456030910661996439995670279305852583596209647900952752170983517249",
+            10,
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        simulate("ec_state_finalize", vec![], vec![doubled.clone()]),
+        Ok((vec![EcPoint(x, y)], 0))
+    );
+    // Multiplying by the order of the curve results in the point at infinity.
+    let order =
+        BigInt::parse_bytes(b"800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f", 16)
+            .unwrap();
+    assert_eq!(add_mul(initial, order), EcState(None));
+}
+
+/// Returns a u256 value from its hex representation.
+fn hex_u256(hex: &[u8]) -> CoreValue {
+    let value = BigInt::parse_bytes(hex, 16).unwrap();
+    let mask = (BigInt::from(1) << 128) - 1;
+    let half = |half: &BigInt| Uint128(u128::try_from(half).unwrap());
+    Struct(vec![half(&(&value & &mask)), half(&(value >> 128))])
+}
+
+/// Returns the point of a secp256 curve with the given hex coordinates.
+fn secp256_point(x: &[u8], y: &[u8]) -> CoreValue {
+    Secp256Point(BigInt::parse_bytes(x, 16).unwrap(), BigInt::parse_bytes(y, 16).unwrap())
+}
+
+#[test]
+fn simulate_secp256k1() {
+    let (gx, gy) = (
+        b"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        b"483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    );
+    let generator = secp256_point(gx, gy);
+    let doubled = secp256_point(
+        b"c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+        b"1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+    );
+    assert_eq!(
+        simulate("secp256k1_new", vec![], vec![System, hex_u256(gx), hex_u256(gy)]),
+        Ok((vec![System, generator.clone()], 0))
+    );
+    assert_eq!(
+        simulate("secp256k1_new", vec![], vec![System, hex_u256(gx), hex_u256(gx)]),
+        Ok((
+            vec![System, Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, b"Invalid point"))])],
+            1
+        ))
+    );
+    assert_eq!(
+        simulate("secp256k1_add", vec![], vec![System, generator.clone(), generator.clone()]),
+        Ok((vec![System, doubled.clone()], 0))
+    );
+    assert_eq!(
+        simulate("secp256k1_mul", vec![], vec![System, generator, hex_u256(b"2")]),
+        Ok((vec![System, doubled], 0))
+    );
+}
+
+#[test]
+fn simulate_secp256r1() {
+    let generator = secp256_point(
+        b"6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+        b"4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+    );
+    let order = b"ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+    assert_eq!(
+        simulate("secp256r1_new", vec![], vec![System, hex_u256(b"0"), hex_u256(b"0")]),
+        Ok((vec![System, secp256_point(b"0", b"0")], 0))
+    );
+    assert_eq!(
+        simulate("secp256r1_mul", vec![], vec![System, generator.clone(), hex_u256(order)]),
+        Ok((vec![System, secp256_point(b"0", b"0")], 0))
+    );
+    assert_eq!(
+        simulate(
+            "secp256r1_add",
+            vec![],
+            vec![System, generator.clone(), secp256_point(b"0", b"0")]
+        ),
+        Ok((vec![System, generator], 0))
+    );
+}
+
+#[test]
+fn simulate_ecdsa_verify_with_signature_verifier() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type Signature = Signature;
+            type felt = felt;
+            libfunc ecdsa_verify = ecdsa_verify;
+            libfunc felt_const_0 = felt_const<0>;
+            libfunc felt_const_1 = felt_const<1>;
+            ecdsa_verify(sig, message, public_key, r, s) { fallthrough(sig) 3(sig) };
+            felt_const_1() -> (valid);
+            return (sig, valid);
+            felt_const_0() -> (valid);
+            return (sig, valid);
+            main@0(sig: Signature, message: felt, public_key: felt, r: felt, s: felt)
+                -> (Signature, felt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let inputs = vec![
+        Signature,
+        Felt(BigInt::from(10)),
+        Felt(BigInt::from(20)),
+        Felt(BigInt::from(1)),
+        Felt(BigInt::from(2)),
+    ];
+
+    let context = SimulationContext::new(&program, &gas_info).unwrap();
+    assert_eq!(
+        context.run(&"main".into(), inputs.clone()),
+        Err(SimulationError::LibFuncSimulationError(
+            LibFuncSimulationError::MissingSignatureVerifier,
+            StatementIdx(0)
+        ))
+    );
+
+    let context = SimulationContext::new(&program, &gas_info).unwrap().with_signature_verifier(
+        |_: &BigInt, public_key: &BigInt, _: &BigInt, _: &BigInt| public_key == &BigInt::from(20),
+    );
+    assert_eq!(context.run(&"main".into(), inputs), Ok(vec![Signature, Felt(BigInt::from(1))]));
+}
+
+#[test]
+fn simulate_contract_address_from_large_felt_const() {
+    let max_address: BigInt = (BigInt::from(1) << 251) - 1;
+    let (outputs, _) =
+        simulate("felt_const", vec![GenericArg::Value(max_address.clone())], vec![]).unwrap();
+    let mut inputs = vec![RangeCheck];
+    inputs.extend(outputs);
+    assert_eq!(
+        simulate("contract_address_try_from_felt252", vec![], inputs),
+        Ok((vec![RangeCheck, ContractAddress(max_address)], 0))
+    );
+}
+
+#[test]
+fn simulate_panic_propagation() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type NonZeroFelt = NonZero<felt>;
+            type ArrayFelt = Array<felt>;
+            type PanicableFelt = Panicable<felt>;
+            libfunc panic = panic;
+            libfunc panicable_wrap = panicable_wrap<felt>;
+            libfunc match_panicable = match_panicable<felt>;
+            libfunc felt_jump_nz = felt_jump_nz;
+            libfunc unwrap_nz = unwrap_non_zero<felt>;
+            libfunc array_new = array_new<felt>;
+            libfunc array_append = array_append<felt>;
+            libfunc felt_const_7 = felt_const<7>;
+            libfunc call_inner = function_call<user@inner>;
+            felt_jump_nz(a) { fallthrough() 5(b) };
+            array_new() -> (data);
+            felt_const_7() -> (c);
+            array_append(data, c) -> (data);
+            panic(data) { };
+            unwrap_nz(b) -> (a);
+            panicable_wrap(a) -> (r);
+            return(r);
+            call_inner(a) -> (r);
+            match_panicable(r) { fallthrough(v) 12(data) };
+            panicable_wrap(v) -> (r);
+            return(r);
+            panic(data) { };
+            inner@0(a: felt) -> (PanicableFelt);
+            outer@8(a: felt) -> (PanicableFelt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let context = SimulationContext::new(&program, &gas_info).unwrap();
+    assert_eq!(
+        context.run(&"outer".into(), vec![Felt(BigInt::from(3))]),
+        Ok(vec![Enum { value: Box::new(Felt(BigInt::from(3))), index: 0 }])
+    );
+    assert_eq!(
+        context.run(&"outer".into(), vec![Felt(BigInt::from(0))]),
+        Ok(vec![Enum { value: Box::new(Array(vec![Felt(BigInt::from(7))])), index: 1 }])
+    );
+}
+
+#[test]
+fn simulate_unwrapped_panic() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc panic = panic;
+            libfunc array_new = array_new<felt>;
+            libfunc felt_const = felt_const<5>;
+            array_new() -> (data);
+            panic(data) { };
+            felt_const() -> (v);
+            return(v);
+            main@0() -> (felt);
+            other@2() -> (felt);
+        "})
+        .unwrap();
+    // The unwrapped panic does not prevent running other functions of the program.
+    let context = SimulationContext::new(&program, &HashMap::new()).unwrap();
+    assert_eq!(context.run(&"other".into(), vec![]), Ok(vec![Felt(BigInt::from(5))]));
+    assert_eq!(
+        context.run(&"main".into(), vec![]),
+        Err(SimulationError::UnwrappedPanic(UnwrappedPanicError {
+            function_id: "main".into(),
+            statement_idx: StatementIdx(1),
+        }))
+    );
+}
+
+#[test]
+fn simulate_panic_with_unconsumed_args() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            type PanicableFelt = Panicable<felt>;
+            libfunc panic = panic;
+            libfunc array_new = array_new<felt>;
+            array_new() -> (data);
+            panic(data) { };
+            main@0(a: felt) -> (PanicableFelt);
+        "})
+        .unwrap();
+    assert_eq!(
+        run(&program, &HashMap::new(), &"main".into(), vec![Felt(BigInt::from(3))]),
+        Err(SimulationError::FunctionDidNotConsumeAllArgs("main".into(), StatementIdx(1)))
+    );
+}
+
+#[test]
+fn simulate_debug_print_struct() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type Pair = Struct<ut@Pair, felt, felt>;
+            libfunc felt_const_1 = felt_const<1>;
+            libfunc felt_const_2 = felt_const<2>;
+            libfunc pair_construct = struct_construct<Pair>;
+            libfunc print_pair = debug_print<Pair>;
+            felt_const_1() -> (a);
+            felt_const_2() -> (b);
+            pair_construct(a, b) -> (pair);
+            print_pair(pair) -> ();
+            return ();
+            main@0() -> ();
+        "})
+        .unwrap();
+    assert_eq!(
+        run_with_debug_output(&program, &HashMap::new(), &"main".into(), vec![]),
+        Ok((vec![], vec!["Pair(1, 2)".to_string()]))
+    );
+}
+
+#[test]
+fn simulate_gas_elapsed_between_marks() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            type GasBuiltin = GasBuiltin;
+            type felt = felt;
+            type uint128 = uint128;
+            libfunc gas_mark = gas_mark;
+            libfunc gas_elapsed = gas_elapsed;
+            libfunc get_gas = get_gas;
+            libfunc felt_const_3 = felt_const<3>;
+            libfunc felt_mul = felt_mul;
+            libfunc felt_drop = drop<felt>;
+            gas_mark(gb) -> (gb, mark);
+            get_gas(rc, gb) { fallthrough(rc, gb) 8(rc, gb) };
+            felt_const_3() -> (a);
+            felt_const_3() -> (b);
+            felt_mul(a, b) -> (c);
+            felt_drop(c) -> ();
+            gas_elapsed(gb, mark) -> (gb, elapsed);
+            return(rc, gb, elapsed);
+            gas_elapsed(gb, mark) -> (gb, elapsed);
+            return(rc, gb, elapsed);
+            main@0(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin, uint128);
+        "})
+        .unwrap();
+    // The cost of the arithmetic block, as withdrawn by `get_gas`.
+    let statement_gas_info = HashMap::from([(StatementIdx(1), 4)]);
+    assert_eq!(
+        run(&program, &statement_gas_info, &"main".into(), vec![RangeCheck, GasBuiltin(10)]),
+        Ok(vec![RangeCheck, GasBuiltin(6), Uint128(4)])
+    );
+}
+
+/// A field backend counting the additions it performs.
+#[derive(Default)]
+struct CountingBackend {
+    additions: Cell<usize>,
+}
+impl FieldBackend for CountingBackend {
+    type Element = BigInt;
+
+    fn from_bigint(&self, value: &BigInt) -> BigInt {
+        value.clone()
+    }
+
+    fn to_bigint(&self, element: &BigInt) -> BigInt {
+        element.clone()
+    }
+
+    fn add(&self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        self.additions.set(self.additions.get() + 1);
+        BigIntBackend::default().add(lhs, rhs)
+    }
+
+    fn sub(&self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        BigIntBackend::default().sub(lhs, rhs)
+    }
+
+    fn mul(&self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        BigIntBackend::default().mul(lhs, rhs)
+    }
+
+    fn inv(&self, element: &BigInt) -> Option<BigInt> {
+        BigIntBackend::default().inv(element)
+    }
+}
+
+#[test]
+fn simulate_felt_add_with_custom_field_backend() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            libfunc felt_const_2 = felt_const<2>;
+            libfunc felt_const_3 = felt_const<3>;
+            libfunc felt_add = felt_add;
+            felt_const_2() -> (a);
+            felt_const_3() -> (b);
+            felt_add(a, b) -> (c);
+            return(c);
+            main@0() -> (felt);
+        "})
+        .unwrap();
+    let statement_gas_info = HashMap::new();
+    let context = SimulationContext::with_field_backend(
+        &program,
+        &statement_gas_info,
+        CountingBackend::default(),
+    )
+    .unwrap();
+    assert_eq!(context.run(&"main".into(), vec![]), Ok(vec![Felt(BigInt::from(5))]));
+    assert_eq!(context.field_backend().additions.get(), 1);
+}
+
+#[test]
+fn simulate_array_reverse_of_structs() {
+    let element = |a: u128, b: i64| Struct(vec![Uint128(a), Felt(BigInt::from(b))]);
+    assert_eq!(
+        simulate(
+            "array_reverse",
+            vec![type_arg("Uint128AndFelt")],
+            vec![Array(vec![element(1, 10), element(2, 20), element(3, 30)])]
+        ),
+        Ok((vec![Array(vec![element(3, 30), element(2, 20), element(1, 10)])], 0))
+    );
+}
+
+#[test]
+fn simulate_restore_checkpoint() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            libfunc print_felt = debug_print<felt>;
+            print_felt(a) -> ();
+            return ();
+            main@0(a: felt) -> ();
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let mut context = SimulationContext::new(&program, &gas_info).unwrap();
+    context.run(&"main".into(), vec![Felt(BigInt::from(1))]).unwrap();
+    let checkpoint = context.checkpoint();
+    context.run(&"main".into(), vec![Felt(BigInt::from(2))]).unwrap();
+    assert_eq!(context.debug_output(), vec!["1".to_string(), "2".to_string()]);
+    context.restore(checkpoint.clone());
+    assert_eq!(context.debug_output(), vec!["1".to_string()]);
+    assert_eq!(context.checkpoint(), checkpoint);
+}
+
+#[test]
+fn simulate_array_fold_sum() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc felt_const_0 = felt_const<0>;
+            libfunc felt_add = felt_add;
+            libfunc fold_sum = array_fold<user@sum>;
+            felt_const_0() -> (zero);
+            fold_sum(arr, zero) -> (total);
+            return (total);
+            felt_add(acc, x) -> (acc);
+            return (acc);
+            main@0(arr: ArrayFelt) -> (felt);
+            sum@3(acc: felt, x: felt) -> (felt);
+        "})
+        .unwrap();
+    assert_eq!(
+        run(
+            &program,
+            &HashMap::new(),
+            &"main".into(),
+            vec![Array(vec![Felt(BigInt::from(1)), Felt(BigInt::from(2)), Felt(BigInt::from(3))])]
+        ),
+        Ok(vec![Felt(BigInt::from(6))])
+    );
+}
+
+#[test]
+fn simulate_result_map_err() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type uint128 = uint128;
+            type ResultFeltUint128 = Enum<ut@Result, felt, uint128>;
+            type ResultFeltFelt = Enum<ut@Result, felt, felt>;
+            libfunc map_err = result_map_err<felt, user@to_felt>;
+            libfunc uint128_to_felt = uint128_to_felt;
+            map_err(r) -> (r);
+            return (r);
+            uint128_to_felt(e) -> (e);
+            return (e);
+            main@0(r: ResultFeltUint128) -> (ResultFeltFelt);
+            to_felt@2(e: uint128) -> (felt);
+        "})
+        .unwrap();
+    let run_main = |value, index| {
+        run(&program, &HashMap::new(), &"main".into(), vec![Enum { value: Box::new(value), index }])
+    };
+    assert_eq!(
+        run_main(Uint128(3), 1),
+        Ok(vec![Enum { value: Box::new(Felt(BigInt::from(3))), index: 1 }])
+    );
+    // An `Ok` value is kept as is, without calling the mapping function.
+    assert_eq!(
+        run_main(Felt(BigInt::from(7)), 0),
+        Ok(vec![Enum { value: Box::new(Felt(BigInt::from(7))), index: 0 }])
+    );
+}
+
+#[test]
+fn simulate_emit_events() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc emit_event = emit_event;
+            libfunc array_new_felt = array_new<felt>;
+            emit_event(system, keys, data) -> (system);
+            array_new_felt() -> (keys);
+            array_new_felt() -> (data);
+            emit_event(system, keys, data) -> (system);
+            return (system);
+            main@0(system: System, keys: ArrayFelt, data: ArrayFelt) -> (System);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let context = SimulationContext::new(&program, &gas_info).unwrap();
+    let felts = |values: &[i64]| values.iter().map(|v| Felt(BigInt::from(*v))).collect();
+    assert_eq!(
+        context.run(&"main".into(), vec![System, Array(felts(&[1])), Array(felts(&[2, 3]))]),
+        Ok(vec![System])
+    );
+    assert_eq!(
+        context.events(),
+        vec![
+            Event { keys: vec![BigInt::from(1)], data: vec![BigInt::from(2), BigInt::from(3)] },
+            Event { keys: vec![], data: vec![] },
+        ]
+    );
+}
+
+#[test]
+fn simulate_send_messages_to_l1() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc send_message_to_l1 = send_message_to_l1;
+            send_message_to_l1(system, to_address, payload) {
+                fallthrough(system) 2(system, reason)
+            };
+            return (system);
+            return (system);
+            main@0(system: System, to_address: felt, payload: ArrayFelt) -> (System);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let mut context = SimulationContext::new(&program, &gas_info).unwrap();
+    let checkpoint = context.checkpoint();
+    let felts = |values: &[i64]| values.iter().map(|v| Felt(BigInt::from(*v))).collect();
+    for (to_address, payload) in [(1, vec![2, 3]), (4, vec![])] {
+        assert_eq!(
+            context.run(
+                &"main".into(),
+                vec![System, Felt(BigInt::from(to_address)), Array(felts(&payload))]
+            ),
+            Ok(vec![System])
+        );
+    }
+    assert_eq!(
+        context.l1_messages(),
+        vec![
+            L1Message {
+                to_address: BigInt::from(1),
+                payload: vec![BigInt::from(2), BigInt::from(3)]
+            },
+            L1Message { to_address: BigInt::from(4), payload: vec![] },
+        ]
+    );
+    context.restore(checkpoint);
+    assert_eq!(context.l1_messages(), vec![]);
+}
+
+/// Returns a program adding a felt to itself and to one, through a function call.
+fn traced_program() -> Program {
+    ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            libfunc felt_dup = dup<felt>;
+            libfunc felt_add = felt_add;
+            libfunc felt_add_1 = felt_add<1>;
+            libfunc call_inc = function_call<user@inc>;
+            felt_dup(a) -> (a, b);
+            felt_add(a, b) -> (a);
+            call_inc(a) -> (a);
+            return (a);
+            felt_add_1(a) -> (a);
+            return (a);
+            main@0(a: felt) -> (felt);
+            inc@4(a: felt) -> (felt);
+        "})
+        .unwrap()
+}
+
+#[test]
+fn replay_recorded_trace() {
+    let program = traced_program();
+    let gas_info = HashMap::new();
+    let context = SimulationContext::new(&program, &gas_info).unwrap();
+    let (outputs, trace) =
+        context.run_with_trace(&"main".into(), vec![Felt(BigInt::from(3))]).unwrap();
+    assert_eq!(outputs, vec![Felt(BigInt::from(7))]);
+    assert_eq!(
+        trace.steps.iter().map(|step| (step.statement_idx, step.branch)).collect::<Vec<_>>(),
+        vec![(0, Some(0)), (1, Some(0)), (4, Some(0)), (5, None), (2, Some(0)), (3, None)]
+    );
+    assert_eq!(replay(&context, &"main".into(), &trace), Ok(outputs));
+
+    let mut tampered = trace.clone();
+    tampered.steps[1].outputs = vec![Felt(BigInt::from(5))];
+    assert_eq!(replay(&context, &"main".into(), &tampered), Err(ReplayError::StepMismatch(1)));
+    tampered = trace;
+    tampered.steps.pop();
+    assert_eq!(
+        replay(&context, &"main".into(), &tampered),
+        Err(ReplayError::StepCountMismatch { expected: 5, actual: 6 })
+    );
+}
+
+#[test]
+fn failing_run_keeps_trace() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc felt_dup = dup<felt>;
+            libfunc felt_add = felt_add;
+            libfunc felt_drop = drop<felt>;
+            libfunc array_new = array_new<felt>;
+            libfunc panic = panic;
+            felt_dup(a) -> (a, b);
+            felt_add(a, b) -> (a);
+            felt_drop(a) -> ();
+            array_new() -> (data);
+            panic(data) { };
+            main@0(a: felt) -> (felt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let context = SimulationContext::new(&program, &gas_info).unwrap();
+    let TracedRunError { error, trace } =
+        context.run_with_trace(&"main".into(), vec![Felt(BigInt::from(3))]).unwrap_err();
+    assert_eq!(
+        error,
+        SimulationError::UnwrappedPanic(UnwrappedPanicError {
+            function_id: "main".into(),
+            statement_idx: StatementIdx(4),
+        })
+    );
+    // The steps run before the failing statement are kept.
+    assert_eq!(
+        trace.steps.iter().map(|step| (step.statement_idx, step.branch)).collect::<Vec<_>>(),
+        vec![(0, Some(0)), (1, Some(0)), (2, Some(0)), (3, Some(0))]
+    );
+    assert_eq!(trace.steps[1].outputs, vec![Felt(BigInt::from(6))]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn trace_json_round_trip() {
+    use super::trace::Trace;
+
+    let program = traced_program();
+    let gas_info = HashMap::new();
+    let context = SimulationContext::new(&program, &gas_info).unwrap();
+    let (_, trace) = context.run_with_trace(&"main".into(), vec![Felt(BigInt::from(3))]).unwrap();
+    let json = serde_json::to_string(&trace).unwrap();
+    let parsed: Trace = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, trace);
+    assert_eq!(replay(&context, &"main".into(), &parsed), Ok(vec![Felt(BigInt::from(7))]));
+}
+
+#[test]
+fn simulate_storage_syscalls() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type StorageAddress = StorageAddress;
+            type ArrayFelt = Array<felt>;
+            libfunc storage_read = storage_read;
+            libfunc storage_write = storage_write;
+            libfunc dup_address = dup<StorageAddress>;
+            dup_address(address) -> (address, address_copy);
+            storage_write(system, address, value) { fallthrough(system) 5(system, reason) };
+            storage_read(system, address_copy) { fallthrough(system, value) 6(system, reason) };
+            return (system, value);
+            return (system, value);
+            return (system, reason);
+            return (system, reason);
+            main@0(system: System, address: StorageAddress, value: felt) -> (System, felt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let inputs = vec![System, StorageAddress(BigInt::from(3)), Felt(BigInt::from(7))];
+
+    let handler = InMemorySyscallHandler::default();
+    let context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(&handler);
+    assert_eq!(context.run(&"main".into(), inputs), Ok(vec![System, Felt(BigInt::from(7))]));
+    assert_eq!(handler.storage(), HashMap::from([(BigInt::from(3), BigInt::from(7))]));
+}
+
+#[test]
+fn simulate_storage_read_after_restore() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type StorageAddress = StorageAddress;
+            type ArrayFelt = Array<felt>;
+            libfunc storage_read = storage_read;
+            libfunc storage_write = storage_write;
+            libfunc array_new_felt = array_new<felt>;
+            storage_write(system, address, value) { fallthrough(system) 3(system, reason) };
+            array_new_felt() -> (reason);
+            return (system, reason);
+            return (system, reason);
+            storage_read(system, address) { fallthrough(system, value) 6(system, reason) };
+            return (system, value);
+            return (system, reason);
+            write@0(system: System, address: StorageAddress, value: felt) -> (System, ArrayFelt);
+            read@4(system: System, address: StorageAddress) -> (System, felt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let mut context = SimulationContext::new(&program, &gas_info).unwrap();
+    let write = |value: i64| {
+        let inputs = vec![System, StorageAddress(BigInt::from(3)), Felt(BigInt::from(value))];
+        assert_eq!(context.run(&"write".into(), inputs), Ok(vec![System, Array(vec![])]));
+    };
+    write(7);
+    let checkpoint = context.checkpoint();
+    write(8);
+    context.restore(checkpoint);
+    assert_eq!(
+        context.run(&"read".into(), vec![System, StorageAddress(BigInt::from(3))]),
+        Ok(vec![System, Felt(BigInt::from(7))])
+    );
+}
+
+#[test]
+fn simulate_restore_checkpoint_of_syscall_state() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type StorageAddress = StorageAddress;
+            type ClassHash = ClassHash;
+            type ArrayFelt = Array<felt>;
+            libfunc storage_write = storage_write;
+            libfunc replace_class = replace_class;
+            libfunc array_new_felt = array_new<felt>;
+            storage_write(system, address, value) { fallthrough(system) 4(system, reason) };
+            replace_class(system, class_hash) { fallthrough(system) 4(system, reason) };
+            array_new_felt() -> (reason);
+            return (system, reason);
+            return (system, reason);
+            main@0(system: System, address: StorageAddress, value: felt, class_hash: ClassHash) \
+                -> (System, ArrayFelt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let inputs = |value: i64, class_hash: i64| {
+        let (value, class_hash) = (BigInt::from(value), BigInt::from(class_hash));
+        vec![System, StorageAddress(BigInt::from(3)), Felt(value), ClassHash(class_hash)]
+    };
+
+    let handler = InMemorySyscallHandler::default()
+        .with_class(BigInt::from(10), |_, _| Ok(vec![]))
+        .with_class(BigInt::from(20), |_, _| Ok(vec![]));
+    let mut context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(&handler);
+    assert_eq!(context.run(&"main".into(), inputs(7, 10)), Ok(vec![System, Array(vec![])]));
+    let checkpoint = context.checkpoint();
+    assert_eq!(context.run(&"main".into(), inputs(8, 20)), Ok(vec![System, Array(vec![])]));
+    assert_eq!(handler.storage(), HashMap::from([(BigInt::from(3), BigInt::from(8))]));
+    assert_eq!(handler.deployed(), HashMap::from([(BigInt::from(0), BigInt::from(20))]));
+    context.restore(checkpoint);
+    assert_eq!(handler.storage(), HashMap::from([(BigInt::from(3), BigInt::from(7))]));
+    assert_eq!(handler.deployed(), HashMap::from([(BigInt::from(0), BigInt::from(10))]));
+}
+
+/// A syscall handler reverting on every syscall.
+struct RevertingSyscallHandler {}
+impl SyscallHandler for RevertingSyscallHandler {
+    fn storage_read(&self, _address: &BigInt) -> SyscallResult<BigInt> {
+        Err(vec![BigInt::from(1)])
+    }
+
+    fn storage_write(&self, _address: &BigInt, _value: &BigInt) -> SyscallResult<()> {
+        Err(vec![BigInt::from(2)])
+    }
+
+    fn call_contract(
+        &self,
+        _address: &BigInt,
+        _selector: &BigInt,
+        _calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>> {
+        Err(vec![BigInt::from(3)])
+    }
+
+    fn deploy(
+        &self,
+        _class_hash: &BigInt,
+        _salt: &BigInt,
+        _calldata: &[BigInt],
+        _deploy_from_zero: bool,
+    ) -> SyscallResult<(BigInt, Vec<BigInt>)> {
+        Err(vec![BigInt::from(5)])
+    }
+
+    fn library_call(
+        &self,
+        _class_hash: &BigInt,
+        _selector: &BigInt,
+        _calldata: &[BigInt],
+    ) -> SyscallResult<Vec<BigInt>> {
+        Err(vec![BigInt::from(6)])
+    }
+
+    fn replace_class(&self, _class_hash: &BigInt) -> SyscallResult<()> {
+        Err(vec![BigInt::from(7)])
+    }
+
+    fn get_execution_info(&self) -> SyscallResult<ExecutionInfo> {
+        Err(vec![BigInt::from(4)])
+    }
+
+    fn get_block_hash(&self, _block_number: u64) -> SyscallResult<BigInt> {
+        Err(vec![BigInt::from(8)])
+    }
+}
+
+#[test]
+fn simulate_reverted_storage_syscall() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type StorageAddress = StorageAddress;
+            type ArrayFelt = Array<felt>;
+            libfunc storage_read = storage_read;
+            libfunc drop_felt = drop<felt>;
+            libfunc array_new_felt = array_new<felt>;
+            storage_read(system, address) { fallthrough(system, value) 4(system, reason) };
+            drop_felt(value) -> ();
+            array_new_felt() -> (reason);
+            return (system, reason);
+            return (system, reason);
+            main@0(system: System, address: StorageAddress) -> (System, ArrayFelt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let context = SimulationContext::new(&program, &gas_info)
+        .unwrap()
+        .with_syscall_handler(RevertingSyscallHandler {});
+    assert_eq!(
+        context.run(&"main".into(), vec![System, StorageAddress(BigInt::from(3))]),
+        Ok(vec![System, Array(vec![Felt(BigInt::from(1))])])
+    );
+}
+
+#[test]
+fn simulate_call_contract() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc call_contract = call_contract;
+            call_contract(system, address, selector, calldata) {
+                fallthrough(system, retdata) 2(system, retdata)
+            };
+            return (system, retdata);
+            return (system, retdata);
+            main@0(system: System, address: felt, selector: felt, calldata: ArrayFelt)
+                -> (System, ArrayFelt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let handler = InMemorySyscallHandler::default().with_contract(
+        BigInt::from(10),
+        |selector: &BigInt, calldata: &[BigInt]| {
+            Ok(calldata.iter().map(|value| value * selector).collect())
+        },
+    );
+    let context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(handler);
+    let felts = |values: &[i64]| values.iter().map(|v| Felt(BigInt::from(*v))).collect();
+    let call = |address: i64| {
+        context.run(
+            &"main".into(),
+            vec![System, Felt(BigInt::from(address)), Felt(BigInt::from(2)), Array(felts(&[3, 4]))],
+        )
+    };
+    assert_eq!(call(10), Ok(vec![System, Array(felts(&[6, 8]))]));
+    assert_eq!(
+        call(11),
+        Ok(vec![
+            System,
+            Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, b"CONTRACT_NOT_DEPLOYED"))])
+        ])
+    );
+}
+
+#[test]
+fn simulate_get_execution_info() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type uint64 = uint64;
+            type ContractAddress = ContractAddress;
+            type ArrayFelt = Array<felt>;
+            type ExecutionInfo = Struct<ut@ExecutionInfo, uint64, uint64, ContractAddress, felt, \
+                                        felt, ContractAddress, ContractAddress, felt>;
+            type BoxExecutionInfo = Box<ExecutionInfo>;
+            libfunc get_execution_info = get_execution_info;
+            libfunc unbox = unbox<ExecutionInfo>;
+            get_execution_info(system) { fallthrough(system, info) 3(system, reason) };
+            unbox(info) -> (info);
+            return (system, info);
+            return (system, reason);
+            main@0(system: System) -> (System, ExecutionInfo);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let handler = InMemorySyscallHandler::default().with_execution_info(ExecutionInfo {
+        block_number: 12,
+        caller_address: BigInt::from(34),
+        ..ExecutionInfo::default()
+    });
+    let context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(handler);
+    let zero = || BigInt::from(0);
+    assert_eq!(
+        context.run(&"main".into(), vec![System]),
+        Ok(vec![
+            System,
+            Struct(vec![
+                Uint64(12),
+                Uint64(0),
+                ContractAddress(zero()),
+                Felt(zero()),
+                Felt(zero()),
+                ContractAddress(BigInt::from(34)),
+                ContractAddress(zero()),
+                Felt(zero()),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn simulate_deploy() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type bool = bool;
+            type ClassHash = ClassHash;
+            type ContractAddress = ContractAddress;
+            type ArrayFelt = Array<felt>;
+            libfunc deploy = deploy;
+            libfunc drop_address = drop<ContractAddress>;
+            deploy(system, class_hash, salt, calldata, from_zero) {
+                fallthrough(system, address, retdata) 3(system, reason)
+            };
+            drop_address(address) -> ();
+            return (system, retdata);
+            return (system, reason);
+            main@0(system: System, class_hash: ClassHash, salt: felt, calldata: ArrayFelt, \
+                   from_zero: bool) -> (System, ArrayFelt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let handler = InMemorySyscallHandler::default().with_class(
+        BigInt::from(7),
+        |selector: &BigInt, calldata: &[BigInt]| {
+            Ok(calldata.iter().map(|value| value + selector).collect())
+        },
+    );
+    let context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(&handler);
+    let felts = |values: &[i64]| values.iter().map(|v| Felt(BigInt::from(*v))).collect();
+    let revert_reason =
+        |reason: &[u8]| Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, reason))]);
+    let deploy = |class_hash: i64| {
+        context.run(
+            &"main".into(),
+            vec![
+                System,
+                ClassHash(BigInt::from(class_hash)),
+                Felt(BigInt::from(10)),
+                Array(felts(&[3, 4])),
+                Bool(false),
+            ],
+        )
+    };
+    assert_eq!(deploy(8), Ok(vec![System, revert_reason(b"CLASS_HASH_NOT_DECLARED")]));
+    assert_eq!(deploy(7), Ok(vec![System, Array(felts(&[3, 4]))]));
+    assert_eq!(deploy(7), Ok(vec![System, revert_reason(b"CONTRACT_ALREADY_DEPLOYED")]));
+    assert_eq!(handler.deployed(), HashMap::from([(BigInt::from(10), BigInt::from(7))]));
+    assert_eq!(
+        handler.call_contract(&BigInt::from(10), &BigInt::from(1), &[BigInt::from(5)]),
+        Ok(vec![BigInt::from(6)])
+    );
+    assert_eq!(
+        handler.library_call(&BigInt::from(7), &BigInt::from(2), &[BigInt::from(5)]),
+        Ok(vec![BigInt::from(7)])
+    );
+}
+
+#[test]
+fn simulate_replace_class() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type ClassHash = ClassHash;
+            type ArrayFelt = Array<felt>;
+            libfunc replace_class = replace_class;
+            libfunc array_new_felt = array_new<felt>;
+            replace_class(system, class_hash) { fallthrough(system) 3(system, reason) };
+            array_new_felt() -> (reason);
+            return (system, reason);
+            return (system, reason);
+            main@0(system: System, class_hash: ClassHash) -> (System, ArrayFelt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let handler = InMemorySyscallHandler::default()
+        .with_contract(BigInt::from(10), |_, _| Ok(vec![BigInt::from(1)]))
+        .with_class(BigInt::from(7), |_, _| Ok(vec![BigInt::from(2)]))
+        .with_execution_info(ExecutionInfo {
+            contract_address: BigInt::from(10),
+            ..ExecutionInfo::default()
+        });
+    let context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(&handler);
+    let call = || handler.call_contract(&BigInt::from(10), &BigInt::from(0), &[]);
+    assert_eq!(call(), Ok(vec![BigInt::from(1)]));
+    assert_eq!(
+        context.run(&"main".into(), vec![System, ClassHash(BigInt::from(8))]),
+        Ok(vec![
+            System,
+            Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, b"CLASS_HASH_NOT_DECLARED"))])
+        ])
+    );
+    assert_eq!(
+        context.run(&"main".into(), vec![System, ClassHash(BigInt::from(7))]),
+        Ok(vec![System, Array(vec![])])
+    );
+    assert_eq!(call(), Ok(vec![BigInt::from(2)]));
+}
+
+#[test]
+fn simulate_get_block_hash() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type System = System;
+            type felt = felt;
+            type uint64 = uint64;
+            type ArrayFelt = Array<felt>;
+            libfunc get_block_hash = get_block_hash;
+            get_block_hash(system, number) { fallthrough(system, hash) 2(system, reason) };
+            return (system, hash);
+            return (system, reason);
+            main@0(system: System, number: uint64) -> (System, felt);
+        "})
+        .unwrap();
+    let gas_info = HashMap::new();
+    let handler = InMemorySyscallHandler::default()
+        .with_execution_info(ExecutionInfo { block_number: 100, ..ExecutionInfo::default() })
+        .with_block_hash_provider(|number| (number != 7).then(|| BigInt::from(number * 1000)));
+    let context =
+        SimulationContext::new(&program, &gas_info).unwrap().with_syscall_handler(handler);
+    let out_of_range =
+        Array(vec![Felt(BigInt::from_bytes_be(Sign::Plus, b"BLOCK_NUMBER_OUT_OF_RANGE"))]);
+    let get_block_hash = |number| context.run(&"main".into(), vec![System, Uint64(number)]);
+    assert_eq!(get_block_hash(90), Ok(vec![System, Felt(BigInt::from(90000))]));
+    assert_eq!(get_block_hash(91), Ok(vec![System, out_of_range.clone()]));
+    assert_eq!(get_block_hash(7), Ok(vec![System, out_of_range.clone()]));
+    assert_eq!(get_block_hash(u64::MAX), Ok(vec![System, out_of_range]));
+}
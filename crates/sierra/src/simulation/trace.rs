@@ -0,0 +1,79 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::field::FieldBackend;
+use super::value::CoreValue;
+use super::{SimulationContext, SimulationError};
+use crate::ids::FunctionId;
+
+/// A recorded run of a function, allowing to replay it and check it runs the same.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trace {
+    /// The inputs the function was run with.
+    pub inputs: Vec<CoreValue>,
+    /// The statements run, in order, including the statements of called functions.
+    pub steps: Vec<TraceStep>,
+}
+
+/// A single statement run during a recorded run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TraceStep {
+    /// The index of the statement.
+    pub statement_idx: usize,
+    /// The branch taken by the invocation, or `None` for a return statement or a panic.
+    pub branch: Option<usize>,
+    /// The values consumed by the statement.
+    pub inputs: Vec<CoreValue>,
+    /// The values produced by the statement.
+    pub outputs: Vec<CoreValue>,
+}
+
+/// Error of a recorded run, along with the trace of the steps run until the failure.
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("{error}")]
+pub struct TracedRunError {
+    pub error: SimulationError,
+    /// The trace of the run, up to the failing statement.
+    pub trace: Trace,
+}
+
+/// Error occurring while replaying a trace.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ReplayError {
+    #[error("error from the simulation")]
+    SimulationError(#[from] SimulationError),
+    #[error("replayed step differs from the recorded step")]
+    StepMismatch(usize),
+    #[error("unexpected number of steps in replay")]
+    StepCountMismatch { expected: usize, actual: usize },
+}
+
+/// Runs the function again with the inputs of the trace, checking that every step matches the
+/// recorded one. Returns the outputs of the function.
+pub fn replay<Field: FieldBackend>(
+    context: &SimulationContext<'_, Field>,
+    function_id: &FunctionId,
+    trace: &Trace,
+) -> Result<Vec<CoreValue>, ReplayError> {
+    let (outputs, replayed) = context
+        .run_with_trace(function_id, trace.inputs.clone())
+        .map_err(|TracedRunError { error, .. }| error)?;
+    if let Some(step) = trace
+        .steps
+        .iter()
+        .zip(replayed.steps.iter())
+        .position(|(expected, actual)| expected != actual)
+    {
+        return Err(ReplayError::StepMismatch(step));
+    }
+    if trace.steps.len() != replayed.steps.len() {
+        return Err(ReplayError::StepCountMismatch {
+            expected: trace.steps.len(),
+            actual: replayed.steps.len(),
+        });
+    }
+    Ok(outputs)
+}
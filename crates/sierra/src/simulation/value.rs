@@ -1,24 +1,112 @@
 use std::collections::HashMap;
 
 use num_bigint::BigInt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// The logical value of a variable for Sierra simulation.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CoreValue {
     // TODO(orizi): Use actual felt object.
+    /// A felt, held by its representative closest to zero, as reduced by the simulation.
     Felt(BigInt),
+    Bool(bool),
     GasBuiltin(i64),
     RangeCheck,
+    Bitwise,
+    Pedersen,
+    Poseidon,
+    EcOp,
+    Signature,
+    System,
+    SegmentArena,
+    AddMod,
+    MulMod,
+    /// A point on the curve, given by its coordinates.
+    EcPoint(BigInt, BigInt),
+    /// An accumulated point on the curve, where `None` is the point at infinity.
+    EcState(Option<(BigInt, BigInt)>),
+    /// A point on a secp256 curve, given by its coordinates, where `(0, 0)` is the point at
+    /// infinity.
+    Secp256Point(BigInt, BigInt),
+    Uint8(u8),
+    Uint16(u16),
+    Uint32(u32),
+    Uint64(u64),
     Uint128(u128),
+    /// A felt known to be smaller than 2^248.
+    Bytes31(BigInt),
+    /// A felt known to be smaller than 2^251 - 256.
+    StorageBaseAddress(BigInt),
+    /// A felt known to be smaller than 2^251.
+    StorageAddress(BigInt),
+    /// A felt known to be smaller than 2^251.
+    ContractAddress(BigInt),
+    /// A felt known to be smaller than 2^251.
+    ClassHash(BigInt),
+    /// A null `Nullable<T>`; non-null values are represented by the boxed value itself.
+    Null,
     NonZero(Box<CoreValue>),
     Ref(Box<CoreValue>),
     Array(Vec<CoreValue>),
-    Dict(HashMap<BigInt, CoreValue>),
+    /// A view over `len` elements of the backing array, starting at `offset`.
+    Span {
+        elements: Vec<CoreValue>,
+        offset: usize,
+        len: usize,
+    },
+    Dict {
+        #[cfg_attr(feature = "serde", serde(with = "dict_entries"))]
+        entries: HashMap<BigInt, CoreValue>,
+        /// The accesses to the dict so far, in order, as (key, prev_value, new_value) entries.
+        accesses: Vec<(BigInt, CoreValue, CoreValue)>,
+        /// The value read for keys that were never written.
+        default_value: Box<CoreValue>,
+    },
+    /// A dict with a pending access to `key`, finalized by providing the key's new value.
+    DictEntry {
+        dict: Box<CoreValue>,
+        key: BigInt,
+    },
     Enum {
         value: Box<CoreValue>,
         /// The index of the relevant variant.
         index: usize,
     },
     Struct(Vec<CoreValue>),
+    /// The inputs of a circuit, either while being provided or once complete.
+    CircuitInputs(Vec<BigInt>),
+    /// The reduced values of the outputs of an evaluated circuit.
+    CircuitOutputs(Vec<BigInt>),
     Uninitialized,
+    /// A pre-paid call of a user function.
+    Coupon,
+    /// The empty value.
+    Unit,
+}
+
+/// Serialization of dict entries as a list of key-value pairs, as formats such as JSON only
+/// support string keys.
+#[cfg(feature = "serde")]
+mod dict_entries {
+    use std::collections::HashMap;
+
+    use num_bigint::BigInt;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::CoreValue;
+
+    pub fn serialize<S: Serializer>(
+        entries: &HashMap<BigInt, CoreValue>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(entries.iter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<BigInt, CoreValue>, D::Error> {
+        Ok(Vec::<(BigInt, CoreValue)>::deserialize(deserializer)?.into_iter().collect())
+    }
 }
@@ -1,28 +1,124 @@
 use bimap::BiMap;
 use itertools::chain;
 
+use crate::extensions::core::CoreLibFunc;
+use crate::extensions::lib_func::SpecializationContext;
+use crate::extensions::{ConcreteLibFunc, GenericLibFunc, SpecializationError};
 use crate::ids::ConcreteTypeId;
 use crate::program::{ConcreteTypeLongId, GenericArg};
 
+/// The param types of a libfunc, and the output types of each of its branches.
+pub type LibFuncSignatureTypes = (Vec<ConcreteTypeId>, Vec<Vec<ConcreteTypeId>>);
+
+/// Runs a signature test row: specializes the core libfunc with the given id and generic args in
+/// `context`, and returns the types of its signature, to compare against the expected types or
+/// error of the row.
+pub fn core_libfunc_signature_types(
+    context: &dyn SpecializationContext,
+    id: &str,
+    generic_args: &[GenericArg],
+) -> Result<LibFuncSignatureTypes, SpecializationError> {
+    let libfunc = CoreLibFunc::by_id(&id.into())
+        .ok_or(SpecializationError::UnsupportedId)?
+        .specialize(context, generic_args)?;
+    Ok((
+        libfunc.param_signatures().iter().map(|param| param.ty.clone()).collect(),
+        libfunc.output_types(),
+    ))
+}
+
 pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
     let mut elements = BiMap::new();
     elements.insert("T".into(), as_type_long_id("T", &[]));
+    elements.insert("bool".into(), as_type_long_id("bool", &[]));
+    elements.insert("uint8".into(), as_type_long_id("uint8", &[]));
+    elements.insert("uint64".into(), as_type_long_id("uint64", &[]));
     elements.insert("uint128".into(), as_type_long_id("uint128", &[]));
+    elements.insert("u256".into(), as_type_long_id("u256", &[]));
+    elements.insert("Secp256k1Point".into(), as_type_long_id("Secp256k1Point", &[]));
+    elements.insert("Secp256r1Point".into(), as_type_long_id("Secp256r1Point", &[]));
+    elements.insert("bytes31".into(), as_type_long_id("bytes31", &[]));
     elements.insert("felt".into(), as_type_long_id("felt", &[]));
     elements.insert("Tuple<>".into(), as_named_type_long_id("Struct", "Tuple", &[]));
     elements.insert(
         "Uint128AndFelt".into(),
         as_named_type_long_id("Struct", "Uint128AndFelt", &["uint128", "felt"]),
     );
+    elements
+        .insert("FeltPair".into(), as_named_type_long_id("Struct", "FeltPair", &["felt", "felt"]));
     elements.insert("Option".into(), as_named_type_long_id("Enum", "Option", &["felt", "Tuple<>"]));
+    elements.insert("Result".into(), as_named_type_long_id("Enum", "Result", &["felt", "uint128"]));
     elements.insert("NonZeroFelt".into(), as_type_long_id("NonZero", &["felt"]));
+    elements.insert("NonZeroUint8".into(), as_type_long_id("NonZero", &["uint8"]));
     elements.insert("NonZeroUint128".into(), as_type_long_id("NonZero", &["uint128"]));
     elements.insert("ArrayFelt".into(), as_type_long_id("Array", &["felt"]));
     elements.insert("ArrayUint128".into(), as_type_long_id("Array", &["uint128"]));
+    elements.insert("ArrayUint64".into(), as_type_long_id("Array", &["uint64"]));
+    elements.insert("SpanFelt".into(), as_type_long_id("Span", &["felt"]));
+    elements.insert("SpanUint128".into(), as_type_long_id("Span", &["uint128"]));
+    elements.insert("ArrayUint128AndFelt".into(), as_type_long_id("Array", &["Uint128AndFelt"]));
+    elements.insert("DictFeltToFelt".into(), as_type_long_id("DictFeltTo", &["felt"]));
+    elements
+        .insert("SquashedDictFeltToFelt".into(), as_type_long_id("SquashedDictFeltTo", &["felt"]));
+    elements.insert("DictFeltToEntryFelt".into(), as_type_long_id("DictFeltToEntry", &["felt"]));
     elements.insert("UninitializedFelt".into(), as_type_long_id("Uninitialized", &["felt"]));
     elements.insert("UninitializedUint128".into(), as_type_long_id("Uninitialized", &["uint128"]));
+    elements.insert("BoxFelt".into(), as_type_long_id("Box", &["felt"]));
+    elements.insert("Unit".into(), as_type_long_id("Unit", &[]));
+    elements.insert(
+        "ExecutionInfo".into(),
+        as_named_type_long_id(
+            "Struct",
+            "ExecutionInfo",
+            &[
+                "uint64",
+                "uint64",
+                "ContractAddress",
+                "felt",
+                "felt",
+                "ContractAddress",
+                "ContractAddress",
+                "felt",
+            ],
+        ),
+    );
+    elements.insert("BoxExecutionInfo".into(), as_type_long_id("Box", &["ExecutionInfo"]));
+    elements.insert("SnapshotFelt".into(), as_type_long_id("Snapshot", &["felt"]));
+    elements.insert("PanicableFelt".into(), as_type_long_id("Panicable", &["felt"]));
+    elements.insert("NullableFelt".into(), as_type_long_id("Nullable", &["felt"]));
     elements.insert("GasBuiltin".into(), as_type_long_id("GasBuiltin", &[]));
     elements.insert("RangeCheck".into(), as_type_long_id("RangeCheck", &[]));
+    elements.insert("SegmentArena".into(), as_type_long_id("SegmentArena", &[]));
+    elements.insert("Bitwise".into(), as_type_long_id("Bitwise", &[]));
+    elements.insert("Pedersen".into(), as_type_long_id("Pedersen", &[]));
+    elements.insert("Poseidon".into(), as_type_long_id("Poseidon", &[]));
+    elements.insert("EcOp".into(), as_type_long_id("EcOp", &[]));
+    elements.insert("EcPoint".into(), as_type_long_id("EcPoint", &[]));
+    elements.insert("EcState".into(), as_type_long_id("EcState", &[]));
+    elements.insert("Signature".into(), as_type_long_id("Signature", &[]));
+    elements.insert("System".into(), as_type_long_id("System", &[]));
+    elements.insert("StorageBaseAddress".into(), as_type_long_id("StorageBaseAddress", &[]));
+    elements.insert("StorageAddress".into(), as_type_long_id("StorageAddress", &[]));
+    elements.insert("ContractAddress".into(), as_type_long_id("ContractAddress", &[]));
+    elements.insert("ClassHash".into(), as_type_long_id("ClassHash", &[]));
+    elements.insert("CouponRegistered".into(), as_coupon_long_id("RegisteredFunction"));
+    elements.insert("CouponIdentity".into(), as_coupon_long_id("identity"));
+    elements.insert("AddMod".into(), as_type_long_id("AddMod", &[]));
+    elements.insert("MulMod".into(), as_type_long_id("MulMod", &[]));
+    elements.insert("In0".into(), as_value_type_long_id("CircuitInput", 0));
+    elements.insert("In1".into(), as_value_type_long_id("CircuitInput", 1));
+    elements.insert("In2".into(), as_value_type_long_id("CircuitInput", 2));
+    elements.insert("Sum".into(), as_type_long_id("AddModGate", &["In0", "In1"]));
+    elements.insert("Product".into(), as_type_long_id("MulModGate", &["Sum", "In1"]));
+    elements.insert("Inverse".into(), as_type_long_id("InverseGate", &["In0"]));
+    elements.insert("Circuit".into(), as_type_long_id("Circuit", &["Product", "Inverse"]));
+    elements.insert("GappedCircuit".into(), as_type_long_id("Circuit", &["In2"]));
+    elements.insert(
+        "CircuitInputAccumulator".into(),
+        as_type_long_id("CircuitInputAccumulator", &["Circuit"]),
+    );
+    elements.insert("CircuitData".into(), as_type_long_id("CircuitData", &["Circuit"]));
+    elements.insert("CircuitOutputs".into(), as_type_long_id("CircuitOutputs", &["Circuit"]));
     elements
 }
 
@@ -33,6 +129,20 @@ fn as_type_long_id(name: &str, args: &[&str]) -> ConcreteTypeLongId {
     }
 }
 
+fn as_value_type_long_id(name: &str, value: i64) -> ConcreteTypeLongId {
+    ConcreteTypeLongId {
+        generic_id: name.into(),
+        generic_args: vec![GenericArg::Value(value.into())],
+    }
+}
+
+fn as_coupon_long_id(function_name: &str) -> ConcreteTypeLongId {
+    ConcreteTypeLongId {
+        generic_id: "Coupon".into(),
+        generic_args: vec![GenericArg::UserFunc(function_name.into())],
+    }
+}
+
 fn as_named_type_long_id(genetic_name: &str, user_name: &str, args: &[&str]) -> ConcreteTypeLongId {
     ConcreteTypeLongId {
         generic_id: genetic_name.into(),
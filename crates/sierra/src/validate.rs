@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use thiserror::Error;
+use utils::ordered_hash_map::OrderedHashMap;
+
+use crate::extensions::lib_func::ConcreteLibFunc;
+use crate::extensions::modules::boxing::BoxType;
+use crate::extensions::modules::function_call::FunctionCallLibFunc;
+use crate::extensions::modules::panic::{PanicTerminatorLibFunc, PanicableType};
+use crate::extensions::{
+    ConcreteType, GenericLibFunc, GenericType, NamedType, NoGenericArgsGenericLibFunc,
+};
+use crate::ids::{ConcreteLibFuncId, ConcreteTypeId, FunctionId, VarId};
+use crate::program::{Function, GenericArg, Program, Statement, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "validate_test.rs"]
+mod test;
+
+/// Error for a type declared through a cycle of by-value members.
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("type `{0}` contains itself by value")]
+pub struct TypeCycleError(pub ConcreteTypeId);
+
+/// Error for an invocation whose branches do not match its libfunc's signature.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ArityError {
+    #[error("error from the program registry")]
+    ProgramRegistryError(StatementIdx, Box<ProgramRegistryError>),
+    #[error("statement is out of bounds")]
+    StatementOutOfBounds(StatementIdx),
+    #[error("unexpected number of branches in invocation")]
+    BranchCountMismatch { statement_idx: StatementIdx, expected: usize, actual: usize },
+    #[error("unexpected number of results bound by invocation branch")]
+    ResultCountMismatch {
+        statement_idx: StatementIdx,
+        branch: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Error for a function that may panic without being panic-wrapped.
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("function `{function_id}` may panic without returning a single `Panicable`")]
+pub struct UnwrappedPanicError {
+    pub function_id: FunctionId,
+    /// The first panicking statement found.
+    pub statement_idx: StatementIdx,
+}
+
+/// Error for a value of a type that may not be dropped, such as a builtin pointer, that is not
+/// threaded linearly through a function.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum LinearityError {
+    #[error("error from the program registry")]
+    ProgramRegistryError(StatementIdx, Box<ProgramRegistryError>),
+    #[error("statement is out of bounds")]
+    StatementOutOfBounds(StatementIdx),
+    #[error("variable is used without being bound, or after being consumed")]
+    MissingVariable { statement_idx: StatementIdx, var: VarId },
+    #[error("variable that may not be dropped is bound again before being consumed")]
+    OverriddenVariable { statement_idx: StatementIdx, var: VarId },
+    #[error("variable that may not be dropped is left unconsumed")]
+    LeakedVariable { statement_idx: StatementIdx, var: VarId },
+    #[error("paths reaching the statement hold different variables that may not be dropped")]
+    MergeMismatch(StatementIdx),
+}
+
+/// Checks that no declared type contains itself by value, directly or through its members.
+/// Members of boxes are not held by value, so boxes break such cycles.
+pub fn check_type_acyclicity(program: &Program) -> Result<(), TypeCycleError> {
+    let members: HashMap<&ConcreteTypeId, Vec<&ConcreteTypeId>> = program
+        .type_declarations
+        .iter()
+        .map(|declaration| {
+            let long_id = &declaration.long_id;
+            let members = if long_id.generic_id == BoxType::ID {
+                vec![]
+            } else {
+                long_id
+                    .generic_args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArg::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect()
+            };
+            (&declaration.id, members)
+        })
+        .collect();
+    let mut visited = HashSet::new();
+    for declaration in &program.type_declarations {
+        visit_type(&declaration.id, &members, &mut vec![], &mut visited)?;
+    }
+    Ok(())
+}
+
+/// Visits a type and the types it contains by value, failing if a type in `path` is reached
+/// again.
+fn visit_type<'a>(
+    ty: &'a ConcreteTypeId,
+    members: &HashMap<&'a ConcreteTypeId, Vec<&'a ConcreteTypeId>>,
+    path: &mut Vec<&'a ConcreteTypeId>,
+    visited: &mut HashSet<&'a ConcreteTypeId>,
+) -> Result<(), TypeCycleError> {
+    if path.contains(&ty) {
+        return Err(TypeCycleError(ty.clone()));
+    }
+    if !visited.insert(ty) {
+        return Ok(());
+    }
+    path.push(ty);
+    for member in members.get(ty).into_iter().flatten() {
+        visit_type(member, members, path, visited)?;
+    }
+    path.pop();
+    Ok(())
+}
+
+/// Checks that every invocation reachable from the function's entry point has a branch per branch
+/// of its libfunc, and that each branch binds as many variables as the libfunc's branch outputs.
+/// Returns all the mismatches found.
+pub fn check_branch_arity<TType: GenericType, TLibFunc: GenericLibFunc>(
+    program: &Program,
+    function: &Function,
+    registry: &ProgramRegistry<TType, TLibFunc>,
+) -> Result<(), Vec<ArityError>> {
+    let mut errors = vec![];
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([function.entry_point]);
+    while let Some(idx) = queue.pop_front() {
+        if !visited.insert(idx) {
+            continue;
+        }
+        let invocation = match program.get_statement(&idx) {
+            Some(Statement::Invocation(invocation)) => invocation,
+            Some(Statement::Return(_)) => continue,
+            None => {
+                errors.push(ArityError::StatementOutOfBounds(idx));
+                continue;
+            }
+        };
+        queue.extend(invocation.branches.iter().map(|branch| idx.next(&branch.target)));
+        let libfunc = match registry.get_libfunc(&invocation.libfunc_id) {
+            Ok(libfunc) => libfunc,
+            Err(error) => {
+                errors.push(ArityError::ProgramRegistryError(idx, error));
+                continue;
+            }
+        };
+        let branch_signatures = libfunc.branch_signatures();
+        if invocation.branches.len() != branch_signatures.len() {
+            errors.push(ArityError::BranchCountMismatch {
+                statement_idx: idx,
+                expected: branch_signatures.len(),
+                actual: invocation.branches.len(),
+            });
+        }
+        for (branch, (branch_info, branch_signature)) in
+            invocation.branches.iter().zip(branch_signatures).enumerate()
+        {
+            if branch_info.results.len() != branch_signature.vars.len() {
+                errors.push(ArityError::ResultCountMismatch {
+                    statement_idx: idx,
+                    branch,
+                    expected: branch_signature.vars.len(),
+                    actual: branch_info.results.len(),
+                });
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that a function that may panic is panic-wrapped, returning a single `Panicable`, so that
+/// the panic data reaches its callers. A function may panic by invoking `panic`, or by calling a
+/// function that may panic and is not panic-wrapped itself. Only statements reachable from the
+/// function's entry point are considered.
+pub fn check_panic_wrapping(
+    program: &Program,
+    function: &Function,
+) -> Result<(), UnwrappedPanicError> {
+    if returns_panicable(program, function) {
+        return Ok(());
+    }
+    let panic_libfuncs: HashSet<&ConcreteLibFuncId> = program
+        .libfunc_declarations
+        .iter()
+        .filter(|declaration| declaration.long_id.generic_id == PanicTerminatorLibFunc::ID)
+        .map(|declaration| &declaration.id)
+        .collect();
+    let called_functions: HashMap<&ConcreteLibFuncId, &Function> = program
+        .libfunc_declarations
+        .iter()
+        .filter(|declaration| declaration.long_id.generic_id == FunctionCallLibFunc::ID)
+        .filter_map(|declaration| match &declaration.long_id.generic_args[..] {
+            [GenericArg::UserFunc(id)] => {
+                Some((&declaration.id, program.funcs.iter().find(|function| &function.id == id)?))
+            }
+            _ => None,
+        })
+        .collect();
+    match find_unwrapped_panic(
+        program,
+        function,
+        &panic_libfuncs,
+        &called_functions,
+        &mut HashSet::new(),
+    ) {
+        Some(statement_idx) => {
+            Err(UnwrappedPanicError { function_id: function.id.clone(), statement_idx })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns whether the function returns a single `Panicable`, and so returns the data of its
+/// panics instead of propagating them.
+pub(crate) fn returns_panicable(program: &Program, function: &Function) -> bool {
+    match &function.signature.ret_types[..] {
+        [ty] => program.type_declarations.iter().any(|declaration| {
+            &declaration.id == ty && declaration.long_id.generic_id == PanicableType::ID
+        }),
+        _ => false,
+    }
+}
+
+/// Returns the first statement reachable from the function's entry point that panics, either by
+/// invoking `panic` or by calling a function that may panic and is not panic-wrapped. Calls to the
+/// functions in `visited_functions`, which were already searched or are being searched, are not
+/// followed.
+fn find_unwrapped_panic<'a>(
+    program: &'a Program,
+    function: &'a Function,
+    panic_libfuncs: &HashSet<&ConcreteLibFuncId>,
+    called_functions: &HashMap<&ConcreteLibFuncId, &'a Function>,
+    visited_functions: &mut HashSet<&'a FunctionId>,
+) -> Option<StatementIdx> {
+    visited_functions.insert(&function.id);
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([function.entry_point]);
+    while let Some(idx) = queue.pop_front() {
+        if !visited.insert(idx) {
+            continue;
+        }
+        if let Some(Statement::Invocation(invocation)) = program.get_statement(&idx) {
+            if panic_libfuncs.contains(&invocation.libfunc_id) {
+                return Some(idx);
+            }
+            if let Some(callee) = called_functions.get(&invocation.libfunc_id) {
+                if !visited_functions.contains(&callee.id)
+                    && !returns_panicable(program, callee)
+                    && find_unwrapped_panic(
+                        program,
+                        callee,
+                        panic_libfuncs,
+                        called_functions,
+                        visited_functions,
+                    )
+                    .is_some()
+                {
+                    return Some(idx);
+                }
+            }
+            queue.extend(invocation.branches.iter().map(|branch| idx.next(&branch.target)));
+        }
+    }
+    None
+}
+
+/// Checks that values of types that may not be dropped, such as the builtin pointers, are threaded
+/// linearly through the function: each is consumed exactly once, and is neither overridden by
+/// another value nor left unconsumed when the function returns or panics. Dropping or duplicating
+/// them through a libfunc is already rejected when the libfunc is specialized. Only statements
+/// reachable from the function's entry point are considered.
+pub fn check_linear_threading<TType: GenericType, TLibFunc: GenericLibFunc>(
+    program: &Program,
+    function: &Function,
+    registry: &ProgramRegistry<TType, TLibFunc>,
+) -> Result<(), LinearityError> {
+    let is_droppable = |idx: StatementIdx, ty: &ConcreteTypeId| {
+        registry
+            .get_type(ty)
+            .map(|concrete_type| concrete_type.info().droppable)
+            .map_err(|error| LinearityError::ProgramRegistryError(idx, error))
+    };
+    // The variables that may not be dropped, held by the first path reaching each statement.
+    let mut visited: HashMap<StatementIdx, HashSet<VarId>> = HashMap::new();
+    let params: OrderedHashMap<VarId, ConcreteTypeId> =
+        function.params.iter().map(|param| (param.id.clone(), param.ty.clone())).collect();
+    let mut queue = VecDeque::from([(function.entry_point, params)]);
+    while let Some((idx, mut vars)) = queue.pop_front() {
+        let mut linear_vars = HashSet::new();
+        for (var, ty) in vars.iter() {
+            if !is_droppable(idx, ty)? {
+                linear_vars.insert(var.clone());
+            }
+        }
+        if let Some(expected) = visited.get(&idx) {
+            if expected != &linear_vars {
+                return Err(LinearityError::MergeMismatch(idx));
+            }
+            continue;
+        }
+        visited.insert(idx, linear_vars);
+        let take = |vars: &mut OrderedHashMap<VarId, ConcreteTypeId>, var: &VarId| {
+            vars.shift_remove(var).map(|_| ()).ok_or_else(|| LinearityError::MissingVariable {
+                statement_idx: idx,
+                var: var.clone(),
+            })
+        };
+        let invocation = match program.get_statement(&idx) {
+            Some(Statement::Invocation(invocation)) => invocation,
+            Some(Statement::Return(returned)) => {
+                for var in returned {
+                    take(&mut vars, var)?;
+                }
+                check_no_leak(idx, &vars, &is_droppable)?;
+                continue;
+            }
+            None => return Err(LinearityError::StatementOutOfBounds(idx)),
+        };
+        for var in &invocation.args {
+            take(&mut vars, var)?;
+        }
+        // A libfunc without branches, such as `panic`, terminates the function.
+        if invocation.branches.is_empty() {
+            check_no_leak(idx, &vars, &is_droppable)?;
+            continue;
+        }
+        let libfunc = registry
+            .get_libfunc(&invocation.libfunc_id)
+            .map_err(|error| LinearityError::ProgramRegistryError(idx, error))?;
+        for (branch_info, branch_signature) in
+            invocation.branches.iter().zip(libfunc.branch_signatures())
+        {
+            let mut branch_vars = vars.clone();
+            for (var, var_info) in branch_info.results.iter().zip(&branch_signature.vars) {
+                if let Some(ty) = branch_vars.insert(var.clone(), var_info.ty.clone()) {
+                    if !is_droppable(idx, &ty)? {
+                        return Err(LinearityError::OverriddenVariable {
+                            statement_idx: idx,
+                            var: var.clone(),
+                        });
+                    }
+                }
+            }
+            queue.push_back((idx.next(&branch_info.target), branch_vars));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that none of the variables left when the function terminates at the statement is of a
+/// type that may not be dropped.
+fn check_no_leak(
+    idx: StatementIdx,
+    vars: &OrderedHashMap<VarId, ConcreteTypeId>,
+    is_droppable: impl Fn(StatementIdx, &ConcreteTypeId) -> Result<bool, LinearityError>,
+) -> Result<(), LinearityError> {
+    for (var, ty) in vars.iter() {
+        if !is_droppable(idx, ty)? {
+            return Err(LinearityError::LeakedVariable { statement_idx: idx, var: var.clone() });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use indoc::indoc;
+use test_case::test_case;
+use test_log::test;
+
+use super::{
+    check_branch_arity, check_linear_threading, check_panic_wrapping, check_type_acyclicity,
+    ArityError, LinearityError, TypeCycleError, UnwrappedPanicError,
+};
+use crate::extensions::core::{CoreLibFunc, CoreType};
+use crate::extensions::{ExtensionError, SpecializationError};
+use crate::program::{Program, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+use crate::ProgramParser;
+
+#[test]
+fn self_referential_struct() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type List = Struct<ut@List, felt, List>;
+        "})
+        .unwrap();
+    assert_eq!(check_type_acyclicity(&program), Err(TypeCycleError("List".into())));
+}
+
+#[test]
+fn boxed_self_referential_struct() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type BoxedList = Box<List>;
+            type List = Struct<ut@List, felt, BoxedList>;
+        "})
+        .unwrap();
+    assert_eq!(check_type_acyclicity(&program), Ok(()));
+}
+
+#[test]
+fn branch_binds_wrong_number_of_results() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type NonZeroFelt = NonZero<felt>;
+            libfunc felt_jump_nz = felt_jump_nz;
+            libfunc felt_drop = drop<felt>;
+            libfunc nz_drop = drop<NonZeroFelt>;
+            felt_jump_nz(a) { fallthrough(a) 3(b) };
+            felt_drop(a) -> ();
+            return();
+            nz_drop(b) -> ();
+            return();
+            main@0(a: felt) -> ();
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    let function = registry.get_function(&"main".into()).unwrap();
+    assert_eq!(
+        check_branch_arity(&program, function, &registry),
+        Err(vec![ArityError::ResultCountMismatch {
+            statement_idx: StatementIdx(0),
+            branch: 0,
+            expected: 0,
+            actual: 1,
+        }])
+    );
+}
+
+#[test]
+fn panicking_functions_are_wrapped() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            type PanicableFelt = Panicable<felt>;
+            libfunc panic = panic;
+            libfunc panicable_wrap = panicable_wrap<felt>;
+            libfunc match_panicable = match_panicable<felt>;
+            libfunc array_new = array_new<felt>;
+            libfunc call_inner = function_call<user@inner>;
+            array_new() -> (data);
+            panic(data) { };
+            call_inner() -> (r);
+            match_panicable(r) { fallthrough(v) 6(data) };
+            panicable_wrap(v) -> (r);
+            return(r);
+            panic(data) { };
+            inner@0() -> (PanicableFelt);
+            outer@2() -> (PanicableFelt);
+        "})
+        .unwrap();
+    for function in &program.funcs {
+        assert_eq!(check_panic_wrapping(&program, function), Ok(()));
+    }
+}
+
+#[test]
+fn panic_propagated_from_unwrapped_function() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            type PanicableFelt = Panicable<felt>;
+            libfunc panic = panic;
+            libfunc match_panicable = match_panicable<felt>;
+            libfunc array_new = array_new<felt>;
+            libfunc call_inner = function_call<user@inner>;
+            array_new() -> (data);
+            panic(data) { };
+            call_inner() -> (r);
+            match_panicable(r) { fallthrough(v) 5(data) };
+            return(v);
+            panic(data) { };
+            inner@0() -> (PanicableFelt);
+            outer@2() -> (felt);
+        "})
+        .unwrap();
+    assert_eq!(check_panic_wrapping(&program, &program.funcs[0]), Ok(()));
+    assert_eq!(
+        check_panic_wrapping(&program, &program.funcs[1]),
+        Err(UnwrappedPanicError { function_id: "outer".into(), statement_idx: StatementIdx(5) })
+    );
+}
+
+#[test]
+fn panic_propagated_through_unwrapped_callee() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type ArrayFelt = Array<felt>;
+            libfunc panic = panic;
+            libfunc array_new = array_new<felt>;
+            libfunc felt_const = felt_const<0>;
+            libfunc call_inner = function_call<user@inner>;
+            libfunc call_outer = function_call<user@outer>;
+            array_new() -> (data);
+            panic(data) { };
+            felt_const() -> (v);
+            call_inner() -> ();
+            return(v);
+            call_outer() -> (v);
+            return(v);
+            inner@0() -> ();
+            outer@2() -> (felt);
+            caller@5() -> (felt);
+        "})
+        .unwrap();
+    assert_eq!(
+        check_panic_wrapping(&program, &program.funcs[0]),
+        Err(UnwrappedPanicError { function_id: "inner".into(), statement_idx: StatementIdx(1) })
+    );
+    assert_eq!(
+        check_panic_wrapping(&program, &program.funcs[1]),
+        Err(UnwrappedPanicError { function_id: "outer".into(), statement_idx: StatementIdx(3) })
+    );
+    assert_eq!(
+        check_panic_wrapping(&program, &program.funcs[2]),
+        Err(UnwrappedPanicError { function_id: "caller".into(), statement_idx: StatementIdx(5) })
+    );
+}
+
+/// Returns the linearity error of the first function of the program, if any.
+fn check_first_function_threading(program: &Program) -> Result<(), LinearityError> {
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(program).unwrap();
+    check_linear_threading(program, &program.funcs[0], &registry)
+}
+
+#[test]
+fn builtin_threaded_linearly() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            type uint128 = uint128;
+            libfunc uint128_add = uint128_add;
+            libfunc drop_uint128 = drop<uint128>;
+            uint128_add(rc, a, b) { fallthrough(rc, c) 3(rc) };
+            drop_uint128(c) -> ();
+            return(rc);
+            return(rc);
+            main@0(rc: RangeCheck, a: uint128, b: uint128) -> (RangeCheck);
+        "})
+        .unwrap();
+    assert_eq!(check_first_function_threading(&program), Ok(()));
+    assert!(ProgramRegistry::<CoreType, CoreLibFunc>::new_validated(&program).is_ok());
+}
+
+#[test]
+fn builtin_leaked() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            type uint128 = uint128;
+            libfunc uint128_add = uint128_add;
+            uint128_add(rc, a, b) { fallthrough(rc, c) 2(rc) };
+            return(rc, c);
+            return();
+            main@0(rc: RangeCheck, a: uint128, b: uint128) -> (RangeCheck, uint128);
+        "})
+        .unwrap();
+    let error = LinearityError::LeakedVariable { statement_idx: StatementIdx(2), var: "rc".into() };
+    assert_eq!(
+        ProgramRegistry::<CoreType, CoreLibFunc>::new_validated(&program).map(|_| ()),
+        Err(Box::new(ProgramRegistryError::NonLinearThreading {
+            function_id: "main".into(),
+            error
+        }))
+    );
+}
+
+#[test]
+fn builtin_used_twice() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            return(rc, rc);
+            main@0(rc: RangeCheck) -> (RangeCheck, RangeCheck);
+        "})
+        .unwrap();
+    assert_eq!(
+        check_first_function_threading(&program),
+        Err(LinearityError::MissingVariable { statement_idx: StatementIdx(0), var: "rc".into() })
+    );
+}
+
+#[test]
+fn builtin_overridden() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            libfunc call_identity = function_call<user@identity>;
+            call_identity(other) -> (rc);
+            return(rc);
+            return(rc);
+            main@0(rc: RangeCheck, other: RangeCheck) -> (RangeCheck);
+            identity@2(rc: RangeCheck) -> (RangeCheck);
+        "})
+        .unwrap();
+    assert_eq!(
+        check_first_function_threading(&program),
+        Err(LinearityError::OverriddenVariable {
+            statement_idx: StatementIdx(0),
+            var: "rc".into()
+        })
+    );
+}
+
+#[test]
+fn builtin_leaked_on_one_branch_of_merge() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            type uint128 = uint128;
+            libfunc uint128_add = uint128_add;
+            libfunc drop_uint128 = drop<uint128>;
+            libfunc jump = jump;
+            uint128_add(rc, a, b) { fallthrough(rc, c) 3(other_rc) };
+            drop_uint128(c) -> ();
+            return(rc);
+            jump() { 4() };
+            jump() { 2() };
+            main@0(rc: RangeCheck, a: uint128, b: uint128) -> (RangeCheck);
+        "})
+        .unwrap();
+    assert_eq!(
+        check_first_function_threading(&program),
+        Err(LinearityError::MergeMismatch(StatementIdx(2)))
+    );
+}
+
+#[test_case("drop<RangeCheck>", SpecializationError::TypeNotDroppable("RangeCheck".into());
+            "drop")]
+#[test_case("dup<RangeCheck>", SpecializationError::TypeNotDuplicatable("RangeCheck".into());
+            "dup")]
+fn builtin_dropped_or_duplicated_by_libfunc(libfunc: &str, error: SpecializationError) {
+    let program = ProgramParser::new()
+        .parse(&format!("type RangeCheck = RangeCheck; libfunc misuse = {libfunc};"))
+        .unwrap();
+    assert_eq!(
+        ProgramRegistry::<CoreType, CoreLibFunc>::new_validated(&program).map(|_| ()),
+        Err(Box::new(ProgramRegistryError::LibFuncSpecialization {
+            concrete_id: "misuse".into(),
+            error: ExtensionError::LibFuncSpecialization {
+                long_id: program.libfunc_declarations[0].long_id.clone(),
+                error
+            },
+        }))
+    );
+}
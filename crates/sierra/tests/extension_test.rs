@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+
+use sierra::extensions::core::{CoreLibFunc, CoreType};
+use sierra::extensions::felt::FeltType;
+use sierra::extensions::lib_func::{
+    LibFuncSignature, OutputVarInfo, SierraApChange, SignatureSpecializationContext,
+};
+use sierra::extensions::registry::{ExtensibleConcreteLibFunc, ExtensibleLibFunc};
+use sierra::extensions::{
+    ConcreteLibFunc, ExtensionError, ExtensionRegistry, GenericLibFunc, NamedType,
+    NoGenericArgsGenericLibFunc, OutputVarReferenceInfo, SpecializationError,
+};
+use sierra::ids::GenericLibFuncId;
+use sierra::program::ConcreteLibFuncLongId;
+use sierra::program_registry::{
+    get_libfunc_signatures, get_libfunc_signatures_with_extensions, ProgramRegistry,
+    ProgramRegistryError,
+};
+use sierra::{define_libfunc_hierarchy, ProgramParser};
+
+/// LibFunc provided outside of the core set, duplicating a felt.
+#[derive(Default)]
+pub struct ExtraFeltDupLibFunc {}
+impl NoGenericArgsGenericLibFunc for ExtraFeltDupLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("extra_felt_dup");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        let felt_ty = context.get_concrete_type(FeltType::id(), &[])?;
+        let output = || OutputVarInfo {
+            ty: felt_ty.clone(),
+            ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 0 },
+        };
+        Ok(LibFuncSignature::new_non_branch(
+            vec![felt_ty.clone()],
+            vec![output(), output()],
+            SierraApChange::Known(0),
+        ))
+    }
+}
+
+define_libfunc_hierarchy! {
+    pub enum ExtendedLibFunc {
+        Core(CoreLibFunc),
+        ExtraFeltDup(ExtraFeltDupLibFunc),
+        #[cfg(not(test))]
+        ExcludedFeltDup(ExcludedFeltDupLibFunc),
+        #[cfg(all())]
+        IncludedFeltDup(IncludedFeltDupLibFunc),
+    }, ExtendedConcreteLibFunc
+}
+
+/// LibFunc of a variant with an attribute that keeps it in the hierarchy.
+#[derive(Default)]
+pub struct IncludedFeltDupLibFunc {}
+impl NoGenericArgsGenericLibFunc for IncludedFeltDupLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("included_felt_dup");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        NoGenericArgsGenericLibFunc::specialize_signature(&ExtraFeltDupLibFunc::default(), context)
+    }
+}
+
+/// LibFunc of a variant that is configured out of the hierarchy.
+#[cfg(not(test))]
+#[derive(Default)]
+pub struct ExcludedFeltDupLibFunc {}
+#[cfg(not(test))]
+impl NoGenericArgsGenericLibFunc for ExcludedFeltDupLibFunc {
+    const ID: GenericLibFuncId = GenericLibFuncId::new_inline("excluded_felt_dup");
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibFuncSignature, SpecializationError> {
+        NoGenericArgsGenericLibFunc::specialize_signature(&ExtraFeltDupLibFunc::default(), context)
+    }
+}
+
+const PROGRAM: &str = "
+    type felt = felt;
+    libfunc felt_add = felt_add;
+    libfunc extra_felt_dup = extra_felt_dup;
+";
+
+#[test]
+fn core_hierarchy_extended_by_other_crate() {
+    let program = ProgramParser::new().parse(PROGRAM).unwrap();
+    let registry = ProgramRegistry::<CoreType, ExtendedLibFunc>::new(&program).unwrap();
+    assert!(matches!(
+        registry.get_libfunc(&"felt_add".into()).unwrap(),
+        ExtendedConcreteLibFunc::Core(_)
+    ));
+    assert!(matches!(
+        registry.get_libfunc(&"extra_felt_dup".into()).unwrap(),
+        ExtendedConcreteLibFunc::ExtraFeltDup(_)
+    ));
+}
+
+#[test]
+fn extra_libfunc_unsupported_by_core() {
+    let program = ProgramParser::new().parse(PROGRAM).unwrap();
+    assert_eq!(
+        ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).map(|_| ()),
+        Err(Box::new(ProgramRegistryError::LibFuncSpecialization {
+            concrete_id: "extra_felt_dup".into(),
+            error: ExtensionError::LibFuncSpecialization {
+                long_id: ConcreteLibFuncLongId {
+                    generic_id: "extra_felt_dup".into(),
+                    generic_args: vec![],
+                },
+                error: SpecializationError::UnsupportedId
+            }
+        }))
+    );
+}
+
+#[test]
+fn configured_out_variant_is_excluded() {
+    assert!(ExtendedLibFunc::by_id(&"extra_felt_dup".into()).is_some());
+    assert!(ExtendedLibFunc::by_id(&"excluded_felt_dup".into()).is_none());
+}
+
+#[test]
+fn configured_in_variant_dispatches() {
+    let program = ProgramParser::new()
+        .parse("type felt = felt; libfunc included_felt_dup = included_felt_dup;")
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, ExtendedLibFunc>::new(&program).unwrap();
+    assert!(matches!(
+        registry.get_libfunc(&"included_felt_dup".into()).unwrap(),
+        ExtendedConcreteLibFunc::IncludedFeltDup(_)
+    ));
+}
+
+#[test]
+fn libfunc_registered_at_runtime() {
+    let mut extensions = ExtensionRegistry::<CoreLibFunc>::new();
+    extensions.register("extra_felt_dup".into(), ExtraFeltDupLibFunc::default()).unwrap();
+    let program = ProgramParser::new().parse(PROGRAM).unwrap();
+    let registry = ProgramRegistry::<CoreType, ExtensibleLibFunc<CoreLibFunc>>::with_extensions(
+        &program,
+        &extensions,
+        HashMap::default(),
+        false,
+    )
+    .unwrap();
+    assert!(matches!(
+        registry.get_libfunc(&"felt_add".into()).unwrap(),
+        ExtensibleConcreteLibFunc::Core(_)
+    ));
+    let extra_felt_dup = registry.get_libfunc(&"extra_felt_dup".into()).unwrap();
+    assert!(matches!(extra_felt_dup, ExtensibleConcreteLibFunc::Registered(_)));
+    assert!(extra_felt_dup
+        .downcast_ref::<<ExtraFeltDupLibFunc as GenericLibFunc>::Concrete>()
+        .is_some());
+}
+
+#[test]
+fn libfunc_registered_at_runtime_with_ap_change_and_type_dependencies() {
+    let mut extensions = ExtensionRegistry::<CoreLibFunc>::new();
+    extensions.register("extra_felt_dup".into(), ExtraFeltDupLibFunc::default()).unwrap();
+    let program = ProgramParser::new()
+        .parse(&format!("{PROGRAM} libfunc call_foo = function_call<user@foo>; foo@0() -> ();"))
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, ExtensibleLibFunc<CoreLibFunc>>::with_extensions(
+        &program,
+        &extensions,
+        HashMap::from([("foo".into(), SierraApChange::Known(3))]),
+        true,
+    )
+    .unwrap();
+    // The call adds its own ap change to the function's.
+    assert_eq!(
+        registry.get_libfunc(&"call_foo".into()).unwrap().branch_signatures()[0].ap_change,
+        SierraApChange::Known(5)
+    );
+    assert_eq!(
+        registry.type_dependencies().unwrap()[&"extra_felt_dup".into()],
+        HashSet::from(["felt".into()])
+    );
+}
+
+#[test]
+fn libfunc_signatures_of_libfunc_registered_at_runtime() {
+    let mut extensions = ExtensionRegistry::<CoreLibFunc>::new();
+    extensions.register("extra_felt_dup".into(), ExtraFeltDupLibFunc::default()).unwrap();
+    let program = ProgramParser::new().parse(PROGRAM).unwrap();
+    let signatures = get_libfunc_signatures_with_extensions::<CoreType, CoreLibFunc>(
+        &program,
+        &extensions,
+        HashMap::default(),
+    )
+    .unwrap();
+    assert_eq!(signatures[&"extra_felt_dup".into()].branch_signatures[0].vars.len(), 2);
+    // Without the extensions, only the static hierarchy is available.
+    assert!(matches!(
+        get_libfunc_signatures::<CoreType, ExtensibleLibFunc<CoreLibFunc>>(
+            &program,
+            HashMap::default()
+        )
+        .map_err(|error| *error),
+        Err(ProgramRegistryError::LibFuncSpecialization {
+            error: ExtensionError::LibFuncSpecialization {
+                error: SpecializationError::UnsupportedId,
+                ..
+            },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn registered_libfunc_ids_are_unique() {
+    let mut extensions = ExtensionRegistry::<CoreLibFunc>::new();
+    assert_eq!(
+        extensions.register("felt_add".into(), ExtraFeltDupLibFunc::default()),
+        Err(ExtensionError::LibFuncIdAlreadyRegistered("felt_add".into()))
+    );
+    extensions.register("extra_felt_dup".into(), ExtraFeltDupLibFunc::default()).unwrap();
+    assert_eq!(
+        extensions.register("extra_felt_dup".into(), ExtraFeltDupLibFunc::default()),
+        Err(ExtensionError::LibFuncIdAlreadyRegistered("extra_felt_dup".into()))
+    );
+}
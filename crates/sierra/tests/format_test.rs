@@ -1,4 +1,6 @@
 use indoc::indoc;
+use sierra::fmt::PrintOptions;
+use sierra::parser_utils::ParseError;
 use test_log::test;
 
 // Testing by parsing code and printing its display, making sure we get back the formatted code.
@@ -20,6 +22,7 @@ fn format_test() {
                 libfunc [5642] = LibFuncId<[22 ], 4>;
                 libfunc CallFunction = Call<user@Function>;
                 libfunc LibDependent = LibDependent<lib@[124]>;
+                libfunc ShortString = felt_const< 'hello, world!' >;
                 callee() -> ();
                 callee(arg1) -> (res1);
                 callee( arg1, arg2) -> ( res1, res2);
@@ -48,6 +51,7 @@ fn format_test() {
             libfunc [5642] = LibFuncId<[22], 4>;
             libfunc CallFunction = Call<user@Function>;
             libfunc LibDependent = LibDependent<lib@[124]>;
+            libfunc ShortString = felt_const<'hello, world!'>;
 
             callee() -> ();
             callee(arg1) -> (res1);
@@ -67,3 +71,103 @@ fn format_test() {
         .to_string())
     );
 }
+
+// Testing that large values are printed through the const table, and parsed back to the same
+// program, also when written inline in hex.
+#[test]
+fn const_table_format_test() {
+    let parser = sierra::ProgramParser::new();
+    let program = parser
+        .parse(indoc! {"
+            type BigArg = TypeId<340282366920938463463374607431768211456>;
+            libfunc big_const = felt_const<340282366920938463463374607431768211456>;
+            libfunc small_const = felt_const<-5>;
+            big_const() -> (x);
+            small_const() -> (y);
+            return(x, y);
+
+            Main@0() -> (felt, felt);
+        "})
+        .unwrap();
+    let options = PrintOptions { hex_threshold_bits: Some(64), const_table: true, labels: false };
+    let formatted = program.display_with_options(&options).to_string();
+    assert_eq!(
+        formatted,
+        indoc! {"
+            const c0 = 0x100000000000000000000000000000000;
+
+            type BigArg = TypeId<const@c0>;
+
+            libfunc big_const = felt_const<const@c0>;
+            libfunc small_const = felt_const<-5>;
+
+            big_const() -> (x);
+            small_const() -> (y);
+            return(x, y);
+
+            Main@0() -> (felt, felt);
+        "}
+    );
+    assert_eq!(parser.parse(&formatted), Ok(program.clone()));
+    let inline_options =
+        PrintOptions { hex_threshold_bits: Some(64), const_table: false, labels: false };
+    assert_eq!(
+        parser.parse(&program.display_with_options(&inline_options).to_string()),
+        Ok(program)
+    );
+}
+
+// Testing that labeled statements are resolved to their indices, and printed back as labels.
+#[test]
+fn labels_format_test() {
+    let parser = sierra::ProgramParser::new();
+    let program = parser
+        .parse(indoc! {"
+            jump_nz(x) { fallthrough() non_zero(y) };
+            zero: return(x);
+            non_zero: jump() { zero() };
+
+            Main@0(x: felt) -> (felt);
+        "})
+        .unwrap();
+    assert_eq!(
+        program.to_string(),
+        indoc! {"
+
+
+            jump_nz(x) { fallthrough() 2(y) };
+            return(x);
+            jump() { 1() };
+
+            Main@0(x: felt) -> (felt);
+        "}
+    );
+    let options = PrintOptions { labels: true, ..PrintOptions::default() };
+    let formatted = program.display_with_options(&options).to_string();
+    assert_eq!(
+        formatted,
+        indoc! {"
+
+
+            jump_nz(x) { fallthrough() L2(y) };
+            L1: return(x);
+            L2: jump() { L1() };
+
+            Main@0(x: felt) -> (felt);
+        "}
+    );
+    assert_eq!(parser.parse(&formatted), Ok(program));
+}
+
+#[test]
+fn labels_error_test() {
+    let parser = sierra::ProgramParser::new();
+    assert_eq!(
+        parser.parse("jump() { missing() }; return();"),
+        Err(lalrpop_util::ParseError::User { error: ParseError::UndefinedLabel("missing".into()) })
+    );
+    assert_eq!(
+        parser.parse("a: return(); a: return();"),
+        Err(lalrpop_util::ParseError::User { error: ParseError::DuplicateLabel("a".into()) })
+    );
+}
@@ -1,21 +1,41 @@
 use sierra::extensions::array::ArrayConcreteLibFunc;
+use sierra::extensions::boolean::BoolConcreteLibFunc;
+use sierra::extensions::bytes31::Bytes31ConcreteLibFunc;
+use sierra::extensions::casts::CastConcreteLibFunc;
+use sierra::extensions::circuit::CircuitConcreteLibFunc;
 use sierra::extensions::core::CoreConcreteLibFunc::{
-    self, ApTracking, Array, Box, DictFeltTo, Drop, Dup, Enum, Felt, FunctionCall, Gas, Mem,
-    Struct, Uint128, UnconditionalJump, UnwrapNonZero,
+    self, ApTracking, Array, Bitwise, Bool, Box, BranchAlign, Bytes31, Cast, Circuit,
+    ClassHashTryFromFelt, ConstAsImmediate, ContractAddressTryFromFelt, Coupon, DebugPrint,
+    DictFeltTo, Drop, Dup, Ec, EcdsaVerify, EmitEvent, Enum, Felt, FunctionCall, Gas, JumpNotZero,
+    Mem, Nullable, Panic, Pedersen, Poseidon, Pow, Secp256, SnapshotTake, Span, StorageAddress,
+    Struct, Syscall, Uint128, Uint16, Uint256, Uint32, Uint64, Uint8, UnconditionalJump, Unit,
+    UnwrapNonZero,
 };
+use sierra::extensions::coupon::{CouponConcreteLibFunc, CouponConcreteLibFuncInfo};
 use sierra::extensions::dict_felt_to::DictFeltToConcreteLibFunc;
+use sierra::extensions::ec::EcConcreteLibFunc;
 use sierra::extensions::enm::EnumConcreteLibFunc;
 use sierra::extensions::felt::FeltConcrete;
 use sierra::extensions::function_call::FunctionCallConcreteLibFunc;
-use sierra::extensions::gas::GasConcreteLibFunc::{BurnGas, GetGas, RefundGas};
+use sierra::extensions::gas::GasConcreteLibFunc::{
+    BurnGas, GasElapsed, GasMark, GetGas, RefundGas,
+};
 use sierra::extensions::integer::{
-    IntOperator, Uint128BinaryOperationConcreteLibFunc, Uint128Concrete,
+    IntOperator, ShiftOperator, Uint128BinaryOperationConcreteLibFunc, Uint128Concrete,
     Uint128OperationConcreteLibFunc, Uint128OperationWithConstConcreteLibFunc,
+    Uint128ShiftConcreteLibFunc,
 };
 use sierra::extensions::mem::MemConcreteLibFunc::{
     AlignTemps, AllocLocal, FinalizeLocals, Rename, StoreLocal, StoreTemp,
 };
+use sierra::extensions::nullable::NullableConcreteLibFunc;
+use sierra::extensions::panic::PanicConcreteLibFunc;
+use sierra::extensions::pow::PowBase;
+use sierra::extensions::span::SpanConcreteLibFunc;
+use sierra::extensions::storage::StorageAddressConcreteLibFunc;
 use sierra::extensions::strct::StructConcreteLibFunc;
+use sierra::extensions::uint::UintConcrete;
+use sierra::extensions::uint256::Uint256Concrete;
 use sierra::program::Function;
 
 /// The operation required for extracting a libfunc's cost.
@@ -47,20 +67,128 @@ pub fn core_libfunc_cost_base<Ops: CostOperations>(
             let func_content_cost = ops.function_cost(function);
             vec![ops.add(ops.const_cost(2), func_content_cost)]
         }
+        // Buying a coupon pays for the content of the call in advance, and refunding it returns
+        // that cost.
+        Coupon(CouponConcreteLibFunc::Buy(CouponConcreteLibFuncInfo { function, .. })) => {
+            vec![ops.function_cost(function)]
+        }
+        Coupon(CouponConcreteLibFunc::Refund(CouponConcreteLibFuncInfo { function, .. })) => {
+            let func_content_cost = ops.function_cost(function);
+            vec![ops.sub(ops.const_cost(0), func_content_cost)]
+        }
         Gas(GetGas(_)) => {
             vec![ops.sub(ops.const_cost(1), ops.statement_var_cost()), ops.const_cost(1)]
         }
         Gas(RefundGas(_)) | Gas(BurnGas(_)) => vec![ops.statement_var_cost()],
+        // The mark is only a reference to the gas counter, while the elapsed gas is a deferred
+        // subtraction, costing a step once stored.
+        Gas(GasMark(_)) => vec![ops.const_cost(0)],
+        Gas(GasElapsed(_)) => vec![ops.const_cost(1)],
         Array(ArrayConcreteLibFunc::New(_)) => vec![ops.const_cost(1)],
         Array(ArrayConcreteLibFunc::Append(_)) => vec![ops.const_cost(2)],
+        // Array libfuncs below are not compiled to CASM yet, so only the invocation itself is
+        // accounted for.
+        Array(ArrayConcreteLibFunc::Reverse(_)) => vec![ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::Zip(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::Fold(_)) => vec![ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::Eq(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::FindConst(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::Rotate(_)) => vec![ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::Len(_)) => vec![ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::At(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::PopFront(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Array(ArrayConcreteLibFunc::Slice(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        // A span shares the array's representation, so creating one is free.
+        Span(SpanConcreteLibFunc::FromArray(_)) => vec![ops.const_cost(0)],
+        Span(SpanConcreteLibFunc::PopFront(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Span(SpanConcreteLibFunc::Len(_)) => vec![ops.const_cost(1)],
+        Bool(BoolConcreteLibFunc::ToFelt(_)) => vec![ops.const_cost(0)],
+        Bool(BoolConcreteLibFunc::Operation(_) | BoolConcreteLibFunc::Not(_)) => {
+            vec![ops.const_cost(1)]
+        }
+        Bool(BoolConcreteLibFunc::FromFelt(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        // Bitwise libfuncs are not compiled to CASM yet.
+        Bitwise(_) => vec![ops.const_cost(1)],
+        // Circuit libfuncs are not compiled to CASM yet.
+        Circuit(
+            CircuitConcreteLibFunc::InitCircuitData(_) | CircuitConcreteLibFunc::GetOutput(_),
+        ) => {
+            vec![ops.const_cost(0)]
+        }
+        Circuit(CircuitConcreteLibFunc::AddInput(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        // A builtin instance per modular operation, on both branches.
+        Circuit(CircuitConcreteLibFunc::Eval(libfunc)) => {
+            let cost = libfunc.circuit.n_operations() as i32 + 1;
+            vec![ops.const_cost(cost), ops.const_cost(cost)]
+        }
+        // Hash libfuncs are not compiled to CASM yet.
+        Pedersen(_) | Poseidon(_) => vec![ops.const_cost(1)],
+        // EC libfuncs are not compiled to CASM yet.
+        Ec(EcConcreteLibFunc::TryNew(_) | EcConcreteLibFunc::StateFinalize(_)) => {
+            vec![ops.const_cost(1), ops.const_cost(1)]
+        }
+        Ec(EcConcreteLibFunc::StateInit(_) | EcConcreteLibFunc::StateAddMul(_)) => {
+            vec![ops.const_cost(1)]
+        }
+        // Signature verification is not compiled to CASM yet.
+        EcdsaVerify(_) => vec![ops.const_cost(1), ops.const_cost(1)],
+        // Bounded felt conversions are not compiled to CASM yet, so their costs follow
+        // bytes31_try_from_felt.
+        ContractAddressTryFromFelt(_) | ClassHashTryFromFelt(_) => {
+            vec![ops.const_cost(3), ops.const_cost(7)]
+        }
+        // Storage address libfuncs are not compiled to CASM yet. Reducing a felt into a base
+        // address is range checked, and adding an offset is a single addition.
+        StorageAddress(StorageAddressConcreteLibFunc::BaseFromFelt(_)) => vec![ops.const_cost(5)],
+        StorageAddress(StorageAddressConcreteLibFunc::FromBaseAndOffset(_)) => {
+            vec![ops.const_cost(0)]
+        }
+        // Syscalls are not compiled to CASM yet.
+        Syscall(_) | Secp256(_) => vec![ops.const_cost(1), ops.const_cost(1)],
+        Uint8(libfunc) | Uint16(libfunc) | Uint32(libfunc) | Uint64(libfunc) => {
+            uint_libfunc_cost(ops, libfunc)
+        }
         Uint128(libfunc) => integer_libfunc_cost(ops, libfunc),
+        Uint256(libfunc) => uint256_libfunc_cost(ops, libfunc),
+        Bytes31(libfunc) => bytes31_libfunc_cost(ops, libfunc),
+        Cast(CastConcreteLibFunc::Upcast(_)) => vec![ops.const_cost(0)],
+        // Downcasts are not compiled to CASM yet, so their costs follow uint128_from_felt.
+        Cast(CastConcreteLibFunc::Downcast(_)) => vec![ops.const_cost(3), ops.const_cost(7)],
         Felt(libfunc) => felt_libfunc_cost(ops, libfunc),
-        Drop(_) | Dup(_) | ApTracking(_) | UnwrapNonZero(_) | Mem(Rename(_)) | Box(_) => {
+        ConstAsImmediate(_) => vec![ops.const_cost(0)],
+        Pow(libfunc) => {
+            let cost = libfunc.n_multiplications() as i32;
+            match libfunc.base {
+                PowBase::Felt => vec![ops.const_cost(cost)],
+                // Every multiplication is range checked for overflow.
+                PowBase::Uint128 => {
+                    vec![ops.const_cost(4 * cost + 3), ops.const_cost(4 * cost + 4)]
+                }
+            }
+        }
+        // The gas of aligned branches is equalized by the solver, not by the libfunc.
+        BranchAlign(_) => vec![ops.const_cost(0)],
+        Drop(_) | Dup(_) | SnapshotTake(_) | ApTracking(_) | UnwrapNonZero(_) | Mem(Rename(_))
+        | Box(_) | DebugPrint(_) | Unit(_) => vec![ops.const_cost(0)],
+        Nullable(NullableConcreteLibFunc::Null(_) | NullableConcreteLibFunc::FromBox(_)) => {
             vec![ops.const_cost(0)]
         }
+        Nullable(NullableConcreteLibFunc::Match(_)) | JumpNotZero(_) => {
+            vec![ops.const_cost(1), ops.const_cost(1)]
+        }
+        // Panicking has no branches, as it terminates the function.
+        Panic(PanicConcreteLibFunc::Panic(_)) => vec![],
+        // Panics are not compiled to CASM yet, so wrapping and matching follow enums.
+        Panic(PanicConcreteLibFunc::Wrap(_)) => vec![ops.const_cost(1)],
+        Panic(PanicConcreteLibFunc::Match(_)) => vec![ops.const_cost(1), ops.const_cost(1)],
+        // Emitting events is not compiled to CASM yet, so only the invocation itself is accounted
+        // for.
+        EmitEvent(_) => vec![ops.const_cost(1)],
         Mem(StoreLocal(_) | AllocLocal(_) | StoreTemp(_) | AlignTemps(_) | FinalizeLocals(_))
         | UnconditionalJump(_) => vec![ops.const_cost(1)],
-        Enum(EnumConcreteLibFunc::Init(_)) => vec![ops.const_cost(1)],
+        Enum(EnumConcreteLibFunc::Init(_))
+        | CoreConcreteLibFunc::Option(_)
+        | CoreConcreteLibFunc::Result(_) => vec![ops.const_cost(1)],
         Enum(EnumConcreteLibFunc::Match(sig)) => {
             vec![ops.const_cost(1); sig.signature.branch_signatures.len()]
         }
@@ -76,6 +204,15 @@ pub fn core_libfunc_cost_base<Ops: CostOperations>(
         DictFeltTo(DictFeltToConcreteLibFunc::Write(_)) => {
             vec![ops.const_cost(4)]
         }
+        // The entry's key is only hashed once, when the entry is taken.
+        DictFeltTo(DictFeltToConcreteLibFunc::EntryGet(_)) => {
+            vec![ops.const_cost(4)]
+        }
+        DictFeltTo(DictFeltToConcreteLibFunc::EntryFinalize(_)) => {
+            vec![ops.const_cost(1)]
+        }
+        // Squashing is not compiled to CASM yet, so only the invocation itself is accounted for.
+        DictFeltTo(DictFeltToConcreteLibFunc::Squash(_)) => vec![ops.const_cost(1)],
     }
 }
 
@@ -123,6 +260,64 @@ fn integer_libfunc_cost<Ops: CostOperations>(
         Uint128Concrete::LessThanOrEqual(_) => {
             vec![ops.const_cost(3), ops.const_cost(4)]
         }
+        // Costs are static, so a loop over an array of any length cannot be charged per element.
+        // Like the array libfuncs, this libfunc is not compiled to CASM yet, so no run executes the
+        // loop, and only the invocation itself is accounted for.
+        Uint128Concrete::ArraySum(_) => {
+            vec![ops.const_cost(1), ops.const_cost(1)]
+        }
+        Uint128Concrete::SafeDivMod(_) | Uint128Concrete::WideMul(_) => vec![ops.const_cost(7)],
+        Uint128Concrete::Sqrt(_) => vec![ops.const_cost(9)],
+        Uint128Concrete::Shift(libfunc) => {
+            let operator = match libfunc {
+                Uint128ShiftConcreteLibFunc::Binary(libfunc) => libfunc.operator,
+                Uint128ShiftConcreteLibFunc::Const(libfunc) => libfunc.operator,
+            };
+            match operator {
+                ShiftOperator::Shl => vec![ops.const_cost(5), ops.const_cost(4)],
+                ShiftOperator::Shr => vec![ops.const_cost(5)],
+            }
+        }
+    }
+}
+
+/// Returns costs for bounded unsigned integer libfuncs.
+fn uint_libfunc_cost<Ops: CostOperations>(ops: &Ops, libfunc: &UintConcrete) -> Vec<Ops::CostType> {
+    // Bounded unsigned integers are not compiled to CASM yet, so their costs follow uint128.
+    match libfunc {
+        UintConcrete::Const(_) => vec![ops.const_cost(0)],
+        UintConcrete::Operation(_) | UintConcrete::LessThanOrEqual(_) => {
+            vec![ops.const_cost(3), ops.const_cost(4)]
+        }
+        UintConcrete::LessThan(_) => vec![ops.const_cost(4), ops.const_cost(3)],
+    }
+}
+
+/// Returns costs for u256 libfuncs.
+fn uint256_libfunc_cost<Ops: CostOperations>(
+    ops: &Ops,
+    libfunc: &Uint256Concrete,
+) -> Vec<Ops::CostType> {
+    match libfunc {
+        Uint256Concrete::Construct(_) | Uint256Concrete::Deconstruct(_) => vec![ops.const_cost(0)],
+        // Operations are not compiled to CASM yet, so their costs follow two uint128 operations.
+        Uint256Concrete::Operation(_) => vec![ops.const_cost(6), ops.const_cost(8)],
+    }
+}
+
+/// Returns costs for bytes31 libfuncs.
+fn bytes31_libfunc_cost<Ops: CostOperations>(
+    ops: &Ops,
+    libfunc: &Bytes31ConcreteLibFunc,
+) -> Vec<Ops::CostType> {
+    match libfunc {
+        Bytes31ConcreteLibFunc::ToFelt(_) => vec![ops.const_cost(0)],
+        // The following are not compiled to CASM yet, so their costs follow range checked uint128
+        // operations.
+        Bytes31ConcreteLibFunc::TryFromFelt(_) => vec![ops.const_cost(3), ops.const_cost(7)],
+        Bytes31ConcreteLibFunc::Concat(_) | Bytes31ConcreteLibFunc::Split(_) => {
+            vec![ops.const_cost(6), ops.const_cost(4)]
+        }
     }
 }
 
@@ -130,8 +325,13 @@ fn integer_libfunc_cost<Ops: CostOperations>(
 fn felt_libfunc_cost<Ops: CostOperations>(ops: &Ops, libfunc: &FeltConcrete) -> Vec<Ops::CostType> {
     match libfunc {
         FeltConcrete::Const(_) | FeltConcrete::Operation(_) => vec![ops.const_cost(0)],
-        FeltConcrete::JumpNotZero(_) => {
+        FeltConcrete::Inverse(_) => vec![ops.const_cost(1)],
+        FeltConcrete::JumpNotZero(_) | FeltConcrete::IsZero(_) => {
             vec![ops.const_cost(1), ops.const_cost(1)]
         }
+        FeltConcrete::AssertLtConst(_) => vec![ops.const_cost(3), ops.const_cost(3)],
+        FeltConcrete::Popcount(_) => vec![ops.const_cost(1)],
+        FeltConcrete::Pack(_) => vec![ops.const_cost(3), ops.const_cost(3)],
+        FeltConcrete::Clamp(_) => vec![ops.const_cost(3)],
     }
 }
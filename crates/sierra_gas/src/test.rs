@@ -1,9 +1,18 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-use sierra::program::{Program, StatementIdx};
+use indoc::indoc;
+use sierra::extensions::core::{CoreLibFunc, CoreType};
+use sierra::program::{Program, Statement, StatementIdx};
+use sierra::program_registry::ProgramRegistry;
+use sierra::simulation::run;
+use sierra::simulation::value::CoreValue;
+use sierra::ProgramParser;
 use test_case::test_case;
+use utils::try_extract_matches;
 
+use crate::core_libfunc_cost::core_libfunc_cost;
 use crate::gas_info::GasInfo;
 use crate::{calc_gas_info, CostError};
 
@@ -52,3 +61,87 @@ fn get_example_program(name: &str) -> Program {
 fn solve_gas(path: &str) -> Result<GasInfo, CostError> {
     calc_gas_info(&get_example_program(path))
 }
+
+#[test]
+fn cost_between_mark_and_elapsed() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type GasBuiltin = GasBuiltin;
+            type RangeCheck = RangeCheck;
+            type felt = felt;
+            type uint128 = uint128;
+            libfunc gas_mark = gas_mark;
+            libfunc gas_elapsed = gas_elapsed;
+            libfunc get_gas = get_gas;
+            libfunc jump = jump;
+            libfunc felt_const_3 = felt_const<3>;
+            libfunc store_temp_felt = store_temp<felt>;
+            libfunc felt_drop = drop<felt>;
+            libfunc store_temp_uint128 = store_temp<uint128>;
+            gas_mark(gb) -> (gb, mark);
+            get_gas(rc, gb) { fallthrough(rc, gb) 6(rc, gb) };
+            felt_const_3() -> (a);
+            store_temp_felt(a) -> (a);
+            felt_drop(a) -> ();
+            jump() { 6() };
+            gas_elapsed(gb, mark) -> (gb, elapsed);
+            store_temp_uint128(elapsed) -> (elapsed);
+            return(rc, gb, elapsed);
+            main@0(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin, uint128);
+        "})
+        .unwrap();
+    let gas_info = calc_gas_info(&program).unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    // The costs of the statements enclosed between the withdrawal of the gas and its measurement.
+    let enclosed_cost: i64 = (2..6)
+        .map(|idx| {
+            let invocation =
+                try_extract_matches!(&program.statements[idx], Statement::Invocation).unwrap();
+            let libfunc = registry.get_libfunc(&invocation.libfunc_id).unwrap();
+            core_libfunc_cost(&gas_info, &StatementIdx(idx), libfunc)[0].unwrap()
+        })
+        .sum();
+    // Storing the felt and jumping cost a step each.
+    assert_eq!(enclosed_cost, 2);
+    // The gas withdrawn for the enclosed statements is the gas elapsed since the mark.
+    assert_eq!(
+        run(
+            &program,
+            &gas_info.variable_values,
+            &"main".into(),
+            vec![CoreValue::RangeCheck, CoreValue::GasBuiltin(10)]
+        ),
+        Ok(vec![
+            CoreValue::RangeCheck,
+            CoreValue::GasBuiltin(10 - enclosed_cost),
+            CoreValue::Uint128(enclosed_cost as u128)
+        ])
+    );
+}
+
+#[test]
+fn uint128_array_sum_cost_is_invocation_only() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type uint128 = uint128;
+            type RangeCheck = RangeCheck;
+            type ArrayUint128 = Array<uint128>;
+            libfunc array_reverse = array_reverse<uint128>;
+            libfunc uint128_array_sum = uint128_array_sum;
+            array_reverse(arr) -> (arr);
+            uint128_array_sum(rc, arr) { fallthrough(rc, sum) 2(rc) };
+        "})
+        .unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibFunc>::new(&program).unwrap();
+    let gas_info = GasInfo { variable_values: HashMap::new(), function_costs: HashMap::new() };
+    let cost = |idx: usize| {
+        let invocation =
+            try_extract_matches!(&program.statements[idx], Statement::Invocation).unwrap();
+        let libfunc = registry.get_libfunc(&invocation.libfunc_id).unwrap();
+        core_libfunc_cost(&gas_info, &StatementIdx(idx), libfunc)
+    };
+    // Neither libfunc is compiled to CASM yet, so, like reversing an array, summing one is charged
+    // a single step per branch regardless of the array's length.
+    assert_eq!(cost(0), vec![Some(1)]);
+    assert_eq!(cost(1), vec![Some(1), Some(1)]);
+}
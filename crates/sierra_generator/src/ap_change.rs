@@ -88,6 +88,8 @@ pub fn get_ap_change(db: &dyn SierraGenGroup, function_id: FreeFunctionId) -> Op
                 {
                     let branch_ap_change = match branch_signature.ap_change {
                         sierra::extensions::lib_func::SierraApChange::Known(value) => value,
+                        // Alignment does not change the ap of its own branch.
+                        sierra::extensions::lib_func::SierraApChange::BranchAlign => 0,
                         sierra::extensions::lib_func::SierraApChange::Unknown
                         | sierra::extensions::lib_func::SierraApChange::NotImplemented
                         | sierra::extensions::lib_func::SierraApChange::FinalizeLocals => {
@@ -134,7 +134,9 @@ fn replace_generic_args(db: &dyn SierraGenGroup, generic_args: &mut Vec<program:
             program::GenericArg::LibFunc(id) => {
                 *id = replace_libfunc_id(db, id);
             }
-            program::GenericArg::Value(_) | program::GenericArg::UserType(_) => {}
+            program::GenericArg::Value(_)
+            | program::GenericArg::ShortString(_)
+            | program::GenericArg::UserType(_) => {}
         }
     }
 }
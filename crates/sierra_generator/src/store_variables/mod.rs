@@ -105,7 +105,9 @@ impl<'a> AddStoreVariableStatements<'a> {
                                 // otherwise should be stored as locals.
                                 self.store_variables_as_locals();
                             }
-                            SierraApChange::Known(_) | SierraApChange::FinalizeLocals => {}
+                            SierraApChange::Known(_)
+                            | SierraApChange::FinalizeLocals
+                            | SierraApChange::BranchAlign => {}
                         }
 
                         self.state().register_outputs(results, branch_signature);
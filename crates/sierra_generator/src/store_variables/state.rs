@@ -50,6 +50,7 @@ impl State {
                 self.clear_known_stack();
             }
             sierra::extensions::lib_func::SierraApChange::FinalizeLocals
+            | sierra::extensions::lib_func::SierraApChange::BranchAlign
             | sierra::extensions::lib_func::SierraApChange::Known(_) => {}
         }
 
@@ -1,9 +1,12 @@
 use indoc::indoc;
 use pretty_assertions;
+use sierra::extensions::{ExtensionError, SpecializationError};
+use sierra::program::{ConcreteLibFuncLongId, GenericArg};
+use sierra::program_registry::ProgramRegistryError;
 use sierra::ProgramParser;
 use test_case::test_case;
 
-use crate::compiler::compile;
+use crate::compiler::{compile, CompilationError};
 use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments_and_linebreaks};
 
 #[test_case(indoc! {"
@@ -20,7 +23,7 @@ use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments
                 libfunc felt_into_box = into_box<felt>;
                 libfunc felt_unbox = unbox<felt>;
                 libfunc jump = jump;
-                libfunc felt_unwrap_nz = unwrap_nz<felt>;
+                libfunc felt_unwrap_nz = unwrap_non_zero<felt>;
                 libfunc store_temp_felt = store_temp<felt>;
                 libfunc store_temp_box_felt = store_temp<BoxFelt>;
                 libfunc rename_felt = rename<felt>;
@@ -350,6 +353,7 @@ use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments
             "fib_recursive")]
 #[test_case(indoc! {"
                 type felt = felt;
+                type SegmentArena = SegmentArena;
                 type DictFeltToFelt = DictFeltTo<felt>;
                 type UninitializedFelt = Uninitialized<felt>;
 
@@ -362,6 +366,7 @@ use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments
                 libfunc dict_felt_to_write<felt> = dict_felt_to_write<felt>;
                 libfunc dict_felt_to_read<felt> = dict_felt_to_read<felt>;
                 libfunc store_temp_dict_felt_to_felt = store_temp<DictFeltToFelt>;
+                libfunc store_temp_segment_arena = store_temp<SegmentArena>;
 
                 felt_const<10>() -> ([0]);
                 store_temp_felt([0]) -> ([0]);
@@ -369,7 +374,7 @@ use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments
                 store_temp_felt([1]) -> ([1]);
                 felt_const<12>() -> ([2]);
                 store_temp_felt([2]) -> ([2]);
-                dict_felt_to_new<felt>([2]) -> ([3]);
+                dict_felt_to_new<felt>([11], [2]) -> ([11], [3]);
                 dict_felt_to_write<felt>([3], [0], [1]) -> ([4]);
                 felt_const<10>() -> ([5]);
                 store_temp_felt([5]) -> ([5]);
@@ -379,12 +384,13 @@ use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments
                 felt_const<10>() -> ([8]);
                 store_temp_felt([8]) -> ([8]);
                 dict_felt_to_read<felt>([7], [8]) -> ([9], [10]);
+                store_temp_segment_arena([11]) -> ([11]);
                 store_temp_dict_felt_to_felt([9]) -> ([9]);
                 store_temp_felt([10]) -> ([10]);
-                return ([9], [10]);
-                test_program@0() -> (DictFeltToFelt, felt);
+                return ([11], [9], [10]);
+                test_program@0([11]: SegmentArena) -> (SegmentArena, DictFeltToFelt, felt);
             "},
-            &[("test_program", 13)], false,
+            &[("test_program", 14)], false,
             indoc! {"
                 [ap + 0] = 10, ap++;
                 [ap + 0] = 11, ap++;
@@ -428,9 +434,10 @@ use crate::test_utils::{build_metadata, read_sierra_example_file, strip_comments
                 [ap + -2] = [[ap + -7] + 6];
                 [ap + -1] = [[ap + -7] + 7];
                 [ap + -1] = [[ap + -7] + 8];
-                [ap + 0] = [ap + -7], ap++;
-                [ap + 0] = [ap + -8] + 9, ap++;
-                [ap + 0] = [ap + -3], ap++;
+                [ap + 0] = [fp + -3], ap++;
+                [ap + 0] = [ap + -8], ap++;
+                [ap + 0] = [ap + -9] + 9, ap++;
+                [ap + 0] = [ap + -4], ap++;
                 ret;
             "};
             "dict test")]
@@ -656,7 +663,7 @@ of the libfunc or return statement.";
 
                 libfunc felt_drop = drop<felt>;
                 libfunc felt_jump_nz = felt_jump_nz;
-                libfunc felt_unwrap_nz = unwrap_nz<felt>;
+                libfunc felt_unwrap_nz = unwrap_non_zero<felt>;
                 libfunc jump = jump;
 
                 felt_jump_nz([1]) { fallthrough() 3([1]) };
@@ -725,20 +732,6 @@ of the libfunc or return statement.";
                 foo@0([1]: felt) -> ();
             "}, &[("foo", 0)], "#3: locals were allocated but finalize_locals was not called.";
             "missing finalize_locals ")]
-#[test_case(indoc! {"
-                type felt = felt;
-                type UninitializedFelt = Uninitialized<felt>;
-
-                libfunc alloc_local_felt = alloc_local<felt>;
-                libfunc store_temp_felt = store_temp<UninitializedFelt>;
-
-                alloc_local_felt() -> ([1]);
-                store_temp_felt([1]) -> ([1]);
-                return ();
-
-                foo@0() -> ();
-            "}, &[], "#1: The functionality is supported only for sized types.";
-            "store_temp<Uninitialized<felt>()")]
 #[test_case(indoc! {"
                 return ();
 
@@ -755,3 +748,37 @@ fn compiler_errors(sierra_code: &str, ap_change_data: &[(&str, usize)], expected
         expected_result
     );
 }
+
+#[test]
+fn compiler_error_for_unstorable_type() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt = felt;
+            type UninitializedFelt = Uninitialized<felt>;
+
+            libfunc alloc_local_felt = alloc_local<felt>;
+            libfunc store_temp_felt = store_temp<UninitializedFelt>;
+
+            alloc_local_felt() -> ([1]);
+            store_temp_felt([1]) -> ([1]);
+            return ();
+
+            foo@0() -> ();
+        "})
+        .unwrap();
+    pretty_assertions::assert_eq!(
+        compile(&program, &build_metadata(&program, &[], false), false),
+        Err(CompilationError::ProgramRegistryError(Box::new(
+            ProgramRegistryError::LibFuncSpecialization {
+                concrete_id: "store_temp_felt".into(),
+                error: ExtensionError::LibFuncSpecialization {
+                    long_id: ConcreteLibFuncLongId {
+                        generic_id: "store_temp".into(),
+                        generic_args: vec![GenericArg::Type("UninitializedFelt".into())],
+                    },
+                    error: SpecializationError::TypeNotStorable("UninitializedFelt".into()),
+                },
+            }
+        )))
+    );
+}
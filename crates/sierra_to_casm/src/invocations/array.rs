@@ -23,6 +23,18 @@ pub fn build(
     match libfunc {
         ArrayConcreteLibFunc::New(_) => build_array_new(builder),
         ArrayConcreteLibFunc::Append(_) => build_array_append(builder),
+        ArrayConcreteLibFunc::Reverse(_)
+        | ArrayConcreteLibFunc::Zip(_)
+        | ArrayConcreteLibFunc::Fold(_)
+        | ArrayConcreteLibFunc::Eq(_)
+        | ArrayConcreteLibFunc::FindConst(_)
+        | ArrayConcreteLibFunc::Rotate(_)
+        | ArrayConcreteLibFunc::Len(_)
+        | ArrayConcreteLibFunc::At(_)
+        | ArrayConcreteLibFunc::PopFront(_)
+        | ArrayConcreteLibFunc::Slice(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
     }
 }
 
@@ -0,0 +1,18 @@
+use sierra::extensions::boolean::BoolConcreteLibFunc;
+
+use super::{misc, CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+
+/// Builds instructions for Sierra boolean operations.
+pub fn build(
+    libfunc: &BoolConcreteLibFunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    match libfunc {
+        BoolConcreteLibFunc::ToFelt(_) => misc::build_identity(builder),
+        BoolConcreteLibFunc::Operation(_)
+        | BoolConcreteLibFunc::Not(_)
+        | BoolConcreteLibFunc::FromFelt(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
+    }
+}
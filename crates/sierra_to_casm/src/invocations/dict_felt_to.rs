@@ -30,6 +30,11 @@ pub fn build(
         DictFeltToConcreteLibFunc::New(_) => build_dict_felt_to_new(builder),
         DictFeltToConcreteLibFunc::Read(_) => build_dict_felt_to_read(builder),
         DictFeltToConcreteLibFunc::Write(_) => build_dict_felt_to_write(builder),
+        DictFeltToConcreteLibFunc::EntryGet(_)
+        | DictFeltToConcreteLibFunc::EntryFinalize(_)
+        | DictFeltToConcreteLibFunc::Squash(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
     }
 }
 
@@ -37,13 +42,14 @@ pub fn build(
 fn build_dict_felt_to_new(
     builder: CompiledInvocationBuilder<'_>,
 ) -> Result<CompiledInvocation, InvocationError> {
-    let default_value = match builder.refs {
-        [ReferenceValue { expression: expr_default_value, .. }] => {
-            try_unpack_deref(expr_default_value)?
-        }
+    let (segment_arena, default_value) = match builder.refs {
+        [
+            ReferenceValue { expression: expr_segment_arena, .. },
+            ReferenceValue { expression: expr_default_value, .. },
+        ] => (try_unpack_deref(expr_segment_arena)?, try_unpack_deref(expr_default_value)?),
         refs => {
             return Err(InvocationError::WrongNumberOfArguments {
-                expected: 1,
+                expected: 2,
                 actual: refs.len(),
             });
         }
@@ -59,12 +65,17 @@ fn build_dict_felt_to_new(
             }],
         }],
         vec![],
-        [[ReferenceExpression {
-            cells: vec![
-                CellExpression::Deref(CellRef { register: Register::AP, offset: -1 }),
-                CellExpression::Deref(CellRef { register: Register::AP, offset: -1 }),
-            ],
-        }]
+        [[
+            ReferenceExpression::from_cell(CellExpression::Deref(
+                segment_arena.unchecked_apply_known_ap_change(1),
+            )),
+            ReferenceExpression {
+                cells: vec![
+                    CellExpression::Deref(CellRef { register: Register::AP, offset: -1 }),
+                    CellExpression::Deref(CellRef { register: Register::AP, offset: -1 }),
+                ],
+            },
+        ]
         .into_iter()]
         .into_iter(),
     ))
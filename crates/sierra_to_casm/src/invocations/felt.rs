@@ -25,7 +25,14 @@ pub fn build(
         FeltConcrete::Operation(FeltOperationConcreteLibFunc::Const(
             FeltOperationWithConstConcreteLibFunc { operator, c, .. },
         )) => build_felt_op_with_const(builder, *operator, c.clone()),
-        FeltConcrete::JumpNotZero(_) => build_jump_nz(builder),
+        FeltConcrete::JumpNotZero(_) | FeltConcrete::IsZero(_) => build_jump_nz(builder),
+        FeltConcrete::Inverse(_)
+        | FeltConcrete::AssertLtConst(_)
+        | FeltConcrete::Popcount(_)
+        | FeltConcrete::Pack(_)
+        | FeltConcrete::Clamp(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
         FeltConcrete::Const(libfunc) => Ok(builder.build_only_reference_changes(
             [ReferenceExpression::from_cell(CellExpression::Immediate(libfunc.c.clone()))]
                 .into_iter(),
@@ -22,6 +22,8 @@ pub fn build(
         GasConcreteLibFunc::GetGas(_) => build_get_gas(builder),
         GasConcreteLibFunc::RefundGas(_) => build_refund_gas(builder),
         GasConcreteLibFunc::BurnGas(_) => Ok(builder.build_only_reference_changes([].into_iter())),
+        GasConcreteLibFunc::GasMark(_) => build_gas_mark(builder),
+        GasConcreteLibFunc::GasElapsed(_) => build_gas_elapsed(builder),
     }
 }
 
@@ -178,3 +180,64 @@ fn build_refund_gas(
         .into_iter(),
     ))
 }
+
+/// Handles the gas mark invocation.
+fn build_gas_mark(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let expression = match builder.refs {
+        [ReferenceValue { expression, .. }] => expression,
+        refs => {
+            return Err(InvocationError::WrongNumberOfArguments {
+                expected: 1,
+                actual: refs.len(),
+            });
+        }
+    };
+    // The mark is the current value of the gas counter.
+    Ok(builder.build_only_reference_changes([expression.clone(), expression.clone()].into_iter()))
+}
+
+/// Handles the gas elapsed invocation.
+fn build_gas_elapsed(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let (gas_counter_expression, mark_expression) = match builder.refs {
+        [
+            ReferenceValue { expression: gas_counter_expression, .. },
+            ReferenceValue { expression: mark_expression, .. },
+        ] => (gas_counter_expression, mark_expression),
+        refs => {
+            return Err(InvocationError::WrongNumberOfArguments {
+                expected: 2,
+                actual: refs.len(),
+            });
+        }
+    };
+    let gas_counter_value = try_extract_matches!(
+        gas_counter_expression
+            .try_unpack_single()
+            .map_err(|_| InvocationError::InvalidReferenceExpressionForArgument)?,
+        CellExpression::Deref
+    )
+    .ok_or(InvocationError::InvalidReferenceExpressionForArgument)?;
+    let mark_value = try_extract_matches!(
+        mark_expression
+            .try_unpack_single()
+            .map_err(|_| InvocationError::InvalidReferenceExpressionForArgument)?,
+        CellExpression::Deref
+    )
+    .ok_or(InvocationError::InvalidReferenceExpressionForArgument)?;
+
+    Ok(builder.build_only_reference_changes(
+        [
+            gas_counter_expression.clone(),
+            ReferenceExpression::from_cell(CellExpression::BinOp(BinOpExpression {
+                op: FeltOperator::Sub,
+                a: mark_value,
+                b: DerefOrImmediate::Deref(gas_counter_value),
+            })),
+        ]
+        .into_iter(),
+    ))
+}
@@ -36,6 +36,13 @@ pub fn build_drop(
     Ok(builder.build_only_reference_changes([].into_iter()))
 }
 
+/// Handles the creation of the unit value, which occupies no cells.
+pub fn build_unit(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    Ok(builder.build_only_reference_changes([ReferenceExpression { cells: vec![] }].into_iter()))
+}
+
 /// Handles a jump non zero statement.
 /// For example, this "Sierra statement"
 /// ```ignore
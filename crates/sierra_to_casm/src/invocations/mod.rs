@@ -3,8 +3,11 @@ use casm::ap_change::ApChange;
 use casm::instructions::Instruction;
 use casm::operand::{CellRef, Register};
 use itertools::zip_eq;
+use sierra::extensions::bytes31::Bytes31ConcreteLibFunc;
+use sierra::extensions::casts::CastConcreteLibFunc;
 use sierra::extensions::core::CoreConcreteLibFunc;
 use sierra::extensions::lib_func::{BranchSignature, SierraApChange};
+use sierra::extensions::span::SpanConcreteLibFunc;
 use sierra::extensions::{ConcreteLibFunc, OutputVarReferenceInfo};
 use sierra::ids::ConcreteTypeId;
 use sierra::program::{BranchInfo, BranchTarget, Invocation, StatementIdx};
@@ -19,6 +22,7 @@ use crate::relocations::RelocationEntry;
 use crate::type_sizes::TypeSizeMap;
 
 mod array;
+mod boolean;
 mod boxing;
 mod dict_felt_to;
 mod enm;
@@ -27,8 +31,11 @@ mod function_call;
 mod gas;
 mod mem;
 mod misc;
+mod nullable;
 mod strct;
+mod uint;
 mod uint128;
+mod uint256;
 
 #[cfg(test)]
 mod test_utils;
@@ -171,6 +178,9 @@ impl CompiledInvocationBuilder<'_> {
             .map(|((branch_signature, gas_change), expressions)| {
                 let ap_change = match branch_signature.ap_change {
                     SierraApChange::Known(x) => ApChange::Known(x),
+                    // Aligning with the converging branches is not part of the libfunc itself,
+                    // which does not move `ap`.
+                    SierraApChange::BranchAlign => ApChange::Known(0),
                     SierraApChange::NotImplemented => panic!("AP change not implemented."),
                     SierraApChange::FinalizeLocals => match self.environment.frame_state {
                         FrameState::Finalized { allocated } => ApChange::Known(allocated),
@@ -221,17 +231,66 @@ pub fn compile_invocation(
     match libfunc {
         // TODO(ilya, 10/10/2022): Handle type.
         CoreConcreteLibFunc::Felt(libfunc) => felt::build(libfunc, builder),
+        CoreConcreteLibFunc::Uint8(libfunc)
+        | CoreConcreteLibFunc::Uint16(libfunc)
+        | CoreConcreteLibFunc::Uint32(libfunc)
+        | CoreConcreteLibFunc::Uint64(libfunc) => uint::build(libfunc, builder),
         CoreConcreteLibFunc::Uint128(libfunc) => uint128::build(libfunc, builder),
+        CoreConcreteLibFunc::Uint256(libfunc) => uint256::build(libfunc, builder),
+        // Upcasting keeps the value's representation as is.
+        CoreConcreteLibFunc::Cast(CastConcreteLibFunc::Upcast(_)) => misc::build_identity(builder),
+        // A bytes31 is represented by the felt it holds.
+        CoreConcreteLibFunc::Bytes31(Bytes31ConcreteLibFunc::ToFelt(_)) => {
+            misc::build_identity(builder)
+        }
         CoreConcreteLibFunc::Gas(libfunc) => gas::build(libfunc, builder),
         CoreConcreteLibFunc::Array(libfunc) => array::build(libfunc, builder),
+        // A span shares the array's start and end pointers.
+        CoreConcreteLibFunc::Span(SpanConcreteLibFunc::FromArray(_)) => misc::build_dup(builder),
+        CoreConcreteLibFunc::Bool(libfunc) => boolean::build(libfunc, builder),
         CoreConcreteLibFunc::Drop(_) => misc::build_drop(builder),
-        CoreConcreteLibFunc::Dup(_) => misc::build_dup(builder),
+        // Debug printing only affects the simulation, so the compiled code just drops the value.
+        CoreConcreteLibFunc::DebugPrint(_) => misc::build_drop(builder),
+        CoreConcreteLibFunc::Dup(_) | CoreConcreteLibFunc::SnapshotTake(_) => {
+            misc::build_dup(builder)
+        }
+        CoreConcreteLibFunc::Bitwise(_)
+        | CoreConcreteLibFunc::BranchAlign(_)
+        | CoreConcreteLibFunc::Bytes31(
+            Bytes31ConcreteLibFunc::TryFromFelt(_)
+            | Bytes31ConcreteLibFunc::Concat(_)
+            | Bytes31ConcreteLibFunc::Split(_),
+        )
+        | CoreConcreteLibFunc::Cast(CastConcreteLibFunc::Downcast(_))
+        | CoreConcreteLibFunc::Circuit(_)
+        | CoreConcreteLibFunc::Coupon(_)
+        | CoreConcreteLibFunc::ConstAsImmediate(_)
+        | CoreConcreteLibFunc::Option(_)
+        | CoreConcreteLibFunc::Panic(_)
+        | CoreConcreteLibFunc::Result(_)
+        | CoreConcreteLibFunc::Ec(_)
+        | CoreConcreteLibFunc::EcdsaVerify(_)
+        | CoreConcreteLibFunc::EmitEvent(_)
+        | CoreConcreteLibFunc::Pedersen(_)
+        | CoreConcreteLibFunc::Poseidon(_)
+        | CoreConcreteLibFunc::Pow(_)
+        | CoreConcreteLibFunc::Span(SpanConcreteLibFunc::PopFront(_) | SpanConcreteLibFunc::Len(_))
+        | CoreConcreteLibFunc::StorageAddress(_)
+        | CoreConcreteLibFunc::ContractAddressTryFromFelt(_)
+        | CoreConcreteLibFunc::ClassHashTryFromFelt(_)
+        | CoreConcreteLibFunc::Syscall(_)
+        | CoreConcreteLibFunc::Secp256(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
         CoreConcreteLibFunc::Mem(libfunc) => mem::build(libfunc, builder),
         CoreConcreteLibFunc::UnwrapNonZero(_) => misc::build_identity(builder),
+        CoreConcreteLibFunc::Unit(_) => misc::build_unit(builder),
+        CoreConcreteLibFunc::JumpNotZero(_) => misc::build_jump_nz(builder),
         CoreConcreteLibFunc::FunctionCall(libfunc) => function_call::build(libfunc, builder),
         CoreConcreteLibFunc::UnconditionalJump(_) => misc::build_jump(builder),
         CoreConcreteLibFunc::ApTracking(_) => misc::build_revoke_ap_tracking(builder),
         CoreConcreteLibFunc::Box(libfunc) => boxing::build(libfunc, builder),
+        CoreConcreteLibFunc::Nullable(libfunc) => nullable::build(libfunc, builder),
         CoreConcreteLibFunc::Enum(libfunc) => enm::build(libfunc, builder),
         CoreConcreteLibFunc::Struct(libfunc) => strct::build(libfunc, builder),
         CoreConcreteLibFunc::DictFeltTo(libfunc) => dict_felt_to::build(libfunc, builder),
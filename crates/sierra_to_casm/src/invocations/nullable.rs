@@ -0,0 +1,25 @@
+use num_bigint::BigInt;
+use sierra::extensions::nullable::NullableConcreteLibFunc;
+
+use super::{misc, CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+use crate::references::{CellExpression, ReferenceExpression};
+
+#[cfg(test)]
+#[path = "nullable_test.rs"]
+mod test;
+
+/// Builds instructions for Sierra nullable operations.
+/// A nullable value is a box pointer, where null is represented by 0.
+pub fn build(
+    libfunc: &NullableConcreteLibFunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    match libfunc {
+        NullableConcreteLibFunc::Null(_) => Ok(builder.build_only_reference_changes(
+            [ReferenceExpression::from_cell(CellExpression::Immediate(BigInt::from(0)))]
+                .into_iter(),
+        )),
+        NullableConcreteLibFunc::FromBox(_) => misc::build_identity(builder),
+        NullableConcreteLibFunc::Match(_) => misc::build_jump_nz(builder),
+    }
+}
@@ -0,0 +1,50 @@
+use casm::ap_change::ApChange;
+use casm::casm;
+use num_bigint::BigInt;
+use sierra::program::StatementIdx;
+use test_log::test;
+
+use crate::invocations::test_utils::{
+    compile_libfunc, ReducedBranchChanges, ReducedCompiledInvocation,
+};
+use crate::ref_expr;
+use crate::references::{CellExpression, ReferenceExpression};
+use crate::relocations::{Relocation, RelocationEntry};
+
+#[test]
+fn test_null() {
+    assert_eq!(
+        compile_libfunc("null<felt>", vec![]),
+        ReducedCompiledInvocation {
+            instructions: vec![],
+            relocations: vec![],
+            results: vec![ReducedBranchChanges {
+                refs: vec![ReferenceExpression::from_cell(CellExpression::Immediate(
+                    BigInt::from(0)
+                ))],
+                ap_change: ApChange::Known(0)
+            }]
+        }
+    );
+}
+
+#[test]
+fn test_match_nullable() {
+    assert_eq!(
+        compile_libfunc("match_nullable<felt>", vec![ref_expr!([fp + 3])]),
+        ReducedCompiledInvocation {
+            instructions: casm! {jmp rel 0 if [fp + 3] != 0;}.instructions,
+            relocations: vec![RelocationEntry {
+                instruction_idx: 0,
+                relocation: Relocation::RelativeStatementId(StatementIdx(1))
+            }],
+            results: vec![
+                ReducedBranchChanges { refs: vec![], ap_change: ApChange::Known(0) },
+                ReducedBranchChanges {
+                    refs: vec![ref_expr!([fp + 3])],
+                    ap_change: ApChange::Known(0)
+                }
+            ]
+        }
+    );
+}
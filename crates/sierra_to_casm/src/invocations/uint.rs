@@ -0,0 +1,23 @@
+use num_bigint::BigInt;
+use sierra::extensions::uint::UintConcrete;
+
+use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+use crate::references::{CellExpression, ReferenceExpression};
+
+/// Builds instructions for Sierra bounded unsigned integer operations.
+pub fn build(
+    libfunc: &UintConcrete,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    match libfunc {
+        UintConcrete::Const(libfunc) => Ok(builder.build_only_reference_changes(
+            [ReferenceExpression::from_cell(CellExpression::Immediate(BigInt::from(libfunc.c)))]
+                .into_iter(),
+        )),
+        UintConcrete::Operation(_)
+        | UintConcrete::LessThan(_)
+        | UintConcrete::LessThanOrEqual(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
+    }
+}
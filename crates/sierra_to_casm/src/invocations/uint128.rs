@@ -35,7 +35,14 @@ pub fn build(
         )) => build_uint128_op(builder, *operator),
         Uint128Concrete::Operation(Uint128OperationConcreteLibFunc::Const(
             Uint128OperationWithConstConcreteLibFunc { operator: _, c: _, .. },
-        )) => Err(InvocationError::NotImplemented(builder.invocation.clone())),
+        ))
+        | Uint128Concrete::ArraySum(_)
+        | Uint128Concrete::SafeDivMod(_)
+        | Uint128Concrete::WideMul(_)
+        | Uint128Concrete::Sqrt(_)
+        | Uint128Concrete::Shift(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
         Uint128Concrete::JumpNotZero(_) => misc::build_jump_nz(builder),
         Uint128Concrete::Const(libfunc) => Ok(builder.build_only_reference_changes(
             [ReferenceExpression::from_cell(CellExpression::Immediate(BigInt::from(libfunc.c)))]
@@ -0,0 +1,44 @@
+use sierra::extensions::uint256::Uint256Concrete;
+
+use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+use crate::references::{ReferenceExpression, ReferenceValue};
+
+/// Builds instructions for Sierra u256 operations.
+pub fn build(
+    libfunc: &Uint256Concrete,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    match libfunc {
+        // A u256 is laid out as its low half followed by its high half.
+        Uint256Concrete::Construct(_) => match builder.refs {
+            [ReferenceValue { expression: low, .. }, ReferenceValue { expression: high, .. }] => {
+                let cells = low.cells.iter().chain(high.cells.iter()).cloned().collect();
+                Ok(builder
+                    .build_only_reference_changes([ReferenceExpression { cells }].into_iter()))
+            }
+            refs => {
+                Err(InvocationError::WrongNumberOfArguments { expected: 2, actual: refs.len() })
+            }
+        },
+        Uint256Concrete::Deconstruct(_) => match builder.refs {
+            [ReferenceValue { expression: ReferenceExpression { cells }, .. }] => {
+                match &cells[..] {
+                    [low, high] => Ok(builder.build_only_reference_changes(
+                        [
+                            ReferenceExpression::from_cell(low.clone()),
+                            ReferenceExpression::from_cell(high.clone()),
+                        ]
+                        .into_iter(),
+                    )),
+                    _ => Err(InvocationError::InvalidReferenceExpressionForArgument),
+                }
+            }
+            refs => {
+                Err(InvocationError::WrongNumberOfArguments { expected: 1, actual: refs.len() })
+            }
+        },
+        Uint256Concrete::Operation(_) => {
+            Err(InvocationError::NotImplemented(builder.invocation.clone()))
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use std::cmp;
 use std::collections::HashMap;
 
+use sierra::extensions::circuit::CircuitTypeConcrete;
 use sierra::extensions::core::{CoreLibFunc, CoreType, CoreTypeConcrete};
 use sierra::extensions::non_zero::NonZeroConcreteType;
+use sierra::extensions::panic::PanicableConcreteType;
+use sierra::extensions::snapshot::SnapshotConcreteType;
 use sierra::ids::ConcreteTypeId;
 use sierra::program::Program;
 use sierra::program_registry::ProgramRegistry;
@@ -18,25 +22,77 @@ pub fn get_type_size_map(
         let ty = registry.get_type(&declaration.id).ok()?;
         let size = match ty {
             CoreTypeConcrete::Felt(_)
+            | CoreTypeConcrete::Bool(_)
             | CoreTypeConcrete::GasBuiltin(_)
+            | CoreTypeConcrete::Uint8(_)
+            | CoreTypeConcrete::Uint16(_)
+            | CoreTypeConcrete::Uint32(_)
+            | CoreTypeConcrete::Uint64(_)
             | CoreTypeConcrete::Uint128(_)
             | CoreTypeConcrete::RangeCheck(_)
-            | CoreTypeConcrete::Box(_) => Some(1),
-            CoreTypeConcrete::Array(_) | CoreTypeConcrete::DictFeltTo(_) => Some(2),
-            CoreTypeConcrete::NonZero(NonZeroConcreteType { ty, .. }) => {
+            | CoreTypeConcrete::SegmentArena(_)
+            | CoreTypeConcrete::Bytes31(_)
+            | CoreTypeConcrete::Bitwise(_)
+            | CoreTypeConcrete::Pedersen(_)
+            | CoreTypeConcrete::Poseidon(_)
+            | CoreTypeConcrete::EcOp(_)
+            | CoreTypeConcrete::Signature(_)
+            | CoreTypeConcrete::System(_)
+            | CoreTypeConcrete::StorageBaseAddress(_)
+            | CoreTypeConcrete::StorageAddress(_)
+            | CoreTypeConcrete::ContractAddress(_)
+            | CoreTypeConcrete::ClassHash(_)
+            | CoreTypeConcrete::Secp256Point(_)
+            | CoreTypeConcrete::Box(_)
+            | CoreTypeConcrete::Nullable(_)
+            | CoreTypeConcrete::Circuit(
+                CircuitTypeConcrete::AddMod(_)
+                | CircuitTypeConcrete::MulMod(_)
+                | CircuitTypeConcrete::Data(_),
+            ) => Some(1),
+            CoreTypeConcrete::Array(_)
+            | CoreTypeConcrete::Span(_)
+            | CoreTypeConcrete::DictFeltTo(_)
+            | CoreTypeConcrete::SquashedDictFeltTo(_)
+            | CoreTypeConcrete::EcPoint(_)
+            | CoreTypeConcrete::Uint256(_)
+            | CoreTypeConcrete::Circuit(
+                CircuitTypeConcrete::InputAccumulator(_) | CircuitTypeConcrete::Outputs(_),
+            ) => Some(2),
+            CoreTypeConcrete::EcState(_) | CoreTypeConcrete::DictFeltToEntry(_) => Some(3),
+            CoreTypeConcrete::NonZero(NonZeroConcreteType { ty, .. })
+            | CoreTypeConcrete::Snapshot(SnapshotConcreteType { ty, .. }) => {
                 type_sizes.get(ty).cloned()
             }
-            CoreTypeConcrete::Enum(enum_type) => {
-                Some(1 + enum_type.variants.iter().map(|variant| type_sizes[variant]).max()?)
+            // The variant index, followed by the larger of the value and the panic data array.
+            CoreTypeConcrete::Panicable(PanicableConcreteType { ty, .. }) => {
+                type_sizes.get(ty).map(|size| 1 + cmp::max(*size, 2))
             }
+            CoreTypeConcrete::Enum(enum_type) => Some(
+                enum_type
+                    .variants
+                    .iter()
+                    .map(|variant| type_sizes[variant])
+                    .max()
+                    .map_or(0, |max_size| 1 + max_size),
+            ),
             CoreTypeConcrete::Struct(struct_type) => {
                 Some(struct_type.members.iter().map(|member| type_sizes[member]).sum())
             }
+            CoreTypeConcrete::Coupon(_) | CoreTypeConcrete::Unit(_) => Some(0),
             CoreTypeConcrete::Uninitialized(_) => {
                 // Any size operations on `Uninitialized` are not supported, so we skip adding them
                 // to the map.
                 continue;
             }
+            CoreTypeConcrete::Circuit(
+                CircuitTypeConcrete::Input(_)
+                | CircuitTypeConcrete::Gate(_)
+                | CircuitTypeConcrete::Circuit(_),
+            ) => {
+                // Types describing a circuit have no values, so they have no size.
+                continue;
+            }
         }?;
         type_sizes.insert(declaration.id.clone(), size);
     }